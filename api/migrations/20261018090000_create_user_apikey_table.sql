@@ -0,0 +1,18 @@
+CREATE TABLE `user_apikey` (
+    `id` int(11) NOT NULL AUTO_INCREMENT,
+    `name` varchar(255) NOT NULL,
+    -- first characters of the key, to tell keys apart without the secret
+    `prefix` varchar(16) NOT NULL,
+    -- hex encoded SHA-256 of the key, the key itself is never stored
+    `key_hash` char(64) NOT NULL,
+    -- comma separated list of API areas the key may be used for
+    `scopes` varchar(255) NOT NULL,
+    `created_at` datetime(6) NOT NULL,
+    `expires_at` datetime(6) DEFAULT NULL,
+    `last_used_at` datetime(6) DEFAULT NULL,
+    `user_id` int(11) NOT NULL,
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `key_hash` (`key_hash`),
+    KEY `user_apikey_user_id_fk_user_user_id` (`user_id`),
+    CONSTRAINT `user_apikey_user_id_fk_user_user_id` FOREIGN KEY (`user_id`) REFERENCES `user_user` (`id`) ON DELETE CASCADE
+) ENGINE=InnoDB AUTO_INCREMENT=1 DEFAULT CHARSET=utf8
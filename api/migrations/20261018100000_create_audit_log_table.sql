@@ -0,0 +1,20 @@
+CREATE TABLE `audit_log` (
+    `id` int(11) NOT NULL AUTO_INCREMENT,
+    `timestamp` datetime(6) NOT NULL,
+    `method` varchar(8) NOT NULL,
+    `endpoint` varchar(255) NOT NULL,
+    `status` smallint(5) unsigned NOT NULL,
+    `resource_type` varchar(64) DEFAULT NULL,
+    `resource_id` int(10) unsigned DEFAULT NULL,
+    -- NOTE: no foreign keys on purpose, entries have to outlive their users
+    `actor_id` int(11) NOT NULL,
+    `actor_name` varchar(255) NOT NULL,
+    `impersonated_id` int(11) DEFAULT NULL,
+    `impersonated_name` varchar(255) DEFAULT NULL,
+    `data_before` longtext DEFAULT NULL,
+    `data_after` longtext DEFAULT NULL,
+    PRIMARY KEY (`id`),
+    KEY `audit_log_timestamp` (`timestamp`),
+    KEY `audit_log_actor_id` (`actor_id`),
+    KEY `audit_log_resource` (`resource_type`, `resource_id`)
+) ENGINE=InnoDB AUTO_INCREMENT=1 DEFAULT CHARSET=utf8
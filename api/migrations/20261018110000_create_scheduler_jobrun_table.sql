@@ -0,0 +1,12 @@
+CREATE TABLE `scheduler_jobrun` (
+    `id` int(11) NOT NULL AUTO_INCREMENT,
+    `job` varchar(32) NOT NULL,
+    `status` varchar(16) NOT NULL,
+    `started_at` datetime(6) NOT NULL,
+    `finished_at` datetime(6) DEFAULT NULL,
+    -- NOTE: no foreign key on purpose, the history has to outlive its users
+    `triggered_by_id` int(11) DEFAULT NULL,
+    `message` longtext DEFAULT NULL,
+    PRIMARY KEY (`id`),
+    KEY `scheduler_jobrun_job_started_at` (`job`, `started_at`)
+) ENGINE=InnoDB AUTO_INCREMENT=1 DEFAULT CHARSET=utf8
@@ -0,0 +1,25 @@
+CREATE TABLE `accounting_unmatched_server` (
+    `id` int(11) NOT NULL AUTO_INCREMENT,
+    `instance_id` char(36) NOT NULL,
+    `instance_name` varchar(255) NOT NULL,
+    `status` varchar(64) NOT NULL,
+    `flavor_openstack_id` varchar(255) NOT NULL,
+    `user_openstack_id` varchar(255) NOT NULL,
+    `reason` varchar(255) NOT NULL,
+    -- server as last returned by Nova, serialized as JSON
+    `data` longtext NOT NULL,
+    `first_seen` datetime(6) NOT NULL,
+    `last_seen` datetime(6) NOT NULL,
+    -- NULL while unresolved, otherwise mapped or ignored
+    `resolution` varchar(16) DEFAULT NULL,
+    `resolved_at` datetime(6) DEFAULT NULL,
+    -- flavor and user the server is billed with once it has been mapped
+    `flavor_id` bigint(20) DEFAULT NULL,
+    `user_id` int(11) DEFAULT NULL,
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `instance_id` (`instance_id`),
+    KEY `accounting_unmatched_server_flavor_id_fk_resources_flavor_id` (`flavor_id`),
+    KEY `accounting_unmatched_server_user_id_fk_user_user_id` (`user_id`),
+    CONSTRAINT `accounting_unmatched_server_flavor_id_fk_resources_flavor_id` FOREIGN KEY (`flavor_id`) REFERENCES `resources_flavor` (`id`) ON DELETE SET NULL,
+    CONSTRAINT `accounting_unmatched_server_user_id_fk_user_user_id` FOREIGN KEY (`user_id`) REFERENCES `user_user` (`id`) ON DELETE SET NULL
+) ENGINE=InnoDB AUTO_INCREMENT=1 DEFAULT CHARSET=utf8
@@ -0,0 +1,19 @@
+CREATE TABLE `accounting_serverstate_summary` (
+    `id` int(11) NOT NULL AUTO_INCREMENT,
+    -- fiscal year the archived server states belonged to
+    `year` int(11) NOT NULL,
+    -- period within the fiscal year without flavor price changes
+    `begin` datetime(6) NOT NULL,
+    `end` datetime(6) NOT NULL,
+    `instance_id` varchar(36) NOT NULL,
+    `flavor_id` bigint(20) NOT NULL,
+    `user_id` int(11) NOT NULL,
+    -- seconds the server spent in a consuming status within the period
+    `seconds` bigint(20) NOT NULL,
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `accounting_serverstate_summary_period` (`instance_id`, `flavor_id`, `user_id`, `begin`),
+    KEY `accounting_serverstate_summary_flavor_id_fk_resources_flavor_id` (`flavor_id`),
+    KEY `accounting_serverstate_summary_user_id_fk_user_user_id` (`user_id`),
+    CONSTRAINT `accounting_serverstate_summary_flavor_id_fk_resources_flavor_id` FOREIGN KEY (`flavor_id`) REFERENCES `resources_flavor` (`id`),
+    CONSTRAINT `accounting_serverstate_summary_user_id_fk_user_user_id` FOREIGN KEY (`user_id`) REFERENCES `user_user` (`id`)
+) ENGINE=InnoDB AUTO_INCREMENT=1 DEFAULT CHARSET=utf8
@@ -0,0 +1,30 @@
+CREATE TABLE `accounting_billingpolicy` (
+    `id` int(11) NOT NULL AUTO_INCREMENT,
+    -- server status the policy applies to, statuses without a policy in
+    -- effect are not billed
+    `status` varchar(32) NOT NULL,
+    -- fraction of the flavor price billed while a server is in the status
+    `multiplier` double NOT NULL,
+    `start_time` datetime(6) NOT NULL,
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `accounting_billingpolicy_status_start_time` (`status`, `start_time`)
+) ENGINE=InnoDB AUTO_INCREMENT=1 DEFAULT CHARSET=utf8;
+
+-- keep billing all previously consuming statuses at the full price
+INSERT INTO `accounting_billingpolicy` (`status`, `multiplier`, `start_time`)
+VALUES
+    ('ACTIVE', 1, '1970-01-01 00:00:00'),
+    ('BUILD', 1, '1970-01-01 00:00:00'),
+    ('HARD_REBOOT', 1, '1970-01-01 00:00:00'),
+    ('MIGRATING', 1, '1970-01-01 00:00:00'),
+    ('PASSWORD', 1, '1970-01-01 00:00:00'),
+    ('PAUSED', 1, '1970-01-01 00:00:00'),
+    ('REBOOT', 1, '1970-01-01 00:00:00'),
+    ('REBUILD', 1, '1970-01-01 00:00:00'),
+    ('RESCUE', 1, '1970-01-01 00:00:00'),
+    ('RESIZE', 1, '1970-01-01 00:00:00'),
+    ('REVERT_RESIZE', 1, '1970-01-01 00:00:00'),
+    ('SHUTOFF', 1, '1970-01-01 00:00:00'),
+    ('SUSPENDED', 1, '1970-01-01 00:00:00'),
+    ('UNKNOWN', 1, '1970-01-01 00:00:00'),
+    ('VERIFY_RESIZE', 1, '1970-01-01 00:00:00');
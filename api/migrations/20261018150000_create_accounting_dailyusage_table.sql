@@ -0,0 +1,27 @@
+CREATE TABLE `accounting_dailyusage` (
+    `id` int(11) NOT NULL AUTO_INCREMENT,
+    -- day in UTC the seconds were spent on
+    `day` date NOT NULL,
+    `instance_id` varchar(36) NOT NULL,
+    `flavor_id` bigint(20) NOT NULL,
+    `user_id` int(11) NOT NULL,
+    `status` varchar(18) NOT NULL,
+    -- seconds the server spent in the status on the day, not weighted by
+    -- any billing policy
+    `seconds` bigint(20) NOT NULL,
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `accounting_dailyusage_day_server` (`day`, `instance_id`, `flavor_id`, `user_id`, `status`),
+    KEY `accounting_dailyusage_instance_id_day` (`instance_id`, `day`),
+    KEY `accounting_dailyusage_user_id_day` (`user_id`, `day`),
+    KEY `accounting_dailyusage_flavor_id_fk_resources_flavor_id` (`flavor_id`),
+    CONSTRAINT `accounting_dailyusage_flavor_id_fk_resources_flavor_id` FOREIGN KEY (`flavor_id`) REFERENCES `resources_flavor` (`id`),
+    CONSTRAINT `accounting_dailyusage_user_id_fk_user_user_id` FOREIGN KEY (`user_id`) REFERENCES `user_user` (`id`)
+) ENGINE=InnoDB AUTO_INCREMENT=1 DEFAULT CHARSET=utf8;
+
+-- days whose usage is completely aggregated, all others are calculated from
+-- the server states
+CREATE TABLE `accounting_dailyusage_day` (
+    `day` date NOT NULL,
+    `aggregated_at` datetime(6) NOT NULL,
+    PRIMARY KEY (`day`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8;
@@ -0,0 +1,14 @@
+CREATE TABLE `user_project_class_history` (
+    `id` int(11) NOT NULL AUTO_INCREMENT,
+    `project_id` int(11) NOT NULL,
+    -- user class of the project from the start time until the next entry
+    `user_class` smallint(5) unsigned NOT NULL,
+    `start_time` datetime(6) NOT NULL,
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `user_project_class_history_project_id_start_time` (`project_id`, `start_time`),
+    CONSTRAINT `user_project_class_history_project_id_fk_user_project_id` FOREIGN KEY (`project_id`) REFERENCES `user_project` (`id`) ON DELETE CASCADE
+) ENGINE=InnoDB AUTO_INCREMENT=1 DEFAULT CHARSET=utf8;
+
+-- keep pricing existing projects with their current user class
+INSERT INTO `user_project_class_history` (`project_id`, `user_class`, `start_time`)
+SELECT `id`, `user_class`, '1970-01-01 00:00:00' FROM `user_project`;
@@ -0,0 +1,27 @@
+CREATE TABLE `accounting_volumestate` (
+    `id` int(11) NOT NULL AUTO_INCREMENT,
+    `begin` datetime(6) NOT NULL,
+    `end` datetime(6) DEFAULT NULL,
+    `volume_id` char(36) NOT NULL,
+    `volume_name` varchar(255) NOT NULL,
+    -- size of the volume in GiB
+    `size` int(10) unsigned NOT NULL,
+    `volume_type` varchar(255) NOT NULL,
+    `status` varchar(32) NOT NULL,
+    `user_id` int(11) NOT NULL,
+    PRIMARY KEY (`id`),
+    KEY `accounting_volumestate_volume_id` (`volume_id`),
+    KEY `accounting_volumestate_user_id_fk_user_user_id` (`user_id`),
+    CONSTRAINT `accounting_volumestate_user_id_fk_user_user_id` FOREIGN KEY (`user_id`) REFERENCES `user_user` (`id`)
+) ENGINE=InnoDB AUTO_INCREMENT=1 DEFAULT CHARSET=utf8;
+
+CREATE TABLE `pricing_volumeprice` (
+    `id` int(11) NOT NULL AUTO_INCREMENT,
+    `volume_type` varchar(255) NOT NULL,
+    `user_class` smallint(5) unsigned NOT NULL,
+    -- price of one GiB for a whole year
+    `unit_price` double NOT NULL,
+    `start_time` datetime(6) NOT NULL,
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `pricing_volumeprice_volume_type_user_class_start_time` (`volume_type`, `user_class`, `start_time`)
+) ENGINE=InnoDB AUTO_INCREMENT=1 DEFAULT CHARSET=utf8;
@@ -0,0 +1,25 @@
+CREATE TABLE `accounting_floatingipstate` (
+    `id` int(11) NOT NULL AUTO_INCREMENT,
+    `begin` datetime(6) NOT NULL,
+    `end` datetime(6) DEFAULT NULL,
+    `floating_ip_id` char(36) NOT NULL,
+    `floating_ip_address` varchar(45) NOT NULL,
+    -- name of the external network the address is allocated from
+    `pool` varchar(255) NOT NULL,
+    `user_id` int(11) NOT NULL,
+    PRIMARY KEY (`id`),
+    KEY `accounting_floatingipstate_floating_ip_id` (`floating_ip_id`),
+    KEY `accounting_floatingipstate_user_id_fk_user_user_id` (`user_id`),
+    CONSTRAINT `accounting_floatingipstate_user_id_fk_user_user_id` FOREIGN KEY (`user_id`) REFERENCES `user_user` (`id`)
+) ENGINE=InnoDB AUTO_INCREMENT=1 DEFAULT CHARSET=utf8;
+
+CREATE TABLE `pricing_floatingipprice` (
+    `id` int(11) NOT NULL AUTO_INCREMENT,
+    `pool` varchar(255) NOT NULL,
+    `user_class` smallint(5) unsigned NOT NULL,
+    -- price of one floating IP for a whole year
+    `unit_price` double NOT NULL,
+    `start_time` datetime(6) NOT NULL,
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `pricing_floatingipprice_pool_user_class_start_time` (`pool`, `user_class`, `start_time`)
+) ENGINE=InnoDB AUTO_INCREMENT=1 DEFAULT CHARSET=utf8;
@@ -0,0 +1,60 @@
+use avina_wire::user::{API_KEY_PREFIX, ApiKeyScope};
+use rand::{Rng, distr::Alphanumeric};
+use sha2::{Digest, Sha256};
+
+const API_KEY_SECRET_LENGTH: usize = 40;
+// NOTE: long enough to tell keys apart in listings, short enough to be
+// useless for brute forcing the remaining characters.
+const API_KEY_VISIBLE_LENGTH: usize = 8;
+
+pub fn generate_api_key() -> String {
+    let secret: String = rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(API_KEY_SECRET_LENGTH)
+        .map(char::from)
+        .collect();
+    format!("{API_KEY_PREFIX}{secret}")
+}
+
+pub fn hash_api_key(key: &str) -> String {
+    format!("{:x}", Sha256::digest(key.as_bytes()))
+}
+
+pub fn api_key_visible_prefix(key: &str) -> String {
+    key.chars()
+        .take(API_KEY_PREFIX.len() + API_KEY_VISIBLE_LENGTH)
+        .collect()
+}
+
+/// Maps a request path like `/api/accounting/serverstates/` to the scope an
+/// API key needs to access it.
+pub fn scope_for_path(path: &str) -> Option<ApiKeyScope> {
+    let area = path.strip_prefix("/api/")?.split('/').next()?;
+    area.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_api_keys_are_unique_and_prefixed() {
+        let key1 = generate_api_key();
+        let key2 = generate_api_key();
+        assert!(key1.starts_with(API_KEY_PREFIX));
+        assert_eq!(key1.len(), API_KEY_PREFIX.len() + API_KEY_SECRET_LENGTH);
+        assert_ne!(key1, key2);
+        assert_ne!(hash_api_key(&key1), hash_api_key(&key2));
+    }
+
+    #[test]
+    fn scope_for_path_uses_first_path_segment() {
+        assert_eq!(
+            scope_for_path("/api/accounting/serverstates/import/"),
+            Some(ApiKeyScope::Accounting)
+        );
+        assert_eq!(scope_for_path("/api/user/import"), Some(ApiKeyScope::User));
+        assert_eq!(scope_for_path("/api/hello"), None);
+        assert_eq!(scope_for_path("/health_check"), None);
+    }
+}
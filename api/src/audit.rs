@@ -0,0 +1,263 @@
+use actix_web::{
+    HttpMessage,
+    body::{BoxBody, MessageBody, to_bytes},
+    dev::{ServiceRequest, ServiceResponse},
+    http::Method,
+    middleware::Next,
+    web::Data,
+};
+use anyhow::Context;
+use avina_wire::user::User;
+use chrono::Utc;
+use serde_json::Value;
+use sqlx::{MySql, MySqlPool, Transaction};
+
+use crate::{
+    authentication::Impersonator,
+    database::{
+        accounting::server_state::select_maybe_server_state_from_db,
+        audit::{NewAuditLogEntry, insert_audit_log_entry_into_db},
+        budgeting::{
+            project_budget::select_maybe_project_budget_from_db,
+            user_budget::select_maybe_user_budget_from_db,
+        },
+        pricing::flavor_price::select_maybe_flavor_price_from_db,
+        quota::flavor_quota::select_maybe_flavor_quota_from_db,
+        resources::{
+            flavor::select_maybe_flavor_from_db,
+            flavor_group::select_maybe_flavor_group_from_db,
+        },
+        user::{
+            api_key::select_maybe_api_key_from_db,
+            project::select_maybe_project_from_db,
+            user::select_maybe_user_from_db,
+        },
+    },
+    error::internal_server_error,
+};
+
+/// Splits a path like `/api/budgeting/userbudgets/3/` into the resource type
+/// `userbudgets` and the resource id `3`.
+pub fn resource_from_path(path: &str) -> (Option<String>, Option<u32>) {
+    let Some(path) = path.strip_prefix("/api/") else {
+        return (None, None);
+    };
+    let segments = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>();
+    let resource_type = segments.get(1).or(segments.first());
+    let resource_id = segments.get(2).and_then(|id| id.parse().ok());
+    (resource_type.map(|r| r.to_string()), resource_id)
+}
+
+fn to_json(value: impl serde::Serialize) -> Result<Value, anyhow::Error> {
+    serde_json::to_value(value).context("Failed to serialize resource")
+}
+
+async fn select_resource_json(
+    transaction: &mut Transaction<'_, MySql>,
+    resource_type: &str,
+    resource_id: u64,
+) -> Result<Option<Value>, anyhow::Error> {
+    let id = resource_id;
+    match resource_type {
+        "projects" => select_maybe_project_from_db(transaction, id)
+            .await?
+            .map(to_json),
+        "users" => select_maybe_user_from_db(transaction, id)
+            .await?
+            .map(to_json),
+        "apikeys" => select_maybe_api_key_from_db(transaction, id)
+            .await?
+            .map(to_json),
+        "flavors" => select_maybe_flavor_from_db(transaction, id)
+            .await?
+            .map(to_json),
+        "flavorgroups" => select_maybe_flavor_group_from_db(transaction, id)
+            .await?
+            .map(to_json),
+        "flavorprices" => select_maybe_flavor_price_from_db(transaction, id)
+            .await?
+            .map(to_json),
+        "flavorquotas" => select_maybe_flavor_quota_from_db(transaction, id)
+            .await?
+            .map(to_json),
+        "serverstates" => select_maybe_server_state_from_db(transaction, id)
+            .await?
+            .map(to_json),
+        "projectbudgets" => {
+            select_maybe_project_budget_from_db(transaction, id)
+                .await?
+                .map(to_json)
+        }
+        "userbudgets" => select_maybe_user_budget_from_db(transaction, id)
+            .await?
+            .map(to_json),
+        _ => None,
+    }
+    .transpose()
+}
+
+async fn select_resource_json_from_pool(
+    db_pool: &MySqlPool,
+    resource_type: &str,
+    resource_id: u32,
+) -> Result<Option<Value>, anyhow::Error> {
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let value = select_resource_json(
+        &mut transaction,
+        resource_type,
+        resource_id as u64,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(value)
+}
+
+async fn insert_audit_log_entry(
+    db_pool: &MySqlPool,
+    new_entry: &NewAuditLogEntry,
+) -> Result<(), anyhow::Error> {
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    insert_audit_log_entry_into_db(&mut transaction, new_entry).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(())
+}
+
+// NOTE: the secret of a new API key is only ever returned once and must not
+// end up in the audit log.
+fn redact(resource_type: Option<&str>, mut value: Value) -> Value {
+    if resource_type == Some("apikeys")
+        && let Some(object) = value.as_object_mut()
+    {
+        object.remove("key");
+    }
+    value
+}
+
+/// Records every POST, PATCH and DELETE request in the audit log, together
+/// with the state of the affected resource before and after the request.
+pub async fn record_audit_log(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, actix_web::Error> {
+    let method = req.method().clone();
+    if !matches!(method, Method::POST | Method::PATCH | Method::DELETE) {
+        return Ok(next.call(req).await?.map_into_boxed_body());
+    }
+    let Some(db_pool) = req.app_data::<Data<MySqlPool>>().cloned() else {
+        return Err(internal_server_error(
+            "No database connection pool in application state",
+        ));
+    };
+    let (actor, impersonated) = {
+        let extensions = req.extensions();
+        let Some(user) = extensions.get::<User>().cloned() else {
+            return Err(internal_server_error("No user in request extensions"));
+        };
+        match extensions.get::<Impersonator>() {
+            Some(impersonator) => (impersonator.0.clone(), Some(user)),
+            None => (user, None),
+        }
+    };
+    let endpoint = req.path().to_string();
+    let (resource_type, mut resource_id) = resource_from_path(&endpoint);
+    let before = match (&resource_type, resource_id) {
+        (Some(resource_type), Some(resource_id)) if method != Method::POST => {
+            select_resource_json_from_pool(&db_pool, resource_type, resource_id)
+                .await
+                .unwrap_or_else(|error| {
+                    tracing::warn!(
+                        "Failed to select audited resource: {error}"
+                    );
+                    None
+                })
+        }
+        _ => None,
+    };
+
+    let res = next.call(req).await?;
+    let status = res.status();
+    let (req, res) = res.into_parts();
+    let (res, body) = res.into_parts();
+    let Ok(body) = to_bytes(body).await else {
+        return Err(internal_server_error("Failed to read response body"));
+    };
+    let after = if status.is_success() {
+        serde_json::from_slice::<Value>(&body)
+            .ok()
+            .map(|value| redact(resource_type.as_deref(), value))
+    } else {
+        None
+    };
+    if resource_id.is_none() && method == Method::POST {
+        resource_id = after
+            .as_ref()
+            .and_then(|after| after.get("id"))
+            .and_then(Value::as_u64)
+            .map(|id| id as u32);
+    }
+
+    let new_entry = NewAuditLogEntry {
+        timestamp: Utc::now(),
+        method: method.to_string(),
+        endpoint,
+        status: status.as_u16(),
+        resource_type,
+        resource_id,
+        actor: actor.id,
+        actor_name: actor.name,
+        impersonated: impersonated.as_ref().map(|i| i.id),
+        impersonated_name: impersonated.map(|i| i.name),
+        before,
+        after,
+    };
+    if let Err(error) = insert_audit_log_entry(&db_pool, &new_entry).await {
+        tracing::error!("Failed to record audit log entry: {error:?}");
+    }
+
+    Ok(ServiceResponse::new(req, res.set_body(BoxBody::new(body))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resource_from_path_extracts_type_and_id() {
+        assert_eq!(
+            resource_from_path("/api/budgeting/userbudgets/3/"),
+            (Some("userbudgets".to_string()), Some(3))
+        );
+        assert_eq!(
+            resource_from_path("/api/pricing/flavorprices/"),
+            (Some("flavorprices".to_string()), None)
+        );
+        assert_eq!(
+            resource_from_path("/api/tokencache/"),
+            (Some("tokencache".to_string()), None)
+        );
+        assert_eq!(resource_from_path("/health_check"), (None, None));
+    }
+
+    #[test]
+    fn redact_drops_api_key_secret() {
+        let value = serde_json::json!({"id": 1, "key": "avina_secret"});
+        let redacted = redact(Some("apikeys"), value.clone());
+        assert!(redacted.get("key").is_none());
+        assert_eq!(redact(Some("users"), value.clone()), value);
+    }
+}
@@ -2,15 +2,28 @@ use actix_web::{
     HttpMessage,
     body::MessageBody,
     dev::{ServiceRequest, ServiceResponse},
+    http::header::AUTHORIZATION,
     middleware::Next,
     web::Data,
 };
 use avina_wire::user::{Project, User};
-use sqlx::MySqlPool;
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, FromRow, MySqlPool};
+use tracing::Instrument;
 
 use crate::{
-    error::{bad_request_error, internal_server_error, unauthorized_error},
+    api_key::{hash_api_key, scope_for_path},
+    authorization::{Action, Target, can},
+    database::user::{
+        api_key::parse_api_key_scopes, project::select_maybe_project_from_db,
+        user::select_maybe_user_from_db,
+    },
+    error::{
+        OpenStackError, bad_request_error, forbidden_error,
+        internal_server_error, service_unavailable_error, unauthorized_error,
+    },
     openstack::{OpenStack, ProjectMinimal as OpenstackProjectMinimal},
+    token_cache::TokenCache,
 };
 
 // TODO revise error handling here as well and test errors
@@ -18,10 +31,31 @@ use crate::{
 #[derive(Clone, Debug)]
 pub struct Token(pub String);
 
+/// The authenticated user behind a request, inserted into the request
+/// extensions only when they impersonate another user via `X-Impersonate`.
+/// The `User` and `Project` extensions always hold the effective identity.
+#[derive(Clone, Debug)]
+pub struct Impersonator(pub User);
+
 pub async fn require_valid_token(
     req: ServiceRequest,
     next: Next<impl MessageBody>,
 ) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+    if req.headers().get("X-Auth-Token").is_none()
+        && let Some(authorization) = req.headers().get(AUTHORIZATION)
+    {
+        let Ok(authorization) = authorization.to_str() else {
+            return Err(bad_request_error(
+                "Authorization header is not a valid string",
+            ));
+        };
+        let Some(key) = authorization.strip_prefix("Bearer ") else {
+            return Err(unauthorized_error("Unsupported authorization scheme"));
+        };
+        let os_project = validate_api_key(&req, key.trim()).await?;
+        req.extensions_mut().insert(os_project);
+        return next.call(req).await;
+    }
     let Some(token) = req.headers().get("X-Auth-Token") else {
         return Err(unauthorized_error("No token in request header"));
     };
@@ -33,14 +67,130 @@ pub async fn require_valid_token(
             "No OpenStack client in application state",
         ));
     };
-    let Ok(os_project) = openstack.validate_user_token(token).await else {
-        return Err(unauthorized_error("Failed to validate user token"));
+    let Some(token_cache) = req.app_data::<Data<TokenCache>>() else {
+        return Err(internal_server_error(
+            "No token cache in application state",
+        ));
+    };
+    let os_project = match token_cache.get(token) {
+        Some(os_project) => os_project,
+        None => {
+            let validated = match openstack.validate_user_token(token).await {
+                Ok(validated) => validated,
+                Err(OpenStackError::ServiceUnavailableError(error)) => {
+                    tracing::error!("Failed to validate user token: {error}");
+                    return Err(service_unavailable_error(
+                        "OpenStack is currently unavailable",
+                    ));
+                }
+                Err(OpenStackError::UnexpectedError(_)) => {
+                    return Err(unauthorized_error(
+                        "Failed to validate user token",
+                    ));
+                }
+            };
+            token_cache.insert(
+                token,
+                validated.project.clone(),
+                validated.expires_at,
+            );
+            validated.project
+        }
     };
     req.extensions_mut().insert(Token(token.into()));
     req.extensions_mut().insert(os_project);
     next.call(req).await
 }
 
+// NOTE: API keys stand in for the Keystone token of their user, so no Token
+// extension is inserted and endpoints forwarding it to other services are
+// not available to them.
+async fn validate_api_key(
+    req: &ServiceRequest,
+    key: &str,
+) -> Result<OpenstackProjectMinimal, actix_web::Error> {
+    let Some(db_pool) = req.app_data::<Data<MySqlPool>>() else {
+        return Err(internal_server_error(
+            "No database connection pool in application state",
+        ));
+    };
+
+    #[derive(FromRow)]
+    struct Row {
+        id: i32,
+        scopes: String,
+        expires_at: Option<DateTime<Utc>>,
+        user_name: String,
+        user_openstack_id: String,
+    }
+
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            k.id AS id,
+            k.scopes AS scopes,
+            k.expires_at AS expires_at,
+            user.name AS user_name,
+            user.openstack_id AS user_openstack_id
+        FROM user_apikey AS k, user_user AS user
+        WHERE
+            k.user_id = user.id AND
+            k.key_hash = ?
+        "#,
+        hash_api_key(key)
+    );
+    let Ok(row) = db_pool.get_ref().fetch_optional(query).await else {
+        return Err(internal_server_error(
+            "Failed to retrieve API key from database",
+        ));
+    };
+    let Some(row) = row else {
+        return Err(unauthorized_error("Invalid API key"));
+    };
+    let Ok(row) = Row::from_row(&row) else {
+        return Err(internal_server_error("Failed to parse API key row"));
+    };
+    let now = Utc::now();
+    if row.expires_at.is_some_and(|expires_at| expires_at <= now) {
+        return Err(unauthorized_error("API key has expired"));
+    }
+    let Ok(scopes) = parse_api_key_scopes(row.scopes.as_str()) else {
+        return Err(internal_server_error("Failed to parse API key scopes"));
+    };
+    // otherwise a key could mint itself a successor with broader scopes
+    if req.path().starts_with("/api/user/apikeys") {
+        return Err(forbidden_error(
+            "API keys cannot be used to manage API keys",
+        ));
+    }
+    match scope_for_path(req.path()) {
+        Some(scope) if scopes.contains(&scope) => {}
+        _ => {
+            return Err(forbidden_error(
+                "API key is not valid for this endpoint",
+            ));
+        }
+    }
+
+    let query = sqlx::query!(
+        r#"
+        UPDATE user_apikey
+        SET last_used_at = ?
+        WHERE id = ?
+        "#,
+        now,
+        row.id
+    );
+    if let Err(error) = db_pool.get_ref().execute(query).await {
+        tracing::warn!("Failed to update last use of API key: {error}");
+    }
+
+    Ok(OpenstackProjectMinimal {
+        id: row.user_openstack_id,
+        name: row.user_name,
+    })
+}
+
 pub async fn extract_user_and_project(
     req: ServiceRequest,
     next: Next<impl MessageBody>,
@@ -102,13 +252,20 @@ pub async fn extract_user_and_project(
         ));
     };
 
+    let role = match row.user_role.try_into() {
+        Ok(r) => r,
+        Err(_) => {
+            return Err(internal_server_error("Failed to parse user role"));
+        }
+    };
+
     let user = User {
         id: row.user_id as u32,
         name: row.user_name,
         openstack_id: row.user_openstack_id,
         project: row.project_id as u32,
         project_name: row.project_name.clone(),
-        role: row.user_role,
+        role,
         is_staff: row.user_is_staff != 0,
         is_active: row.user_is_active != 0,
     };
@@ -127,8 +284,86 @@ pub async fn extract_user_and_project(
         user_class,
     };
 
+    let impersonate = match req.headers().get("X-Impersonate") {
+        Some(header) => {
+            let Ok(header) = header.to_str() else {
+                return Err(bad_request_error(
+                    "Impersonate header is not a valid string",
+                ));
+            };
+            let Ok(user_id) = header.trim().parse::<u32>() else {
+                return Err(bad_request_error(
+                    "Impersonate header is not a valid user ID",
+                ));
+            };
+            Some(user_id)
+        }
+        None => None,
+    };
+
+    let (user, project, impersonator) = match impersonate {
+        Some(user_id) => {
+            if !can(&user, Action::Impersonate, Target::Global) {
+                return Err(forbidden_error(
+                    "Admin privileges required for impersonation",
+                ));
+            }
+            let (impersonated_user, impersonated_project) =
+                select_impersonated_user_and_project(db_pool, user_id).await?;
+            (impersonated_user, impersonated_project, Some(user))
+        }
+        None => (user, project, None),
+    };
+
+    let (real_user_id, real_user_name) = match &impersonator {
+        Some(impersonator) => (impersonator.id, impersonator.name.clone()),
+        None => (user.id, user.name.clone()),
+    };
+    let span = tracing::info_span!(
+        "authenticated_request",
+        real_user_id,
+        real_user_name,
+        effective_user_id = user.id,
+        effective_user_name = user.name,
+        impersonated = impersonator.is_some(),
+    );
+
     req.extensions_mut().insert(user);
     req.extensions_mut().insert(project);
+    if let Some(impersonator) = impersonator {
+        req.extensions_mut().insert(Impersonator(impersonator));
+    }
 
-    next.call(req).await
+    next.call(req).instrument(span).await
+}
+
+async fn select_impersonated_user_and_project(
+    db_pool: &MySqlPool,
+    user_id: u32,
+) -> Result<(User, Project), actix_web::Error> {
+    let Ok(mut transaction) = db_pool.begin().await else {
+        return Err(internal_server_error("Failed to begin transaction"));
+    };
+    let Ok(user) =
+        select_maybe_user_from_db(&mut transaction, user_id as u64).await
+    else {
+        return Err(internal_server_error(
+            "Failed to retrieve impersonated user from database",
+        ));
+    };
+    let Some(user) = user else {
+        return Err(bad_request_error("User to impersonate does not exist"));
+    };
+    let Ok(Some(project)) =
+        select_maybe_project_from_db(&mut transaction, user.project as u64)
+            .await
+    else {
+        return Err(internal_server_error(
+            "Failed to retrieve impersonated project from database",
+        ));
+    };
+    if transaction.commit().await.is_err() {
+        return Err(internal_server_error("Failed to commit transaction"));
+    }
+    Ok((user, project))
 }
@@ -1,68 +1,154 @@
-use avina_wire::user::User;
+use avina_wire::user::{Role, User};
 
 use crate::error::{AuthOnlyError, NotFoundOnlyError};
 
-pub fn require_admin_user(user: &User) -> Result<(), AuthOnlyError> {
-    if !user.is_staff {
-        return Err(AuthOnlyError::AuthorizationError(
-            "Admin privileges required".to_string(),
-        ));
+/// What a user wants to do with a [`Target`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Read the target itself, e.g. a project or a user and their resources.
+    View,
+    /// Read resources of all members of the target project.
+    ViewMembers,
+    /// Change budgets belonging to the target.
+    ModifyBudget,
+    /// Read the audit log of mutating operations.
+    ViewAuditLog,
+    /// Act as another user, whose privileges then apply instead.
+    Impersonate,
+    /// Anything else that changes state or spans all projects.
+    Administrate,
+}
+
+/// What an [`Action`] is applied to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    /// Everything, independent of any project.
+    Global,
+    Project(u32),
+    User {
+        id: u32,
+        project: u32,
+    },
+}
+
+/// How far a capability granted by a role reaches.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Reach {
+    /// Only the user themself and the project they belong to.
+    Own,
+    /// The project of the user and all its members.
+    Project,
+}
+
+fn capabilities(role: Role) -> &'static [(Action, Reach)] {
+    match role {
+        Role::User => &[(Action::View, Reach::Own)],
+        Role::MasterUser => &[
+            (Action::View, Reach::Project),
+            (Action::ViewMembers, Reach::Project),
+            (Action::ModifyBudget, Reach::Project),
+        ],
+    }
+}
+
+fn reaches(user: &User, reach: Reach, target: Target) -> bool {
+    match (reach, target) {
+        (_, Target::Global) => false,
+        (_, Target::Project(project)) => project == user.project,
+        (Reach::Own, Target::User { id, .. }) => id == user.id,
+        (Reach::Project, Target::User { project, .. }) => {
+            project == user.project
+        }
+    }
+}
+
+pub fn can(user: &User, action: Action, target: Target) -> bool {
+    if user.is_staff {
+        return true;
+    }
+    capabilities(user.role)
+        .iter()
+        .any(|(a, reach)| *a == action && reaches(user, *reach, target))
+}
+
+pub fn require(
+    user: &User,
+    action: Action,
+    target: Target,
+    message: &str,
+) -> Result<(), AuthOnlyError> {
+    if !can(user, action, target) {
+        return Err(AuthOnlyError::AuthorizationError(message.to_string()));
     }
     Ok(())
 }
 
-pub fn require_admin_user_or_return_not_found(
+pub fn require_or_return_not_found(
     user: &User,
+    action: Action,
+    target: Target,
 ) -> Result<(), NotFoundOnlyError> {
-    if !user.is_staff {
+    if !can(user, action, target) {
         return Err(NotFoundOnlyError::NotFoundError);
     }
     Ok(())
 }
 
+pub fn require_admin_user(user: &User) -> Result<(), AuthOnlyError> {
+    require(
+        user,
+        Action::Administrate,
+        Target::Global,
+        "Admin privileges required",
+    )
+}
+
+pub fn require_admin_user_or_return_not_found(
+    user: &User,
+) -> Result<(), NotFoundOnlyError> {
+    require_or_return_not_found(user, Action::Administrate, Target::Global)
+}
+
 pub fn require_master_user(
     user: &User,
     project_id: u32,
 ) -> Result<(), AuthOnlyError> {
-    if !user.is_staff && (user.role != 2 || user.project != project_id) {
-        return Err(AuthOnlyError::AuthorizationError(
-            "Admin or master user privileges for respective project required"
-                .to_string(),
-        ));
-    }
-    Ok(())
+    require(
+        user,
+        Action::ViewMembers,
+        Target::Project(project_id),
+        "Admin or master user privileges for respective project required",
+    )
 }
 
 pub fn require_master_user_or_return_not_found(
     user: &User,
     project_id: u32,
 ) -> Result<(), NotFoundOnlyError> {
-    if !user.is_staff && (user.role != 2 || user.project != project_id) {
-        return Err(NotFoundOnlyError::NotFoundError);
-    }
-    Ok(())
+    require_or_return_not_found(
+        user,
+        Action::ViewMembers,
+        Target::Project(project_id),
+    )
 }
 
 pub fn require_project_user(
     user: &User,
     project_id: u32,
 ) -> Result<(), AuthOnlyError> {
-    if !user.is_staff && user.project != project_id {
-        return Err(AuthOnlyError::AuthorizationError(
-            "Must be admin or user of respective project".to_string(),
-        ));
-    }
-    Ok(())
+    require(
+        user,
+        Action::View,
+        Target::Project(project_id),
+        "Must be admin or user of respective project",
+    )
 }
 
 pub fn require_project_user_or_return_not_found(
     user: &User,
     project_id: u32,
 ) -> Result<(), NotFoundOnlyError> {
-    if !user.is_staff && user.project != project_id {
-        return Err(NotFoundOnlyError::NotFoundError);
-    }
-    Ok(())
+    require_or_return_not_found(user, Action::View, Target::Project(project_id))
 }
 
 pub fn require_user_or_project_master_or_not_found(
@@ -70,12 +156,79 @@ pub fn require_user_or_project_master_or_not_found(
     user_id: u32,
     project_id: u32,
 ) -> Result<(), NotFoundOnlyError> {
-    #[allow(clippy::nonminimal_bool)]
-    if !user.is_staff
-        && !(user.role == 1 && user.id == user_id)
-        && !(user.role == 2 && user.project == project_id)
-    {
-        return Err(NotFoundOnlyError::NotFoundError);
+    require_or_return_not_found(
+        user,
+        Action::View,
+        Target::User {
+            id: user_id,
+            project: project_id,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(id: u32, project: u32, role: Role, is_staff: bool) -> User {
+        User {
+            id,
+            name: format!("user{id}"),
+            openstack_id: String::new(),
+            project,
+            project_name: format!("project{project}"),
+            role,
+            is_staff,
+            is_active: true,
+        }
+    }
+
+    #[test]
+    fn staff_can_do_everything() {
+        let admin = user(1, 1, Role::User, true);
+        assert!(can(&admin, Action::Administrate, Target::Global));
+        assert!(can(&admin, Action::ModifyBudget, Target::Project(2)));
+        assert!(can(&admin, Action::Impersonate, Target::Global));
+    }
+
+    #[test]
+    fn normal_user_only_reaches_themself_and_their_project() {
+        let normal = user(1, 1, Role::User, false);
+        assert!(can(&normal, Action::View, Target::Project(1)));
+        assert!(!can(&normal, Action::View, Target::Project(2)));
+        assert!(can(
+            &normal,
+            Action::View,
+            Target::User { id: 1, project: 1 }
+        ));
+        assert!(!can(
+            &normal,
+            Action::View,
+            Target::User { id: 2, project: 1 }
+        ));
+        assert!(!can(&normal, Action::ViewMembers, Target::Project(1)));
+        assert!(!can(&normal, Action::ModifyBudget, Target::Project(1)));
+        assert!(!can(&normal, Action::Administrate, Target::Global));
+        assert!(!can(&normal, Action::Impersonate, Target::Global));
+    }
+
+    #[test]
+    fn master_user_reaches_members_of_their_project() {
+        let master = user(1, 1, Role::MasterUser, false);
+        assert!(can(
+            &master,
+            Action::View,
+            Target::User { id: 2, project: 1 }
+        ));
+        assert!(!can(
+            &master,
+            Action::View,
+            Target::User { id: 2, project: 2 }
+        ));
+        assert!(can(&master, Action::ViewMembers, Target::Project(1)));
+        assert!(can(&master, Action::ModifyBudget, Target::Project(1)));
+        assert!(!can(&master, Action::ModifyBudget, Target::Project(2)));
+        assert!(!can(&master, Action::Administrate, Target::Global));
+        assert!(!can(&master, Action::Impersonate, Target::Global));
     }
-    Ok(())
 }
@@ -1,3 +1,9 @@
+use std::{fs::File, io::BufReader, sync::Arc};
+
+use actix_cors::Cors;
+use anyhow::{Context, anyhow};
+use avina_wire::scheduler::JobName;
+use rustls::{ServerConfig, crypto::ring::default_provider};
 use secrecy::{ExposeSecret, SecretString};
 use serde::Deserialize;
 use serde_aux::field_attributes::deserialize_number_from_string;
@@ -8,6 +14,10 @@ pub struct Settings {
     pub database: DatabaseSettings,
     pub application: ApplicationSettings,
     pub openstack: OpenStackSettings,
+    #[serde(default)]
+    pub scheduler: SchedulerSettings,
+    #[serde(default)]
+    pub accounting: AccountingSettings,
 }
 
 #[derive(Clone, serde::Deserialize)]
@@ -18,6 +28,57 @@ pub struct ApplicationSettings {
     pub base_url: String,
     pub insert_admin: bool,
     pub cloud_usage_url: Option<String>,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub token_cache_capacity: usize,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub token_cache_max_ttl: u64,
+    pub cors: CorsSettings,
+    #[serde(default)]
+    pub tls: TlsSettings,
+}
+
+/// An entry of `*` allows any origin, method or header respectively.
+#[derive(Clone, serde::Deserialize)]
+pub struct CorsSettings {
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<String>,
+    pub allowed_headers: Vec<String>,
+    /// Seconds browsers may cache the result of a preflight request.
+    pub max_age: Option<usize>,
+}
+
+/// The server listens on HTTPS instead of plain HTTP when both paths are set.
+#[derive(Clone, Default, serde::Deserialize)]
+pub struct TlsSettings {
+    pub certificate: Option<String>,
+    pub key: Option<String>,
+}
+
+#[derive(Clone, Default, serde::Deserialize)]
+pub struct SchedulerSettings {
+    pub enabled: bool,
+    #[serde(default)]
+    pub jobs: Vec<JobSettings>,
+}
+
+/// Either an interval in seconds or a cron expression with seconds, e.g.
+/// `0 0 * * * *` for every full hour.
+#[derive(Clone, serde::Deserialize)]
+pub struct JobSettings {
+    pub job: JobName,
+    pub interval: Option<u64>,
+    pub cron: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+pub struct AccountingSettings {
+    /// Number of closed fiscal years whose server states are kept when
+    /// compacting, older ones are archived into summaries. Nothing is
+    /// archived when unset.
+    pub retention_years: Option<u32>,
+    /// Unit price of the flavor prices created when initializing prices,
+    /// 0 when unset.
+    pub default_flavor_price: Option<f64>,
 }
 
 fn deserialize_secret_string<'de, D>(
@@ -52,6 +113,8 @@ pub struct OpenStackSettings {
     pub domain_id: String,
     pub keystone_endpoint: String,
     pub nova_endpoint: String,
+    pub cinder_endpoint: String,
+    pub neutron_endpoint: String,
 }
 
 impl DatabaseSettings {
@@ -74,6 +137,71 @@ impl DatabaseSettings {
     }
 }
 
+impl CorsSettings {
+    pub fn cors(&self) -> Cors {
+        let mut cors = Cors::default().expose_any_header();
+        for origin in &self.allowed_origins {
+            cors = if origin == "*" {
+                cors.allow_any_origin()
+            } else {
+                cors.allowed_origin(origin)
+            };
+        }
+        cors = if self.allowed_methods.iter().any(|m| m == "*") {
+            cors.allow_any_method()
+        } else {
+            cors.allowed_methods(
+                self.allowed_methods.iter().map(String::as_str),
+            )
+        };
+        cors = if self.allowed_headers.iter().any(|h| h == "*") {
+            cors.allow_any_header()
+        } else {
+            cors.allowed_headers(
+                self.allowed_headers.iter().map(String::as_str),
+            )
+        };
+        cors.max_age(self.max_age)
+    }
+}
+
+impl TlsSettings {
+    pub fn server_config(&self) -> Result<Option<ServerConfig>, anyhow::Error> {
+        let (certificate, key) = match (&self.certificate, &self.key) {
+            (Some(certificate), Some(key)) => (certificate, key),
+            (None, None) => return Ok(None),
+            _ => {
+                return Err(anyhow!(
+                    "TLS requires both a certificate and a key path"
+                ));
+            }
+        };
+        let mut reader = BufReader::new(
+            File::open(certificate)
+                .context("Failed to open TLS certificate file")?,
+        );
+        let certificates = rustls_pemfile::certs(&mut reader)
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to parse TLS certificate file")?;
+        let mut reader = BufReader::new(
+            File::open(key).context("Failed to open TLS key file")?,
+        );
+        let key = rustls_pemfile::private_key(&mut reader)
+            .context("Failed to parse TLS key file")?
+            .ok_or_else(|| anyhow!("No private key found in TLS key file"))?;
+        // NOTE: other dependencies may enable further crypto providers, so
+        // the process-wide default cannot be relied upon.
+        let config =
+            ServerConfig::builder_with_provider(Arc::new(default_provider()))
+                .with_safe_default_protocol_versions()
+                .context("Failed to select TLS protocol versions")?
+                .with_no_client_auth()
+                .with_single_cert(certificates, key)
+                .context("Failed to build TLS server configuration")?;
+        Ok(Some(config))
+    }
+}
+
 pub fn get_configuration() -> Result<Settings, config::ConfigError> {
     let base_path = std::env::current_dir()
         .expect("Failed to determine current directory.");
@@ -0,0 +1,167 @@
+use anyhow::Context;
+use avina_wire::accounting::{BillingPolicy, BillingPolicyCreateData};
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, FromRow, MySql, Transaction};
+
+use crate::error::{
+    MinimalApiError, NotFoundOrUnexpectedApiError, UnexpectedOnlyError,
+};
+
+#[derive(FromRow)]
+pub struct BillingPolicyRow {
+    #[sqlx(try_from = "i32")]
+    pub id: u32,
+    pub status: String,
+    pub multiplier: f64,
+    pub start_time: DateTime<Utc>,
+}
+
+impl From<BillingPolicyRow> for BillingPolicy {
+    fn from(row: BillingPolicyRow) -> Self {
+        Self {
+            id: row.id,
+            status: row.status,
+            multiplier: row.multiplier,
+            start_time: row.start_time.fixed_offset(),
+        }
+    }
+}
+
+#[tracing::instrument(
+    name = "select_maybe_billing_policy_from_db",
+    skip(transaction)
+)]
+pub async fn select_maybe_billing_policy_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    billing_policy_id: u64,
+) -> Result<Option<BillingPolicy>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            id,
+            status,
+            multiplier,
+            start_time
+        FROM accounting_billingpolicy
+        WHERE id = ?
+        "#,
+        billing_policy_id
+    );
+    let row = transaction
+        .fetch_optional(query)
+        .await
+        .context("Failed to execute select query")?;
+    Ok(match row {
+        Some(row) => Some(
+            BillingPolicyRow::from_row(&row)
+                .context("Failed to parse billing policy row")?
+                .into(),
+        ),
+        None => None,
+    })
+}
+
+#[tracing::instrument(
+    name = "select_billing_policy_from_db",
+    skip(transaction)
+)]
+pub async fn select_billing_policy_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    billing_policy_id: u64,
+) -> Result<BillingPolicy, NotFoundOrUnexpectedApiError> {
+    select_maybe_billing_policy_from_db(transaction, billing_policy_id)
+        .await?
+        .ok_or(NotFoundOrUnexpectedApiError::NotFoundError)
+}
+
+#[tracing::instrument(
+    name = "select_all_billing_policies_from_db",
+    skip(transaction)
+)]
+pub async fn select_all_billing_policies_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+) -> Result<Vec<BillingPolicy>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            id,
+            status,
+            multiplier,
+            start_time
+        FROM accounting_billingpolicy
+        ORDER BY status, start_time
+        "#,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| BillingPolicyRow::from_row(&r).map(BillingPolicy::from))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to billing policy")?;
+    Ok(rows)
+}
+
+pub struct NewBillingPolicy {
+    pub status: String,
+    pub multiplier: f64,
+    pub start_time: DateTime<Utc>,
+}
+
+impl TryFrom<BillingPolicyCreateData> for NewBillingPolicy {
+    type Error = String;
+
+    fn try_from(data: BillingPolicyCreateData) -> Result<Self, Self::Error> {
+        if data.status.is_empty() {
+            return Err(
+                "Status of billing policy must not be empty".to_string()
+            );
+        }
+        let multiplier = data.multiplier.unwrap_or(1.);
+        if !multiplier.is_finite() || multiplier < 0. {
+            return Err(
+                "Multiplier of billing policy must not be negative".to_string()
+            );
+        }
+        Ok(Self {
+            status: data.status.to_uppercase(),
+            multiplier,
+            start_time: data
+                .start_time
+                .map(|d| d.to_utc())
+                .unwrap_or(Utc::now()),
+        })
+    }
+}
+
+#[tracing::instrument(
+    name = "insert_billing_policy_into_db",
+    skip(new_billing_policy, transaction)
+)]
+pub async fn insert_billing_policy_into_db(
+    transaction: &mut Transaction<'_, MySql>,
+    new_billing_policy: &NewBillingPolicy,
+) -> Result<u64, MinimalApiError> {
+    let query = sqlx::query!(
+        r#"
+        INSERT IGNORE INTO accounting_billingpolicy (status, multiplier, start_time)
+        VALUES (?, ?, ?)
+        "#,
+        new_billing_policy.status,
+        new_billing_policy.multiplier,
+        new_billing_policy.start_time,
+    );
+    let result = transaction
+        .execute(query)
+        .await
+        .context("Failed to execute insert query")?;
+    if result.rows_affected() == 0 {
+        return Err(MinimalApiError::ValidationError(
+            "Failed to insert new billing policy, a conflicting entry exists"
+                .to_string(),
+        ));
+    }
+    let id = result.last_insert_id();
+    Ok(id)
+}
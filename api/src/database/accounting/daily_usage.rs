@@ -0,0 +1,300 @@
+use std::str::FromStr;
+
+use anyhow::Context;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{Executor, FromRow, MySql, Transaction};
+use uuid::Uuid;
+
+use crate::error::{MinimalApiError, UnexpectedOnlyError};
+
+/// Seconds a server spent in one status with one flavor and user on a day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DailyUsage {
+    pub day: NaiveDate,
+    pub instance_id: Uuid,
+    pub flavor: u32,
+    pub flavor_name: String,
+    pub user: u32,
+    pub status: String,
+    pub seconds: i64,
+}
+
+#[derive(FromRow)]
+pub struct DailyUsageRow {
+    pub day: NaiveDate,
+    pub instance_id: String,
+    #[sqlx(try_from = "i64")]
+    pub flavor: u32,
+    pub flavor_name: String,
+    #[sqlx(try_from = "i32")]
+    pub user: u32,
+    pub status: String,
+    pub seconds: i64,
+}
+
+impl TryFrom<DailyUsageRow> for DailyUsage {
+    type Error = anyhow::Error;
+
+    fn try_from(row: DailyUsageRow) -> Result<Self, Self::Error> {
+        Ok(DailyUsage {
+            day: row.day,
+            instance_id: Uuid::from_str(row.instance_id.as_str())
+                .context("Could not parse instance id String")?,
+            flavor: row.flavor,
+            flavor_name: row.flavor_name,
+            user: row.user,
+            status: row.status,
+            seconds: row.seconds,
+        })
+    }
+}
+
+/// Returns the days from first up to but excluding last, which are
+/// completely aggregated.
+#[tracing::instrument(
+    name = "select_aggregated_days_from_db",
+    skip(transaction)
+)]
+pub async fn select_aggregated_days_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    first: NaiveDate,
+    last: NaiveDate,
+) -> Result<Vec<NaiveDate>, UnexpectedOnlyError> {
+    #[derive(FromRow)]
+    struct Row {
+        day: NaiveDate,
+    }
+    let query = sqlx::query!(
+        r#"
+        SELECT day
+        FROM accounting_dailyusage_day
+        WHERE
+            day >= ? AND
+            day < ?
+        ORDER BY day
+        "#,
+        first,
+        last,
+    );
+    let days = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| Row::from_row(&r).map(|row| row.day))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to aggregated day")?;
+    Ok(days)
+}
+
+#[tracing::instrument(
+    name = "select_daily_usages_by_server_from_db",
+    skip(transaction)
+)]
+pub async fn select_daily_usages_by_server_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    server_id: Uuid,
+    first: NaiveDate,
+    last: NaiveDate,
+) -> Result<Vec<DailyUsage>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            du.day as day,
+            du.instance_id as instance_id,
+            f.id as flavor,
+            f.name as flavor_name,
+            du.user_id as user,
+            du.status as status,
+            du.seconds as seconds
+        FROM
+            accounting_dailyusage as du,
+            resources_flavor as f
+        WHERE
+            du.flavor_id = f.id AND
+            du.instance_id = ? AND
+            du.day >= ? AND
+            du.day < ?
+        ORDER BY du.day
+        "#,
+        server_id.to_string(),
+        first,
+        last,
+    );
+    let usages = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| DailyUsageRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to daily usage row")?
+        .into_iter()
+        .map(DailyUsage::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(usages)
+}
+
+#[tracing::instrument(
+    name = "select_daily_usages_by_user_from_db",
+    skip(transaction)
+)]
+pub async fn select_daily_usages_by_user_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    user_id: u64,
+    first: NaiveDate,
+    last: NaiveDate,
+) -> Result<Vec<DailyUsage>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            du.day as day,
+            du.instance_id as instance_id,
+            f.id as flavor,
+            f.name as flavor_name,
+            du.user_id as user,
+            du.status as status,
+            du.seconds as seconds
+        FROM
+            accounting_dailyusage as du,
+            resources_flavor as f
+        WHERE
+            du.flavor_id = f.id AND
+            du.user_id = ? AND
+            du.day >= ? AND
+            du.day < ?
+        ORDER BY du.day
+        "#,
+        user_id,
+        first,
+        last,
+    );
+    let usages = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| DailyUsageRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to daily usage row")?
+        .into_iter()
+        .map(DailyUsage::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(usages)
+}
+
+pub struct NewDailyUsage {
+    pub day: NaiveDate,
+    pub instance_id: Uuid,
+    pub flavor: u32,
+    pub user: u32,
+    pub status: String,
+    pub seconds: i64,
+}
+
+#[tracing::instrument(
+    name = "insert_daily_usage_into_db",
+    skip(new_daily_usage, transaction)
+)]
+pub async fn insert_daily_usage_into_db(
+    transaction: &mut Transaction<'_, MySql>,
+    new_daily_usage: &NewDailyUsage,
+) -> Result<(), MinimalApiError> {
+    let query = sqlx::query!(
+        r#"
+        INSERT IGNORE INTO accounting_dailyusage (
+            day, instance_id, flavor_id, user_id, status, seconds
+        )
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+        new_daily_usage.day,
+        new_daily_usage.instance_id.to_string(),
+        new_daily_usage.flavor,
+        new_daily_usage.user,
+        new_daily_usage.status,
+        new_daily_usage.seconds,
+    );
+    let result = transaction
+        .execute(query)
+        .await
+        .context("Failed to execute insert query")?;
+    if result.rows_affected() == 0 {
+        return Err(MinimalApiError::ValidationError(
+            "Failed to insert new daily usage, a conflicting entry exists"
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[tracing::instrument(
+    name = "insert_aggregated_day_into_db",
+    skip(transaction)
+)]
+pub async fn insert_aggregated_day_into_db(
+    transaction: &mut Transaction<'_, MySql>,
+    day: NaiveDate,
+    aggregated_at: DateTime<Utc>,
+) -> Result<(), UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        INSERT INTO accounting_dailyusage_day (day, aggregated_at)
+        VALUES (?, ?)
+        ON DUPLICATE KEY UPDATE aggregated_at = VALUES(aggregated_at)
+        "#,
+        day,
+        aggregated_at,
+    );
+    transaction
+        .execute(query)
+        .await
+        .context("Failed to execute insert query")?;
+    Ok(())
+}
+
+/// Drops the aggregates of all days overlapping the given period, which
+/// reaches into the future without an end, since the server states within
+/// it have changed. The days are aggregated again by the next import.
+#[tracing::instrument(
+    name = "invalidate_daily_usages_in_db",
+    skip(transaction)
+)]
+pub async fn invalidate_daily_usages_in_db(
+    transaction: &mut Transaction<'_, MySql>,
+    begin: DateTime<Utc>,
+    end: Option<DateTime<Utc>>,
+) -> Result<(), UnexpectedOnlyError> {
+    let first = begin.date_naive();
+    let last = end.map(|end| end.date_naive());
+    let query1 = sqlx::query!(
+        r#"
+        DELETE FROM accounting_dailyusage_day
+        WHERE
+            day >= ? AND
+            (? IS NULL OR day <= ?)
+        "#,
+        first,
+        last,
+        last,
+    );
+    transaction
+        .execute(query1)
+        .await
+        .context("Failed to execute delete query")?;
+    let query2 = sqlx::query!(
+        r#"
+        DELETE FROM accounting_dailyusage
+        WHERE
+            day >= ? AND
+            (? IS NULL OR day <= ?)
+        "#,
+        first,
+        last,
+        last,
+    );
+    transaction
+        .execute(query2)
+        .await
+        .context("Failed to execute delete query")?;
+    Ok(())
+}
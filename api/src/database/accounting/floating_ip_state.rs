@@ -0,0 +1,430 @@
+use std::str::FromStr;
+
+use anyhow::Context;
+use avina_wire::accounting::FloatingIpState;
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, FromRow, MySql, Transaction};
+use uuid::Uuid;
+
+use crate::error::{
+    MinimalApiError, NotFoundOrUnexpectedApiError, UnexpectedOnlyError,
+};
+
+#[derive(FromRow)]
+pub struct FloatingIpStateRow {
+    #[sqlx(try_from = "i32")]
+    pub id: u32,
+    pub begin: DateTime<Utc>,
+    pub end: Option<DateTime<Utc>>,
+    pub floating_ip_id: String,
+    pub floating_ip_address: String,
+    pub pool: String,
+    #[sqlx(try_from = "i32")]
+    pub user: u32,
+    pub username: String,
+}
+
+impl TryFrom<FloatingIpStateRow> for FloatingIpState {
+    type Error = anyhow::Error;
+
+    fn try_from(row: FloatingIpStateRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            begin: row.begin.fixed_offset(),
+            end: row.end.map(|end| end.fixed_offset()),
+            floating_ip_id: Uuid::from_str(row.floating_ip_id.as_str())
+                .context("Could not parse floating IP id String")?,
+            floating_ip_address: row.floating_ip_address,
+            pool: row.pool,
+            user: row.user,
+            username: row.username,
+        })
+    }
+}
+
+#[tracing::instrument(
+    name = "select_maybe_floating_ip_state_from_db",
+    skip(transaction)
+)]
+pub async fn select_maybe_floating_ip_state_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    floating_ip_state_id: u64,
+) -> Result<Option<FloatingIpState>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            s.id as id,
+            s.begin as begin,
+            s.end as end,
+            s.floating_ip_id as floating_ip_id,
+            s.floating_ip_address as floating_ip_address,
+            s.pool as pool,
+            u.id as user,
+            u.name as username
+        FROM
+            accounting_floatingipstate as s,
+            user_user as u
+        WHERE
+            s.user_id = u.id AND
+            s.id = ?
+        ORDER BY s.begin
+        "#,
+        floating_ip_state_id
+    );
+    let row = transaction
+        .fetch_optional(query)
+        .await
+        .context("Failed to execute select query")?;
+    Ok(match row {
+        Some(row) => Some(
+            FloatingIpStateRow::from_row(&row)
+                .context("Failed to parse floating IP state row")?
+                .try_into()?,
+        ),
+        None => None,
+    })
+}
+
+#[tracing::instrument(
+    name = "select_floating_ip_state_from_db",
+    skip(transaction)
+)]
+pub async fn select_floating_ip_state_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    floating_ip_state_id: u64,
+) -> Result<FloatingIpState, NotFoundOrUnexpectedApiError> {
+    select_maybe_floating_ip_state_from_db(transaction, floating_ip_state_id)
+        .await?
+        .ok_or(NotFoundOrUnexpectedApiError::NotFoundError)
+}
+
+#[tracing::instrument(
+    name = "select_all_floating_ip_states_from_db",
+    skip(transaction)
+)]
+pub async fn select_all_floating_ip_states_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+) -> Result<Vec<FloatingIpState>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            s.id as id,
+            s.begin as begin,
+            s.end as end,
+            s.floating_ip_id as floating_ip_id,
+            s.floating_ip_address as floating_ip_address,
+            s.pool as pool,
+            u.id as user,
+            u.name as username
+        FROM
+            accounting_floatingipstate as s,
+            user_user as u
+        WHERE
+            s.user_id = u.id
+        ORDER BY s.begin
+        "#,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| FloatingIpStateRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to floating IP state")?
+        .into_iter()
+        .map(FloatingIpState::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+#[tracing::instrument(
+    name = "select_floating_ip_states_by_project_from_db",
+    skip(transaction)
+)]
+pub async fn select_floating_ip_states_by_project_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    project_id: u64,
+) -> Result<Vec<FloatingIpState>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            s.id as id,
+            s.begin as begin,
+            s.end as end,
+            s.floating_ip_id as floating_ip_id,
+            s.floating_ip_address as floating_ip_address,
+            s.pool as pool,
+            u.id as user,
+            u.name as username
+        FROM
+            accounting_floatingipstate as s,
+            user_user as u
+        WHERE
+            s.user_id = u.id AND
+            u.project_id = ?
+        ORDER BY s.begin
+        "#,
+        project_id,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| FloatingIpStateRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to floating IP state")?
+        .into_iter()
+        .map(FloatingIpState::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+#[tracing::instrument(
+    name = "select_floating_ip_states_by_user_from_db",
+    skip(transaction)
+)]
+pub async fn select_floating_ip_states_by_user_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    user_id: u64,
+) -> Result<Vec<FloatingIpState>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            s.id as id,
+            s.begin as begin,
+            s.end as end,
+            s.floating_ip_id as floating_ip_id,
+            s.floating_ip_address as floating_ip_address,
+            s.pool as pool,
+            u.id as user,
+            u.name as username
+        FROM
+            accounting_floatingipstate as s,
+            user_user as u
+        WHERE
+            s.user_id = u.id AND
+            u.id = ?
+        ORDER BY s.begin
+        "#,
+        user_id,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| FloatingIpStateRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to floating IP state")?
+        .into_iter()
+        .map(FloatingIpState::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+#[tracing::instrument(
+    name = "select_floating_ip_states_by_floating_ip_from_db",
+    skip(transaction)
+)]
+pub async fn select_floating_ip_states_by_floating_ip_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    floating_ip_id: Uuid,
+) -> Result<Vec<FloatingIpState>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            s.id as id,
+            s.begin as begin,
+            s.end as end,
+            s.floating_ip_id as floating_ip_id,
+            s.floating_ip_address as floating_ip_address,
+            s.pool as pool,
+            u.id as user,
+            u.name as username
+        FROM
+            accounting_floatingipstate as s,
+            user_user as u
+        WHERE
+            s.user_id = u.id AND
+            s.floating_ip_id = ?
+        ORDER BY s.begin
+        "#,
+        floating_ip_id.to_string(),
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| FloatingIpStateRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to floating IP state")?
+        .into_iter()
+        .map(FloatingIpState::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+#[tracing::instrument(
+    name = "select_unfinished_floating_ip_states_from_db",
+    skip(transaction)
+)]
+pub async fn select_unfinished_floating_ip_states_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+) -> Result<Vec<FloatingIpState>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            s.id as id,
+            s.begin as begin,
+            s.end as end,
+            s.floating_ip_id as floating_ip_id,
+            s.floating_ip_address as floating_ip_address,
+            s.pool as pool,
+            u.id as user,
+            u.name as username
+        FROM
+            accounting_floatingipstate as s,
+            user_user as u
+        WHERE
+            s.user_id = u.id AND
+            s.end IS NULL
+        ORDER BY s.begin
+        "#,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| FloatingIpStateRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to floating IP state")?
+        .into_iter()
+        .map(FloatingIpState::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Selects the states overlapping the period, optionally only those of the
+/// given user or project.
+#[tracing::instrument(
+    name = "select_floating_ip_states_by_scope_begin_and_end_from_db",
+    skip(transaction)
+)]
+pub async fn select_floating_ip_states_by_scope_begin_and_end_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    user_id: Option<u64>,
+    project_id: Option<u64>,
+    begin: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<FloatingIpState>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            s.id as id,
+            s.begin as begin,
+            s.end as end,
+            s.floating_ip_id as floating_ip_id,
+            s.floating_ip_address as floating_ip_address,
+            s.pool as pool,
+            u.id as user,
+            u.name as username
+        FROM
+            accounting_floatingipstate as s,
+            user_user as u
+        WHERE
+            s.user_id = u.id AND
+            (? IS NULL OR u.id = ?) AND
+            (? IS NULL OR u.project_id = ?) AND
+            s.begin < ? AND
+            (s.end IS NULL OR s.end > ?)
+        ORDER BY s.begin
+        "#,
+        user_id,
+        user_id,
+        project_id,
+        project_id,
+        end,
+        begin,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| FloatingIpStateRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to floating IP state")?
+        .into_iter()
+        .map(FloatingIpState::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+pub struct NewFloatingIpState {
+    pub begin: DateTime<Utc>,
+    pub end: Option<DateTime<Utc>>,
+    pub floating_ip_id: Uuid,
+    pub floating_ip_address: String,
+    pub pool: String,
+    pub user: u32,
+}
+
+#[tracing::instrument(
+    name = "insert_floating_ip_state_into_db",
+    skip(new_floating_ip_state, transaction)
+)]
+pub async fn insert_floating_ip_state_into_db(
+    transaction: &mut Transaction<'_, MySql>,
+    new_floating_ip_state: &NewFloatingIpState,
+) -> Result<u64, MinimalApiError> {
+    let query = sqlx::query!(
+        r#"
+        INSERT INTO accounting_floatingipstate (
+            begin,
+            end,
+            floating_ip_id,
+            floating_ip_address,
+            pool,
+            user_id
+        )
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+        new_floating_ip_state.begin,
+        new_floating_ip_state.end,
+        new_floating_ip_state.floating_ip_id.to_string(),
+        new_floating_ip_state.floating_ip_address,
+        new_floating_ip_state.pool,
+        new_floating_ip_state.user,
+    );
+    let result = transaction
+        .execute(query)
+        .await
+        .context("Failed to execute insert query")?;
+    Ok(result.last_insert_id())
+}
+
+#[tracing::instrument(name = "end_floating_ip_state_in_db", skip(transaction))]
+pub async fn end_floating_ip_state_in_db(
+    transaction: &mut Transaction<'_, MySql>,
+    floating_ip_state_id: u64,
+    end: DateTime<Utc>,
+) -> Result<(), UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        UPDATE accounting_floatingipstate
+        SET end = ?
+        WHERE id = ?
+        "#,
+        end,
+        floating_ip_state_id,
+    );
+    transaction
+        .execute(query)
+        .await
+        .context("Failed to execute update query")?;
+    Ok(())
+}
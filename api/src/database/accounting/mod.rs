@@ -1 +1,7 @@
+pub mod billing_policy;
+pub mod daily_usage;
+pub mod floating_ip_state;
 pub mod server_state;
+pub mod server_state_summary;
+pub mod unmatched_server;
+pub mod volume_state;
@@ -3,14 +3,23 @@ use std::str::FromStr;
 use anyhow::Context;
 use avina_wire::{
     accounting::{ServerState, ServerStateCreateData},
-    user::UserClass,
+    user::Project,
 };
 use chrono::{DateTime, Utc};
 use sqlx::{Executor, FromRow, MySql, Transaction, types::uuid};
 use uuid::Uuid;
 
-use crate::error::{
-    MinimalApiError, NotFoundOrUnexpectedApiError, UnexpectedOnlyError,
+use crate::{
+    database::{
+        accounting::{
+            daily_usage::invalidate_daily_usages_in_db,
+            server_state_summary::select_maybe_server_state_summary_user_from_db,
+        },
+        user::user::select_maybe_project_by_user_from_db,
+    },
+    error::{
+        MinimalApiError, NotFoundOrUnexpectedApiError, UnexpectedOnlyError,
+    },
 };
 
 #[derive(FromRow)]
@@ -361,20 +370,19 @@ pub async fn select_server_states_by_server_from_db(
 }
 
 #[tracing::instrument(
-    name = "select_user_class_by_server_from_db",
+    name = "select_maybe_project_by_server_from_db",
     skip(transaction)
 )]
-pub async fn select_user_class_by_server_from_db(
+pub async fn select_maybe_project_by_server_from_db(
     transaction: &mut Transaction<'_, MySql>,
     server_id: Uuid,
-) -> Result<Option<UserClass>, UnexpectedOnlyError> {
-    #[derive(FromRow)]
-    struct Row {
-        user_class: u32,
-    }
+) -> Result<Option<Project>, UnexpectedOnlyError> {
     let query = sqlx::query!(
         r#"
         SELECT
+            p.id as id,
+            p.name as name,
+            p.openstack_id as openstack_id,
             p.user_class as user_class
         FROM
             accounting_serverstate as ss,
@@ -395,13 +403,21 @@ pub async fn select_user_class_by_server_from_db(
 
     Ok(match row {
         Some(row) => Some(
-            Row::from_row(&row)
-                .context("Failed to parse user class row")?
-                .user_class
-                .try_into()
-                .context("Failed to parse user class")?,
+            Project::from_row(&row).context("Failed to parse project row")?,
         ),
-        None => None,
+        // NOTE: all states of the server might have been archived already.
+        None => match select_maybe_server_state_summary_user_from_db(
+            transaction,
+            server_id,
+        )
+        .await?
+        {
+            Some(user) => {
+                select_maybe_project_by_user_from_db(transaction, user as u64)
+                    .await?
+            }
+            None => None,
+        },
     })
 }
 
@@ -618,6 +634,12 @@ pub async fn insert_server_state_into_db(
                 .to_string(),
         ));
     }
+    invalidate_daily_usages_in_db(
+        transaction,
+        new_server_state.begin,
+        new_server_state.end,
+    )
+    .await?;
     Ok(id)
 }
 
@@ -1015,3 +1037,197 @@ pub async fn select_unfinished_server_states_from_db(
         .context("Failed to convert server state row to server state")?;
     Ok(rows)
 }
+
+#[tracing::instrument(
+    name = "select_unfinished_server_states_by_user_from_db",
+    skip(transaction)
+)]
+pub async fn select_unfinished_server_states_by_user_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    user_id: u64,
+) -> Result<Vec<ServerState>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            s.id as id,
+            s.begin as begin,
+            s.end as end,
+            ss.instance_id as instance_id,
+            ss.instance_name as instance_name,
+            f.id as flavor,
+            f.name as flavor_name,
+            ss.status as status,
+            u.id as user,
+            u.name as username
+        FROM
+            accounting_state as s,
+            accounting_serverstate as ss,
+            resources_flavor as f,
+            user_user as u
+        WHERE
+            ss.flavor_id = f.id AND
+            ss.user_id = u.id AND
+            ss.state_ptr_id = s.id AND
+            ss.user_id = ? AND
+            s.end is NULL
+        "#,
+        user_id,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| ServerStateRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to server state")?
+        .into_iter()
+        .map(|r| {
+            Ok::<ServerState, UnexpectedOnlyError>(ServerState {
+                id: r.id,
+                begin: r.begin.fixed_offset(),
+                end: r.end.map(|end| end.fixed_offset()),
+                instance_id: Uuid::from_str(r.instance_id.as_str())
+                    .context("Could not parse instance id String")?,
+                instance_name: r.instance_name,
+                flavor: r.flavor,
+                flavor_name: r.flavor_name,
+                status: r.status,
+                user: r.user,
+                username: r.username,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert server state row to server state")?;
+    Ok(rows)
+}
+
+/// Returns the latest begin or end of any server state, which is the last
+/// change the imports have recorded.
+#[tracing::instrument(
+    name = "select_latest_server_state_change_from_db",
+    skip(transaction)
+)]
+pub async fn select_latest_server_state_change_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+) -> Result<Option<DateTime<Utc>>, UnexpectedOnlyError> {
+    #[derive(FromRow)]
+    struct Row {
+        latest_change: Option<DateTime<Utc>>,
+    }
+    let query = sqlx::query!(
+        r#"
+        SELECT MAX(GREATEST(s.begin, COALESCE(s.end, s.begin))) AS latest_change
+        FROM
+            accounting_state as s,
+            accounting_serverstate as ss
+        WHERE
+            ss.state_ptr_id = s.id
+        "#,
+    );
+    let row = transaction
+        .fetch_one(query)
+        .await
+        .context("Failed to execute select query")?;
+    Ok(Row::from_row(&row)
+        .context("Failed to parse latest change row")?
+        .latest_change)
+}
+
+/// Returns the earliest begin of any server state.
+#[tracing::instrument(
+    name = "select_first_server_state_begin_from_db",
+    skip(transaction)
+)]
+pub async fn select_first_server_state_begin_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+) -> Result<Option<DateTime<Utc>>, UnexpectedOnlyError> {
+    #[derive(FromRow)]
+    struct Row {
+        first_begin: Option<DateTime<Utc>>,
+    }
+    let query = sqlx::query!(
+        r#"
+        SELECT MIN(s.begin) AS first_begin
+        FROM
+            accounting_state as s,
+            accounting_serverstate as ss
+        WHERE
+            ss.state_ptr_id = s.id
+        "#,
+    );
+    let row = transaction
+        .fetch_one(query)
+        .await
+        .context("Failed to execute select query")?;
+    Ok(Row::from_row(&row)
+        .context("Failed to parse first begin row")?
+        .first_begin)
+}
+
+#[tracing::instrument(
+    name = "select_server_states_by_begin_and_end_from_db",
+    skip(transaction)
+)]
+pub async fn select_server_states_by_begin_and_end_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    begin: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<ServerState>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            s.id as id,
+            s.begin as begin,
+            s.end as end,
+            ss.instance_id as instance_id,
+            ss.instance_name as instance_name,
+            f.id as flavor,
+            f.name as flavor_name,
+            ss.status as status,
+            u.id as user,
+            u.name as username
+        FROM
+            accounting_state as s,
+            accounting_serverstate as ss,
+            resources_flavor as f,
+            user_user as u
+        WHERE
+            ss.flavor_id = f.id AND
+            ss.user_id = u.id AND
+            ss.state_ptr_id = s.id AND
+            (s.end > ? OR s.end IS NULL) AND
+            s.begin < ?
+        ORDER BY s.id
+        "#,
+        begin,
+        end
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| ServerStateRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to server state")?
+        .into_iter()
+        .map(|r| {
+            Ok::<ServerState, UnexpectedOnlyError>(ServerState {
+                id: r.id,
+                begin: r.begin.fixed_offset(),
+                end: r.end.map(|end| end.fixed_offset()),
+                instance_id: Uuid::from_str(r.instance_id.as_str())
+                    .context("Could not parse instance id String")?,
+                instance_name: r.instance_name,
+                flavor: r.flavor,
+                flavor_name: r.flavor_name,
+                status: r.status,
+                user: r.user,
+                username: r.username,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert server state row to server state")?;
+    Ok(rows)
+}
@@ -0,0 +1,239 @@
+use std::str::FromStr;
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, FromRow, MySql, Transaction};
+use uuid::Uuid;
+
+use crate::error::{MinimalApiError, UnexpectedOnlyError};
+
+/// Seconds a server consumed with one flavor and user within a period of an
+/// archived fiscal year, in which the flavor prices did not change.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ServerStateSummary {
+    pub year: u32,
+    pub begin: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub instance_id: Uuid,
+    pub flavor: u32,
+    pub flavor_name: String,
+    pub user: u32,
+    pub seconds: i64,
+}
+
+#[derive(FromRow)]
+pub struct ServerStateSummaryRow {
+    #[sqlx(try_from = "i32")]
+    pub year: u32,
+    pub begin: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub instance_id: String,
+    #[sqlx(try_from = "i64")]
+    pub flavor: u32,
+    pub flavor_name: String,
+    #[sqlx(try_from = "i32")]
+    pub user: u32,
+    pub seconds: i64,
+}
+
+impl TryFrom<ServerStateSummaryRow> for ServerStateSummary {
+    type Error = anyhow::Error;
+
+    fn try_from(row: ServerStateSummaryRow) -> Result<Self, Self::Error> {
+        Ok(ServerStateSummary {
+            year: row.year,
+            begin: row.begin,
+            end: row.end,
+            instance_id: Uuid::from_str(row.instance_id.as_str())
+                .context("Could not parse instance id String")?,
+            flavor: row.flavor,
+            flavor_name: row.flavor_name,
+            user: row.user,
+            seconds: row.seconds,
+        })
+    }
+}
+
+#[tracing::instrument(
+    name = "select_server_state_summaries_by_server_from_db",
+    skip(transaction)
+)]
+pub async fn select_server_state_summaries_by_server_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    server_id: Uuid,
+    begin: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+) -> Result<Vec<ServerStateSummary>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            sm.year as year,
+            sm.begin as begin,
+            sm.end as end,
+            sm.instance_id as instance_id,
+            f.id as flavor,
+            f.name as flavor_name,
+            sm.user_id as user,
+            sm.seconds as seconds
+        FROM
+            accounting_serverstate_summary as sm,
+            resources_flavor as f
+        WHERE
+            sm.flavor_id = f.id AND
+            sm.instance_id = ? AND
+            (? IS NULL OR sm.end > ?) AND
+            (? IS NULL OR sm.begin < ?)
+        ORDER BY sm.begin
+        "#,
+        server_id.to_string(),
+        begin,
+        begin,
+        end,
+        end,
+    );
+    let summaries = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| ServerStateSummaryRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to server state summary row")?
+        .into_iter()
+        .map(ServerStateSummary::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(summaries)
+}
+
+#[tracing::instrument(
+    name = "select_server_state_summaries_by_user_from_db",
+    skip(transaction)
+)]
+pub async fn select_server_state_summaries_by_user_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    user_id: u64,
+    begin: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+) -> Result<Vec<ServerStateSummary>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            sm.year as year,
+            sm.begin as begin,
+            sm.end as end,
+            sm.instance_id as instance_id,
+            f.id as flavor,
+            f.name as flavor_name,
+            sm.user_id as user,
+            sm.seconds as seconds
+        FROM
+            accounting_serverstate_summary as sm,
+            resources_flavor as f
+        WHERE
+            sm.flavor_id = f.id AND
+            sm.user_id = ? AND
+            (? IS NULL OR sm.end > ?) AND
+            (? IS NULL OR sm.begin < ?)
+        ORDER BY sm.begin
+        "#,
+        user_id,
+        begin,
+        begin,
+        end,
+        end,
+    );
+    let summaries = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| ServerStateSummaryRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to server state summary row")?
+        .into_iter()
+        .map(ServerStateSummary::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(summaries)
+}
+
+/// Returns the user of a server whose states have all been archived.
+#[tracing::instrument(
+    name = "select_maybe_server_state_summary_user_from_db",
+    skip(transaction)
+)]
+pub async fn select_maybe_server_state_summary_user_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    server_id: Uuid,
+) -> Result<Option<u32>, UnexpectedOnlyError> {
+    #[derive(FromRow)]
+    struct Row {
+        #[sqlx(try_from = "i32")]
+        user: u32,
+    }
+    let query = sqlx::query!(
+        r#"
+        SELECT user_id as user
+        FROM accounting_serverstate_summary
+        WHERE instance_id = ?
+        ORDER BY begin DESC
+        LIMIT 1
+        "#,
+        server_id.to_string(),
+    );
+    let row = transaction
+        .fetch_optional(query)
+        .await
+        .context("Failed to execute select query")?;
+    Ok(match row {
+        Some(row) => Some(
+            Row::from_row(&row)
+                .context("Failed to parse server state summary row")?
+                .user,
+        ),
+        None => None,
+    })
+}
+
+pub struct NewServerStateSummary {
+    pub year: u32,
+    pub begin: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub instance_id: Uuid,
+    pub flavor: u32,
+    pub user: u32,
+    pub seconds: i64,
+}
+
+/// Adds the seconds to an existing summary of the same period, which
+/// happens when states are created for an already archived fiscal year.
+#[tracing::instrument(
+    name = "upsert_server_state_summary_into_db",
+    skip(transaction, summary)
+)]
+pub async fn upsert_server_state_summary_into_db(
+    transaction: &mut Transaction<'_, MySql>,
+    summary: &NewServerStateSummary,
+) -> Result<(), MinimalApiError> {
+    let query = sqlx::query!(
+        r#"
+        INSERT INTO accounting_serverstate_summary (
+            year, begin, end, instance_id, flavor_id, user_id, seconds
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        ON DUPLICATE KEY UPDATE
+            seconds = seconds + VALUES(seconds)
+        "#,
+        summary.year,
+        summary.begin,
+        summary.end,
+        summary.instance_id.to_string(),
+        summary.flavor,
+        summary.user,
+        summary.seconds,
+    );
+    transaction
+        .execute(query)
+        .await
+        .context("Failed to execute insert query")?;
+    Ok(())
+}
@@ -0,0 +1,290 @@
+use std::str::FromStr;
+
+use anyhow::Context;
+use avina_wire::accounting::{UnmatchedServer, UnmatchedServerResolution};
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, FromRow, MySql, Transaction};
+use uuid::Uuid;
+
+use crate::error::{NotFoundOrUnexpectedApiError, UnexpectedOnlyError};
+
+#[derive(FromRow)]
+pub struct UnmatchedServerRow {
+    #[sqlx(try_from = "i32")]
+    pub id: u32,
+    pub instance_id: String,
+    pub instance_name: String,
+    pub status: String,
+    pub flavor_openstack_id: String,
+    pub user_openstack_id: String,
+    pub reason: String,
+    pub data: String,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    pub resolution: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub flavor: Option<i64>,
+    pub user: Option<i32>,
+}
+
+impl TryFrom<UnmatchedServerRow> for UnmatchedServer {
+    type Error = anyhow::Error;
+
+    fn try_from(row: UnmatchedServerRow) -> Result<Self, Self::Error> {
+        Ok(UnmatchedServer {
+            id: row.id,
+            instance_id: Uuid::from_str(row.instance_id.as_str())
+                .context("Could not parse instance id String")?,
+            instance_name: row.instance_name,
+            status: row.status,
+            flavor_openstack_id: row.flavor_openstack_id,
+            user_openstack_id: row.user_openstack_id,
+            reason: row.reason,
+            data: row.data,
+            first_seen: row.first_seen.fixed_offset(),
+            last_seen: row.last_seen.fixed_offset(),
+            resolution: row.resolution.map(|r| r.parse()).transpose()?,
+            resolved_at: row.resolved_at.map(|r| r.fixed_offset()),
+            flavor: row.flavor.map(|f| f as u32),
+            user: row.user.map(|u| u as u32),
+        })
+    }
+}
+
+#[tracing::instrument(
+    name = "select_unmatched_servers_from_db",
+    skip(transaction)
+)]
+pub async fn select_unmatched_servers_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    all: bool,
+) -> Result<Vec<UnmatchedServer>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            id,
+            instance_id,
+            instance_name,
+            status,
+            flavor_openstack_id,
+            user_openstack_id,
+            reason,
+            data,
+            first_seen,
+            last_seen,
+            resolution,
+            resolved_at,
+            flavor_id as flavor,
+            user_id as user
+        FROM accounting_unmatched_server
+        WHERE ? OR resolution IS NULL
+        ORDER BY id
+        "#,
+        all,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| UnmatchedServerRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to unmatched server row")?
+        .into_iter()
+        .map(UnmatchedServer::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+#[tracing::instrument(
+    name = "select_maybe_unmatched_server_from_db",
+    skip(transaction)
+)]
+pub async fn select_maybe_unmatched_server_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    unmatched_server_id: u64,
+) -> Result<Option<UnmatchedServer>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            id,
+            instance_id,
+            instance_name,
+            status,
+            flavor_openstack_id,
+            user_openstack_id,
+            reason,
+            data,
+            first_seen,
+            last_seen,
+            resolution,
+            resolved_at,
+            flavor_id as flavor,
+            user_id as user
+        FROM accounting_unmatched_server
+        WHERE id = ?
+        "#,
+        unmatched_server_id,
+    );
+    let row = transaction
+        .fetch_optional(query)
+        .await
+        .context("Failed to execute select query")?;
+    Ok(match row {
+        Some(row) => Some(
+            UnmatchedServerRow::from_row(&row)
+                .context("Failed to parse unmatched server row")?
+                .try_into()?,
+        ),
+        None => None,
+    })
+}
+
+#[tracing::instrument(
+    name = "select_unmatched_server_from_db",
+    skip(transaction)
+)]
+pub async fn select_unmatched_server_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    unmatched_server_id: u64,
+) -> Result<UnmatchedServer, NotFoundOrUnexpectedApiError> {
+    select_maybe_unmatched_server_from_db(transaction, unmatched_server_id)
+        .await?
+        .ok_or(NotFoundOrUnexpectedApiError::NotFoundError)
+}
+
+#[tracing::instrument(
+    name = "select_maybe_unmatched_server_by_instance_from_db",
+    skip(transaction)
+)]
+pub async fn select_maybe_unmatched_server_by_instance_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    instance_id: Uuid,
+) -> Result<Option<UnmatchedServer>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            id,
+            instance_id,
+            instance_name,
+            status,
+            flavor_openstack_id,
+            user_openstack_id,
+            reason,
+            data,
+            first_seen,
+            last_seen,
+            resolution,
+            resolved_at,
+            flavor_id as flavor,
+            user_id as user
+        FROM accounting_unmatched_server
+        WHERE instance_id = ?
+        "#,
+        instance_id.to_string(),
+    );
+    let row = transaction
+        .fetch_optional(query)
+        .await
+        .context("Failed to execute select query")?;
+    Ok(match row {
+        Some(row) => Some(
+            UnmatchedServerRow::from_row(&row)
+                .context("Failed to parse unmatched server row")?
+                .try_into()?,
+        ),
+        None => None,
+    })
+}
+
+#[derive(Clone, Debug)]
+pub struct NewUnmatchedServer {
+    pub instance_id: Uuid,
+    pub instance_name: String,
+    pub status: String,
+    pub flavor_openstack_id: String,
+    pub user_openstack_id: String,
+    pub reason: String,
+    pub data: String,
+    pub seen: DateTime<Utc>,
+}
+
+/// Records the server or, if it is known already, updates what was seen
+/// last, keeping when it was first seen and how it was resolved.
+#[tracing::instrument(
+    name = "upsert_unmatched_server_into_db",
+    skip(transaction, unmatched_server)
+)]
+pub async fn upsert_unmatched_server_into_db(
+    transaction: &mut Transaction<'_, MySql>,
+    unmatched_server: &NewUnmatchedServer,
+) -> Result<(), UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        INSERT INTO accounting_unmatched_server (
+            instance_id,
+            instance_name,
+            status,
+            flavor_openstack_id,
+            user_openstack_id,
+            reason,
+            data,
+            first_seen,
+            last_seen
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON DUPLICATE KEY UPDATE
+            instance_name = VALUES(instance_name),
+            status = VALUES(status),
+            flavor_openstack_id = VALUES(flavor_openstack_id),
+            user_openstack_id = VALUES(user_openstack_id),
+            reason = VALUES(reason),
+            data = VALUES(data),
+            last_seen = VALUES(last_seen)
+        "#,
+        unmatched_server.instance_id.to_string(),
+        unmatched_server.instance_name,
+        unmatched_server.status,
+        unmatched_server.flavor_openstack_id,
+        unmatched_server.user_openstack_id,
+        unmatched_server.reason,
+        unmatched_server.data,
+        unmatched_server.seen,
+        unmatched_server.seen,
+    );
+    transaction
+        .execute(query)
+        .await
+        .context("Failed to execute insert query")?;
+    Ok(())
+}
+
+#[tracing::instrument(
+    name = "resolve_unmatched_server_in_db",
+    skip(transaction)
+)]
+pub async fn resolve_unmatched_server_in_db(
+    transaction: &mut Transaction<'_, MySql>,
+    unmatched_server_id: u64,
+    resolution: UnmatchedServerResolution,
+    flavor: Option<u32>,
+    user: Option<u32>,
+) -> Result<(), UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        UPDATE accounting_unmatched_server
+        SET resolution = ?, resolved_at = ?, flavor_id = ?, user_id = ?
+        WHERE id = ?
+        "#,
+        resolution.as_str(),
+        Utc::now(),
+        flavor,
+        user,
+        unmatched_server_id,
+    );
+    transaction
+        .execute(query)
+        .await
+        .context("Failed to execute update query")?;
+    Ok(())
+}
@@ -0,0 +1,456 @@
+use std::str::FromStr;
+
+use anyhow::Context;
+use avina_wire::accounting::VolumeState;
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, FromRow, MySql, Transaction};
+use uuid::Uuid;
+
+use crate::error::{
+    MinimalApiError, NotFoundOrUnexpectedApiError, UnexpectedOnlyError,
+};
+
+#[derive(FromRow)]
+pub struct VolumeStateRow {
+    #[sqlx(try_from = "i32")]
+    pub id: u32,
+    pub begin: DateTime<Utc>,
+    pub end: Option<DateTime<Utc>>,
+    pub volume_id: String,
+    pub volume_name: String,
+    pub size: u32,
+    pub volume_type: String,
+    pub status: String,
+    #[sqlx(try_from = "i32")]
+    pub user: u32,
+    pub username: String,
+}
+
+impl TryFrom<VolumeStateRow> for VolumeState {
+    type Error = anyhow::Error;
+
+    fn try_from(row: VolumeStateRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            begin: row.begin.fixed_offset(),
+            end: row.end.map(|end| end.fixed_offset()),
+            volume_id: Uuid::from_str(row.volume_id.as_str())
+                .context("Could not parse volume id String")?,
+            volume_name: row.volume_name,
+            size: row.size,
+            volume_type: row.volume_type,
+            status: row.status,
+            user: row.user,
+            username: row.username,
+        })
+    }
+}
+
+#[tracing::instrument(
+    name = "select_maybe_volume_state_from_db",
+    skip(transaction)
+)]
+pub async fn select_maybe_volume_state_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    volume_state_id: u64,
+) -> Result<Option<VolumeState>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            s.id as id,
+            s.begin as begin,
+            s.end as end,
+            s.volume_id as volume_id,
+            s.volume_name as volume_name,
+            s.size as size,
+            s.volume_type as volume_type,
+            s.status as status,
+            u.id as user,
+            u.name as username
+        FROM
+            accounting_volumestate as s,
+            user_user as u
+        WHERE
+            s.user_id = u.id AND
+            s.id = ?
+        ORDER BY s.begin
+        "#,
+        volume_state_id
+    );
+    let row = transaction
+        .fetch_optional(query)
+        .await
+        .context("Failed to execute select query")?;
+    Ok(match row {
+        Some(row) => Some(
+            VolumeStateRow::from_row(&row)
+                .context("Failed to parse volume state row")?
+                .try_into()?,
+        ),
+        None => None,
+    })
+}
+
+#[tracing::instrument(name = "select_volume_state_from_db", skip(transaction))]
+pub async fn select_volume_state_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    volume_state_id: u64,
+) -> Result<VolumeState, NotFoundOrUnexpectedApiError> {
+    select_maybe_volume_state_from_db(transaction, volume_state_id)
+        .await?
+        .ok_or(NotFoundOrUnexpectedApiError::NotFoundError)
+}
+
+#[tracing::instrument(
+    name = "select_all_volume_states_from_db",
+    skip(transaction)
+)]
+pub async fn select_all_volume_states_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+) -> Result<Vec<VolumeState>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            s.id as id,
+            s.begin as begin,
+            s.end as end,
+            s.volume_id as volume_id,
+            s.volume_name as volume_name,
+            s.size as size,
+            s.volume_type as volume_type,
+            s.status as status,
+            u.id as user,
+            u.name as username
+        FROM
+            accounting_volumestate as s,
+            user_user as u
+        WHERE
+            s.user_id = u.id
+        ORDER BY s.begin
+        "#,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| VolumeStateRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to volume state")?
+        .into_iter()
+        .map(VolumeState::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+#[tracing::instrument(
+    name = "select_volume_states_by_project_from_db",
+    skip(transaction)
+)]
+pub async fn select_volume_states_by_project_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    project_id: u64,
+) -> Result<Vec<VolumeState>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            s.id as id,
+            s.begin as begin,
+            s.end as end,
+            s.volume_id as volume_id,
+            s.volume_name as volume_name,
+            s.size as size,
+            s.volume_type as volume_type,
+            s.status as status,
+            u.id as user,
+            u.name as username
+        FROM
+            accounting_volumestate as s,
+            user_user as u
+        WHERE
+            s.user_id = u.id AND
+            u.project_id = ?
+        ORDER BY s.begin
+        "#,
+        project_id,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| VolumeStateRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to volume state")?
+        .into_iter()
+        .map(VolumeState::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+#[tracing::instrument(
+    name = "select_volume_states_by_user_from_db",
+    skip(transaction)
+)]
+pub async fn select_volume_states_by_user_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    user_id: u64,
+) -> Result<Vec<VolumeState>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            s.id as id,
+            s.begin as begin,
+            s.end as end,
+            s.volume_id as volume_id,
+            s.volume_name as volume_name,
+            s.size as size,
+            s.volume_type as volume_type,
+            s.status as status,
+            u.id as user,
+            u.name as username
+        FROM
+            accounting_volumestate as s,
+            user_user as u
+        WHERE
+            s.user_id = u.id AND
+            u.id = ?
+        ORDER BY s.begin
+        "#,
+        user_id,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| VolumeStateRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to volume state")?
+        .into_iter()
+        .map(VolumeState::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+#[tracing::instrument(
+    name = "select_volume_states_by_volume_from_db",
+    skip(transaction)
+)]
+pub async fn select_volume_states_by_volume_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    volume_id: Uuid,
+) -> Result<Vec<VolumeState>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            s.id as id,
+            s.begin as begin,
+            s.end as end,
+            s.volume_id as volume_id,
+            s.volume_name as volume_name,
+            s.size as size,
+            s.volume_type as volume_type,
+            s.status as status,
+            u.id as user,
+            u.name as username
+        FROM
+            accounting_volumestate as s,
+            user_user as u
+        WHERE
+            s.user_id = u.id AND
+            s.volume_id = ?
+        ORDER BY s.begin
+        "#,
+        volume_id.to_string(),
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| VolumeStateRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to volume state")?
+        .into_iter()
+        .map(VolumeState::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+#[tracing::instrument(
+    name = "select_unfinished_volume_states_from_db",
+    skip(transaction)
+)]
+pub async fn select_unfinished_volume_states_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+) -> Result<Vec<VolumeState>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            s.id as id,
+            s.begin as begin,
+            s.end as end,
+            s.volume_id as volume_id,
+            s.volume_name as volume_name,
+            s.size as size,
+            s.volume_type as volume_type,
+            s.status as status,
+            u.id as user,
+            u.name as username
+        FROM
+            accounting_volumestate as s,
+            user_user as u
+        WHERE
+            s.user_id = u.id AND
+            s.end IS NULL
+        ORDER BY s.begin
+        "#,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| VolumeStateRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to volume state")?
+        .into_iter()
+        .map(VolumeState::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Selects the states overlapping the period, optionally only those of the
+/// given volume, user or project.
+#[tracing::instrument(
+    name = "select_volume_states_by_scope_begin_and_end_from_db",
+    skip(transaction)
+)]
+pub async fn select_volume_states_by_scope_begin_and_end_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    volume_id: Option<Uuid>,
+    user_id: Option<u64>,
+    project_id: Option<u64>,
+    begin: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<VolumeState>, UnexpectedOnlyError> {
+    let volume_id = volume_id.map(|volume_id| volume_id.to_string());
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            s.id as id,
+            s.begin as begin,
+            s.end as end,
+            s.volume_id as volume_id,
+            s.volume_name as volume_name,
+            s.size as size,
+            s.volume_type as volume_type,
+            s.status as status,
+            u.id as user,
+            u.name as username
+        FROM
+            accounting_volumestate as s,
+            user_user as u
+        WHERE
+            s.user_id = u.id AND
+            (? IS NULL OR s.volume_id = ?) AND
+            (? IS NULL OR u.id = ?) AND
+            (? IS NULL OR u.project_id = ?) AND
+            s.begin < ? AND
+            (s.end IS NULL OR s.end > ?)
+        ORDER BY s.begin
+        "#,
+        volume_id,
+        volume_id,
+        user_id,
+        user_id,
+        project_id,
+        project_id,
+        end,
+        begin,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| VolumeStateRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to volume state")?
+        .into_iter()
+        .map(VolumeState::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+pub struct NewVolumeState {
+    pub begin: DateTime<Utc>,
+    pub end: Option<DateTime<Utc>>,
+    pub volume_id: Uuid,
+    pub volume_name: String,
+    pub size: u32,
+    pub volume_type: String,
+    pub status: String,
+    pub user: u32,
+}
+
+#[tracing::instrument(
+    name = "insert_volume_state_into_db",
+    skip(new_volume_state, transaction)
+)]
+pub async fn insert_volume_state_into_db(
+    transaction: &mut Transaction<'_, MySql>,
+    new_volume_state: &NewVolumeState,
+) -> Result<u64, MinimalApiError> {
+    let query = sqlx::query!(
+        r#"
+        INSERT INTO accounting_volumestate (
+            begin,
+            end,
+            volume_id,
+            volume_name,
+            size,
+            volume_type,
+            status,
+            user_id
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+        new_volume_state.begin,
+        new_volume_state.end,
+        new_volume_state.volume_id.to_string(),
+        new_volume_state.volume_name,
+        new_volume_state.size,
+        new_volume_state.volume_type,
+        new_volume_state.status,
+        new_volume_state.user,
+    );
+    let result = transaction
+        .execute(query)
+        .await
+        .context("Failed to execute insert query")?;
+    Ok(result.last_insert_id())
+}
+
+#[tracing::instrument(name = "end_volume_state_in_db", skip(transaction))]
+pub async fn end_volume_state_in_db(
+    transaction: &mut Transaction<'_, MySql>,
+    volume_state_id: u64,
+    end: DateTime<Utc>,
+) -> Result<(), UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        UPDATE accounting_volumestate
+        SET end = ?
+        WHERE id = ?
+        "#,
+        end,
+        volume_state_id,
+    );
+    transaction
+        .execute(query)
+        .await
+        .context("Failed to execute update query")?;
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use anyhow::Context;
+use avina_wire::audit::AuditLogEntry;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::{Executor, FromRow, MySql, Transaction};
+
+use crate::error::{MinimalApiError, UnexpectedOnlyError};
+
+#[derive(FromRow)]
+pub struct AuditLogEntryRow {
+    #[sqlx(try_from = "i32")]
+    pub id: u32,
+    pub timestamp: DateTime<Utc>,
+    pub method: String,
+    pub endpoint: String,
+    pub status: u16,
+    pub resource_type: Option<String>,
+    pub resource_id: Option<u32>,
+    #[sqlx(try_from = "i32")]
+    pub actor: u32,
+    pub actor_name: String,
+    pub impersonated: Option<i32>,
+    pub impersonated_name: Option<String>,
+    pub data_before: Option<String>,
+    pub data_after: Option<String>,
+}
+
+fn parse_json(data: Option<String>) -> Result<Option<Value>, anyhow::Error> {
+    data.map(|d| serde_json::from_str(d.as_str()))
+        .transpose()
+        .context("Failed to parse JSON data of audit log entry")
+}
+
+impl TryFrom<AuditLogEntryRow> for AuditLogEntry {
+    type Error = anyhow::Error;
+
+    fn try_from(row: AuditLogEntryRow) -> Result<Self, Self::Error> {
+        Ok(AuditLogEntry {
+            id: row.id,
+            timestamp: row.timestamp.fixed_offset(),
+            method: row.method,
+            endpoint: row.endpoint,
+            status: row.status,
+            resource_type: row.resource_type,
+            resource_id: row.resource_id,
+            actor: row.actor,
+            actor_name: row.actor_name,
+            impersonated: row.impersonated.map(|i| i as u32),
+            impersonated_name: row.impersonated_name,
+            before: parse_json(row.data_before)?,
+            after: parse_json(row.data_after)?,
+        })
+    }
+}
+
+pub struct AuditLogFilter {
+    pub begin: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub actor: Option<u32>,
+    pub resource_type: Option<String>,
+    pub resource_id: Option<u32>,
+}
+
+#[tracing::instrument(
+    name = "select_audit_log_entries_from_db",
+    skip(transaction, filter)
+)]
+pub async fn select_audit_log_entries_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    filter: &AuditLogFilter,
+) -> Result<Vec<AuditLogEntry>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            id,
+            timestamp,
+            method,
+            endpoint,
+            status,
+            resource_type,
+            resource_id,
+            actor_id as actor,
+            actor_name,
+            impersonated_id as impersonated,
+            impersonated_name,
+            data_before,
+            data_after
+        FROM audit_log
+        WHERE
+            (? IS NULL OR timestamp >= ?) AND
+            (? IS NULL OR timestamp <= ?) AND
+            (? IS NULL OR actor_id = ?) AND
+            (? IS NULL OR resource_type = ?) AND
+            (? IS NULL OR resource_id = ?)
+        ORDER BY id
+        "#,
+        filter.begin,
+        filter.begin,
+        filter.end,
+        filter.end,
+        filter.actor,
+        filter.actor,
+        filter.resource_type,
+        filter.resource_type,
+        filter.resource_id,
+        filter.resource_id,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| AuditLogEntryRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to audit log entry row")?
+        .into_iter()
+        .map(AuditLogEntry::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+pub struct NewAuditLogEntry {
+    pub timestamp: DateTime<Utc>,
+    pub method: String,
+    pub endpoint: String,
+    pub status: u16,
+    pub resource_type: Option<String>,
+    pub resource_id: Option<u32>,
+    pub actor: u32,
+    pub actor_name: String,
+    pub impersonated: Option<u32>,
+    pub impersonated_name: Option<String>,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+#[tracing::instrument(
+    name = "insert_audit_log_entry_into_db",
+    skip(new_entry, transaction)
+)]
+pub async fn insert_audit_log_entry_into_db(
+    transaction: &mut Transaction<'_, MySql>,
+    new_entry: &NewAuditLogEntry,
+) -> Result<u64, MinimalApiError> {
+    let query = sqlx::query!(
+        r#"
+        INSERT INTO audit_log (
+            timestamp, method, endpoint, status, resource_type, resource_id,
+            actor_id, actor_name, impersonated_id, impersonated_name,
+            data_before, data_after
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+        new_entry.timestamp,
+        new_entry.method,
+        new_entry.endpoint,
+        new_entry.status,
+        new_entry.resource_type,
+        new_entry.resource_id,
+        new_entry.actor,
+        new_entry.actor_name,
+        new_entry.impersonated,
+        new_entry.impersonated_name,
+        new_entry.before.as_ref().map(|b| b.to_string()),
+        new_entry.after.as_ref().map(|a| a.to_string()),
+    );
+    let result = transaction
+        .execute(query)
+        .await
+        .context("Failed to execute insert query")?;
+    Ok(result.last_insert_id())
+}
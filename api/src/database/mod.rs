@@ -1,6 +1,8 @@
 pub mod accounting;
+pub mod audit;
 pub mod budgeting;
 pub mod pricing;
 pub mod quota;
 pub mod resources;
+pub mod scheduler;
 pub mod user;
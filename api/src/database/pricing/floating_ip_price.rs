@@ -0,0 +1,219 @@
+use anyhow::Context;
+use avina_wire::{
+    pricing::{FloatingIpPrice, FloatingIpPriceCreateData},
+    user::UserClass,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, FromRow, MySql, Transaction};
+
+use crate::error::{
+    MinimalApiError, NotFoundOrUnexpectedApiError, UnexpectedOnlyError,
+};
+
+#[derive(FromRow)]
+pub struct FloatingIpPriceRow {
+    #[sqlx(try_from = "i32")]
+    pub id: u32,
+    pub pool: String,
+    pub user_class: u32,
+    pub unit_price: f64,
+    pub start_time: DateTime<Utc>,
+}
+
+impl TryFrom<FloatingIpPriceRow> for FloatingIpPrice {
+    type Error = anyhow::Error;
+
+    fn try_from(row: FloatingIpPriceRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            pool: row.pool,
+            user_class: row
+                .user_class
+                .try_into()
+                .context("Failed to parse user class")?,
+            unit_price: row.unit_price,
+            start_time: row.start_time.fixed_offset(),
+        })
+    }
+}
+
+#[tracing::instrument(
+    name = "select_maybe_floating_ip_price_from_db",
+    skip(transaction)
+)]
+pub async fn select_maybe_floating_ip_price_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    floating_ip_price_id: u64,
+) -> Result<Option<FloatingIpPrice>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            id,
+            pool,
+            user_class,
+            unit_price,
+            start_time
+        FROM pricing_floatingipprice
+        WHERE id = ?
+        "#,
+        floating_ip_price_id
+    );
+    let row = transaction
+        .fetch_optional(query)
+        .await
+        .context("Failed to execute select query")?;
+    Ok(match row {
+        Some(row) => Some(
+            FloatingIpPriceRow::from_row(&row)
+                .context("Failed to parse floating IP price row")?
+                .try_into()?,
+        ),
+        None => None,
+    })
+}
+
+#[tracing::instrument(
+    name = "select_floating_ip_price_from_db",
+    skip(transaction)
+)]
+pub async fn select_floating_ip_price_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    floating_ip_price_id: u64,
+) -> Result<FloatingIpPrice, NotFoundOrUnexpectedApiError> {
+    select_maybe_floating_ip_price_from_db(transaction, floating_ip_price_id)
+        .await?
+        .ok_or(NotFoundOrUnexpectedApiError::NotFoundError)
+}
+
+#[tracing::instrument(
+    name = "select_all_floating_ip_prices_from_db",
+    skip(transaction)
+)]
+pub async fn select_all_floating_ip_prices_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+) -> Result<Vec<FloatingIpPrice>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            id,
+            pool,
+            user_class,
+            unit_price,
+            start_time
+        FROM pricing_floatingipprice
+        ORDER BY pool, user_class, start_time
+        "#,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| FloatingIpPriceRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to floating IP price")?
+        .into_iter()
+        .map(FloatingIpPrice::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+#[tracing::instrument(
+    name = "select_floating_ip_prices_for_period_from_db",
+    skip(transaction)
+)]
+pub async fn select_floating_ip_prices_for_period_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    end: DateTime<Utc>,
+) -> Result<Vec<FloatingIpPrice>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            id,
+            pool,
+            user_class,
+            unit_price,
+            start_time
+        FROM pricing_floatingipprice
+        WHERE start_time <= ?
+        ORDER BY start_time
+        "#,
+        end,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| FloatingIpPriceRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to floating IP price")?
+        .into_iter()
+        .map(FloatingIpPrice::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+pub struct NewFloatingIpPrice {
+    pub pool: String,
+    pub user_class: UserClass,
+    pub unit_price: f64,
+    pub start_time: DateTime<Utc>,
+}
+
+impl TryFrom<FloatingIpPriceCreateData> for NewFloatingIpPrice {
+    type Error = String;
+
+    fn try_from(data: FloatingIpPriceCreateData) -> Result<Self, Self::Error> {
+        if data.pool.is_empty() {
+            return Err(
+                "Pool of floating IP price must not be empty".to_string()
+            );
+        }
+        let unit_price = data.price.unwrap_or(0.);
+        if !unit_price.is_finite() || unit_price < 0. {
+            return Err("Floating IP price must not be negative".to_string());
+        }
+        Ok(Self {
+            pool: data.pool,
+            user_class: data.user_class,
+            unit_price,
+            start_time: data
+                .start_time
+                .map(|d| d.to_utc())
+                .unwrap_or(Utc::now()),
+        })
+    }
+}
+
+#[tracing::instrument(
+    name = "insert_floating_ip_price_into_db",
+    skip(new_floating_ip_price, transaction)
+)]
+pub async fn insert_floating_ip_price_into_db(
+    transaction: &mut Transaction<'_, MySql>,
+    new_floating_ip_price: &NewFloatingIpPrice,
+) -> Result<u64, MinimalApiError> {
+    let query = sqlx::query!(
+        r#"
+        INSERT IGNORE INTO pricing_floatingipprice (pool, user_class, unit_price, start_time)
+        VALUES (?, ?, ?, ?)
+        "#,
+        new_floating_ip_price.pool,
+        new_floating_ip_price.user_class as u32,
+        new_floating_ip_price.unit_price,
+        new_floating_ip_price.start_time,
+    );
+    let result = transaction
+        .execute(query)
+        .await
+        .context("Failed to execute insert query")?;
+    if result.rows_affected() == 0 {
+        return Err(MinimalApiError::ValidationError(
+            "Failed to insert new floating IP price, a conflicting entry exists"
+                .to_string(),
+        ));
+    }
+    let id = result.last_insert_id();
+    Ok(id)
+}
@@ -1 +1,3 @@
 pub mod flavor_price;
+pub mod floating_ip_price;
+pub mod volume_price;
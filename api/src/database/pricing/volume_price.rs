@@ -0,0 +1,216 @@
+use anyhow::Context;
+use avina_wire::{
+    pricing::{VolumePrice, VolumePriceCreateData},
+    user::UserClass,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, FromRow, MySql, Transaction};
+
+use crate::error::{
+    MinimalApiError, NotFoundOrUnexpectedApiError, UnexpectedOnlyError,
+};
+
+#[derive(FromRow)]
+pub struct VolumePriceRow {
+    #[sqlx(try_from = "i32")]
+    pub id: u32,
+    pub volume_type: String,
+    pub user_class: u32,
+    pub unit_price: f64,
+    pub start_time: DateTime<Utc>,
+}
+
+impl TryFrom<VolumePriceRow> for VolumePrice {
+    type Error = anyhow::Error;
+
+    fn try_from(row: VolumePriceRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            volume_type: row.volume_type,
+            user_class: row
+                .user_class
+                .try_into()
+                .context("Failed to parse user class")?,
+            unit_price: row.unit_price,
+            start_time: row.start_time.fixed_offset(),
+        })
+    }
+}
+
+#[tracing::instrument(
+    name = "select_maybe_volume_price_from_db",
+    skip(transaction)
+)]
+pub async fn select_maybe_volume_price_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    volume_price_id: u64,
+) -> Result<Option<VolumePrice>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            id,
+            volume_type,
+            user_class,
+            unit_price,
+            start_time
+        FROM pricing_volumeprice
+        WHERE id = ?
+        "#,
+        volume_price_id
+    );
+    let row = transaction
+        .fetch_optional(query)
+        .await
+        .context("Failed to execute select query")?;
+    Ok(match row {
+        Some(row) => Some(
+            VolumePriceRow::from_row(&row)
+                .context("Failed to parse volume price row")?
+                .try_into()?,
+        ),
+        None => None,
+    })
+}
+
+#[tracing::instrument(name = "select_volume_price_from_db", skip(transaction))]
+pub async fn select_volume_price_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    volume_price_id: u64,
+) -> Result<VolumePrice, NotFoundOrUnexpectedApiError> {
+    select_maybe_volume_price_from_db(transaction, volume_price_id)
+        .await?
+        .ok_or(NotFoundOrUnexpectedApiError::NotFoundError)
+}
+
+#[tracing::instrument(
+    name = "select_all_volume_prices_from_db",
+    skip(transaction)
+)]
+pub async fn select_all_volume_prices_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+) -> Result<Vec<VolumePrice>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            id,
+            volume_type,
+            user_class,
+            unit_price,
+            start_time
+        FROM pricing_volumeprice
+        ORDER BY volume_type, user_class, start_time
+        "#,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| VolumePriceRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to volume price")?
+        .into_iter()
+        .map(VolumePrice::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+#[tracing::instrument(
+    name = "select_volume_prices_for_period_from_db",
+    skip(transaction)
+)]
+pub async fn select_volume_prices_for_period_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    end: DateTime<Utc>,
+) -> Result<Vec<VolumePrice>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            id,
+            volume_type,
+            user_class,
+            unit_price,
+            start_time
+        FROM pricing_volumeprice
+        WHERE start_time <= ?
+        ORDER BY start_time
+        "#,
+        end,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| VolumePriceRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to volume price")?
+        .into_iter()
+        .map(VolumePrice::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+pub struct NewVolumePrice {
+    pub volume_type: String,
+    pub user_class: UserClass,
+    pub unit_price: f64,
+    pub start_time: DateTime<Utc>,
+}
+
+impl TryFrom<VolumePriceCreateData> for NewVolumePrice {
+    type Error = String;
+
+    fn try_from(data: VolumePriceCreateData) -> Result<Self, Self::Error> {
+        if data.volume_type.is_empty() {
+            return Err(
+                "Volume type of volume price must not be empty".to_string()
+            );
+        }
+        let unit_price = data.price.unwrap_or(0.);
+        if !unit_price.is_finite() || unit_price < 0. {
+            return Err("Volume price must not be negative".to_string());
+        }
+        Ok(Self {
+            volume_type: data.volume_type,
+            user_class: data.user_class,
+            unit_price,
+            start_time: data
+                .start_time
+                .map(|d| d.to_utc())
+                .unwrap_or(Utc::now()),
+        })
+    }
+}
+
+#[tracing::instrument(
+    name = "insert_volume_price_into_db",
+    skip(new_volume_price, transaction)
+)]
+pub async fn insert_volume_price_into_db(
+    transaction: &mut Transaction<'_, MySql>,
+    new_volume_price: &NewVolumePrice,
+) -> Result<u64, MinimalApiError> {
+    let query = sqlx::query!(
+        r#"
+        INSERT IGNORE INTO pricing_volumeprice (volume_type, user_class, unit_price, start_time)
+        VALUES (?, ?, ?, ?)
+        "#,
+        new_volume_price.volume_type,
+        new_volume_price.user_class as u32,
+        new_volume_price.unit_price,
+        new_volume_price.start_time,
+    );
+    let result = transaction
+        .execute(query)
+        .await
+        .context("Failed to execute insert query")?;
+    if result.rows_affected() == 0 {
+        return Err(MinimalApiError::ValidationError(
+            "Failed to insert new volume price, a conflicting entry exists"
+                .to_string(),
+        ));
+    }
+    let id = result.last_insert_id();
+    Ok(id)
+}
@@ -320,3 +320,25 @@ pub async fn insert_flavor_into_db(
     let id = result.last_insert_id();
     Ok(id)
 }
+
+#[tracing::instrument(name = "update_flavor_group_in_db", skip(transaction))]
+pub async fn update_flavor_group_in_db(
+    transaction: &mut Transaction<'_, MySql>,
+    flavor_id: u32,
+    group_id: Option<u32>,
+) -> Result<(), UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        UPDATE resources_flavor
+        SET group_id = ?
+        WHERE id = ?
+        "#,
+        group_id,
+        flavor_id,
+    );
+    transaction
+        .execute(query)
+        .await
+        .context("Failed to execute update query")?;
+    Ok(())
+}
@@ -0,0 +1,207 @@
+use anyhow::Context;
+use avina_wire::scheduler::{JobName, JobRun, JobRunStatus};
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, FromRow, MySql, MySqlConnection, Transaction};
+
+use crate::error::{MinimalApiError, UnexpectedOnlyError};
+
+#[derive(FromRow)]
+pub struct JobRunRow {
+    #[sqlx(try_from = "i32")]
+    pub id: u32,
+    pub job: String,
+    pub status: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub triggered_by: Option<i32>,
+    pub message: Option<String>,
+}
+
+impl TryFrom<JobRunRow> for JobRun {
+    type Error = anyhow::Error;
+
+    fn try_from(row: JobRunRow) -> Result<Self, Self::Error> {
+        Ok(JobRun {
+            id: row.id,
+            job: row.job.parse()?,
+            status: row.status.parse()?,
+            started_at: row.started_at.fixed_offset(),
+            finished_at: row.finished_at.map(|f| f.fixed_offset()),
+            triggered_by: row.triggered_by.map(|t| t as u32),
+            message: row.message,
+        })
+    }
+}
+
+#[tracing::instrument(name = "select_job_runs_from_db", skip(transaction))]
+pub async fn select_job_runs_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    job: Option<JobName>,
+) -> Result<Vec<JobRun>, UnexpectedOnlyError> {
+    let job = job.map(|j| j.as_str());
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            id,
+            job,
+            status,
+            started_at,
+            finished_at,
+            triggered_by_id as triggered_by,
+            message
+        FROM scheduler_jobrun
+        WHERE ? IS NULL OR job = ?
+        ORDER BY id DESC
+        "#,
+        job,
+        job,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| JobRunRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to job run row")?
+        .into_iter()
+        .map(JobRun::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+#[tracing::instrument(name = "select_last_job_run_from_db", skip(transaction))]
+pub async fn select_last_job_run_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    job: JobName,
+) -> Result<Option<JobRun>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            id,
+            job,
+            status,
+            started_at,
+            finished_at,
+            triggered_by_id as triggered_by,
+            message
+        FROM scheduler_jobrun
+        WHERE job = ?
+        ORDER BY started_at DESC, id DESC
+        LIMIT 1
+        "#,
+        job.as_str(),
+    );
+    let row = transaction
+        .fetch_optional(query)
+        .await
+        .context("Failed to execute select query")?;
+    Ok(match row {
+        Some(row) => Some(
+            JobRunRow::from_row(&row)
+                .context("Failed to parse job run row")?
+                .try_into()?,
+        ),
+        None => None,
+    })
+}
+
+#[tracing::instrument(name = "insert_job_run_into_db", skip(transaction))]
+pub async fn insert_job_run_into_db(
+    transaction: &mut Transaction<'_, MySql>,
+    job: JobName,
+    started_at: DateTime<Utc>,
+    triggered_by: Option<u32>,
+) -> Result<u64, MinimalApiError> {
+    let query = sqlx::query!(
+        r#"
+        INSERT INTO scheduler_jobrun (job, status, started_at, triggered_by_id)
+        VALUES (?, ?, ?, ?)
+        "#,
+        job.as_str(),
+        JobRunStatus::Running.as_str(),
+        started_at,
+        triggered_by,
+    );
+    let result = transaction
+        .execute(query)
+        .await
+        .context("Failed to execute insert query")?;
+    Ok(result.last_insert_id())
+}
+
+#[tracing::instrument(name = "finish_job_run_in_db", skip(transaction))]
+pub async fn finish_job_run_in_db(
+    transaction: &mut Transaction<'_, MySql>,
+    job_run_id: u64,
+    status: JobRunStatus,
+    finished_at: DateTime<Utc>,
+    message: Option<String>,
+) -> Result<(), UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        UPDATE scheduler_jobrun
+        SET status = ?, finished_at = ?, message = ?
+        WHERE id = ?
+        "#,
+        status.as_str(),
+        finished_at,
+        message,
+        job_run_id,
+    );
+    transaction
+        .execute(query)
+        .await
+        .context("Failed to execute update query")?;
+    Ok(())
+}
+
+fn job_lock_name(job: JobName) -> String {
+    format!("avina_scheduler_{job}")
+}
+
+/// Takes the advisory lock of the job without waiting, returns whether it
+/// was acquired. The lock belongs to the given connection, so it has to be
+/// released on the same one.
+#[tracing::instrument(name = "acquire_job_lock", skip(connection))]
+pub async fn acquire_job_lock(
+    connection: &mut MySqlConnection,
+    job: JobName,
+) -> Result<bool, UnexpectedOnlyError> {
+    #[derive(FromRow)]
+    struct Row {
+        locked: Option<i64>,
+    }
+    let query = sqlx::query!(
+        r#"
+        SELECT GET_LOCK(?, 0) AS locked
+        "#,
+        job_lock_name(job),
+    );
+    let row = connection
+        .fetch_one(query)
+        .await
+        .context("Failed to execute lock query")?;
+    let locked = Row::from_row(&row)
+        .context("Failed to parse lock row")?
+        .locked;
+    Ok(locked == Some(1))
+}
+
+#[tracing::instrument(name = "release_job_lock", skip(connection))]
+pub async fn release_job_lock(
+    connection: &mut MySqlConnection,
+    job: JobName,
+) -> Result<(), UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT RELEASE_LOCK(?) AS released
+        "#,
+        job_lock_name(job),
+    );
+    connection
+        .fetch_one(query)
+        .await
+        .context("Failed to execute unlock query")?;
+    Ok(())
+}
@@ -0,0 +1,242 @@
+use anyhow::Context;
+use avina_wire::{
+    error::ConversionError,
+    user::{ApiKey, ApiKeyScope},
+};
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, FromRow, MySql, Transaction};
+
+use crate::error::{
+    MinimalApiError, NotFoundOrUnexpectedApiError, UnexpectedOnlyError,
+};
+
+pub fn parse_api_key_scopes(
+    scopes: &str,
+) -> Result<Vec<ApiKeyScope>, ConversionError> {
+    scopes
+        .split(',')
+        .filter(|scope| !scope.is_empty())
+        .map(|scope| scope.parse())
+        .collect()
+}
+
+pub fn format_api_key_scopes(scopes: &[ApiKeyScope]) -> String {
+    scopes
+        .iter()
+        .map(|scope| scope.as_str())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[derive(FromRow)]
+pub struct ApiKeyRow {
+    #[sqlx(try_from = "i32")]
+    pub id: u32,
+    pub name: String,
+    pub prefix: String,
+    pub scopes: String,
+    #[sqlx(try_from = "i32")]
+    pub user: u32,
+    pub username: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<ApiKeyRow> for ApiKey {
+    type Error = ConversionError;
+
+    fn try_from(row: ApiKeyRow) -> Result<Self, Self::Error> {
+        Ok(ApiKey {
+            id: row.id,
+            name: row.name,
+            prefix: row.prefix,
+            scopes: parse_api_key_scopes(row.scopes.as_str())?,
+            user: row.user,
+            username: row.username,
+            created_at: row.created_at.fixed_offset(),
+            expires_at: row.expires_at.map(|e| e.fixed_offset()),
+            last_used_at: row.last_used_at.map(|l| l.fixed_offset()),
+        })
+    }
+}
+
+#[tracing::instrument(name = "select_maybe_api_key_from_db", skip(transaction))]
+pub async fn select_maybe_api_key_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    api_key_id: u64,
+) -> Result<Option<ApiKey>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            k.id as id,
+            k.name as name,
+            k.prefix as prefix,
+            k.scopes as scopes,
+            u.id as user,
+            u.name as username,
+            k.created_at as created_at,
+            k.expires_at as expires_at,
+            k.last_used_at as last_used_at
+        FROM
+            user_apikey as k,
+            user_user as u
+        WHERE
+            k.user_id = u.id AND
+            k.id = ?
+        "#,
+        api_key_id
+    );
+    let row = transaction
+        .fetch_optional(query)
+        .await
+        .context("Failed to execute select query")?;
+    Ok(match row {
+        Some(row) => Some(
+            ApiKeyRow::from_row(&row)
+                .context("Failed to parse API key row")?
+                .try_into()
+                .context("Failed to convert API key row to API key")?,
+        ),
+        None => None,
+    })
+}
+
+#[tracing::instrument(name = "select_api_key_from_db", skip(transaction))]
+pub async fn select_api_key_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    api_key_id: u64,
+) -> Result<ApiKey, NotFoundOrUnexpectedApiError> {
+    select_maybe_api_key_from_db(transaction, api_key_id)
+        .await?
+        .ok_or(NotFoundOrUnexpectedApiError::NotFoundError)
+}
+
+#[tracing::instrument(name = "select_all_api_keys_from_db", skip(transaction))]
+pub async fn select_all_api_keys_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+) -> Result<Vec<ApiKey>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            k.id as id,
+            k.name as name,
+            k.prefix as prefix,
+            k.scopes as scopes,
+            u.id as user,
+            u.name as username,
+            k.created_at as created_at,
+            k.expires_at as expires_at,
+            k.last_used_at as last_used_at
+        FROM
+            user_apikey as k,
+            user_user as u
+        WHERE
+            k.user_id = u.id
+        "#,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| ApiKeyRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to API key row")?
+        .into_iter()
+        .map(ApiKey::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert API key row to API key")?;
+    Ok(rows)
+}
+
+#[tracing::instrument(
+    name = "select_api_keys_by_user_from_db",
+    skip(transaction)
+)]
+pub async fn select_api_keys_by_user_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    user_id: u64,
+) -> Result<Vec<ApiKey>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            k.id as id,
+            k.name as name,
+            k.prefix as prefix,
+            k.scopes as scopes,
+            u.id as user,
+            u.name as username,
+            k.created_at as created_at,
+            k.expires_at as expires_at,
+            k.last_used_at as last_used_at
+        FROM
+            user_apikey as k,
+            user_user as u
+        WHERE
+            k.user_id = u.id AND
+            u.id = ?
+        "#,
+        user_id
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| ApiKeyRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to API key row")?
+        .into_iter()
+        .map(ApiKey::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert API key row to API key")?;
+    Ok(rows)
+}
+
+pub struct NewApiKey {
+    pub name: String,
+    pub prefix: String,
+    pub key_hash: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub user: u32,
+}
+
+#[tracing::instrument(
+    name = "insert_api_key_into_db",
+    skip(new_api_key, transaction)
+)]
+pub async fn insert_api_key_into_db(
+    transaction: &mut Transaction<'_, MySql>,
+    new_api_key: &NewApiKey,
+) -> Result<u64, MinimalApiError> {
+    // TODO: MariaDB 10.5 introduced INSERT ... RETURNING
+    let query = sqlx::query!(
+        r#"
+        INSERT IGNORE INTO user_apikey (
+            name, prefix, key_hash, scopes, created_at, expires_at, user_id
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+        new_api_key.name,
+        new_api_key.prefix,
+        new_api_key.key_hash,
+        format_api_key_scopes(&new_api_key.scopes),
+        new_api_key.created_at,
+        new_api_key.expires_at,
+        new_api_key.user,
+    );
+    let result = transaction
+        .execute(query)
+        .await
+        .context("Failed to execute insert query")?;
+    if result.rows_affected() == 0 {
+        return Err(MinimalApiError::ValidationError(
+            "Failed to insert new API key, a conflicting entry exists"
+                .to_string(),
+        ));
+    }
+    Ok(result.last_insert_id())
+}
@@ -1,3 +1,5 @@
+pub mod api_key;
 pub mod project;
+pub mod project_class_change;
 #[allow(clippy::module_inception)]
 pub mod user;
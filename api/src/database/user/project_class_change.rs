@@ -0,0 +1,205 @@
+use anyhow::Context;
+use avina_wire::user::{ProjectClassChange, UserClass};
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, FromRow, MySql, Transaction};
+
+use crate::error::{
+    MinimalApiError, NotFoundOrUnexpectedApiError, UnexpectedOnlyError,
+};
+
+#[derive(FromRow)]
+pub struct ProjectClassChangeRow {
+    #[sqlx(try_from = "i32")]
+    pub id: u32,
+    #[sqlx(try_from = "i32")]
+    pub project: u32,
+    pub project_name: String,
+    pub user_class: u32,
+    pub start_time: DateTime<Utc>,
+}
+
+impl TryFrom<ProjectClassChangeRow> for ProjectClassChange {
+    type Error = anyhow::Error;
+
+    fn try_from(row: ProjectClassChangeRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            project: row.project,
+            project_name: row.project_name,
+            user_class: row
+                .user_class
+                .try_into()
+                .context("Failed to parse user class")?,
+            start_time: row.start_time.fixed_offset(),
+        })
+    }
+}
+
+#[tracing::instrument(
+    name = "select_maybe_project_class_change_from_db",
+    skip(transaction)
+)]
+pub async fn select_maybe_project_class_change_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    project_class_change_id: u64,
+) -> Result<Option<ProjectClassChange>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            h.id as id,
+            p.id as project,
+            p.name as project_name,
+            h.user_class as user_class,
+            h.start_time as start_time
+        FROM
+            user_project_class_history as h,
+            user_project as p
+        WHERE
+            h.project_id = p.id AND
+            h.id = ?
+        "#,
+        project_class_change_id
+    );
+    let row = transaction
+        .fetch_optional(query)
+        .await
+        .context("Failed to execute select query")?;
+    Ok(match row {
+        Some(row) => Some(
+            ProjectClassChangeRow::from_row(&row)
+                .context("Failed to parse project class change row")?
+                .try_into()?,
+        ),
+        None => None,
+    })
+}
+
+#[tracing::instrument(
+    name = "select_project_class_change_from_db",
+    skip(transaction)
+)]
+pub async fn select_project_class_change_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    project_class_change_id: u64,
+) -> Result<ProjectClassChange, NotFoundOrUnexpectedApiError> {
+    select_maybe_project_class_change_from_db(
+        transaction,
+        project_class_change_id,
+    )
+    .await?
+    .ok_or(NotFoundOrUnexpectedApiError::NotFoundError)
+}
+
+#[tracing::instrument(
+    name = "select_all_project_class_changes_from_db",
+    skip(transaction)
+)]
+pub async fn select_all_project_class_changes_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+) -> Result<Vec<ProjectClassChange>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            h.id as id,
+            p.id as project,
+            p.name as project_name,
+            h.user_class as user_class,
+            h.start_time as start_time
+        FROM
+            user_project_class_history as h,
+            user_project as p
+        WHERE
+            h.project_id = p.id
+        ORDER BY p.id, h.start_time
+        "#,
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| ProjectClassChangeRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to project class change")?
+        .into_iter()
+        .map(ProjectClassChange::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+#[tracing::instrument(
+    name = "select_project_class_changes_by_project_from_db",
+    skip(transaction)
+)]
+pub async fn select_project_class_changes_by_project_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    project_id: u64,
+) -> Result<Vec<ProjectClassChange>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            h.id as id,
+            p.id as project,
+            p.name as project_name,
+            h.user_class as user_class,
+            h.start_time as start_time
+        FROM
+            user_project_class_history as h,
+            user_project as p
+        WHERE
+            h.project_id = p.id AND
+            p.id = ?
+        ORDER BY h.start_time
+        "#,
+        project_id
+    );
+    let rows = transaction
+        .fetch_all(query)
+        .await
+        .context("Failed to execute select query")?
+        .into_iter()
+        .map(|r| ProjectClassChangeRow::from_row(&r))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to convert row to project class change")?
+        .into_iter()
+        .map(ProjectClassChange::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+pub struct NewProjectClassChange {
+    pub project_id: u64,
+    pub user_class: UserClass,
+    pub start_time: DateTime<Utc>,
+}
+
+#[tracing::instrument(
+    name = "insert_project_class_change_into_db",
+    skip(new_project_class_change, transaction)
+)]
+pub async fn insert_project_class_change_into_db(
+    transaction: &mut Transaction<'_, MySql>,
+    new_project_class_change: &NewProjectClassChange,
+) -> Result<u64, MinimalApiError> {
+    let query = sqlx::query!(
+        r#"
+        INSERT IGNORE INTO user_project_class_history (project_id, user_class, start_time)
+        VALUES (?, ?, ?)
+        "#,
+        new_project_class_change.project_id,
+        new_project_class_change.user_class as u32,
+        new_project_class_change.start_time,
+    );
+    let result = transaction
+        .execute(query)
+        .await
+        .context("Failed to execute insert query")?;
+    if result.rows_affected() == 0 {
+        return Err(MinimalApiError::ValidationError(
+            "Failed to insert new project class change, a conflicting entry exists"
+                .to_string(),
+        ));
+    }
+    let id = result.last_insert_id();
+    Ok(id)
+}
@@ -1,5 +1,5 @@
 use anyhow::Context;
-use avina_wire::user::{User, UserClass, UserDetailed, UserMinimal};
+use avina_wire::user::{Project, User, UserClass, UserDetailed, UserMinimal};
 use sqlx::{Executor, FromRow, MySql, Transaction};
 
 use crate::error::{NotFoundOrUnexpectedApiError, UnexpectedOnlyError};
@@ -314,3 +314,39 @@ pub async fn select_user_class_by_user_from_db(
         None => None,
     })
 }
+
+#[tracing::instrument(
+    name = "select_maybe_project_by_user_from_db",
+    skip(transaction)
+)]
+pub async fn select_maybe_project_by_user_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    user_id: u64,
+) -> Result<Option<Project>, UnexpectedOnlyError> {
+    let query = sqlx::query!(
+        r#"
+        SELECT
+            p.id as id,
+            p.name as name,
+            p.openstack_id as openstack_id,
+            p.user_class as user_class
+        FROM
+            user_user AS u,
+            user_project AS p
+        WHERE
+            u.project_id = p.id AND
+            u.id = ?
+        "#,
+        user_id
+    );
+    let row = transaction
+        .fetch_optional(query)
+        .await
+        .context("Failed to execute select query")?;
+    Ok(match row {
+        Some(row) => Some(
+            Project::from_row(&row).context("Failed to parse project row")?,
+        ),
+        None => None,
+    })
+}
@@ -19,6 +19,16 @@ pub fn unauthorized_error(message: &str) -> actix_web::Error {
     .into()
 }
 
+pub fn forbidden_error(message: &str) -> actix_web::Error {
+    InternalError::from_response(
+        anyhow::anyhow!(message.to_string()),
+        HttpResponse::Forbidden().json(ErrorResponse {
+            detail: message.to_string(),
+        }),
+    )
+    .into()
+}
+
 pub fn internal_server_error(message: &str) -> actix_web::Error {
     InternalError::from_response(
         anyhow::anyhow!(message.to_string()),
@@ -39,6 +49,16 @@ pub fn bad_request_error(message: &str) -> actix_web::Error {
     .into()
 }
 
+pub fn service_unavailable_error(message: &str) -> actix_web::Error {
+    InternalError::from_response(
+        anyhow::anyhow!(message.to_string()),
+        HttpResponse::ServiceUnavailable().json(ErrorResponse {
+            detail: message.to_string(),
+        }),
+    )
+    .into()
+}
+
 pub fn not_found_error(message: &str) -> actix_web::Error {
     InternalError::from_response(
         anyhow::anyhow!(message.to_string()),
@@ -63,6 +83,8 @@ pub enum OptionApiError {
     NotFoundError,
     #[error("{0}")]
     AuthorizationError(String),
+    #[error("{0}")]
+    ServiceUnavailableError(String),
     #[error(transparent)]
     UnexpectedError(#[from] anyhow::Error),
 }
@@ -85,6 +107,9 @@ impl ResponseError for OptionApiError {
             OptionApiError::AuthorizationError(message) => {
                 (StatusCode::FORBIDDEN, message.clone())
             }
+            OptionApiError::ServiceUnavailableError(message) => {
+                (StatusCode::SERVICE_UNAVAILABLE, message.clone())
+            }
             OptionApiError::UnexpectedError(_) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "Internal server error, contact admin or check logs"
@@ -113,6 +138,9 @@ impl From<NormalApiError> for OptionApiError {
             NormalApiError::AuthorizationError(message) => {
                 Self::AuthorizationError(message)
             }
+            NormalApiError::ServiceUnavailableError(message) => {
+                Self::ServiceUnavailableError(message)
+            }
             NormalApiError::UnexpectedError(error) => {
                 Self::UnexpectedError(error)
             }
@@ -190,6 +218,8 @@ pub enum NormalApiError {
     ValidationError(String),
     #[error("{0}")]
     AuthorizationError(String),
+    #[error("{0}")]
+    ServiceUnavailableError(String),
     #[error(transparent)]
     UnexpectedError(#[from] anyhow::Error),
 }
@@ -209,6 +239,9 @@ impl ResponseError for NormalApiError {
             NormalApiError::AuthorizationError(message) => {
                 (StatusCode::FORBIDDEN, message.clone())
             }
+            NormalApiError::ServiceUnavailableError(message) => {
+                (StatusCode::SERVICE_UNAVAILABLE, message.clone())
+            }
             NormalApiError::UnexpectedError(_) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "Internal server error, contact admin or check logs"
@@ -423,3 +456,80 @@ impl From<UnexpectedOnlyError> for NormalApiError {
         }
     }
 }
+
+#[derive(thiserror::Error)]
+pub enum OpenStackError {
+    // NOTE: no service token could be obtained from Keystone, so no request
+    // to OpenStack can currently succeed.
+    #[error("OpenStack is currently unavailable")]
+    ServiceUnavailableError(#[source] anyhow::Error),
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl std::fmt::Debug for OpenStackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for OpenStackError {
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        let (status_code, message) = match self {
+            OpenStackError::ServiceUnavailableError(_) => {
+                (StatusCode::SERVICE_UNAVAILABLE, self.to_string())
+            }
+            OpenStackError::UnexpectedError(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Internal server error, contact admin or check logs"
+                    .to_string(),
+            ),
+        };
+        HttpResponse::build(status_code)
+            .insert_header((
+                CONTENT_TYPE,
+                HeaderValue::from_static("application/json"),
+            ))
+            // TODO: handle unwrap
+            .body(
+                serde_json::to_string(&ErrorResponse { detail: message })
+                    .unwrap(),
+            )
+    }
+}
+
+impl From<UnexpectedOnlyError> for OpenStackError {
+    fn from(value: UnexpectedOnlyError) -> Self {
+        match value {
+            UnexpectedOnlyError::UnexpectedError(message) => {
+                Self::UnexpectedError(message)
+            }
+        }
+    }
+}
+
+impl From<OpenStackError> for OptionApiError {
+    fn from(value: OpenStackError) -> Self {
+        match value {
+            OpenStackError::ServiceUnavailableError(_) => {
+                Self::ServiceUnavailableError(value.to_string())
+            }
+            OpenStackError::UnexpectedError(error) => {
+                Self::UnexpectedError(error)
+            }
+        }
+    }
+}
+
+impl From<OpenStackError> for NormalApiError {
+    fn from(value: OpenStackError) -> Self {
+        match value {
+            OpenStackError::ServiceUnavailableError(_) => {
+                Self::ServiceUnavailableError(value.to_string())
+            }
+            OpenStackError::UnexpectedError(error) => {
+                Self::UnexpectedError(error)
+            }
+        }
+    }
+}
@@ -1,3 +1,5 @@
+pub mod api_key;
+pub mod audit;
 pub mod authentication;
 pub mod authorization;
 pub mod configuration;
@@ -5,6 +7,8 @@ pub mod database;
 pub mod error;
 pub mod openstack;
 pub mod routes;
+pub mod scheduler;
 pub mod startup;
 pub mod telemetry;
+pub mod token_cache;
 pub mod utils;
@@ -1,62 +1,137 @@
-use std::{collections::HashMap, time::Instant};
+use std::{collections::HashMap, sync::Mutex, time::Duration};
 
 use anyhow::Context;
+use chrono::{DateTime, NaiveDateTime, TimeDelta, Utc};
 use jzon::object;
 use reqwest::{
     ClientBuilder,
     header::{CONTENT_TYPE, HeaderMap, HeaderValue},
 };
-use tokio::sync::RwLock;
+use serde::Serialize;
+use tokio::sync::{Mutex as AsyncMutex, RwLock};
 use uuid::Uuid;
 
-use crate::configuration::OpenStackSettings;
+use crate::{configuration::OpenStackSettings, error::OpenStackError};
+
+// NOTE: renew well before the token expires, so that requests already in
+// flight do not end up using an expired token.
+const TOKEN_RENEWAL_MARGIN: TimeDelta = TimeDelta::minutes(5);
+// NOTE: only used when Keystone does not tell us when the token expires.
+const TOKEN_DEFAULT_LIFETIME: TimeDelta = TimeDelta::hours(1);
+const TOKEN_RENEWAL_ATTEMPTS: u32 = 4;
+const TOKEN_RENEWAL_INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+// NOTE: while Keystone is down, do not let every request go through all
+// renewal attempts again.
+const TOKEN_RENEWAL_COOLDOWN: TimeDelta = TimeDelta::seconds(10);
 
 struct Token {
-    settings: OpenStackSettings,
     token: String,
-    renewed_at: Instant,
+    expires_at: DateTime<Utc>,
+    renewed_at: DateTime<Utc>,
 }
 
 impl Token {
-    async fn new(settings: &OpenStackSettings) -> Result<Self, anyhow::Error> {
-        Ok(Self {
-            settings: settings.clone(),
-            token: issue_token(settings).await?,
-            renewed_at: Instant::now(),
-        })
+    fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
     }
 
-    async fn renew(&mut self) -> Result<(), anyhow::Error> {
-        self.token = issue_token(&self.settings).await?;
-        self.renewed_at = Instant::now();
-        Ok(())
+    fn needs_renewal(&self) -> bool {
+        self.expires_at - TOKEN_RENEWAL_MARGIN <= Utc::now()
     }
+}
 
-    fn is_expired(&self) -> bool {
-        self.renewed_at.elapsed().as_secs() > 3600
-    }
+#[derive(Clone, Debug, Serialize)]
+pub struct TokenStatus {
+    pub valid: bool,
+    pub expires_at: DateTime<Utc>,
+    pub renewed_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub failed_at: Option<DateTime<Utc>>,
+}
 
-    fn get(&self) -> String {
-        self.token.clone()
-    }
+#[derive(Default)]
+struct RenewalFailure {
+    error: Option<String>,
+    failed_at: Option<DateTime<Utc>>,
 }
 
 struct TokenHandler {
+    settings: OpenStackSettings,
     token: RwLock<Token>,
+    // NOTE: held while renewing, so concurrent requests wait for a single
+    // renewal instead of each asking Keystone for a new token.
+    renewal: AsyncMutex<()>,
+    failure: Mutex<RenewalFailure>,
 }
 
 impl TokenHandler {
     async fn new(settings: &OpenStackSettings) -> Result<Self, anyhow::Error> {
         Ok(TokenHandler {
-            token: RwLock::new(Token::new(settings).await?),
+            settings: settings.clone(),
+            token: RwLock::new(issue_token_with_backoff(settings).await?),
+            renewal: AsyncMutex::new(()),
+            failure: Mutex::new(RenewalFailure::default()),
         })
     }
 
-    async fn get(&self) -> String {
-        if self.token.read().await.is_expired() {
-            self.token.write().await.renew().await.unwrap();
+    async fn current(&self) -> Option<String> {
+        let token = self.token.read().await;
+        (!token.needs_renewal()).then(|| token.token.clone())
+    }
+
+    async fn get(&self) -> Result<String, OpenStackError> {
+        if let Some(token) = self.current().await {
+            return Ok(token);
+        }
+        let _renewal = self.renewal.lock().await;
+        // NOTE: another request may have renewed the token in the meantime.
+        if let Some(token) = self.current().await {
+            return Ok(token);
+        }
+        let recently_failed = self
+            .failure
+            .lock()
+            .unwrap()
+            .failed_at
+            .is_some_and(|failed_at| {
+                Utc::now() - failed_at < TOKEN_RENEWAL_COOLDOWN
+            });
+        if !recently_failed {
+            match issue_token_with_backoff(&self.settings).await {
+                Ok(token) => {
+                    let value = token.token.clone();
+                    *self.token.write().await = token;
+                    *self.failure.lock().unwrap() = RenewalFailure::default();
+                    return Ok(value);
+                }
+                Err(error) => {
+                    tracing::error!("Failed to renew OpenStack token: {error}");
+                    *self.failure.lock().unwrap() = RenewalFailure {
+                        error: Some(error.to_string()),
+                        failed_at: Some(Utc::now()),
+                    };
+                }
+            }
+        }
+        let token = self.token.read().await;
+        if token.is_expired() {
+            return Err(OpenStackError::ServiceUnavailableError(
+                anyhow::anyhow!("OpenStack token expired and renewal failed"),
+            ));
+        }
+        Ok(token.token.clone())
+    }
+
+    async fn status(&self) -> TokenStatus {
+        let token = self.token.read().await;
+        let failure = self.failure.lock().unwrap();
+        TokenStatus {
+            valid: !token.is_expired(),
+            expires_at: token.expires_at,
+            renewed_at: token.renewed_at,
+            last_error: failure.error.clone(),
+            failed_at: failure.failed_at,
         }
-        self.token.read().await.get()
     }
 }
 
@@ -73,6 +148,12 @@ pub struct ProjectMinimal {
 }
 
 #[derive(Clone, Debug, serde::Deserialize)]
+pub struct ValidatedToken {
+    pub project: ProjectMinimal,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, Serialize)]
 pub struct Link {
     pub href: String,
     pub rel: String,
@@ -107,26 +188,26 @@ pub struct FlavorDetailedList {
     flavors: Vec<FlavorDetailed>,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug, serde::Deserialize, Serialize)]
 #[allow(unused)]
 pub struct ServerDetailedFlavor {
     pub id: String,
     pub links: Vec<Link>,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug, serde::Deserialize, Serialize)]
 #[allow(unused)]
 pub struct ServerDetailedSecurityGroup {
     pub name: String,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug, serde::Deserialize, Serialize)]
 #[allow(unused)]
 pub struct ServerDetailedVolumesAttached {
     pub id: String,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug, serde::Deserialize, Serialize)]
 #[allow(unused)]
 pub struct ServerDetailedAddress {
     pub version: usize,
@@ -137,7 +218,7 @@ pub struct ServerDetailedAddress {
     pub mac_addr: String,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug, serde::Deserialize, Serialize)]
 #[allow(unused)]
 #[serde(untagged)]
 pub enum ServerDetailedImage {
@@ -146,7 +227,7 @@ pub enum ServerDetailedImage {
 }
 
 // TODO: there are many missing fields here.
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug, serde::Deserialize, Serialize)]
 #[allow(unused)]
 pub struct ServerDetailed {
     pub id: Uuid,
@@ -160,10 +241,10 @@ pub struct ServerDetailed {
     pub host_id: String,
     pub image: ServerDetailedImage,
     pub flavor: ServerDetailedFlavor,
-    // TODO: this is actually a datetime
-    pub created: String,
-    // TODO: this is actually a datetime
-    pub updated: String,
+    #[serde(deserialize_with = "deserialize_nova_datetime")]
+    pub created: DateTime<Utc>,
+    #[serde(deserialize_with = "deserialize_nova_datetime")]
+    pub updated: DateTime<Utc>,
     pub addresses: HashMap<String, Vec<ServerDetailedAddress>>,
     #[serde(rename = "accessIPv4")]
     pub access_ipv4: String,
@@ -176,12 +257,16 @@ pub struct ServerDetailed {
     pub availability_zone: String,
     pub config_drive: String,
     pub key_name: Option<String>,
-    // TODO: this is actually a datetime
-    #[serde(rename = "OS-SRV-USG:launched_at")]
-    pub launched_at: Option<String>,
-    // TODO: this is actually a datetime
-    #[serde(rename = "OS-SRV-USG:terminated_at")]
-    pub terminated_at: Option<String>,
+    #[serde(
+        rename = "OS-SRV-USG:launched_at",
+        deserialize_with = "deserialize_optional_nova_datetime"
+    )]
+    pub launched_at: Option<DateTime<Utc>>,
+    #[serde(
+        rename = "OS-SRV-USG:terminated_at",
+        deserialize_with = "deserialize_optional_nova_datetime"
+    )]
+    pub terminated_at: Option<DateTime<Utc>>,
     #[serde(rename = "OS-EXT-SRV-ATTR:host")]
     pub host: Option<String>,
     #[serde(rename = "OS-EXT-SRV-ATTR:instance_name")]
@@ -199,11 +284,108 @@ pub struct ServerDetailed {
     pub security_groups: Option<Vec<ServerDetailedSecurityGroup>>,
 }
 
+// NOTE: Nova returns created and updated in RFC 3339, but launched_at and
+// terminated_at without a timezone, which are UTC as well.
+fn parse_nova_datetime(value: &str) -> Result<DateTime<Utc>, anyhow::Error> {
+    match DateTime::parse_from_rfc3339(value) {
+        Ok(datetime) => Ok(datetime.to_utc()),
+        Err(_) => {
+            Ok(NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+                .context(format!("Could not parse Nova timestamp {value}"))?
+                .and_utc())
+        }
+    }
+}
+
+fn deserialize_nova_datetime<'de, D>(
+    deserializer: D,
+) -> Result<DateTime<Utc>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = <String as serde::Deserialize>::deserialize(deserializer)?;
+    parse_nova_datetime(&value).map_err(serde::de::Error::custom)
+}
+
+fn deserialize_optional_nova_datetime<'de, D>(
+    deserializer: D,
+) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value =
+        <Option<String> as serde::Deserialize>::deserialize(deserializer)?;
+    value
+        .map(|value| parse_nova_datetime(&value))
+        .transpose()
+        .map_err(serde::de::Error::custom)
+}
+
 #[derive(Clone, Debug, serde::Deserialize)]
 pub struct ServerDetailedList {
     servers: Vec<ServerDetailed>,
 }
 
+// TODO: there are many missing fields here.
+#[derive(Clone, Debug, serde::Deserialize, Serialize)]
+#[allow(unused)]
+pub struct VolumeDetailed {
+    pub id: Uuid,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub status: String,
+    /// Size in GiB.
+    pub size: u32,
+    pub volume_type: Option<String>,
+    pub bootable: String,
+    #[serde(rename = "os-vol-tenant-attr:tenant_id")]
+    pub tenant_id: String,
+    pub user_id: String,
+    // NOTE: Cinder uses the same timestamps without timezone as Nova.
+    #[serde(deserialize_with = "deserialize_nova_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(deserialize_with = "deserialize_optional_nova_datetime")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct VolumeDetailedList {
+    volumes: Vec<VolumeDetailed>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, Serialize)]
+#[allow(unused)]
+pub struct FloatingIp {
+    pub id: Uuid,
+    pub floating_ip_address: String,
+    pub floating_network_id: String,
+    pub fixed_ip_address: Option<String>,
+    pub port_id: Option<String>,
+    pub status: String,
+    pub project_id: String,
+    #[serde(deserialize_with = "deserialize_nova_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(deserialize_with = "deserialize_optional_nova_datetime")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct FloatingIpList {
+    floatingips: Vec<FloatingIp>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[allow(unused)]
+pub struct Network {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct NetworkList {
+    networks: Vec<Network>,
+}
+
 #[derive(Clone, Debug, serde::Deserialize)]
 #[allow(unused)]
 pub struct Domain {
@@ -247,32 +429,37 @@ impl OpenStack {
         })
     }
 
-    async fn client(&self) -> Result<reqwest::Client, anyhow::Error> {
+    /// Renews the token if necessary before reporting its status, since the
+    /// token is otherwise only renewed by requests to OpenStack and would
+    /// expire on an idle instance.
+    pub async fn token_status(&self) -> TokenStatus {
+        // NOTE: a failed renewal is recorded in the status itself.
+        let _ = self.token.get().await;
+        self.token.status().await
+    }
+
+    async fn client(&self) -> Result<reqwest::Client, OpenStackError> {
         let mut headers = HeaderMap::new();
         headers
             .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
         headers.insert(
             "X-Auth-Token",
-            HeaderValue::from_str(self.token.get().await.as_str())
+            HeaderValue::from_str(self.token.get().await?.as_str())
                 .context("Could not create token header")?,
         );
-        ClientBuilder::new()
+        Ok(ClientBuilder::new()
             .default_headers(headers)
             .build()
-            .context("Could not create client")
+            .context("Could not create client")?)
     }
 
     pub async fn validate_user_token(
         &self,
         token: &str,
-    ) -> Result<ProjectMinimal, anyhow::Error> {
-        #[derive(Debug, serde::Deserialize)]
-        struct ValidateResponseToken {
-            project: ProjectMinimal,
-        }
+    ) -> Result<ValidatedToken, OpenStackError> {
         #[derive(Debug, serde::Deserialize)]
         struct ValidateResponse {
-            token: ValidateResponseToken,
+            token: ValidatedToken,
         }
 
         let client = self.client().await?;
@@ -287,7 +474,8 @@ impl OpenStack {
             return Err(anyhow::anyhow!(
                 "Failed to validate user token, returned code {}",
                 response.status().as_u16()
-            ));
+            )
+            .into());
         }
         let project: ValidateResponse = serde_json::from_str(
             response
@@ -297,12 +485,12 @@ impl OpenStack {
                 .as_str(),
         )
         .context("Could not parse response")?;
-        Ok(project.token.project)
+        Ok(project.token)
     }
 
     pub async fn get_flavors(
         &self,
-    ) -> Result<Vec<FlavorDetailed>, anyhow::Error> {
+    ) -> Result<Vec<FlavorDetailed>, OpenStackError> {
         let client = self.client().await?;
         let url = format!(
             "{}/v2.1/flavors/detail?is_public=False",
@@ -317,7 +505,8 @@ impl OpenStack {
             return Err(anyhow::anyhow!(
                 "Failed to validate user token, returned code {}",
                 response.status().as_u16()
-            ));
+            )
+            .into());
         }
         let flavors: FlavorDetailedList = serde_json::from_str(
             response
@@ -332,7 +521,7 @@ impl OpenStack {
 
     pub async fn get_servers(
         &self,
-    ) -> Result<Vec<ServerDetailed>, anyhow::Error> {
+    ) -> Result<Vec<ServerDetailed>, OpenStackError> {
         let client = self.client().await?;
         let url = format!(
             "{}/v2.1/servers/detail?all_tenants=True",
@@ -347,7 +536,43 @@ impl OpenStack {
             return Err(anyhow::anyhow!(
                 "Failed to validate user token, returned code {}",
                 response.status().as_u16()
-            ));
+            )
+            .into());
+        }
+        let servers: ServerDetailedList = serde_json::from_str(
+            response
+                .text()
+                .await
+                .context("Could not read response text")?
+                .as_str(),
+        )
+        .context("Could not parse response")?;
+        Ok(servers.servers)
+    }
+
+    /// Returns the servers deleted since the given time, which Nova only
+    /// lists when asked for them explicitly.
+    pub async fn get_deleted_servers(
+        &self,
+        changes_since: DateTime<Utc>,
+    ) -> Result<Vec<ServerDetailed>, OpenStackError> {
+        let client = self.client().await?;
+        let url = format!(
+            "{}/v2.1/servers/detail?all_tenants=True&deleted=True&changes-since={}",
+            self.settings.nova_endpoint,
+            changes_since.format("%Y-%m-%dT%H:%M:%SZ"),
+        );
+        let response = client
+            .get(url.as_str())
+            .send()
+            .await
+            .context("Could not retrieve deleted server list")?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Failed to retrieve deleted servers, returned code {}",
+                response.status().as_u16()
+            )
+            .into());
         }
         let servers: ServerDetailedList = serde_json::from_str(
             response
@@ -363,7 +588,7 @@ impl OpenStack {
     pub async fn get_servers_of_project(
         &self,
         project_id: String,
-    ) -> Result<Vec<ServerDetailed>, anyhow::Error> {
+    ) -> Result<Vec<ServerDetailed>, OpenStackError> {
         let client = self.client().await?;
         let url = format!(
             "{}/v2.1/servers/detail?all_tenants=True&tenant_id={}",
@@ -378,7 +603,8 @@ impl OpenStack {
             return Err(anyhow::anyhow!(
                 "Failed to validate user token, returned code {}",
                 response.status().as_u16()
-            ));
+            )
+            .into());
         }
         let servers: ServerDetailedList = serde_json::from_str(
             response
@@ -391,7 +617,100 @@ impl OpenStack {
         Ok(servers.servers)
     }
 
-    pub async fn get_domains(&self) -> Result<Vec<Domain>, anyhow::Error> {
+    pub async fn get_volumes(
+        &self,
+    ) -> Result<Vec<VolumeDetailed>, OpenStackError> {
+        let client = self.client().await?;
+        let url = format!(
+            "{}/{}/volumes/detail?all_tenants=True",
+            self.settings.cinder_endpoint, self.settings.project_id,
+        );
+        let response = client
+            .get(url.as_str())
+            .send()
+            .await
+            .context("Could not retrieve volume list")?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Failed to retrieve volumes, returned code {}",
+                response.status().as_u16()
+            )
+            .into());
+        }
+        let volumes: VolumeDetailedList = serde_json::from_str(
+            response
+                .text()
+                .await
+                .context("Could not read response text")?
+                .as_str(),
+        )
+        .context("Could not parse response")?;
+        Ok(volumes.volumes)
+    }
+
+    pub async fn get_floating_ips(
+        &self,
+    ) -> Result<Vec<FloatingIp>, OpenStackError> {
+        let client = self.client().await?;
+        let url =
+            format!("{}/v2.0/floatingips", self.settings.neutron_endpoint);
+        let response = client
+            .get(url.as_str())
+            .send()
+            .await
+            .context("Could not retrieve floating IP list")?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Failed to retrieve floating IPs, returned code {}",
+                response.status().as_u16()
+            )
+            .into());
+        }
+        let floating_ips: FloatingIpList = serde_json::from_str(
+            response
+                .text()
+                .await
+                .context("Could not read response text")?
+                .as_str(),
+        )
+        .context("Could not parse response")?;
+        Ok(floating_ips.floatingips)
+    }
+
+    /// Returns the external networks, which are the pools floating IPs are
+    /// allocated from.
+    pub async fn get_external_networks(
+        &self,
+    ) -> Result<Vec<Network>, OpenStackError> {
+        let client = self.client().await?;
+        let url = format!(
+            "{}/v2.0/networks?router:external=True",
+            self.settings.neutron_endpoint
+        );
+        let response = client
+            .get(url.as_str())
+            .send()
+            .await
+            .context("Could not retrieve network list")?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Failed to retrieve networks, returned code {}",
+                response.status().as_u16()
+            )
+            .into());
+        }
+        let networks: NetworkList = serde_json::from_str(
+            response
+                .text()
+                .await
+                .context("Could not read response text")?
+                .as_str(),
+        )
+        .context("Could not parse response")?;
+        Ok(networks.networks)
+    }
+
+    pub async fn get_domains(&self) -> Result<Vec<Domain>, OpenStackError> {
         let client = self.client().await?;
         let url = format!("{}/domains", self.settings.keystone_endpoint);
         let response = client
@@ -403,7 +722,8 @@ impl OpenStack {
             return Err(anyhow::anyhow!(
                 "Failed to validate user token, returned code {}",
                 response.status().as_u16()
-            ));
+            )
+            .into());
         }
         let domains: DomainList = serde_json::from_str(
             response
@@ -416,7 +736,7 @@ impl OpenStack {
         Ok(domains.domains)
     }
 
-    pub async fn get_projects(&self) -> Result<Vec<Project>, anyhow::Error> {
+    pub async fn get_projects(&self) -> Result<Vec<Project>, OpenStackError> {
         let client = self.client().await?;
         let url = format!("{}/projects", self.settings.keystone_endpoint);
         let response = client
@@ -428,7 +748,8 @@ impl OpenStack {
             return Err(anyhow::anyhow!(
                 "Failed to validate user token, returned code {}",
                 response.status().as_u16()
-            ));
+            )
+            .into());
         }
         let projects: ProjectList = serde_json::from_str(
             response
@@ -442,10 +763,32 @@ impl OpenStack {
     }
 }
 
+async fn issue_token_with_backoff(
+    settings: &OpenStackSettings,
+) -> Result<Token, anyhow::Error> {
+    let mut backoff = TOKEN_RENEWAL_INITIAL_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match issue_token(settings).await {
+            Ok(token) => return Ok(token),
+            Err(error) if attempt < TOKEN_RENEWAL_ATTEMPTS => {
+                tracing::warn!(
+                    "Failed to issue OpenStack token in attempt {attempt}: \
+                    {error}"
+                );
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                attempt += 1;
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
 #[tracing::instrument(name = "Issue an OpenStack token", skip(settings))]
-pub async fn issue_token(
+async fn issue_token(
     settings: &OpenStackSettings,
-) -> Result<String, anyhow::Error> {
+) -> Result<Token, anyhow::Error> {
     let mut headers = HeaderMap::new();
     headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
     let client = ClientBuilder::new()
@@ -504,5 +847,59 @@ pub async fn issue_token(
     }
     .trim()
     .to_string();
-    Ok(token)
+    let renewed_at = Utc::now();
+    let expires_at = response
+        .json::<serde_json::Value>()
+        .await
+        .ok()
+        .and_then(|body| {
+            body["token"]["expires_at"]
+                .as_str()
+                .and_then(|expires_at| expires_at.parse().ok())
+        })
+        .unwrap_or(renewed_at + TOKEN_DEFAULT_LIFETIME);
+    Ok(Token {
+        token,
+        expires_at,
+        renewed_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_is_renewed_before_it_expires() {
+        let now = Utc::now();
+        let token = Token {
+            token: "token".to_string(),
+            expires_at: now + TOKEN_RENEWAL_MARGIN / 2,
+            renewed_at: now,
+        };
+        assert!(token.needs_renewal());
+        assert!(!token.is_expired());
+
+        let token = Token {
+            expires_at: now + TOKEN_RENEWAL_MARGIN * 2,
+            ..token
+        };
+        assert!(!token.needs_renewal());
+    }
+
+    #[test]
+    fn nova_timestamps_with_and_without_timezone_are_parsed() {
+        let expected = DateTime::parse_from_rfc3339("2025-03-04T05:06:07Z")
+            .unwrap()
+            .to_utc();
+        assert_eq!(
+            parse_nova_datetime("2025-03-04T05:06:07Z").unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_nova_datetime("2025-03-04T05:06:07.000000").unwrap(),
+            expected
+        );
+        assert!(parse_nova_datetime("yesterday").is_err());
+    }
 }
@@ -0,0 +1,51 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{
+    accounting::{BillingPolicy, BillingPolicyCreateData},
+    user::User,
+};
+use sqlx::MySqlPool;
+
+use crate::{
+    authorization::require_admin_user,
+    database::accounting::billing_policy::{
+        NewBillingPolicy, insert_billing_policy_into_db,
+    },
+    error::{NormalApiError, OptionApiError},
+};
+
+#[tracing::instrument(name = "billing_policy_create")]
+pub async fn billing_policy_create(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    data: Json<BillingPolicyCreateData>,
+) -> Result<HttpResponse, OptionApiError> {
+    require_admin_user(&user)?;
+    let new_billing_policy: NewBillingPolicy = data
+        .clone()
+        .try_into()
+        .map_err(NormalApiError::ValidationError)?;
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let id =
+        insert_billing_policy_into_db(&mut transaction, &new_billing_policy)
+            .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    let billing_policy_created = BillingPolicy {
+        id: id as u32,
+        status: new_billing_policy.status,
+        multiplier: new_billing_policy.multiplier,
+        start_time: new_billing_policy.start_time.fixed_offset(),
+    };
+    Ok(HttpResponse::Created()
+        .content_type("application/json")
+        .json(billing_policy_created))
+}
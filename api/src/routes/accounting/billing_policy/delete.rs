@@ -0,0 +1,63 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Path, ReqData},
+};
+use anyhow::Context;
+use avina_wire::user::User;
+use sqlx::{Executor, MySql, MySqlPool, Transaction};
+
+use super::BillingPolicyIdParam;
+use crate::{
+    authorization::require_admin_user,
+    error::{MinimalApiError, NormalApiError},
+};
+
+#[tracing::instrument(name = "billing_policy_delete")]
+pub async fn billing_policy_delete(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    params: Path<BillingPolicyIdParam>,
+) -> Result<HttpResponse, NormalApiError> {
+    require_admin_user(&user)?;
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    delete_billing_policy_from_db(
+        &mut transaction,
+        params.billing_policy_id as u64,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[tracing::instrument(
+    name = "delete_billing_policy_from_db",
+    skip(transaction)
+)]
+async fn delete_billing_policy_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    billing_policy_id: u64,
+) -> Result<(), MinimalApiError> {
+    let query = sqlx::query!(
+        r#"
+        DELETE IGNORE FROM accounting_billingpolicy
+        WHERE id = ?
+        "#,
+        billing_policy_id
+    );
+    let result = transaction
+        .execute(query)
+        .await
+        .context("Failed to execute delete query")?;
+    if result.rows_affected() == 0 {
+        return Err(MinimalApiError::ValidationError(
+            "Failed to delete billing policy.".to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, ReqData},
+};
+use anyhow::Context;
+use avina_wire::user::User;
+use sqlx::MySqlPool;
+
+use crate::{
+    database::accounting::billing_policy::select_all_billing_policies_from_db,
+    error::NormalApiError,
+};
+
+#[tracing::instrument(name = "billing_policy_list")]
+pub async fn billing_policy_list(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+) -> Result<HttpResponse, NormalApiError> {
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let billing_policies =
+        select_all_billing_policies_from_db(&mut transaction).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(billing_policies))
+}
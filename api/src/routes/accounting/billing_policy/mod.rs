@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+
+use actix_web::{
+    Scope,
+    web::{delete, get, patch, post, scope},
+};
+use avina_wire::accounting::BillingPolicy;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::{MySql, Transaction};
+
+use crate::{
+    database::accounting::billing_policy::select_all_billing_policies_from_db,
+    error::UnexpectedOnlyError,
+};
+
+mod create;
+use create::billing_policy_create;
+mod list;
+use list::billing_policy_list;
+mod get;
+use get::billing_policy_get;
+mod modify;
+use modify::billing_policy_modify;
+mod delete;
+use delete::billing_policy_delete;
+
+pub fn billing_policies_scope() -> Scope {
+    scope("/billingpolicies")
+        .route("/", post().to(billing_policy_create))
+        .route("", get().to(billing_policy_list))
+        .route("/{billing_policy_id}", get().to(billing_policy_get))
+        .route("/{billing_policy_id}/", patch().to(billing_policy_modify))
+        .route("/{billing_policy_id}/", delete().to(billing_policy_delete))
+}
+
+#[derive(Deserialize, Debug)]
+struct BillingPolicyIdParam {
+    #[allow(unused)]
+    billing_policy_id: u32,
+}
+
+/// Multipliers of each server status, ordered by the time from which they are
+/// in effect. Statuses without a policy in effect are not billed.
+#[derive(Default)]
+pub struct BillingPolicies(HashMap<String, Vec<(DateTime<Utc>, f64)>>);
+
+impl BillingPolicies {
+    pub async fn load(
+        transaction: &mut Transaction<'_, MySql>,
+    ) -> Result<Self, UnexpectedOnlyError> {
+        Ok(select_all_billing_policies_from_db(transaction)
+            .await?
+            .into())
+    }
+
+    /// Returns the multiplier of the status in effect at the given time.
+    pub fn multiplier(&self, status: &str, at: DateTime<Utc>) -> f64 {
+        self.0
+            .get(status)
+            .and_then(|policies| {
+                policies
+                    .iter()
+                    .take_while(|(start_time, _)| *start_time <= at)
+                    .last()
+            })
+            .map_or(0., |(_, multiplier)| *multiplier)
+    }
+
+    /// Returns whether any policy comes into effect after begin and before
+    /// end.
+    pub fn change_within(
+        &self,
+        begin: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> bool {
+        self.0
+            .values()
+            .flatten()
+            .any(|(start_time, _)| begin < *start_time && *start_time < end)
+    }
+
+    /// Returns the seconds between begin and end spent in the status, each
+    /// weighted by the multiplier in effect at that time.
+    pub fn billed_seconds(
+        &self,
+        status: &str,
+        begin: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> f64 {
+        self.billed_seconds_since(status, begin, begin, end)
+    }
+
+    /// Like billed_seconds, but counts the whole seconds of each policy from
+    /// the given time or the start of the policy, whichever is later. This
+    /// way, the seconds of adjacent parts of a state, e.g. the days of the
+    /// daily usage aggregates, add up to those of the whole state.
+    pub fn billed_seconds_since(
+        &self,
+        status: &str,
+        since: DateTime<Utc>,
+        begin: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> f64 {
+        let Some(policies) = self.0.get(status) else {
+            return 0.;
+        };
+        let ends = policies
+            .iter()
+            .skip(1)
+            .map(|(start_time, _)| Some(*start_time))
+            .chain([None]);
+        policies
+            .iter()
+            .zip(ends)
+            .map(|((start_time, multiplier), policy_end)| {
+                let covered_begin = begin.max(*start_time);
+                let covered_end = policy_end.map_or(end, |e| end.min(e));
+                if covered_end <= covered_begin {
+                    return 0.;
+                }
+                whole_seconds_since(
+                    since.max(*start_time),
+                    covered_begin,
+                    covered_end,
+                ) as f64
+                    * multiplier
+            })
+            .sum()
+    }
+}
+
+/// Returns the whole seconds between begin and end, counted from the given
+/// time. The fraction of a second left over at the begin is carried over
+/// from the previous part instead of being dropped, so that the parts add up
+/// to the whole seconds between since and the end of the last part.
+pub fn whole_seconds_since(
+    since: DateTime<Utc>,
+    begin: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> i64 {
+    if end <= begin {
+        return 0;
+    }
+    (end - since).num_seconds() - (begin - since).num_seconds()
+}
+
+impl From<Vec<BillingPolicy>> for BillingPolicies {
+    fn from(policies: Vec<BillingPolicy>) -> Self {
+        let mut map: HashMap<String, Vec<(DateTime<Utc>, f64)>> =
+            HashMap::new();
+        for policy in policies {
+            map.entry(policy.status)
+                .or_default()
+                .push((policy.start_time.to_utc(), policy.multiplier));
+        }
+        for policies in map.values_mut() {
+            policies.sort_by_key(|(start_time, _)| *start_time);
+        }
+        Self(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Days, TimeDelta, TimeZone};
+
+    use super::*;
+    use crate::utils::start_of_the_day;
+
+    fn policy(status: &str, multiplier: f64, day: u32) -> BillingPolicy {
+        BillingPolicy {
+            id: 0,
+            status: status.to_string(),
+            multiplier,
+            start_time: Utc
+                .with_ymd_and_hms(2025, 1, day, 0, 0, 0)
+                .unwrap()
+                .fixed_offset(),
+        }
+    }
+
+    #[test]
+    fn billed_seconds_are_weighted_by_the_policy_in_effect() {
+        let policies = BillingPolicies::from(vec![
+            policy("SHUTOFF", 0.25, 3),
+            policy("SHUTOFF", 1., 1),
+            policy("ACTIVE", 1., 1),
+        ]);
+        let day = |day| Utc.with_ymd_and_hms(2025, 1, day, 0, 0, 0).unwrap();
+        let seconds_per_day = (24 * 60 * 60) as f64;
+        assert_eq!(
+            policies.billed_seconds("SHUTOFF", day(2), day(5)),
+            seconds_per_day * 1.5
+        );
+        assert_eq!(
+            policies.billed_seconds("ACTIVE", day(2), day(5)),
+            seconds_per_day * 3.
+        );
+        assert_eq!(policies.billed_seconds("SHELVED", day(2), day(5)), 0.);
+        assert_eq!(policies.multiplier("SHUTOFF", day(4)), 0.25);
+        assert_eq!(policies.multiplier("SHUTOFF", day(2)), 1.);
+        assert!(policies.change_within(day(2), day(4)));
+        assert!(!policies.change_within(day(3), day(4)));
+    }
+
+    #[test]
+    fn seconds_of_days_add_up_to_those_of_the_state() {
+        let policies = BillingPolicies::from(vec![policy("ACTIVE", 1., 1)]);
+        let begin = Utc.with_ymd_and_hms(2025, 1, 1, 23, 0, 0).unwrap()
+            + TimeDelta::milliseconds(700);
+        let end = Utc.with_ymd_and_hms(2025, 1, 3, 1, 0, 0).unwrap()
+            + TimeDelta::milliseconds(600);
+        let days = [
+            begin,
+            start_of_the_day(begin.date_naive() + Days::new(1)),
+            start_of_the_day(begin.date_naive() + Days::new(2)),
+            end,
+        ];
+        let seconds = days
+            .windows(2)
+            .map(|day| {
+                policies.billed_seconds_since("ACTIVE", begin, day[0], day[1])
+            })
+            .sum::<f64>();
+        assert_eq!(seconds, (end - begin).num_seconds() as f64);
+        assert_eq!(
+            policies.billed_seconds("ACTIVE", begin, end),
+            (end - begin).num_seconds() as f64
+        );
+        assert_eq!(whole_seconds_since(begin, end, begin), 0);
+    }
+}
@@ -0,0 +1,97 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, Path, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{
+    accounting::{BillingPolicy, BillingPolicyModifyData},
+    user::User,
+};
+use sqlx::{Executor, MySql, MySqlPool, Transaction};
+
+use super::BillingPolicyIdParam;
+use crate::{
+    authorization::require_admin_user,
+    database::accounting::billing_policy::select_billing_policy_from_db,
+    error::{NotFoundOrUnexpectedApiError, OptionApiError},
+};
+
+#[tracing::instrument(name = "billing_policy_modify")]
+pub async fn billing_policy_modify(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    data: Json<BillingPolicyModifyData>,
+    params: Path<BillingPolicyIdParam>,
+) -> Result<HttpResponse, OptionApiError> {
+    require_admin_user(&user)?;
+    if data.id != params.billing_policy_id {
+        return Err(OptionApiError::ValidationError(
+            "ID in URL does not match ID in body".to_string(),
+        ));
+    }
+    if data
+        .multiplier
+        .is_some_and(|multiplier| !multiplier.is_finite() || multiplier < 0.)
+    {
+        return Err(OptionApiError::ValidationError(
+            "Multiplier of billing policy must not be negative".to_string(),
+        ));
+    }
+    if data.status.as_ref().is_some_and(|status| status.is_empty()) {
+        return Err(OptionApiError::ValidationError(
+            "Status of billing policy must not be empty".to_string(),
+        ));
+    }
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let billing_policy =
+        update_billing_policy_in_db(&mut transaction, &data).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(billing_policy))
+}
+
+#[tracing::instrument(
+    name = "update_billing_policy_in_db",
+    skip(data, transaction)
+)]
+pub async fn update_billing_policy_in_db(
+    transaction: &mut Transaction<'_, MySql>,
+    data: &BillingPolicyModifyData,
+) -> Result<BillingPolicy, NotFoundOrUnexpectedApiError> {
+    let row =
+        select_billing_policy_from_db(transaction, data.id as u64).await?;
+    let status = data
+        .status
+        .as_ref()
+        .map_or(row.status, |status| status.to_uppercase());
+    let multiplier = data.multiplier.unwrap_or(row.multiplier);
+    let start_time = data.start_time.unwrap_or(row.start_time);
+    let query = sqlx::query!(
+        r#"
+        UPDATE accounting_billingpolicy
+        SET status = ?, multiplier = ?, start_time = ?
+        WHERE id = ?
+        "#,
+        status,
+        multiplier,
+        start_time.to_utc(),
+        data.id,
+    );
+    transaction
+        .execute(query)
+        .await
+        .context("Failed to execute update query")?;
+    Ok(BillingPolicy {
+        id: data.id,
+        status,
+        multiplier,
+        start_time,
+    })
+}
@@ -0,0 +1,139 @@
+use std::collections::{HashMap, HashSet};
+
+use actix_web::{
+    HttpResponse,
+    web::{Data, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{accounting::DailyUsageBackfill, user::User};
+use chrono::{DateTime, Days, Utc};
+use sqlx::{MySql, MySqlPool, Transaction};
+
+use crate::{
+    authorization::require_admin_user,
+    database::accounting::{
+        daily_usage::{
+            NewDailyUsage, insert_aggregated_day_into_db,
+            insert_daily_usage_into_db, select_aggregated_days_from_db,
+        },
+        server_state::{
+            select_first_server_state_begin_from_db,
+            select_server_states_by_begin_and_end_from_db,
+        },
+    },
+    error::{MinimalApiError, NormalApiError},
+    routes::accounting::billing_policy::whole_seconds_since,
+    utils::start_of_the_day,
+};
+
+#[tracing::instrument(name = "daily_usage_backfill")]
+pub async fn daily_usage_backfill(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+) -> Result<HttpResponse, NormalApiError> {
+    require_admin_user(&user)?;
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let backfill = aggregate_daily_usages(&mut transaction, Utc::now()).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(backfill))
+}
+
+/// Aggregates the seconds each server spent in each status on all closed
+/// days before the given time, which are not aggregated yet. The seconds are
+/// counted from the begin of each state, so that the days of a state add up
+/// to its whole seconds.
+#[tracing::instrument(name = "aggregate_daily_usages", skip(transaction))]
+pub async fn aggregate_daily_usages(
+    transaction: &mut Transaction<'_, MySql>,
+    until: DateTime<Utc>,
+) -> Result<DailyUsageBackfill, MinimalApiError> {
+    let mut backfill = DailyUsageBackfill {
+        aggregated_day_count: 0,
+        daily_usage_count: 0,
+    };
+    let Some(first_begin) =
+        select_first_server_state_begin_from_db(transaction).await?
+    else {
+        return Ok(backfill);
+    };
+    let (first, last) = (first_begin.date_naive(), until.date_naive());
+    if first >= last {
+        return Ok(backfill);
+    }
+    let aggregated_days =
+        select_aggregated_days_from_db(transaction, first, last)
+            .await?
+            .into_iter()
+            .collect::<HashSet<_>>();
+    let days = first
+        .iter_days()
+        .take_while(|day| *day < last)
+        .filter(|day| !aggregated_days.contains(day))
+        .collect::<Vec<_>>();
+    let Some(first_day) = days.first() else {
+        return Ok(backfill);
+    };
+    let days = days.iter().cloned().collect::<HashSet<_>>();
+
+    let (begin, end) = (start_of_the_day(*first_day), start_of_the_day(last));
+    let states =
+        select_server_states_by_begin_and_end_from_db(transaction, begin, end)
+            .await?;
+    let mut usages = HashMap::new();
+    for state in states {
+        let state_begin = state.begin.to_utc().max(begin);
+        let state_end = state.end.map_or(end, |e| e.to_utc().min(end));
+        let mut day = state_begin.date_naive();
+        // NOTE: the state belongs to the same days as when it is looked up
+        // for each of them, even when it lasts no time at all.
+        while start_of_the_day(day) < state_end {
+            let next_day = day + Days::new(1);
+            if days.contains(&day) {
+                *usages
+                    .entry((
+                        day,
+                        state.instance_id,
+                        state.flavor,
+                        state.user,
+                        state.status.clone(),
+                    ))
+                    .or_default() += whole_seconds_since(
+                    state.begin.to_utc(),
+                    state_begin.max(start_of_the_day(day)),
+                    state_end.min(start_of_the_day(next_day)),
+                );
+            }
+            day = next_day;
+        }
+    }
+
+    for ((day, instance_id, flavor, user, status), seconds) in usages {
+        insert_daily_usage_into_db(
+            transaction,
+            &NewDailyUsage {
+                day,
+                instance_id,
+                flavor,
+                user,
+                status,
+                seconds,
+            },
+        )
+        .await?;
+        backfill.daily_usage_count += 1;
+    }
+    let aggregated_at = Utc::now();
+    for day in days {
+        insert_aggregated_day_into_db(transaction, day, aggregated_at).await?;
+        backfill.aggregated_day_count += 1;
+    }
+    Ok(backfill)
+}
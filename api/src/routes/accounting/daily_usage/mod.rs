@@ -0,0 +1,130 @@
+use std::collections::HashSet;
+
+use actix_web::{
+    Scope,
+    web::{post, scope},
+};
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use sqlx::{MySql, Transaction};
+
+use crate::{
+    database::accounting::daily_usage::select_aggregated_days_from_db,
+    error::UnexpectedOnlyError,
+    routes::accounting::{
+        billing_policy::BillingPolicies, server_consumption::series::Period,
+    },
+    utils::start_of_the_day,
+};
+
+pub(crate) mod backfill;
+use backfill::daily_usage_backfill;
+
+pub fn daily_usage_scope() -> Scope {
+    scope("/dailyusage").route("/backfill/", post().to(daily_usage_backfill))
+}
+
+/// A period split into the whole days within it, whose usage is read from
+/// the daily aggregates, and the remaining parts, whose usage has to be
+/// calculated from the server states.
+pub struct AggregatedPeriod {
+    /// First day and the day after the last one to read the aggregates for.
+    pub first: NaiveDate,
+    pub last: NaiveDate,
+    days: HashSet<NaiveDate>,
+    /// Parts of the period not covered by the aggregated days.
+    pub remaining: Vec<Period>,
+}
+
+impl AggregatedPeriod {
+    /// Only days without a change of the billing policies are taken from
+    /// the aggregates, since their seconds are weighted for the whole day.
+    pub async fn new(
+        transaction: &mut Transaction<'_, MySql>,
+        begin: DateTime<Utc>,
+        end: DateTime<Utc>,
+        policies: &BillingPolicies,
+    ) -> Result<Self, UnexpectedOnlyError> {
+        let first = if start_of_the_day(begin.date_naive()) == begin {
+            begin.date_naive()
+        } else {
+            begin.date_naive() + Days::new(1)
+        };
+        let last = end.date_naive();
+        let days = if first < last {
+            select_aggregated_days_from_db(transaction, first, last)
+                .await?
+                .into_iter()
+                .filter(|day| {
+                    !policies.change_within(
+                        start_of_the_day(*day),
+                        start_of_the_day(*day + Days::new(1)),
+                    )
+                })
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+        Ok(Self {
+            first,
+            last,
+            remaining: remaining_periods(begin, end, &days),
+            days: days.into_iter().collect(),
+        })
+    }
+
+    pub fn contains(&self, day: NaiveDate) -> bool {
+        self.days.contains(&day)
+    }
+}
+
+/// Returns the parts of the period outside of the given ascending days.
+fn remaining_periods(
+    begin: DateTime<Utc>,
+    end: DateTime<Utc>,
+    days: &[NaiveDate],
+) -> Vec<Period> {
+    let mut periods = Vec::new();
+    let mut period_begin = begin;
+    for day in days {
+        let day_begin = start_of_the_day(*day);
+        if period_begin < day_begin {
+            periods.push((period_begin, day_begin));
+        }
+        period_begin = start_of_the_day(*day + Days::new(1));
+    }
+    if period_begin < end {
+        periods.push((period_begin, end));
+    }
+    periods
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn remaining_periods_leave_out_aggregated_days() {
+        let begin = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 1, 6, 6, 0, 0).unwrap();
+        let day = |day| NaiveDate::from_ymd_opt(2025, 1, day).unwrap();
+        assert_eq!(
+            remaining_periods(begin, end, &[day(2), day(4)]),
+            vec![
+                (begin, start_of_the_day(day(2))),
+                (start_of_the_day(day(3)), start_of_the_day(day(4))),
+                (start_of_the_day(day(5)), end),
+            ]
+        );
+        assert_eq!(
+            remaining_periods(
+                start_of_the_day(day(2)),
+                start_of_the_day(day(3)),
+                &[day(2)]
+            ),
+            vec![]
+        );
+        assert_eq!(remaining_periods(begin, end, &[]), vec![(begin, end)]);
+    }
+}
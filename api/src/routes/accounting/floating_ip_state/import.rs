@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+
+use actix_web::{
+    HttpResponse,
+    web::{Data, Query, ReqData},
+};
+use anyhow::{Context, anyhow};
+use avina_wire::{
+    accounting::{
+        FloatingIpState, FloatingIpStateImport, FloatingIpStateImportParams,
+    },
+    user::User,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{MySql, MySqlPool, Transaction};
+
+use crate::{
+    authorization::require_admin_user,
+    database::accounting::floating_ip_state::{
+        NewFloatingIpState, end_floating_ip_state_in_db,
+        insert_floating_ip_state_into_db,
+        select_unfinished_floating_ip_states_from_db,
+    },
+    error::OptionApiError,
+    openstack::{FloatingIp, OpenStack},
+    routes::accounting::server_state::import::{
+        select_maybe_user_id_by_openstack_id_from_db, union_hash_zip,
+    },
+};
+
+#[tracing::instrument(name = "floating_ip_state_import", skip(openstack))]
+pub async fn floating_ip_state_import(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    openstack: Data<OpenStack>,
+    params: Query<FloatingIpStateImportParams>,
+) -> Result<HttpResponse, OptionApiError> {
+    require_admin_user(&user)?;
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let import =
+        import_floating_ip_states(&mut transaction, &openstack).await?;
+    if params.dry_run.unwrap_or(false) {
+        transaction
+            .rollback()
+            .await
+            .context("Failed to roll back transaction")?;
+    } else {
+        transaction
+            .commit()
+            .await
+            .context("Failed to commit transaction")?;
+    }
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(import))
+}
+
+#[tracing::instrument(name = "import_floating_ip_states", skip(openstack))]
+pub async fn import_floating_ip_states(
+    transaction: &mut Transaction<'_, MySql>,
+    openstack: &OpenStack,
+) -> Result<FloatingIpStateImport, OptionApiError> {
+    // NOTE: the pools are named after the external networks, falling back to
+    // the network ID for networks that are not listed.
+    let pools = openstack
+        .get_external_networks()
+        .await?
+        .into_iter()
+        .map(|n| (n.id, n.name))
+        .collect::<HashMap<_, _>>();
+    let floating_ips = openstack
+        .get_floating_ips()
+        .await?
+        .into_iter()
+        .map(|f| (f.id, f))
+        .collect::<HashMap<_, _>>();
+    let states = select_unfinished_floating_ip_states_from_db(transaction)
+        .await?
+        .into_iter()
+        .map(|s| (s.floating_ip_id, s))
+        .collect::<HashMap<_, _>>();
+    let now = Utc::now();
+
+    let mut import = FloatingIpStateImport {
+        new_state_count: 0,
+        end_state_count: 0,
+        skipped_floating_ip_count: 0,
+    };
+
+    for floating_ip_and_state in union_hash_zip(floating_ips, states).values() {
+        match floating_ip_and_state {
+            (Some(floating_ip), Some(state)) => {
+                let user_id = select_maybe_user_id_by_openstack_id_from_db(
+                    transaction,
+                    floating_ip.project_id.clone(),
+                )
+                .await?;
+                let pool = pool_name(&pools, floating_ip);
+                if !floating_ip_changed(floating_ip, &pool, state, user_id) {
+                    continue;
+                }
+                // NOTE: Neutron does not tell when exactly the change
+                // happened, but it was the latest update of the floating IP.
+                let changed_at = floating_ip
+                    .updated_at
+                    .unwrap_or(now)
+                    .min(now)
+                    .max(state.begin.to_utc());
+                end_floating_ip_state_in_db(
+                    transaction,
+                    state.id as u64,
+                    changed_at,
+                )
+                .await?;
+                import.end_state_count += 1;
+                create_floating_ip_state(
+                    transaction,
+                    floating_ip,
+                    pool,
+                    user_id,
+                    changed_at,
+                    &mut import,
+                )
+                .await?;
+            }
+            (Some(floating_ip), None) => {
+                let user_id = select_maybe_user_id_by_openstack_id_from_db(
+                    transaction,
+                    floating_ip.project_id.clone(),
+                )
+                .await?;
+                create_floating_ip_state(
+                    transaction,
+                    floating_ip,
+                    pool_name(&pools, floating_ip),
+                    user_id,
+                    floating_ip.created_at.min(now),
+                    &mut import,
+                )
+                .await?;
+            }
+            (None, Some(state)) => {
+                // NOTE: Neutron does not list released floating IPs, so they
+                // are accounted until they vanished.
+                end_floating_ip_state_in_db(transaction, state.id as u64, now)
+                    .await?;
+                import.end_state_count += 1;
+            }
+            (None, None) => {
+                return Err(anyhow!(
+                    "Floating IP state hash map contains invalid none-none pair."
+                )
+                .into());
+            }
+        }
+    }
+
+    Ok(import)
+}
+
+fn pool_name(
+    pools: &HashMap<String, String>,
+    floating_ip: &FloatingIp,
+) -> String {
+    pools
+        .get(&floating_ip.floating_network_id)
+        .cloned()
+        .unwrap_or(floating_ip.floating_network_id.clone())
+}
+
+/// Returns whether the allocation of the floating IP differs from its
+/// current state.
+fn floating_ip_changed(
+    floating_ip: &FloatingIp,
+    pool: &str,
+    state: &FloatingIpState,
+    user_id: Option<u64>,
+) -> bool {
+    floating_ip.floating_ip_address != state.floating_ip_address
+        || pool != state.pool
+        || user_id != Some(state.user as u64)
+}
+
+async fn create_floating_ip_state(
+    transaction: &mut Transaction<'_, MySql>,
+    floating_ip: &FloatingIp,
+    pool: String,
+    user_id: Option<u64>,
+    begin: DateTime<Utc>,
+    import: &mut FloatingIpStateImport,
+) -> Result<(), OptionApiError> {
+    let Some(user_id) = user_id else {
+        tracing::warn!(
+            "Unknown user {} of floating IP {}, skipping floating IP state creation.",
+            floating_ip.project_id,
+            floating_ip.id
+        );
+        import.skipped_floating_ip_count += 1;
+        return Ok(());
+    };
+    let floating_ip_state = NewFloatingIpState {
+        begin,
+        end: None,
+        floating_ip_id: floating_ip.id,
+        floating_ip_address: floating_ip.floating_ip_address.clone(),
+        pool,
+        user: user_id as u32,
+    };
+    let _ = insert_floating_ip_state_into_db(transaction, &floating_ip_state)
+        .await?;
+    import.new_state_count += 1;
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Query, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{accounting::FloatingIpStateListParams, user::User};
+use sqlx::MySqlPool;
+
+use crate::{
+    authorization::{
+        require_admin_user, require_master_user_or_return_not_found,
+        require_user_or_project_master_or_not_found,
+    },
+    database::{
+        accounting::floating_ip_state::{
+            select_all_floating_ip_states_from_db,
+            select_floating_ip_states_by_floating_ip_from_db,
+            select_floating_ip_states_by_project_from_db,
+            select_floating_ip_states_by_user_from_db,
+        },
+        user::user::select_user_from_db,
+    },
+    error::OptionApiError,
+};
+
+#[tracing::instrument(name = "floating_ip_state_list")]
+pub async fn floating_ip_state_list(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    params: Query<FloatingIpStateListParams>,
+) -> Result<HttpResponse, OptionApiError> {
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let mut floating_ip_states = if params.all.unwrap_or(false) {
+        require_admin_user(&user)?;
+        select_all_floating_ip_states_from_db(&mut transaction).await?
+    } else if let Some(project_id) = params.project {
+        require_master_user_or_return_not_found(&user, project_id)?;
+        select_floating_ip_states_by_project_from_db(
+            &mut transaction,
+            project_id as u64,
+        )
+        .await?
+    } else if let Some(user_id) = params.user {
+        let user1 = select_user_from_db(&mut transaction, user_id as u64)
+            .await
+            .context("Failed to select user")?;
+        require_user_or_project_master_or_not_found(
+            &user,
+            user1.id,
+            user1.project,
+        )?;
+        select_floating_ip_states_by_user_from_db(
+            &mut transaction,
+            user1.id as u64,
+        )
+        .await?
+    } else if let Some(floating_ip_id) = params.floating_ip {
+        let floating_ip_states =
+            select_floating_ip_states_by_floating_ip_from_db(
+                &mut transaction,
+                floating_ip_id,
+            )
+            .await?;
+        let Some(floating_ip_state) = floating_ip_states.first() else {
+            return Err(OptionApiError::NotFoundError);
+        };
+        let floating_ip_state_user = select_user_from_db(
+            &mut transaction,
+            floating_ip_state.user as u64,
+        )
+        .await?;
+        require_user_or_project_master_or_not_found(
+            &user,
+            floating_ip_state_user.id,
+            floating_ip_state_user.project,
+        )?;
+        floating_ip_states
+    } else {
+        select_floating_ip_states_by_user_from_db(
+            &mut transaction,
+            user.id as u64,
+        )
+        .await?
+    };
+    if let Some(floating_ip_id) = params.floating_ip {
+        floating_ip_states.retain(|s| s.floating_ip_id == floating_ip_id);
+    }
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(floating_ip_states))
+}
@@ -0,0 +1,25 @@
+use actix_web::{
+    Scope,
+    web::{get, scope},
+};
+use serde::Deserialize;
+
+mod list;
+use list::floating_ip_state_list;
+mod get;
+use get::floating_ip_state_get;
+pub(crate) mod import;
+use import::floating_ip_state_import;
+
+pub fn floating_ip_states_scope() -> Scope {
+    scope("/floatingipstates")
+        .route("", get().to(floating_ip_state_list))
+        .route("/{floating_ip_state_id}", get().to(floating_ip_state_get))
+        .route("/import/", get().to(floating_ip_state_import))
+}
+
+#[derive(Deserialize, Debug)]
+struct FloatingIpStateIdParam {
+    #[allow(unused)]
+    floating_ip_state_id: u32,
+}
@@ -1,15 +1,36 @@
 use actix_web::{Scope, web::scope};
 
-mod server_state;
+pub(crate) mod billing_policy;
+use billing_policy::billing_policies_scope;
+pub(crate) mod daily_usage;
+use daily_usage::daily_usage_scope;
+pub(crate) mod floating_ip_state;
+use floating_ip_state::floating_ip_states_scope;
+pub(crate) mod server_state;
 use server_state::server_states_scope;
-mod server_consumption;
+pub(crate) mod server_consumption;
 use server_consumption::server_consumption_scope;
 pub(crate) mod server_cost;
 use server_cost::server_cost_scope;
+mod server;
+use server::servers_scope;
+mod unmatched_server;
+use unmatched_server::unmatched_servers_scope;
+pub(crate) mod volume_cost;
+use volume_cost::volume_cost_scope;
+pub(crate) mod volume_state;
+use volume_state::volume_states_scope;
 
 pub fn accounting_scope() -> Scope {
     scope("/accounting")
+        .service(billing_policies_scope())
+        .service(daily_usage_scope())
+        .service(floating_ip_states_scope())
         .service(server_states_scope())
         .service(server_consumption_scope())
         .service(server_cost_scope())
+        .service(servers_scope())
+        .service(unmatched_servers_scope())
+        .service(volume_cost_scope())
+        .service(volume_states_scope())
 }
@@ -0,0 +1,18 @@
+use actix_web::{
+    Scope,
+    web::{get, scope},
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+mod timeline;
+use timeline::server_timeline;
+
+pub fn servers_scope() -> Scope {
+    scope("/servers").route("/{server_id}/timeline/", get().to(server_timeline))
+}
+
+#[derive(Deserialize, Debug)]
+struct ServerIdParam {
+    server_id: Uuid,
+}
@@ -0,0 +1,159 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Path, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{
+    accounting::{ServerState, ServerTimelineSegment},
+    user::{Project, User},
+};
+use chrono::{DateTime, Utc};
+use sqlx::MySqlPool;
+
+use super::ServerIdParam;
+use crate::{
+    authorization::require_user_or_project_master_or_not_found,
+    database::{
+        accounting::server_state::{
+            select_maybe_project_by_server_from_db,
+            select_server_states_by_server_from_db,
+        },
+        user::user::select_user_from_db,
+    },
+    error::OptionApiError,
+    routes::{
+        accounting::{
+            billing_policy::BillingPolicies,
+            server_consumption::get::select_user_id_by_server,
+            server_cost::get::{
+                PricePeriods, calculate_flavor_consumption_cost,
+                get_flavor_price_periods,
+            },
+        },
+        user::project_class_change::UserClassHistory,
+    },
+};
+
+/// Returns the cost of the state until its end or now, with the seconds in
+/// each price period being weighted by the billing policy of its status.
+fn calculate_server_state_cost(
+    state: &ServerState,
+    project: &Project,
+    price_periods: &PricePeriods,
+    policies: &BillingPolicies,
+    user_classes: &UserClassHistory,
+    now: DateTime<Utc>,
+) -> f64 {
+    let begin = state.begin.to_utc();
+    let end = state.end.map_or(now, |end| end.to_utc());
+    let mut end_times =
+        price_periods.keys().skip(1).cloned().collect::<Vec<_>>();
+    end_times.push(now);
+
+    let mut cost = 0.;
+    for ((start_time, prices), end_time) in price_periods.iter().zip(end_times)
+    {
+        let covered_begin = begin.max(*start_time);
+        let covered_end = end.min(end_time);
+        if covered_end <= covered_begin {
+            continue;
+        }
+        cost += calculate_flavor_consumption_cost(
+            policies.billed_seconds_since(
+                &state.status,
+                begin,
+                covered_begin,
+                covered_end,
+            ),
+            prices,
+            user_classes.user_class(project, *start_time),
+            &state.flavor_name,
+        );
+    }
+    cost
+}
+
+/// Lists the states of the server in chronological order, each one priced
+/// for the period it lasted.
+#[tracing::instrument(name = "server_timeline")]
+pub async fn server_timeline(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    params: Path<ServerIdParam>,
+) -> Result<HttpResponse, OptionApiError> {
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let server_user_id =
+        select_user_id_by_server(&mut transaction, params.server_id).await?;
+    let server_user =
+        select_user_from_db(&mut transaction, server_user_id as u64).await?;
+    require_user_or_project_master_or_not_found(
+        &user,
+        server_user.id,
+        server_user.project,
+    )?;
+
+    let mut states = select_server_states_by_server_from_db(
+        &mut transaction,
+        params.server_id,
+        false,
+    )
+    .await?;
+    states.sort_by_key(|s| (s.begin, s.id));
+
+    let now = Utc::now();
+    let mut timeline = Vec::new();
+    let (Some(first), Some(project)) = (
+        states.first(),
+        select_maybe_project_by_server_from_db(
+            &mut transaction,
+            params.server_id,
+        )
+        .await?,
+    ) else {
+        return Ok(HttpResponse::Ok()
+            .content_type("application/json")
+            .json(timeline));
+    };
+    // NOTE: everything needed to price the states is loaded once for the
+    // whole lifetime of the server instead of once per state.
+    let price_periods =
+        get_flavor_price_periods(&mut transaction, first.begin.to_utc(), now)
+            .await?;
+    let policies = BillingPolicies::load(&mut transaction).await?;
+    let user_classes = UserClassHistory::load(&mut transaction).await?;
+    for state in states {
+        let begin = state.begin.to_utc();
+        let end = state.end.map_or(now, |end| end.to_utc());
+        let cost = calculate_server_state_cost(
+            &state,
+            &project,
+            &price_periods,
+            &policies,
+            &user_classes,
+            now,
+        );
+        timeline.push(ServerTimelineSegment {
+            state: state.id,
+            begin: state.begin,
+            end: state.end,
+            instance_name: state.instance_name,
+            flavor: state.flavor,
+            flavor_name: state.flavor_name,
+            status: state.status,
+            user: state.user,
+            username: state.username,
+            duration: (end - begin).num_seconds().max(0) as u64,
+            cost,
+        });
+    }
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(timeline))
+}
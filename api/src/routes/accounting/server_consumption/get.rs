@@ -1,5 +1,3 @@
-use std::collections::HashMap;
-
 use actix_web::{
     HttpResponse,
     web::{Data, Query, ReqData},
@@ -24,10 +22,22 @@ use crate::{
         require_user_or_project_master_or_not_found,
     },
     database::{
-        accounting::server_state::{
-            select_ordered_server_states_by_server_begin_and_end_from_db,
-            select_ordered_server_states_by_user_begin_and_end_from_db,
-            select_server_states_by_server_from_db,
+        accounting::{
+            daily_usage::{
+                DailyUsage, select_daily_usages_by_server_from_db,
+                select_daily_usages_by_user_from_db,
+            },
+            server_state::{
+                select_ordered_server_states_by_server_begin_and_end_from_db,
+                select_ordered_server_states_by_user_begin_and_end_from_db,
+                select_server_states_by_server_from_db,
+            },
+            server_state_summary::{
+                ServerStateSummary,
+                select_maybe_server_state_summary_user_from_db,
+                select_server_state_summaries_by_server_from_db,
+                select_server_state_summaries_by_user_from_db,
+            },
         },
         user::{
             project::select_all_projects_from_db,
@@ -35,74 +45,158 @@ use crate::{
         },
     },
     error::{OptionApiError, UnexpectedOnlyError},
+    routes::accounting::{
+        billing_policy::BillingPolicies,
+        daily_usage::AggregatedPeriod,
+        server_consumption::series::{
+            ServerConsumptionSeries, bucket_periods,
+            calculate_server_consumption_series_for_all,
+            calculate_server_consumption_series_for_project,
+            calculate_server_consumption_series_for_server,
+            calculate_server_consumption_series_for_user,
+        },
+    },
+    utils::start_of_the_day,
 };
 
-const CONSUMING_STATES: [&str; 15] = [
-    "ACTIVE",
-    "BUILD",
-    "HARD_REBOOT",
-    "MIGRATING",
-    "PASSWORD",
-    "PAUSED",
-    "REBOOT",
-    "REBUILD",
-    "RESCUE",
-    "RESIZE",
-    "REVERT_RESIZE",
-    "SHUTOFF",
-    "SUSPENDED",
-    "UNKNOWN",
-    "VERIFY_RESIZE",
-];
+/// Adds the consumption archived in the summary, which is prorated when the
+/// given period only covers part of the one of the summary.
+pub(super) fn add_server_state_summary_consumption(
+    consumption: &mut ServerConsumptionServer,
+    summary: &ServerStateSummary,
+    begin: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+) {
+    let entry = consumption.entry(summary.flavor_name.clone()).or_default();
+    let period = (summary.end - summary.begin).num_seconds();
+    let covered = (end.map_or(summary.end, |end| end.min(summary.end))
+        - begin.map_or(summary.begin, |begin| begin.max(summary.begin)))
+    .num_seconds();
+    if period <= 0 || covered <= 0 {
+        return;
+    }
+    *entry += if covered >= period {
+        summary.seconds as f64
+    } else {
+        summary.seconds as f64 * covered as f64 / period as f64
+    };
+}
 
+/// Adds the seconds the state spent within the period, weighted by the
+/// billing policies in effect at the time. The seconds are counted since the
+/// later of the state's begin and the given time, usually the begin of the
+/// whole requested period, so that they add up with the daily aggregates.
+fn add_server_state_consumption(
+    consumption: &mut ServerConsumptionServer,
+    state: &ServerState,
+    since: Option<DateTime<Utc>>,
+    begin: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    policies: &BillingPolicies,
+) {
+    let state_since = since.map_or(state.begin.to_utc(), |since| {
+        since.max(state.begin.to_utc())
+    });
+    let state_begin = begin.map_or(state.begin.to_utc(), |begin| {
+        begin.max(state.begin.to_utc())
+    });
+    let state_end = match (state.end, end) {
+        (Some(state_end), Some(end)) => state_end.to_utc().min(end),
+        (Some(state_end), None) => state_end.to_utc(),
+        (None, Some(end)) => end,
+        (None, None) => Utc::now(),
+    };
+    *consumption.entry(state.flavor_name.clone()).or_default() += policies
+        .billed_seconds_since(
+            &state.status,
+            state_since,
+            state_begin,
+            state_end,
+        );
+}
+
+/// Adds the aggregated seconds of the day, weighted by the billing policy in
+/// effect on that day.
+fn add_daily_usage_consumption(
+    consumption: &mut ServerConsumptionServer,
+    usage: &DailyUsage,
+    policies: &BillingPolicies,
+) {
+    *consumption.entry(usage.flavor_name.clone()).or_default() += usage.seconds
+        as f64
+        * policies.multiplier(&usage.status, start_of_the_day(usage.day));
+}
+
+/// Includes the consumption archived for the server. Whole days of the
+/// period are read from the daily aggregates where available.
 pub async fn calculate_server_consumption_for_server(
     transaction: &mut Transaction<'_, MySql>,
     server_uuid: Uuid,
     begin: Option<DateTime<Utc>>,
     end: Option<DateTime<Utc>>,
-    states: Option<Vec<ServerState>>,
 ) -> Result<ServerConsumptionServer, UnexpectedOnlyError> {
-    let mut states = match states {
-        Some(states) => states,
-        None => {
-            select_ordered_server_states_by_server_begin_and_end_from_db(
+    let mut consumption = ServerConsumptionServer::default();
+    for summary in select_server_state_summaries_by_server_from_db(
+        transaction,
+        server_uuid,
+        begin,
+        end,
+    )
+    .await?
+    {
+        add_server_state_summary_consumption(
+            &mut consumption,
+            &summary,
+            begin,
+            end,
+        );
+    }
+    let policies = BillingPolicies::load(transaction).await?;
+    let periods = match (begin, end) {
+        (Some(begin), Some(end)) => {
+            let period =
+                AggregatedPeriod::new(transaction, begin, end, &policies)
+                    .await?;
+            for usage in select_daily_usages_by_server_from_db(
                 transaction,
                 server_uuid,
-                begin,
-                end,
+                period.first,
+                period.last,
             )
             .await?
+            .iter()
+            .filter(|usage| period.contains(usage.day))
+            {
+                add_daily_usage_consumption(&mut consumption, usage, &policies);
+            }
+            period
+                .remaining
+                .into_iter()
+                .map(|(begin, end)| (Some(begin), Some(end)))
+                .collect()
         }
+        _ => vec![(begin, end)],
     };
-    let mut consumption = ServerConsumptionServer::default();
-    if states.is_empty() {
-        return Ok(consumption);
-    }
-    let first = states.first_mut().unwrap();
-    if let Some(begin) = begin
-        && begin.fixed_offset() > first.begin
-    {
-        first.begin = begin.fixed_offset();
-    }
-    let last = states.last_mut().unwrap();
-    if last.end.is_none()
-        && let Some(end) = end
-    {
-        last.end = Some(end.fixed_offset());
-    }
-    if let Some(end) = end
-        && end.fixed_offset() < last.end.unwrap()
-    {
-        last.end = Some(end.fixed_offset());
-    }
-    for state in states {
-        let entry = consumption.entry(state.flavor_name).or_default();
-        if !CONSUMING_STATES.contains(&state.status.as_str()) {
-            continue;
+    for (period_begin, period_end) in periods {
+        for state in
+            select_ordered_server_states_by_server_begin_and_end_from_db(
+                transaction,
+                server_uuid,
+                period_begin,
+                period_end,
+            )
+            .await?
+        {
+            add_server_state_consumption(
+                &mut consumption,
+                &state,
+                begin,
+                period_begin,
+                period_end,
+                &policies,
+            );
         }
-        *entry += (state.end.unwrap() - state.begin).num_seconds() as f64;
     }
-    // TODO:
     Ok(consumption)
 }
 
@@ -120,34 +214,70 @@ pub async fn calculate_server_consumption_for_user(
     end: Option<DateTime<Utc>>,
     detail: Option<bool>,
 ) -> Result<ServerConsumptionForUser, UnexpectedOnlyError> {
-    let states = select_ordered_server_states_by_user_begin_and_end_from_db(
+    let mut consumption = ServerConsumptionUser::default();
+    let policies = BillingPolicies::load(transaction).await?;
+    let periods = match (begin, end) {
+        (Some(begin), Some(end)) => {
+            let period =
+                AggregatedPeriod::new(transaction, begin, end, &policies)
+                    .await?;
+            for usage in select_daily_usages_by_user_from_db(
+                transaction,
+                user_id,
+                period.first,
+                period.last,
+            )
+            .await?
+            .iter()
+            .filter(|usage| period.contains(usage.day))
+            {
+                add_daily_usage_consumption(
+                    consumption.servers.entry(usage.instance_id).or_default(),
+                    usage,
+                    &policies,
+                );
+            }
+            period
+                .remaining
+                .into_iter()
+                .map(|(begin, end)| (Some(begin), Some(end)))
+                .collect()
+        }
+        _ => vec![(begin, end)],
+    };
+    for (period_begin, period_end) in periods {
+        for state in select_ordered_server_states_by_user_begin_and_end_from_db(
+            transaction,
+            user_id,
+            period_begin,
+            period_end,
+        )
+        .await?
+        {
+            add_server_state_consumption(
+                consumption.servers.entry(state.instance_id).or_default(),
+                &state,
+                begin,
+                period_begin,
+                period_end,
+                &policies,
+            );
+        }
+    }
+
+    for summary in select_server_state_summaries_by_user_from_db(
         transaction,
         user_id,
         begin,
         end,
     )
-    .await?;
-
-    let mut server_state_map: HashMap<Uuid, Vec<ServerState>> = HashMap::new();
-    for state in states {
-        server_state_map
-            .entry(state.instance_id)
-            .or_default()
-            .push(state);
-    }
-
-    let mut consumption = ServerConsumptionUser::default();
-    for (server_uuid, server_states) in server_state_map {
-        consumption.servers.insert(
-            server_uuid,
-            calculate_server_consumption_for_server(
-                transaction,
-                server_uuid,
-                begin,
-                end,
-                Some(server_states),
-            )
-            .await?,
+    .await?
+    {
+        add_server_state_summary_consumption(
+            consumption.servers.entry(summary.instance_id).or_default(),
+            &summary,
+            begin,
+            end,
         );
     }
 
@@ -259,6 +389,24 @@ pub async fn calculate_server_consumption_for_all(
     })
 }
 
+/// Returns the user of the server, which is also known once all its states
+/// have been archived.
+pub async fn select_user_id_by_server(
+    transaction: &mut Transaction<'_, MySql>,
+    server_uuid: Uuid,
+) -> Result<u32, OptionApiError> {
+    if let Some(state) =
+        select_server_states_by_server_from_db(transaction, server_uuid, false)
+            .await?
+            .first()
+    {
+        return Ok(state.user);
+    }
+    select_maybe_server_state_summary_user_from_db(transaction, server_uuid)
+        .await?
+        .ok_or(OptionApiError::NotFoundError)
+}
+
 #[derive(Serialize)]
 #[serde(untagged)]
 pub enum ServerConsumption {
@@ -266,6 +414,7 @@ pub enum ServerConsumption {
     User(ServerConsumptionForUser),
     Project(ServerConsumptionForProject),
     All(ServerConsumptionForAll),
+    Series(ServerConsumptionSeries),
 }
 
 #[tracing::instrument(name = "server_consumption")]
@@ -281,33 +430,57 @@ pub async fn server_consumption(
             .unwrap()
             .fixed_offset(),
     );
+    let buckets = params.granularity.map(|granularity| {
+        bucket_periods(granularity, begin.into(), end.into())
+    });
     let mut transaction = db_pool
         .begin()
         .await
         .context("Failed to begin transaction")?;
     let consumption = if params.all.unwrap_or(false) {
         require_admin_user(&user)?;
-        ServerConsumption::All(
-            calculate_server_consumption_for_all(
-                &mut transaction,
-                Some(begin.into()),
-                Some(end.into()),
-                params.detail,
-            )
-            .await?,
-        )
+        match &buckets {
+            Some(buckets) => ServerConsumption::Series(
+                calculate_server_consumption_series_for_all(
+                    &mut transaction,
+                    buckets,
+                    params.detail,
+                )
+                .await?,
+            ),
+            None => ServerConsumption::All(
+                calculate_server_consumption_for_all(
+                    &mut transaction,
+                    Some(begin.into()),
+                    Some(end.into()),
+                    params.detail,
+                )
+                .await?,
+            ),
+        }
     } else if let Some(project_id) = params.project {
         require_master_user_or_return_not_found(&user, project_id)?;
-        ServerConsumption::Project(
-            calculate_server_consumption_for_project(
-                &mut transaction,
-                project_id as u64,
-                Some(begin.into()),
-                Some(end.into()),
-                params.detail,
-            )
-            .await?,
-        )
+        match &buckets {
+            Some(buckets) => ServerConsumption::Series(
+                calculate_server_consumption_series_for_project(
+                    &mut transaction,
+                    project_id as u64,
+                    buckets,
+                    params.detail,
+                )
+                .await?,
+            ),
+            None => ServerConsumption::Project(
+                calculate_server_consumption_for_project(
+                    &mut transaction,
+                    project_id as u64,
+                    Some(begin.into()),
+                    Some(end.into()),
+                    params.detail,
+                )
+                .await?,
+            ),
+        }
     } else if let Some(user_id) = params.user {
         let user_queried =
             select_user_from_db(&mut transaction, user_id as u64).await?;
@@ -316,52 +489,79 @@ pub async fn server_consumption(
             user_id,
             user_queried.project,
         )?;
-        ServerConsumption::User(
-            calculate_server_consumption_for_user(
-                &mut transaction,
-                user_id as u64,
-                Some(begin.into()),
-                Some(end.into()),
-                params.detail,
-            )
-            .await?,
-        )
+        match &buckets {
+            Some(buckets) => ServerConsumption::Series(
+                calculate_server_consumption_series_for_user(
+                    &mut transaction,
+                    user_id as u64,
+                    buckets,
+                    params.detail,
+                )
+                .await?,
+            ),
+            None => ServerConsumption::User(
+                calculate_server_consumption_for_user(
+                    &mut transaction,
+                    user_id as u64,
+                    Some(begin.into()),
+                    Some(end.into()),
+                    params.detail,
+                )
+                .await?,
+            ),
+        }
     } else if let Some(server_id) = params.server {
-        let server_state = select_server_states_by_server_from_db(
-            &mut transaction,
-            server_id,
-            true,
-        )
-        .await?;
+        let server_user_id =
+            select_user_id_by_server(&mut transaction, server_id).await?;
         let server_state_user =
-            select_user_from_db(&mut transaction, server_state[0].user as u64)
+            select_user_from_db(&mut transaction, server_user_id as u64)
                 .await?;
         require_user_or_project_master_or_not_found(
             &user,
             server_state_user.id,
             server_state_user.project,
         )?;
-        ServerConsumption::Server(
-            calculate_server_consumption_for_server(
-                &mut transaction,
-                server_id,
-                Some(begin.into()),
-                Some(end.into()),
-                None,
-            )
-            .await?,
-        )
+        match &buckets {
+            Some(buckets) => ServerConsumption::Series(
+                calculate_server_consumption_series_for_server(
+                    &mut transaction,
+                    server_id,
+                    buckets,
+                )
+                .await?,
+            ),
+            None => ServerConsumption::Server(
+                calculate_server_consumption_for_server(
+                    &mut transaction,
+                    server_id,
+                    Some(begin.into()),
+                    Some(end.into()),
+                )
+                .await?,
+            ),
+        }
     } else {
-        ServerConsumption::User(
-            calculate_server_consumption_for_user(
-                &mut transaction,
-                user.id as u64,
-                Some(begin.into()),
-                Some(end.into()),
-                params.detail,
-            )
-            .await?,
-        )
+        match &buckets {
+            Some(buckets) => ServerConsumption::Series(
+                calculate_server_consumption_series_for_user(
+                    &mut transaction,
+                    user.id as u64,
+                    buckets,
+                    params.detail,
+                )
+                .await?,
+            ),
+            None => ServerConsumption::User(
+                calculate_server_consumption_for_user(
+                    &mut transaction,
+                    user.id as u64,
+                    Some(begin.into()),
+                    Some(end.into()),
+                    params.detail,
+                )
+                .await?,
+            ),
+        }
     };
     transaction
         .commit()
@@ -4,6 +4,7 @@ use actix_web::{
 };
 
 pub(crate) mod get;
+pub(crate) mod series;
 use get::server_consumption;
 
 pub fn server_consumption_scope() -> Scope {
@@ -0,0 +1,412 @@
+use std::collections::HashMap;
+
+use avina_wire::accounting::{
+    Bucket, Granularity, ServerConsumptionAll, ServerConsumptionFlavors,
+    ServerConsumptionProject, ServerConsumptionServer, ServerConsumptionUser,
+    ServerState,
+};
+use chrono::{DateTime, Datelike, Days, Months, NaiveTime, Utc};
+use serde::Serialize;
+use sqlx::{MySql, Transaction};
+use uuid::Uuid;
+
+use super::get::add_server_state_summary_consumption;
+use crate::{
+    database::{
+        accounting::{
+            server_state::{
+                select_ordered_server_states_by_server_begin_and_end_from_db,
+                select_ordered_server_states_by_user_begin_and_end_from_db,
+            },
+            server_state_summary::{
+                ServerStateSummary,
+                select_server_state_summaries_by_server_from_db,
+                select_server_state_summaries_by_user_from_db,
+            },
+        },
+        user::{
+            project::select_all_projects_from_db,
+            user::select_users_by_project_from_db,
+        },
+    },
+    error::UnexpectedOnlyError,
+    routes::accounting::billing_policy::BillingPolicies,
+};
+
+pub type Period = (DateTime<Utc>, DateTime<Utc>);
+
+fn next_bucket_begin(
+    granularity: Granularity,
+    time: DateTime<Utc>,
+) -> DateTime<Utc> {
+    let date = time.date_naive();
+    let next = match granularity {
+        Granularity::Day => date + Days::new(1),
+        Granularity::Week => {
+            date + Days::new(7 - date.weekday().num_days_from_monday() as u64)
+        }
+        Granularity::Month => date.with_day(1).unwrap() + Months::new(1),
+    };
+    next.and_time(NaiveTime::MIN).and_utc()
+}
+
+/// Splits the period into buckets starting at midnight UTC, on Mondays or
+/// on the first of the month, where the first and last one are cut to the
+/// period.
+pub fn bucket_periods(
+    granularity: Granularity,
+    begin: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Vec<Period> {
+    let mut periods = Vec::new();
+    let mut bucket_begin = begin;
+    while bucket_begin < end {
+        let bucket_end = next_bucket_begin(granularity, bucket_begin).min(end);
+        periods.push((bucket_begin, bucket_end));
+        bucket_begin = bucket_end;
+    }
+    periods
+}
+
+/// Splits the periods further at the given ordered times.
+pub fn split_periods_at(
+    periods: &[Period],
+    times: &[DateTime<Utc>],
+) -> Vec<Period> {
+    let mut slices = Vec::new();
+    for (begin, end) in periods {
+        let mut slice_begin = *begin;
+        for time in times.iter().filter(|time| begin < *time && *time < end) {
+            slices.push((slice_begin, *time));
+            slice_begin = *time;
+        }
+        slices.push((slice_begin, *end));
+    }
+    slices
+}
+
+pub fn into_buckets<T>(periods: &[Period], values: Vec<T>) -> Vec<Bucket<T>> {
+    periods
+        .iter()
+        .zip(values)
+        .map(|((begin, end), value)| Bucket {
+            begin: begin.fixed_offset(),
+            end: end.fixed_offset(),
+            value,
+        })
+        .collect()
+}
+
+/// Consumption of the servers within each of the ordered and adjacent
+/// slices, indexed like them.
+pub type SliceConsumption = Vec<HashMap<Uuid, ServerConsumptionServer>>;
+
+/// Distributes the states and summaries over the slices in a single pass,
+/// clipping each of them to the slices it overlaps and weighting the states
+/// by the billing policies.
+fn calculate_slice_consumption(
+    slices: &[Period],
+    states: Vec<ServerState>,
+    summaries: Vec<ServerStateSummary>,
+    policies: &BillingPolicies,
+) -> SliceConsumption {
+    let mut consumption = vec![HashMap::new(); slices.len()];
+    let Some((_, slices_end)) = slices.last() else {
+        return consumption;
+    };
+    for state in states {
+        let begin = state.begin.to_utc();
+        let end = state.end.map_or(*slices_end, |end| end.to_utc());
+        let first =
+            slices.partition_point(|(_, slice_end)| *slice_end <= begin);
+        for (i, (slice_begin, slice_end)) in
+            slices.iter().enumerate().skip(first)
+        {
+            if *slice_begin >= end {
+                break;
+            }
+            let entry = consumption[i]
+                .entry(state.instance_id)
+                .or_default()
+                .entry(state.flavor_name.clone())
+                .or_default();
+            *entry += policies.billed_seconds(
+                &state.status,
+                begin.max(*slice_begin),
+                end.min(*slice_end),
+            );
+        }
+    }
+    for summary in summaries {
+        let first = slices
+            .partition_point(|(_, slice_end)| *slice_end <= summary.begin);
+        for (i, (slice_begin, slice_end)) in
+            slices.iter().enumerate().skip(first)
+        {
+            if *slice_begin >= summary.end {
+                break;
+            }
+            add_server_state_summary_consumption(
+                consumption[i].entry(summary.instance_id).or_default(),
+                &summary,
+                Some(*slice_begin),
+                Some(*slice_end),
+            );
+        }
+    }
+    consumption
+}
+
+pub async fn calculate_server_consumption_slices_for_server(
+    transaction: &mut Transaction<'_, MySql>,
+    server_uuid: Uuid,
+    slices: &[Period],
+) -> Result<SliceConsumption, UnexpectedOnlyError> {
+    let (Some((begin, _)), Some((_, end))) = (slices.first(), slices.last())
+    else {
+        return Ok(SliceConsumption::new());
+    };
+    let states = select_ordered_server_states_by_server_begin_and_end_from_db(
+        transaction,
+        server_uuid,
+        Some(*begin),
+        Some(*end),
+    )
+    .await?;
+    let summaries = select_server_state_summaries_by_server_from_db(
+        transaction,
+        server_uuid,
+        Some(*begin),
+        Some(*end),
+    )
+    .await?;
+    let policies = BillingPolicies::load(transaction).await?;
+    Ok(calculate_slice_consumption(
+        slices, states, summaries, &policies,
+    ))
+}
+
+pub async fn calculate_server_consumption_slices_for_user(
+    transaction: &mut Transaction<'_, MySql>,
+    user_id: u64,
+    slices: &[Period],
+) -> Result<SliceConsumption, UnexpectedOnlyError> {
+    let (Some((begin, _)), Some((_, end))) = (slices.first(), slices.last())
+    else {
+        return Ok(SliceConsumption::new());
+    };
+    let states = select_ordered_server_states_by_user_begin_and_end_from_db(
+        transaction,
+        user_id,
+        Some(*begin),
+        Some(*end),
+    )
+    .await?;
+    let summaries = select_server_state_summaries_by_user_from_db(
+        transaction,
+        user_id,
+        Some(*begin),
+        Some(*end),
+    )
+    .await?;
+    let policies = BillingPolicies::load(transaction).await?;
+    Ok(calculate_slice_consumption(
+        slices, states, summaries, &policies,
+    ))
+}
+
+fn add_flavors(
+    total: &mut ServerConsumptionFlavors,
+    flavors: &ServerConsumptionFlavors,
+) {
+    for (flavor, value) in flavors {
+        *total.entry(flavor.clone()).or_default() += value;
+    }
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum ServerConsumptionSeries {
+    Flavors(Vec<Bucket<ServerConsumptionFlavors>>),
+    User(Vec<Bucket<ServerConsumptionUser>>),
+    Project(Vec<Bucket<ServerConsumptionProject>>),
+    All(Vec<Bucket<ServerConsumptionAll>>),
+}
+
+pub async fn calculate_server_consumption_series_for_server(
+    transaction: &mut Transaction<'_, MySql>,
+    server_uuid: Uuid,
+    buckets: &[Period],
+) -> Result<ServerConsumptionSeries, UnexpectedOnlyError> {
+    let consumption = calculate_server_consumption_slices_for_server(
+        transaction,
+        server_uuid,
+        buckets,
+    )
+    .await?
+    .into_iter()
+    .map(|mut servers| servers.remove(&server_uuid).unwrap_or_default())
+    .collect();
+    Ok(ServerConsumptionSeries::Flavors(into_buckets(
+        buckets,
+        consumption,
+    )))
+}
+
+async fn calculate_server_consumption_user_series(
+    transaction: &mut Transaction<'_, MySql>,
+    user_id: u64,
+    buckets: &[Period],
+) -> Result<Vec<ServerConsumptionUser>, UnexpectedOnlyError> {
+    Ok(calculate_server_consumption_slices_for_user(
+        transaction,
+        user_id,
+        buckets,
+    )
+    .await?
+    .into_iter()
+    .map(|servers| {
+        let mut consumption = ServerConsumptionUser {
+            servers,
+            ..Default::default()
+        };
+        for server_consumption in consumption.servers.values() {
+            add_flavors(&mut consumption.total, server_consumption);
+        }
+        consumption
+    })
+    .collect())
+}
+
+async fn calculate_server_consumption_project_series(
+    transaction: &mut Transaction<'_, MySql>,
+    project_id: u64,
+    buckets: &[Period],
+) -> Result<Vec<ServerConsumptionProject>, UnexpectedOnlyError> {
+    let mut consumption =
+        vec![ServerConsumptionProject::default(); buckets.len()];
+    let users =
+        select_users_by_project_from_db(transaction, project_id).await?;
+    for user in users {
+        let user_consumption = calculate_server_consumption_user_series(
+            transaction,
+            user.id as u64,
+            buckets,
+        )
+        .await?;
+        for (project_consumption, user_consumption) in
+            consumption.iter_mut().zip(user_consumption)
+        {
+            add_flavors(
+                &mut project_consumption.total,
+                &user_consumption.total,
+            );
+            project_consumption
+                .users
+                .insert(user.name.clone(), user_consumption);
+        }
+    }
+    Ok(consumption)
+}
+
+pub async fn calculate_server_consumption_series_for_user(
+    transaction: &mut Transaction<'_, MySql>,
+    user_id: u64,
+    buckets: &[Period],
+    detail: Option<bool>,
+) -> Result<ServerConsumptionSeries, UnexpectedOnlyError> {
+    let consumption =
+        calculate_server_consumption_user_series(transaction, user_id, buckets)
+            .await?;
+    Ok(if detail.unwrap_or(false) {
+        ServerConsumptionSeries::User(into_buckets(buckets, consumption))
+    } else {
+        ServerConsumptionSeries::Flavors(into_buckets(
+            buckets,
+            consumption.into_iter().map(|c| c.total).collect(),
+        ))
+    })
+}
+
+pub async fn calculate_server_consumption_series_for_project(
+    transaction: &mut Transaction<'_, MySql>,
+    project_id: u64,
+    buckets: &[Period],
+    detail: Option<bool>,
+) -> Result<ServerConsumptionSeries, UnexpectedOnlyError> {
+    let consumption = calculate_server_consumption_project_series(
+        transaction,
+        project_id,
+        buckets,
+    )
+    .await?;
+    Ok(if detail.unwrap_or(false) {
+        ServerConsumptionSeries::Project(into_buckets(buckets, consumption))
+    } else {
+        ServerConsumptionSeries::Flavors(into_buckets(
+            buckets,
+            consumption.into_iter().map(|c| c.total).collect(),
+        ))
+    })
+}
+
+pub async fn calculate_server_consumption_series_for_all(
+    transaction: &mut Transaction<'_, MySql>,
+    buckets: &[Period],
+    detail: Option<bool>,
+) -> Result<ServerConsumptionSeries, UnexpectedOnlyError> {
+    let mut consumption = vec![ServerConsumptionAll::default(); buckets.len()];
+    let projects = select_all_projects_from_db(transaction).await?;
+    for project in projects {
+        let project_consumption = calculate_server_consumption_project_series(
+            transaction,
+            project.id as u64,
+            buckets,
+        )
+        .await?;
+        for (all_consumption, project_consumption) in
+            consumption.iter_mut().zip(project_consumption)
+        {
+            add_flavors(&mut all_consumption.total, &project_consumption.total);
+            all_consumption
+                .projects
+                .insert(project.name.clone(), project_consumption);
+        }
+    }
+    Ok(if detail.unwrap_or(false) {
+        ServerConsumptionSeries::All(into_buckets(buckets, consumption))
+    } else {
+        ServerConsumptionSeries::Flavors(into_buckets(
+            buckets,
+            consumption.into_iter().map(|c| c.total).collect(),
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn buckets_are_aligned_and_cut_to_the_period() {
+        let begin = Utc.with_ymd_and_hms(2025, 1, 29, 12, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 3, 3, 6, 0, 0).unwrap();
+        let months = bucket_periods(Granularity::Month, begin, end);
+        assert_eq!(months.len(), 3);
+        assert_eq!(months[0].0, begin);
+        assert_eq!(
+            months[1].0,
+            Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(months[2].1, end);
+        let weeks = bucket_periods(Granularity::Week, begin, end);
+        assert_eq!(
+            weeks[1].0,
+            Utc.with_ymd_and_hms(2025, 2, 3, 0, 0, 0).unwrap()
+        );
+        assert_eq!(weeks.last().unwrap().0, weeks[1].0 + Days::new(28));
+        assert_eq!(bucket_periods(Granularity::Day, begin, end).len(), 34);
+    }
+}
@@ -0,0 +1,341 @@
+use std::collections::HashMap;
+
+use avina_wire::{
+    accounting::{
+        FloatingIpState, ServerCostAll, ServerCostProject, ServerCostUser,
+    },
+    user::Project,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{MySql, Transaction};
+
+use super::get::{PricePeriods, Prices};
+use crate::{
+    database::{
+        accounting::floating_ip_state::select_floating_ip_states_by_scope_begin_and_end_from_db,
+        pricing::floating_ip_price::select_floating_ip_prices_for_period_from_db,
+        user::{
+            project::select_maybe_project_from_db,
+            user::select_maybe_project_by_user_from_db,
+        },
+    },
+    error::UnexpectedOnlyError,
+    routes::user::project_class_change::UserClassHistory,
+};
+
+/// Returns the floating IP prices in effect from each start time on, split
+/// at price and user class changes like the flavor price periods.
+pub async fn get_floating_ip_price_periods(
+    transaction: &mut Transaction<'_, MySql>,
+    user_classes: &UserClassHistory,
+    begin: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<PricePeriods, UnexpectedOnlyError> {
+    let prices =
+        select_floating_ip_prices_for_period_from_db(transaction, end).await?;
+    let mut start_times = prices
+        .iter()
+        .map(|price| price.start_time.to_utc())
+        .filter(|start_time| begin < *start_time && *start_time < end)
+        .collect::<Vec<_>>();
+    start_times.extend(user_classes.changes_within(begin, end));
+    start_times.push(begin);
+    start_times.sort();
+    start_times.dedup();
+
+    let mut periods = PricePeriods::new();
+    for start_time in start_times {
+        let mut period_prices = Prices::new();
+        // NOTE: the prices are ordered by their start time, so later ones
+        // replace earlier ones.
+        for price in prices
+            .iter()
+            .filter(|price| price.start_time.to_utc() <= start_time)
+        {
+            period_prices
+                .entry(price.user_class)
+                .or_default()
+                .insert(price.pool.clone(), price.unit_price);
+        }
+        periods.insert(start_time, period_prices);
+    }
+    Ok(periods)
+}
+
+/// Returns the cost of one floating IP over the given seconds, with the
+/// price being the one of a floating IP for a whole year.
+pub fn calculate_floating_ip_cost(seconds: i64, price: f64) -> f64 {
+    (seconds as f64 * price) / ((365 * 24 * 60 * 60) as f64)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum FloatingIpCostScope {
+    User(u32),
+    Project(u32),
+    All,
+}
+
+pub struct FloatingIpStateCost {
+    state: FloatingIpState,
+    project: Project,
+    cost: f64,
+}
+
+/// Floating IP states within the scope and period together with everything
+/// needed to price them, so that the costs of several parts of the period,
+/// e.g. the buckets of a series, can be calculated without loading them
+/// again.
+pub struct FloatingIpStates {
+    states: Vec<(FloatingIpState, Project)>,
+    user_classes: UserClassHistory,
+    price_periods: PricePeriods,
+    end: DateTime<Utc>,
+}
+
+impl FloatingIpStates {
+    pub async fn load(
+        transaction: &mut Transaction<'_, MySql>,
+        scope: FloatingIpCostScope,
+        begin: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Self, UnexpectedOnlyError> {
+        let (user_id, project_id) = match scope {
+            FloatingIpCostScope::User(user_id) => (Some(user_id as u64), None),
+            FloatingIpCostScope::Project(project_id) => {
+                (None, Some(project_id as u64))
+            }
+            FloatingIpCostScope::All => (None, None),
+        };
+        let states = select_floating_ip_states_by_scope_begin_and_end_from_db(
+            transaction,
+            user_id,
+            project_id,
+            begin,
+            end,
+        )
+        .await?;
+        if states.is_empty() {
+            return Ok(Self {
+                states: Vec::new(),
+                user_classes: UserClassHistory::default(),
+                price_periods: PricePeriods::new(),
+                end,
+            });
+        }
+        let user_classes = UserClassHistory::load(transaction).await?;
+        let price_periods = get_floating_ip_price_periods(
+            transaction,
+            &user_classes,
+            begin,
+            end,
+        )
+        .await?;
+
+        // NOTE: all users of a project share it, so it only has to be looked
+        // up once instead of for each of them.
+        let scope_project = match project_id {
+            Some(project_id) => {
+                select_maybe_project_from_db(transaction, project_id).await?
+            }
+            None => None,
+        };
+        let mut projects: HashMap<u32, Option<Project>> = HashMap::new();
+        let mut states_with_project = vec![];
+        for state in states {
+            let project = if scope_project.is_some() {
+                scope_project.clone()
+            } else if let Some(project) = projects.get(&state.user) {
+                project.clone()
+            } else {
+                let project = select_maybe_project_by_user_from_db(
+                    transaction,
+                    state.user as u64,
+                )
+                .await?;
+                projects.insert(state.user, project.clone());
+                project
+            };
+            if let Some(project) = project {
+                states_with_project.push((state, project));
+            }
+        }
+        Ok(Self {
+            states: states_with_project,
+            user_classes,
+            price_periods,
+            end,
+        })
+    }
+
+    /// Returns the cost of each state within the given part of the period,
+    /// with floating IPs being billed as long as they are allocated, whether
+    /// they are attached to a server or not.
+    pub fn costs(
+        &self,
+        begin: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Vec<FloatingIpStateCost> {
+        let mut end_times = self
+            .price_periods
+            .keys()
+            .skip(1)
+            .cloned()
+            .collect::<Vec<_>>();
+        end_times.push(self.end);
+
+        let mut costs = vec![];
+        for (state, project) in &self.states {
+            let state_begin = state.begin.to_utc().max(begin);
+            let state_end = state.end.map_or(end, |e| e.to_utc().min(end));
+            if state_end <= state_begin {
+                continue;
+            }
+            let mut cost = 0.;
+            for ((start_time, prices), end_time) in
+                self.price_periods.iter().zip(end_times.iter())
+            {
+                let covered_begin = state_begin.max(*start_time);
+                let covered_end = state_end.min(*end_time);
+                if covered_end <= covered_begin {
+                    continue;
+                }
+                let Some(price) = prices
+                    .get(&self.user_classes.user_class(project, *start_time))
+                    .and_then(|prices| prices.get(&state.pool))
+                else {
+                    continue;
+                };
+                cost += calculate_floating_ip_cost(
+                    (covered_end - covered_begin).num_seconds(),
+                    *price,
+                );
+            }
+            costs.push(FloatingIpStateCost {
+                state: state.clone(),
+                project: project.clone(),
+                cost,
+            });
+        }
+        costs
+    }
+
+    /// Returns the cost per second of the states not ended at the given
+    /// time, at the prices in effect then.
+    pub fn rate(&self, at: DateTime<Utc>) -> f64 {
+        let Some((start_time, prices)) = self
+            .price_periods
+            .iter()
+            .rev()
+            .find(|(start_time, _)| **start_time <= at)
+        else {
+            return 0.;
+        };
+        self.states
+            .iter()
+            .filter(|(state, _)| {
+                state.begin.to_utc() <= at
+                    && state.end.is_none_or(|end| end.to_utc() > at)
+            })
+            .filter_map(|(state, project)| {
+                prices
+                    .get(&self.user_classes.user_class(project, *start_time))
+                    .and_then(|prices| prices.get(&state.pool))
+                    .map(|price| calculate_floating_ip_cost(1, *price))
+            })
+            .sum()
+    }
+}
+
+/// Returns the cost of each floating IP state within the scope and period.
+pub async fn calculate_floating_ip_state_costs(
+    transaction: &mut Transaction<'_, MySql>,
+    scope: FloatingIpCostScope,
+    begin: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<FloatingIpStateCost>, UnexpectedOnlyError> {
+    Ok(FloatingIpStates::load(transaction, scope, begin, end)
+        .await?
+        .costs(begin, end))
+}
+
+pub fn floating_ip_cost_total(costs: &[FloatingIpStateCost]) -> f64 {
+    costs.iter().map(|state_cost| state_cost.cost).sum()
+}
+
+fn add_pool_cost(
+    total: &mut f64,
+    floating_ips: &mut HashMap<String, f64>,
+    state_cost: &FloatingIpStateCost,
+) {
+    *total += state_cost.cost;
+    *floating_ips
+        .entry(state_cost.state.pool.clone())
+        .or_default() += state_cost.cost;
+}
+
+pub fn add_floating_ip_costs_to_user(
+    cost: &mut ServerCostUser,
+    costs: &[FloatingIpStateCost],
+) {
+    for state_cost in costs {
+        add_pool_cost(&mut cost.total, &mut cost.floating_ips, state_cost);
+    }
+}
+
+pub fn add_floating_ip_costs_to_project(
+    cost: &mut ServerCostProject,
+    costs: &[FloatingIpStateCost],
+) {
+    for state_cost in costs {
+        add_pool_cost(&mut cost.total, &mut cost.floating_ips, state_cost);
+        let user_cost = cost
+            .users
+            .entry(state_cost.state.username.clone())
+            .or_default();
+        add_pool_cost(
+            &mut user_cost.total,
+            &mut user_cost.floating_ips,
+            state_cost,
+        );
+    }
+}
+
+pub fn add_floating_ip_costs_to_all(
+    cost: &mut ServerCostAll,
+    costs: &[FloatingIpStateCost],
+) {
+    for state_cost in costs {
+        add_pool_cost(&mut cost.total, &mut cost.floating_ips, state_cost);
+        let project_cost = cost
+            .projects
+            .entry(state_cost.project.name.clone())
+            .or_default();
+        add_pool_cost(
+            &mut project_cost.total,
+            &mut project_cost.floating_ips,
+            state_cost,
+        );
+        let user_cost = project_cost
+            .users
+            .entry(state_cost.state.username.clone())
+            .or_default();
+        add_pool_cost(
+            &mut user_cost.total,
+            &mut user_cost.floating_ips,
+            state_cost,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::calculate_floating_ip_cost;
+
+    #[test]
+    fn floating_ip_cost_is_prorated_per_year() {
+        let day = 24 * 60 * 60;
+        assert_eq!(calculate_floating_ip_cost(day, 365.), 1.);
+        assert_eq!(calculate_floating_ip_cost(365 * day, 12.), 12.);
+        assert_eq!(calculate_floating_ip_cost(0, 365.), 0.);
+    }
+}
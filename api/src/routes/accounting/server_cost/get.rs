@@ -26,27 +26,47 @@ use crate::{
         require_user_or_project_master_or_not_found,
     },
     database::{
-        accounting::server_state::{
-            select_server_states_by_server_from_db,
-            select_user_class_by_server_from_db,
-        },
+        accounting::server_state::select_maybe_project_by_server_from_db,
         pricing::flavor_price::select_flavor_prices_for_period_from_db,
         resources::flavor::select_all_flavors_from_db,
         user::{
             project::{
-                select_all_projects_from_db,
-                select_user_class_by_project_from_db,
+                select_all_projects_from_db, select_maybe_project_from_db,
             },
-            user::{select_user_class_by_user_from_db, select_user_from_db},
+            user::{select_maybe_project_by_user_from_db, select_user_from_db},
         },
     },
     error::{OptionApiError, UnexpectedOnlyError},
-    routes::accounting::server_consumption::get::{
-        ServerConsumptionForAll, ServerConsumptionForProject,
-        ServerConsumptionForUser, calculate_server_consumption_for_all,
-        calculate_server_consumption_for_project,
-        calculate_server_consumption_for_server,
-        calculate_server_consumption_for_user,
+    routes::{
+        accounting::{
+            server_consumption::{
+                get::{
+                    ServerConsumptionForAll, ServerConsumptionForProject,
+                    ServerConsumptionForUser,
+                    calculate_server_consumption_for_all,
+                    calculate_server_consumption_for_project,
+                    calculate_server_consumption_for_server,
+                    calculate_server_consumption_for_user,
+                    select_user_id_by_server,
+                },
+                series::bucket_periods,
+            },
+            server_cost::{
+                floating_ip::{
+                    FloatingIpCostScope, add_floating_ip_costs_to_all,
+                    add_floating_ip_costs_to_project,
+                    add_floating_ip_costs_to_user,
+                    calculate_floating_ip_state_costs, floating_ip_cost_total,
+                },
+                series::{
+                    ServerCostSeries, calculate_server_cost_series_for_all,
+                    calculate_server_cost_series_for_project,
+                    calculate_server_cost_series_for_server,
+                    calculate_server_cost_series_for_user,
+                },
+            },
+        },
+        user::project_class_change::UserClassHistory,
     },
 };
 
@@ -99,10 +119,10 @@ async fn get_flavor_prices_for_period(
     Ok(prices)
 }
 
-type Prices = HashMap<UserClass, HashMap<String, f64>>;
-type PricePeriods = IndexMap<DateTime<Utc>, Prices>;
+pub type Prices = HashMap<UserClass, HashMap<String, f64>>;
+pub type PricePeriods = IndexMap<DateTime<Utc>, Prices>;
 
-async fn get_flavor_price_periods(
+pub async fn get_flavor_price_periods(
     transaction: &mut Transaction<'_, MySql>,
     begin: DateTime<Utc>,
     end: DateTime<Utc>,
@@ -140,38 +160,56 @@ async fn get_flavor_price_periods(
     let mut current_time = begin;
     periods.insert(current_time, current_prices.clone());
 
-    if i == prices.len() {
-        return Ok(periods);
+    if i < prices.len() {
+        current_time = prices.get(i).unwrap().start_time.to_utc();
+        while i < prices.len() {
+            let price = prices.get(i).unwrap();
+            if price.start_time.to_utc() == current_time {
+                *current_prices
+                    .get_mut(&price.user_class)
+                    .unwrap()
+                    .entry(price.flavor_name.clone())
+                    .or_insert(0.0) = price.unit_price;
+            } else {
+                periods.insert(current_time, current_prices.clone());
+                current_time = prices.get(i).unwrap().start_time.to_utc();
+            }
+            i += 1;
+        }
+        periods.insert(current_time, current_prices.clone());
     }
 
-    current_time = prices.get(i).unwrap().start_time.to_utc();
-    while i < prices.len() {
-        let price = prices.get(i).unwrap();
-        if price.start_time.to_utc() == current_time {
-            *current_prices
-                .get_mut(&price.user_class)
-                .unwrap()
-                .entry(price.flavor_name.clone())
-                .or_insert(0.0) = price.unit_price;
-        } else {
-            periods.insert(current_time, current_prices.clone());
-            current_time = prices.get(i).unwrap().start_time.to_utc();
+    // NOTE: the periods are split at user class changes as well, so that
+    // each project has a single user class within each of them.
+    let class_changes = UserClassHistory::load(transaction)
+        .await?
+        .changes_within(begin, end);
+    for change in class_changes {
+        if periods.contains_key(&change) {
+            continue;
         }
-        i += 1;
+        let (_, prices) = periods
+            .iter()
+            .take_while(|(start_time, _)| **start_time <= change)
+            .last()
+            .unwrap();
+        periods.insert(change, prices.clone());
     }
-    periods.insert(current_time, current_prices.clone());
+    periods.sort_keys();
 
     Ok(periods)
 }
 
-fn calculate_flavor_consumption_cost(
+/// The consumption is already weighted by the billing policies in effect
+/// when the server states happened, so only the price has to be applied.
+pub fn calculate_flavor_consumption_cost(
     flavor_consumption: f64,
-    prices: Prices,
+    prices: &Prices,
     user_class: UserClass,
-    flavor: String,
+    flavor: &str,
 ) -> f64 {
     let mut cost = 0.0;
-    if let Some(price) = prices.get(&user_class).unwrap().get(&flavor) {
+    if let Some(price) = prices.get(&user_class).unwrap().get(flavor) {
         cost = (flavor_consumption * price) / ((365 * 24 * 60 * 60) as f64);
     }
     cost
@@ -191,11 +229,13 @@ pub async fn calculate_server_cost_for_server_normal(
     end: DateTime<Utc>,
 ) -> Result<ServerCostSimple, UnexpectedOnlyError> {
     let mut cost = ServerCostSimple { total: 0.0 };
-    let Some(user_class) =
-        select_user_class_by_server_from_db(transaction, server_uuid).await?
+    let Some(project) =
+        select_maybe_project_by_server_from_db(transaction, server_uuid)
+            .await?
     else {
         return Ok(cost);
     };
+    let user_classes = UserClassHistory::load(transaction).await?;
     let price_periods =
         get_flavor_price_periods(transaction, begin, end).await?;
 
@@ -210,7 +250,6 @@ pub async fn calculate_server_cost_for_server_normal(
             server_uuid,
             Some(*start_time),
             Some(end_time),
-            None,
         )
         .await?;
         for (flavor_name, flavor_consumption) in consumption {
@@ -219,9 +258,9 @@ pub async fn calculate_server_cost_for_server_normal(
             }
             let flavor_cost = calculate_flavor_consumption_cost(
                 flavor_consumption,
-                prices.clone(),
-                user_class,
-                flavor_name,
+                prices,
+                user_classes.user_class(&project, *start_time),
+                &flavor_name,
             );
             if flavor_cost <= 0. {
                 continue;
@@ -244,11 +283,13 @@ pub async fn calculate_server_cost_for_server_detail(
         total: 0.0,
         flavors: HashMap::new(),
     };
-    let Some(user_class) =
-        select_user_class_by_server_from_db(transaction, server_uuid).await?
+    let Some(project) =
+        select_maybe_project_by_server_from_db(transaction, server_uuid)
+            .await?
     else {
         return Ok(cost);
     };
+    let user_classes = UserClassHistory::load(transaction).await?;
     let price_periods =
         get_flavor_price_periods(transaction, begin, end).await?;
 
@@ -263,15 +304,14 @@ pub async fn calculate_server_cost_for_server_detail(
             server_uuid,
             Some(*start_time),
             Some(end_time),
-            None,
         )
         .await?;
         for (flavor_name, flavor_consumption) in consumption {
             let flavor_cost = calculate_flavor_consumption_cost(
                 flavor_consumption,
-                prices.clone(),
-                user_class,
-                flavor_name.clone(),
+                prices,
+                user_classes.user_class(&project, *start_time),
+                &flavor_name,
             );
             *cost.flavors.entry(flavor_name).or_default() += flavor_cost;
             if flavor_cost <= 0. {
@@ -328,11 +368,12 @@ pub async fn calculate_server_cost_for_user_normal(
     end: DateTime<Utc>,
 ) -> Result<ServerCostSimple, UnexpectedOnlyError> {
     let mut cost = ServerCostSimple { total: 0.0 };
-    let Some(user_class) =
-        select_user_class_by_user_from_db(transaction, user_id).await?
+    let Some(project) =
+        select_maybe_project_by_user_from_db(transaction, user_id).await?
     else {
         return Ok(cost);
     };
+    let user_classes = UserClassHistory::load(transaction).await?;
     let price_periods =
         get_flavor_price_periods(transaction, begin, end).await?;
 
@@ -362,14 +403,24 @@ pub async fn calculate_server_cost_for_user_normal(
             }
             let flavor_cost = calculate_flavor_consumption_cost(
                 flavor_consumption,
-                prices.clone(),
-                user_class,
-                flavor_name,
+                prices,
+                user_classes.user_class(&project, *start_time),
+                &flavor_name,
             );
             cost.total += flavor_cost;
         }
     }
 
+    cost.total += floating_ip_cost_total(
+        &calculate_floating_ip_state_costs(
+            transaction,
+            FloatingIpCostScope::User(user_id as u32),
+            begin,
+            end,
+        )
+        .await?,
+    );
+
     Ok(cost)
 }
 
@@ -383,13 +434,15 @@ pub async fn calculate_server_cost_for_user_detail(
     let mut cost = ServerCostUser {
         total: 0.0,
         flavors: HashMap::new(),
+        floating_ips: HashMap::new(),
         servers: HashMap::new(),
     };
-    let Some(user_class) =
-        select_user_class_by_user_from_db(transaction, user_id).await?
+    let Some(project) =
+        select_maybe_project_by_user_from_db(transaction, user_id).await?
     else {
         return Ok(cost);
     };
+    let user_classes = UserClassHistory::load(transaction).await?;
     let price_periods =
         get_flavor_price_periods(transaction, begin, end).await?;
 
@@ -423,9 +476,9 @@ pub async fn calculate_server_cost_for_user_detail(
             for (flavor_name, flavor_consumption) in server_consumption {
                 let flavor_cost = calculate_flavor_consumption_cost(
                     flavor_consumption,
-                    prices.clone(),
-                    user_class,
-                    flavor_name.clone(),
+                    prices,
+                    user_classes.user_class(&project, *start_time),
+                    &flavor_name,
                 );
                 *server_cost.flavors.entry(flavor_name.clone()).or_default() +=
                     flavor_cost;
@@ -439,6 +492,17 @@ pub async fn calculate_server_cost_for_user_detail(
         }
     }
 
+    add_floating_ip_costs_to_user(
+        &mut cost,
+        &calculate_floating_ip_state_costs(
+            transaction,
+            FloatingIpCostScope::User(user_id as u32),
+            begin,
+            end,
+        )
+        .await?,
+    );
+
     Ok(cost)
 }
 
@@ -485,11 +549,12 @@ pub async fn calculate_server_cost_for_project_normal(
     end: DateTime<Utc>,
 ) -> Result<ServerCostSimple, UnexpectedOnlyError> {
     let mut cost = ServerCostSimple { total: 0.0 };
-    let Some(user_class) =
-        select_user_class_by_project_from_db(transaction, project_id).await?
+    let Some(project) =
+        select_maybe_project_from_db(transaction, project_id).await?
     else {
         return Ok(cost);
     };
+    let user_classes = UserClassHistory::load(transaction).await?;
     let price_periods =
         get_flavor_price_periods(transaction, begin, end).await?;
 
@@ -520,9 +585,9 @@ pub async fn calculate_server_cost_for_project_normal(
             }
             let flavor_cost = calculate_flavor_consumption_cost(
                 flavor_consumption,
-                prices.clone(),
-                user_class,
-                flavor_name,
+                prices,
+                user_classes.user_class(&project, *start_time),
+                &flavor_name,
             );
             if flavor_cost <= 0. {
                 continue;
@@ -531,6 +596,16 @@ pub async fn calculate_server_cost_for_project_normal(
         }
     }
 
+    cost.total += floating_ip_cost_total(
+        &calculate_floating_ip_state_costs(
+            transaction,
+            FloatingIpCostScope::Project(project_id as u32),
+            begin,
+            end,
+        )
+        .await?,
+    );
+
     Ok(cost)
 }
 
@@ -544,13 +619,15 @@ pub async fn calculate_server_cost_for_project_detail(
     let mut cost = ServerCostProject {
         total: 0.0,
         flavors: HashMap::new(),
+        floating_ips: HashMap::new(),
         users: HashMap::new(),
     };
-    let Some(user_class) =
-        select_user_class_by_project_from_db(transaction, project_id).await?
+    let Some(project) =
+        select_maybe_project_from_db(transaction, project_id).await?
     else {
         return Ok(cost);
     };
+    let user_classes = UserClassHistory::load(transaction).await?;
     let price_periods =
         get_flavor_price_periods(transaction, begin, end).await?;
 
@@ -582,6 +659,7 @@ pub async fn calculate_server_cost_for_project_detail(
                     .or_insert(ServerCostUser {
                         total: 0.0,
                         flavors: HashMap::new(),
+                        floating_ips: HashMap::new(),
                         servers: HashMap::new(),
                     });
             for (server_uuid, server_consumption) in user_consumption.servers {
@@ -595,9 +673,9 @@ pub async fn calculate_server_cost_for_project_detail(
                 for (flavor_name, flavor_consumption) in server_consumption {
                     let flavor_cost = calculate_flavor_consumption_cost(
                         flavor_consumption,
-                        prices.clone(),
-                        user_class,
-                        flavor_name.clone(),
+                        prices,
+                        user_classes.user_class(&project, *start_time),
+                        &flavor_name,
                     );
                     *server_cost
                         .flavors
@@ -620,6 +698,17 @@ pub async fn calculate_server_cost_for_project_detail(
         }
     }
 
+    add_floating_ip_costs_to_project(
+        &mut cost,
+        &calculate_floating_ip_state_costs(
+            transaction,
+            FloatingIpCostScope::Project(project_id as u32),
+            begin,
+            end,
+        )
+        .await?,
+    );
+
     Ok(cost)
 }
 
@@ -678,6 +767,7 @@ pub async fn calculate_server_cost_for_all_normal(
         .into_iter()
         .map(|p| (p.name.clone(), p))
         .collect::<HashMap<_, _>>();
+    let user_classes = UserClassHistory::load(transaction).await?;
 
     for ((start_time, prices), end_time) in price_periods.iter().zip(end_times)
     {
@@ -705,9 +795,9 @@ pub async fn calculate_server_cost_for_all_normal(
                 }
                 let flavor_cost = calculate_flavor_consumption_cost(
                     flavor_consumption,
-                    prices.clone(),
-                    project.user_class,
-                    flavor_name,
+                    prices,
+                    user_classes.user_class(project, *start_time),
+                    &flavor_name,
                 );
                 if flavor_cost <= 0. {
                     continue;
@@ -717,6 +807,16 @@ pub async fn calculate_server_cost_for_all_normal(
         }
     }
 
+    cost.total += floating_ip_cost_total(
+        &calculate_floating_ip_state_costs(
+            transaction,
+            FloatingIpCostScope::All,
+            begin,
+            end,
+        )
+        .await?,
+    );
+
     Ok(cost)
 }
 
@@ -725,14 +825,33 @@ pub async fn calculate_server_cost_for_all_detail(
     transaction: &mut Transaction<'_, MySql>,
     begin: DateTime<Utc>,
     end: DateTime<Utc>,
+) -> Result<ServerCostAll, UnexpectedOnlyError> {
+    let price_periods =
+        get_flavor_price_periods(transaction, begin, end).await?;
+    calculate_server_cost_for_all_detail_with_price_periods(
+        transaction,
+        &price_periods,
+        begin,
+        end,
+    )
+    .await
+}
+
+/// Like calculate_server_cost_for_all_detail, but prices the servers with
+/// the given price periods instead of the stored ones, e.g. to preview price
+/// changes. The first period has to start at begin.
+pub async fn calculate_server_cost_for_all_detail_with_price_periods(
+    transaction: &mut Transaction<'_, MySql>,
+    price_periods: &PricePeriods,
+    begin: DateTime<Utc>,
+    end: DateTime<Utc>,
 ) -> Result<ServerCostAll, UnexpectedOnlyError> {
     let mut cost = ServerCostAll {
         total: 0.0,
         flavors: HashMap::new(),
+        floating_ips: HashMap::new(),
         projects: HashMap::new(),
     };
-    let price_periods =
-        get_flavor_price_periods(transaction, begin, end).await?;
 
     let mut end_times =
         price_periods.keys().skip(1).cloned().collect::<Vec<_>>();
@@ -743,6 +862,7 @@ pub async fn calculate_server_cost_for_all_detail(
         .into_iter()
         .map(|p| (p.name.clone(), p))
         .collect::<HashMap<_, _>>();
+    let user_classes = UserClassHistory::load(transaction).await?;
 
     for ((start_time, prices), end_time) in price_periods.iter().zip(end_times)
     {
@@ -769,6 +889,7 @@ pub async fn calculate_server_cost_for_all_detail(
                 .or_insert(ServerCostProject {
                     total: 0.0,
                     flavors: HashMap::new(),
+                    floating_ips: HashMap::new(),
                     users: HashMap::new(),
                 });
 
@@ -779,6 +900,7 @@ pub async fn calculate_server_cost_for_all_detail(
                     .or_insert(ServerCostUser {
                         total: 0.0,
                         flavors: HashMap::new(),
+                        floating_ips: HashMap::new(),
                         servers: HashMap::new(),
                     });
                 for (server_uuid, server_consumption) in
@@ -795,9 +917,9 @@ pub async fn calculate_server_cost_for_all_detail(
                     {
                         let flavor_cost = calculate_flavor_consumption_cost(
                             flavor_consumption,
-                            prices.clone(),
-                            project.user_class,
-                            flavor_name.clone(),
+                            prices,
+                            user_classes.user_class(project, *start_time),
+                            &flavor_name,
                         );
                         *server_cost
                             .flavors
@@ -826,6 +948,17 @@ pub async fn calculate_server_cost_for_all_detail(
         }
     }
 
+    add_floating_ip_costs_to_all(
+        &mut cost,
+        &calculate_floating_ip_state_costs(
+            transaction,
+            FloatingIpCostScope::All,
+            begin,
+            end,
+        )
+        .await?,
+    );
+
     Ok(cost)
 }
 
@@ -854,6 +987,7 @@ pub enum ServerCost {
     User(ServerCostForUser),
     Project(ServerCostForProject),
     All(ServerCostForAll),
+    Series(ServerCostSeries),
 }
 
 #[tracing::instrument(name = "server_cost")]
@@ -869,33 +1003,57 @@ pub async fn server_cost(
             .unwrap()
             .fixed_offset(),
     );
+    let buckets = params.granularity.map(|granularity| {
+        bucket_periods(granularity, begin.into(), end.into())
+    });
     let mut transaction = db_pool
         .begin()
         .await
         .context("Failed to begin transaction")?;
     let cost = if params.all.unwrap_or(false) {
         require_admin_user(&user)?;
-        ServerCost::All(
-            calculate_server_cost_for_all(
-                &mut transaction,
-                begin.into(),
-                end.into(),
-                params.detail,
-            )
-            .await?,
-        )
+        match &buckets {
+            Some(buckets) => ServerCost::Series(
+                calculate_server_cost_series_for_all(
+                    &mut transaction,
+                    buckets,
+                    params.detail,
+                )
+                .await?,
+            ),
+            None => ServerCost::All(
+                calculate_server_cost_for_all(
+                    &mut transaction,
+                    begin.into(),
+                    end.into(),
+                    params.detail,
+                )
+                .await?,
+            ),
+        }
     } else if let Some(project_id) = params.project {
         require_master_user_or_return_not_found(&user, project_id)?;
-        ServerCost::Project(
-            calculate_server_cost_for_project(
-                &mut transaction,
-                project_id as u64,
-                begin.into(),
-                end.into(),
-                params.detail,
-            )
-            .await?,
-        )
+        match &buckets {
+            Some(buckets) => ServerCost::Series(
+                calculate_server_cost_series_for_project(
+                    &mut transaction,
+                    project_id as u64,
+                    buckets,
+                    params.detail,
+                )
+                .await?,
+            ),
+            None => ServerCost::Project(
+                calculate_server_cost_for_project(
+                    &mut transaction,
+                    project_id as u64,
+                    begin.into(),
+                    end.into(),
+                    params.detail,
+                )
+                .await?,
+            ),
+        }
     } else if let Some(user_id) = params.user {
         let user_queried =
             select_user_from_db(&mut transaction, user_id as u64).await?;
@@ -904,52 +1062,81 @@ pub async fn server_cost(
             user_id,
             user_queried.project,
         )?;
-        ServerCost::User(
-            calculate_server_cost_for_user(
-                &mut transaction,
-                user_id as u64,
-                begin.into(),
-                end.into(),
-                params.detail,
-            )
-            .await?,
-        )
+        match &buckets {
+            Some(buckets) => ServerCost::Series(
+                calculate_server_cost_series_for_user(
+                    &mut transaction,
+                    user_id as u64,
+                    buckets,
+                    params.detail,
+                )
+                .await?,
+            ),
+            None => ServerCost::User(
+                calculate_server_cost_for_user(
+                    &mut transaction,
+                    user_id as u64,
+                    begin.into(),
+                    end.into(),
+                    params.detail,
+                )
+                .await?,
+            ),
+        }
     } else if let Some(server_id) = params.server {
-        let server_state = select_server_states_by_server_from_db(
-            &mut transaction,
-            server_id,
-            true,
-        )
-        .await?;
+        let server_user_id =
+            select_user_id_by_server(&mut transaction, server_id).await?;
         let server_state_user =
-            select_user_from_db(&mut transaction, server_state[0].user as u64)
+            select_user_from_db(&mut transaction, server_user_id as u64)
                 .await?;
         require_user_or_project_master_or_not_found(
             &user,
             server_state_user.id,
             server_state_user.project,
         )?;
-        ServerCost::Server(
-            calculate_server_cost_for_server(
-                &mut transaction,
-                server_id,
-                begin.into(),
-                end.into(),
-                params.detail,
-            )
-            .await?,
-        )
+        match &buckets {
+            Some(buckets) => ServerCost::Series(
+                calculate_server_cost_series_for_server(
+                    &mut transaction,
+                    server_id,
+                    buckets,
+                    params.detail,
+                )
+                .await?,
+            ),
+            None => ServerCost::Server(
+                calculate_server_cost_for_server(
+                    &mut transaction,
+                    server_id,
+                    begin.into(),
+                    end.into(),
+                    params.detail,
+                )
+                .await?,
+            ),
+        }
     } else {
-        ServerCost::User(
-            calculate_server_cost_for_user(
-                &mut transaction,
-                user.id as u64,
-                begin.into(),
-                end.into(),
-                params.detail,
-            )
-            .await?,
-        )
+        match &buckets {
+            Some(buckets) => ServerCost::Series(
+                calculate_server_cost_series_for_user(
+                    &mut transaction,
+                    user.id as u64,
+                    buckets,
+                    params.detail,
+                )
+                .await?,
+            ),
+            None => ServerCost::User(
+                calculate_server_cost_for_user(
+                    &mut transaction,
+                    user.id as u64,
+                    begin.into(),
+                    end.into(),
+                    params.detail,
+                )
+                .await?,
+            ),
+        }
     };
     transaction
         .commit()
@@ -3,7 +3,11 @@ use actix_web::{
     web::{get, scope},
 };
 
+pub(crate) mod floating_ip;
 pub(crate) mod get;
+pub(crate) mod series;
+pub(crate) mod total;
+pub(crate) mod volume;
 use get::server_cost;
 
 pub fn server_cost_scope() -> Scope {
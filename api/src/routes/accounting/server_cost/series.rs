@@ -0,0 +1,351 @@
+use avina_wire::{
+    accounting::{
+        Bucket, ServerCostAll, ServerCostProject, ServerCostServer,
+        ServerCostSimple, ServerCostUser,
+    },
+    user::Project,
+};
+use serde::Serialize;
+use sqlx::{MySql, Transaction};
+use uuid::Uuid;
+
+use super::{
+    floating_ip::{
+        FloatingIpCostScope, FloatingIpStates, add_floating_ip_costs_to_all,
+        add_floating_ip_costs_to_project, add_floating_ip_costs_to_user,
+    },
+    get::{
+        PricePeriods, calculate_flavor_consumption_cost,
+        get_flavor_price_periods,
+    },
+};
+use crate::{
+    database::{
+        accounting::server_state::select_maybe_project_by_server_from_db,
+        user::{
+            project::{
+                select_all_projects_from_db, select_maybe_project_from_db,
+            },
+            user::{
+                select_maybe_project_by_user_from_db,
+                select_users_by_project_from_db,
+            },
+        },
+    },
+    error::UnexpectedOnlyError,
+    routes::{
+        accounting::server_consumption::series::{
+            Period, SliceConsumption,
+            calculate_server_consumption_slices_for_server,
+            calculate_server_consumption_slices_for_user, into_buckets,
+            split_periods_at,
+        },
+        user::project_class_change::UserClassHistory,
+    },
+};
+
+/// The buckets split further wherever the flavor prices or user classes
+/// change, so that each slice has a single price and belongs to a single
+/// bucket.
+struct PricedSlices {
+    slices: Vec<Period>,
+    buckets: Vec<usize>,
+    prices: Vec<usize>,
+    bucket_count: usize,
+    price_periods: PricePeriods,
+    user_classes: UserClassHistory,
+}
+
+impl PricedSlices {
+    async fn new(
+        transaction: &mut Transaction<'_, MySql>,
+        buckets: &[Period],
+    ) -> Result<Self, UnexpectedOnlyError> {
+        let (Some((begin, _)), Some((_, end))) =
+            (buckets.first(), buckets.last())
+        else {
+            return Ok(Self {
+                slices: Vec::new(),
+                buckets: Vec::new(),
+                prices: Vec::new(),
+                bucket_count: 0,
+                price_periods: PricePeriods::new(),
+                user_classes: UserClassHistory::default(),
+            });
+        };
+        let price_periods =
+            get_flavor_price_periods(transaction, *begin, *end).await?;
+        let price_starts = price_periods.keys().cloned().collect::<Vec<_>>();
+        let slices = split_periods_at(buckets, &price_starts);
+        let index_of = |starts: &[_], slice_begin| {
+            starts
+                .partition_point(|start| *start <= slice_begin)
+                .saturating_sub(1)
+        };
+        let bucket_starts =
+            buckets.iter().map(|(begin, _)| *begin).collect::<Vec<_>>();
+        Ok(Self {
+            buckets: slices
+                .iter()
+                .map(|(begin, _)| index_of(&bucket_starts, *begin))
+                .collect(),
+            prices: slices
+                .iter()
+                .map(|(begin, _)| index_of(&price_starts, *begin))
+                .collect(),
+            slices,
+            bucket_count: buckets.len(),
+            price_periods,
+            user_classes: UserClassHistory::load(transaction).await?,
+        })
+    }
+
+    fn calculate_user_cost(
+        &self,
+        consumption: SliceConsumption,
+        project: &Project,
+    ) -> Vec<ServerCostUser> {
+        let mut costs = vec![ServerCostUser::default(); self.bucket_count];
+        for (i, servers) in consumption.into_iter().enumerate() {
+            let cost = &mut costs[self.buckets[i]];
+            let (_, prices) =
+                self.price_periods.get_index(self.prices[i]).unwrap();
+            let (slice_begin, _) = self.slices[i];
+            let user_class = self.user_classes.user_class(project, slice_begin);
+            for (server_uuid, server_consumption) in servers {
+                let server_cost = cost.servers.entry(server_uuid).or_default();
+                for (flavor_name, flavor_consumption) in server_consumption {
+                    let flavor_cost = calculate_flavor_consumption_cost(
+                        flavor_consumption,
+                        prices,
+                        user_class,
+                        &flavor_name,
+                    );
+                    *server_cost
+                        .flavors
+                        .entry(flavor_name.clone())
+                        .or_default() += flavor_cost;
+                    *cost.flavors.entry(flavor_name).or_default() +=
+                        flavor_cost;
+                    server_cost.total += flavor_cost;
+                    cost.total += flavor_cost;
+                }
+            }
+        }
+        costs
+    }
+
+    async fn calculate_project_cost(
+        &self,
+        transaction: &mut Transaction<'_, MySql>,
+        project: &Project,
+    ) -> Result<Vec<ServerCostProject>, UnexpectedOnlyError> {
+        let mut costs = vec![ServerCostProject::default(); self.bucket_count];
+        let users =
+            select_users_by_project_from_db(transaction, project.id as u64)
+                .await?;
+        for user in users {
+            let consumption = calculate_server_consumption_slices_for_user(
+                transaction,
+                user.id as u64,
+                &self.slices,
+            )
+            .await?;
+            for (cost, user_cost) in costs
+                .iter_mut()
+                .zip(self.calculate_user_cost(consumption, project))
+            {
+                for (flavor_name, flavor_cost) in &user_cost.flavors {
+                    *cost.flavors.entry(flavor_name.clone()).or_default() +=
+                        flavor_cost;
+                }
+                cost.total += user_cost.total;
+                cost.users.insert(user.name.clone(), user_cost);
+            }
+        }
+        Ok(costs)
+    }
+}
+
+fn simple_buckets(
+    buckets: &[Period],
+    totals: impl Iterator<Item = f64>,
+) -> ServerCostSeries {
+    ServerCostSeries::Simple(into_buckets(
+        buckets,
+        totals.map(|total| ServerCostSimple { total }).collect(),
+    ))
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum ServerCostSeries {
+    Simple(Vec<Bucket<ServerCostSimple>>),
+    Server(Vec<Bucket<ServerCostServer>>),
+    User(Vec<Bucket<ServerCostUser>>),
+    Project(Vec<Bucket<ServerCostProject>>),
+    All(Vec<Bucket<ServerCostAll>>),
+}
+
+pub async fn calculate_server_cost_series_for_server(
+    transaction: &mut Transaction<'_, MySql>,
+    server_uuid: Uuid,
+    buckets: &[Period],
+    detail: Option<bool>,
+) -> Result<ServerCostSeries, UnexpectedOnlyError> {
+    let slices = PricedSlices::new(transaction, buckets).await?;
+    let costs =
+        match select_maybe_project_by_server_from_db(transaction, server_uuid)
+            .await?
+        {
+            Some(project) => {
+                let consumption =
+                    calculate_server_consumption_slices_for_server(
+                        transaction,
+                        server_uuid,
+                        &slices.slices,
+                    )
+                    .await?;
+                slices
+                    .calculate_user_cost(consumption, &project)
+                    .into_iter()
+                    .map(|mut cost| {
+                        cost.servers.remove(&server_uuid).unwrap_or_default()
+                    })
+                    .collect()
+            }
+            None => vec![ServerCostServer::default(); buckets.len()],
+        };
+    Ok(if detail.unwrap_or(false) {
+        ServerCostSeries::Server(into_buckets(buckets, costs))
+    } else {
+        simple_buckets(buckets, costs.into_iter().map(|c| c.total))
+    })
+}
+
+pub async fn calculate_server_cost_series_for_user(
+    transaction: &mut Transaction<'_, MySql>,
+    user_id: u64,
+    buckets: &[Period],
+    detail: Option<bool>,
+) -> Result<ServerCostSeries, UnexpectedOnlyError> {
+    let slices = PricedSlices::new(transaction, buckets).await?;
+    let mut costs =
+        match select_maybe_project_by_user_from_db(transaction, user_id).await?
+        {
+            Some(project) => {
+                let consumption = calculate_server_consumption_slices_for_user(
+                    transaction,
+                    user_id,
+                    &slices.slices,
+                )
+                .await?;
+                slices.calculate_user_cost(consumption, &project)
+            }
+            None => vec![ServerCostUser::default(); buckets.len()],
+        };
+    if let (Some((begin, _)), Some((_, end))) =
+        (buckets.first(), buckets.last())
+    {
+        let floating_ip_states = FloatingIpStates::load(
+            transaction,
+            FloatingIpCostScope::User(user_id as u32),
+            *begin,
+            *end,
+        )
+        .await?;
+        for (cost, (begin, end)) in costs.iter_mut().zip(buckets) {
+            add_floating_ip_costs_to_user(
+                cost,
+                &floating_ip_states.costs(*begin, *end),
+            );
+        }
+    }
+    Ok(if detail.unwrap_or(false) {
+        ServerCostSeries::User(into_buckets(buckets, costs))
+    } else {
+        simple_buckets(buckets, costs.into_iter().map(|c| c.total))
+    })
+}
+
+pub async fn calculate_server_cost_series_for_project(
+    transaction: &mut Transaction<'_, MySql>,
+    project_id: u64,
+    buckets: &[Period],
+    detail: Option<bool>,
+) -> Result<ServerCostSeries, UnexpectedOnlyError> {
+    let slices = PricedSlices::new(transaction, buckets).await?;
+    let mut costs =
+        match select_maybe_project_from_db(transaction, project_id).await? {
+            Some(project) => {
+                slices.calculate_project_cost(transaction, &project).await?
+            }
+            None => vec![ServerCostProject::default(); buckets.len()],
+        };
+    if let (Some((begin, _)), Some((_, end))) =
+        (buckets.first(), buckets.last())
+    {
+        let floating_ip_states = FloatingIpStates::load(
+            transaction,
+            FloatingIpCostScope::Project(project_id as u32),
+            *begin,
+            *end,
+        )
+        .await?;
+        for (cost, (begin, end)) in costs.iter_mut().zip(buckets) {
+            add_floating_ip_costs_to_project(
+                cost,
+                &floating_ip_states.costs(*begin, *end),
+            );
+        }
+    }
+    Ok(if detail.unwrap_or(false) {
+        ServerCostSeries::Project(into_buckets(buckets, costs))
+    } else {
+        simple_buckets(buckets, costs.into_iter().map(|c| c.total))
+    })
+}
+
+pub async fn calculate_server_cost_series_for_all(
+    transaction: &mut Transaction<'_, MySql>,
+    buckets: &[Period],
+    detail: Option<bool>,
+) -> Result<ServerCostSeries, UnexpectedOnlyError> {
+    let slices = PricedSlices::new(transaction, buckets).await?;
+    let mut costs = vec![ServerCostAll::default(); buckets.len()];
+    let projects = select_all_projects_from_db(transaction).await?;
+    for project in projects {
+        let project_costs =
+            slices.calculate_project_cost(transaction, &project).await?;
+        for (cost, project_cost) in costs.iter_mut().zip(project_costs) {
+            for (flavor_name, flavor_cost) in &project_cost.flavors {
+                *cost.flavors.entry(flavor_name.clone()).or_default() +=
+                    flavor_cost;
+            }
+            cost.total += project_cost.total;
+            cost.projects.insert(project.name.clone(), project_cost);
+        }
+    }
+    if let (Some((begin, _)), Some((_, end))) =
+        (buckets.first(), buckets.last())
+    {
+        let floating_ip_states = FloatingIpStates::load(
+            transaction,
+            FloatingIpCostScope::All,
+            *begin,
+            *end,
+        )
+        .await?;
+        for (cost, (begin, end)) in costs.iter_mut().zip(buckets) {
+            add_floating_ip_costs_to_all(
+                cost,
+                &floating_ip_states.costs(*begin, *end),
+            );
+        }
+    }
+    Ok(if detail.unwrap_or(false) {
+        ServerCostSeries::All(into_buckets(buckets, costs))
+    } else {
+        simple_buckets(buckets, costs.into_iter().map(|c| c.total))
+    })
+}
@@ -0,0 +1,119 @@
+use avina_wire::accounting::{
+    ServerCostAll, ServerCostProject, ServerCostSimple,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{MySql, Transaction};
+
+use super::{
+    get::{
+        calculate_server_cost_for_all_detail,
+        calculate_server_cost_for_project_detail,
+        calculate_server_cost_for_project_normal,
+        calculate_server_cost_for_user_normal,
+    },
+    volume::{
+        VolumeCostScope, add_volume_costs_to_all, add_volume_costs_to_project,
+        calculate_volume_state_costs, volume_cost_total,
+    },
+};
+use crate::error::UnexpectedOnlyError;
+
+// NOTE: budgets are compared against the cost of all resources, i.e. the
+// servers, their floating IPs and the volumes. The server cost already
+// includes the floating IPs, so only the volumes have to be added.
+
+pub async fn calculate_total_cost_for_user(
+    transaction: &mut Transaction<'_, MySql>,
+    user_id: u32,
+    begin: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<ServerCostSimple, UnexpectedOnlyError> {
+    let mut cost = calculate_server_cost_for_user_normal(
+        transaction,
+        user_id as u64,
+        begin,
+        end,
+    )
+    .await?;
+    cost.total += volume_cost_total(
+        &calculate_volume_state_costs(
+            transaction,
+            VolumeCostScope::User(user_id),
+            begin,
+            end,
+        )
+        .await?,
+    );
+    Ok(cost)
+}
+
+pub async fn calculate_total_cost_for_project(
+    transaction: &mut Transaction<'_, MySql>,
+    project_id: u32,
+    begin: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<ServerCostSimple, UnexpectedOnlyError> {
+    let mut cost = calculate_server_cost_for_project_normal(
+        transaction,
+        project_id as u64,
+        begin,
+        end,
+    )
+    .await?;
+    cost.total += volume_cost_total(
+        &calculate_volume_state_costs(
+            transaction,
+            VolumeCostScope::Project(project_id),
+            begin,
+            end,
+        )
+        .await?,
+    );
+    Ok(cost)
+}
+
+pub async fn calculate_total_cost_for_project_detail(
+    transaction: &mut Transaction<'_, MySql>,
+    project_id: u32,
+    begin: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<ServerCostProject, UnexpectedOnlyError> {
+    let mut cost = calculate_server_cost_for_project_detail(
+        transaction,
+        project_id as u64,
+        begin,
+        end,
+    )
+    .await?;
+    add_volume_costs_to_project(
+        &mut cost,
+        &calculate_volume_state_costs(
+            transaction,
+            VolumeCostScope::Project(project_id),
+            begin,
+            end,
+        )
+        .await?,
+    );
+    Ok(cost)
+}
+
+pub async fn calculate_total_cost_for_all_detail(
+    transaction: &mut Transaction<'_, MySql>,
+    begin: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<ServerCostAll, UnexpectedOnlyError> {
+    let mut cost =
+        calculate_server_cost_for_all_detail(transaction, begin, end).await?;
+    add_volume_costs_to_all(
+        &mut cost,
+        &calculate_volume_state_costs(
+            transaction,
+            VolumeCostScope::All,
+            begin,
+            end,
+        )
+        .await?,
+    );
+    Ok(cost)
+}
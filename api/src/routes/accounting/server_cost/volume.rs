@@ -0,0 +1,452 @@
+use std::collections::HashMap;
+
+use avina_wire::{
+    accounting::{
+        ServerCostAll, ServerCostProject, VolumeCostAll, VolumeCostProject,
+        VolumeCostSimple, VolumeCostUser, VolumeCostVolume, VolumeState,
+    },
+    user::Project,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{MySql, Transaction};
+use uuid::Uuid;
+
+use super::get::{PricePeriods, Prices};
+use crate::{
+    database::{
+        accounting::volume_state::select_volume_states_by_scope_begin_and_end_from_db,
+        pricing::volume_price::select_volume_prices_for_period_from_db,
+        user::{
+            project::select_maybe_project_from_db,
+            user::select_maybe_project_by_user_from_db,
+        },
+    },
+    error::UnexpectedOnlyError,
+    routes::user::project_class_change::UserClassHistory,
+};
+
+/// Volumes are billed for the space they occupy, unless they ended up in
+/// an error status.
+pub fn is_billed_volume_status(status: &str) -> bool {
+    !status.starts_with("error")
+}
+
+/// Returns the volume prices in effect from each start time on, split at
+/// price and user class changes like the flavor price periods.
+pub async fn get_volume_price_periods(
+    transaction: &mut Transaction<'_, MySql>,
+    user_classes: &UserClassHistory,
+    begin: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<PricePeriods, UnexpectedOnlyError> {
+    let prices =
+        select_volume_prices_for_period_from_db(transaction, end).await?;
+    let mut start_times = prices
+        .iter()
+        .map(|price| price.start_time.to_utc())
+        .filter(|start_time| begin < *start_time && *start_time < end)
+        .collect::<Vec<_>>();
+    start_times.extend(user_classes.changes_within(begin, end));
+    start_times.push(begin);
+    start_times.sort();
+    start_times.dedup();
+
+    let mut periods = PricePeriods::new();
+    for start_time in start_times {
+        let mut period_prices = Prices::new();
+        // NOTE: the prices are ordered by their start time, so later ones
+        // replace earlier ones.
+        for price in prices
+            .iter()
+            .filter(|price| price.start_time.to_utc() <= start_time)
+        {
+            period_prices
+                .entry(price.user_class)
+                .or_default()
+                .insert(price.volume_type.clone(), price.unit_price);
+        }
+        periods.insert(start_time, period_prices);
+    }
+    Ok(periods)
+}
+
+/// Returns the cost of size GiB over the given seconds, with the price being
+/// the one of a GiB for a whole year.
+pub fn calculate_volume_size_cost(size: u32, seconds: i64, price: f64) -> f64 {
+    (size as f64 * seconds as f64 * price) / ((365 * 24 * 60 * 60) as f64)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum VolumeCostScope {
+    Volume(Uuid),
+    User(u32),
+    Project(u32),
+    All,
+}
+
+pub struct VolumeStateCost {
+    state: VolumeState,
+    project: Project,
+    cost: f64,
+}
+
+/// Volume states within the scope and period together with everything
+/// needed to price them, like FloatingIpStates.
+pub struct VolumeStates {
+    states: Vec<(VolumeState, Project)>,
+    user_classes: UserClassHistory,
+    price_periods: PricePeriods,
+    end: DateTime<Utc>,
+}
+
+impl VolumeStates {
+    pub async fn load(
+        transaction: &mut Transaction<'_, MySql>,
+        scope: VolumeCostScope,
+        begin: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Self, UnexpectedOnlyError> {
+        let (volume_id, user_id, project_id) = match scope {
+            VolumeCostScope::Volume(volume_id) => (Some(volume_id), None, None),
+            VolumeCostScope::User(user_id) => {
+                (None, Some(user_id as u64), None)
+            }
+            VolumeCostScope::Project(project_id) => {
+                (None, None, Some(project_id as u64))
+            }
+            VolumeCostScope::All => (None, None, None),
+        };
+        let states = select_volume_states_by_scope_begin_and_end_from_db(
+            transaction,
+            volume_id,
+            user_id,
+            project_id,
+            begin,
+            end,
+        )
+        .await?
+        .into_iter()
+        .filter(|state| is_billed_volume_status(&state.status))
+        .collect::<Vec<_>>();
+        if states.is_empty() {
+            return Ok(Self {
+                states: Vec::new(),
+                user_classes: UserClassHistory::default(),
+                price_periods: PricePeriods::new(),
+                end,
+            });
+        }
+        let user_classes = UserClassHistory::load(transaction).await?;
+        let price_periods =
+            get_volume_price_periods(transaction, &user_classes, begin, end)
+                .await?;
+
+        // NOTE: all users of a project share it, so it only has to be looked
+        // up once instead of for each of them.
+        let scope_project = match project_id {
+            Some(project_id) => {
+                select_maybe_project_from_db(transaction, project_id).await?
+            }
+            None => None,
+        };
+        let mut projects: HashMap<u32, Option<Project>> = HashMap::new();
+        let mut states_with_project = vec![];
+        for state in states {
+            let project = if scope_project.is_some() {
+                scope_project.clone()
+            } else if let Some(project) = projects.get(&state.user) {
+                project.clone()
+            } else {
+                let project = select_maybe_project_by_user_from_db(
+                    transaction,
+                    state.user as u64,
+                )
+                .await?;
+                projects.insert(state.user, project.clone());
+                project
+            };
+            if let Some(project) = project {
+                states_with_project.push((state, project));
+            }
+        }
+        Ok(Self {
+            states: states_with_project,
+            user_classes,
+            price_periods,
+            end,
+        })
+    }
+
+    /// Returns the cost of each state within the given part of the period.
+    pub fn costs(
+        &self,
+        begin: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Vec<VolumeStateCost> {
+        let mut end_times = self
+            .price_periods
+            .keys()
+            .skip(1)
+            .cloned()
+            .collect::<Vec<_>>();
+        end_times.push(self.end);
+
+        let mut costs = vec![];
+        for (state, project) in &self.states {
+            let state_begin = state.begin.to_utc().max(begin);
+            let state_end = state.end.map_or(end, |e| e.to_utc().min(end));
+            if state_end <= state_begin {
+                continue;
+            }
+            let mut cost = 0.;
+            for ((start_time, prices), end_time) in
+                self.price_periods.iter().zip(end_times.iter())
+            {
+                let covered_begin = state_begin.max(*start_time);
+                let covered_end = state_end.min(*end_time);
+                if covered_end <= covered_begin {
+                    continue;
+                }
+                let Some(price) = prices
+                    .get(&self.user_classes.user_class(project, *start_time))
+                    .and_then(|prices| prices.get(&state.volume_type))
+                else {
+                    continue;
+                };
+                cost += calculate_volume_size_cost(
+                    state.size,
+                    (covered_end - covered_begin).num_seconds(),
+                    *price,
+                );
+            }
+            costs.push(VolumeStateCost {
+                state: state.clone(),
+                project: project.clone(),
+                cost,
+            });
+        }
+        costs
+    }
+
+    /// Returns the cost per second of the states not ended at the given
+    /// time, at the prices in effect then.
+    pub fn rate(&self, at: DateTime<Utc>) -> f64 {
+        let Some((start_time, prices)) = self
+            .price_periods
+            .iter()
+            .rev()
+            .find(|(start_time, _)| **start_time <= at)
+        else {
+            return 0.;
+        };
+        self.states
+            .iter()
+            .filter(|(state, _)| {
+                state.begin.to_utc() <= at
+                    && state.end.is_none_or(|end| end.to_utc() > at)
+            })
+            .filter_map(|(state, project)| {
+                prices
+                    .get(&self.user_classes.user_class(project, *start_time))
+                    .and_then(|prices| prices.get(&state.volume_type))
+                    .map(|price| {
+                        calculate_volume_size_cost(state.size, 1, *price)
+                    })
+            })
+            .sum()
+    }
+}
+
+/// Returns the cost of each volume state within the scope and period.
+pub async fn calculate_volume_state_costs(
+    transaction: &mut Transaction<'_, MySql>,
+    scope: VolumeCostScope,
+    begin: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<VolumeStateCost>, UnexpectedOnlyError> {
+    Ok(VolumeStates::load(transaction, scope, begin, end)
+        .await?
+        .costs(begin, end))
+}
+
+pub fn volume_cost_total(costs: &[VolumeStateCost]) -> f64 {
+    costs.iter().map(|state_cost| state_cost.cost).sum()
+}
+
+fn add_volume_type_cost(
+    total: &mut f64,
+    volume_types: &mut HashMap<String, f64>,
+    state_cost: &VolumeStateCost,
+) {
+    *total += state_cost.cost;
+    *volume_types
+        .entry(state_cost.state.volume_type.clone())
+        .or_default() += state_cost.cost;
+}
+
+fn volume_cost_for_volumes(costs: &[VolumeStateCost]) -> VolumeCostUser {
+    let mut cost = VolumeCostUser::default();
+    for state_cost in costs {
+        add_volume_type_cost(
+            &mut cost.total,
+            &mut cost.volume_types,
+            state_cost,
+        );
+        let volume_cost =
+            cost.volumes.entry(state_cost.state.volume_id).or_default();
+        add_volume_type_cost(
+            &mut volume_cost.total,
+            &mut volume_cost.volume_types,
+            state_cost,
+        );
+    }
+    cost
+}
+
+fn volume_cost_for_users(costs: &[VolumeStateCost]) -> VolumeCostProject {
+    let mut cost = VolumeCostProject::default();
+    let mut users: HashMap<String, Vec<&VolumeStateCost>> = HashMap::new();
+    for state_cost in costs {
+        add_volume_type_cost(
+            &mut cost.total,
+            &mut cost.volume_types,
+            state_cost,
+        );
+        users
+            .entry(state_cost.state.username.clone())
+            .or_default()
+            .push(state_cost);
+    }
+    for (username, user_costs) in users {
+        let mut user_cost = VolumeCostUser::default();
+        for state_cost in user_costs {
+            add_volume_type_cost(
+                &mut user_cost.total,
+                &mut user_cost.volume_types,
+                state_cost,
+            );
+            let volume_cost = user_cost
+                .volumes
+                .entry(state_cost.state.volume_id)
+                .or_default();
+            add_volume_type_cost(
+                &mut volume_cost.total,
+                &mut volume_cost.volume_types,
+                state_cost,
+            );
+        }
+        cost.users.insert(username, user_cost);
+    }
+    cost
+}
+
+/// Adds the volume costs to the totals of the project and its users, which
+/// have no breakdown of them unlike the floating IPs.
+pub fn add_volume_costs_to_project(
+    cost: &mut ServerCostProject,
+    costs: &[VolumeStateCost],
+) {
+    for state_cost in costs {
+        cost.total += state_cost.cost;
+        cost.users
+            .entry(state_cost.state.username.clone())
+            .or_default()
+            .total += state_cost.cost;
+    }
+}
+
+pub fn add_volume_costs_to_all(
+    cost: &mut ServerCostAll,
+    costs: &[VolumeStateCost],
+) {
+    for state_cost in costs {
+        cost.total += state_cost.cost;
+        let project_cost = cost
+            .projects
+            .entry(state_cost.project.name.clone())
+            .or_default();
+        project_cost.total += state_cost.cost;
+        project_cost
+            .users
+            .entry(state_cost.state.username.clone())
+            .or_default()
+            .total += state_cost.cost;
+    }
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum VolumeCost {
+    Normal(VolumeCostSimple),
+    Volume(VolumeCostVolume),
+    User(VolumeCostUser),
+    Project(VolumeCostProject),
+    All(VolumeCostAll),
+}
+
+pub async fn calculate_volume_cost(
+    transaction: &mut Transaction<'_, MySql>,
+    scope: VolumeCostScope,
+    begin: DateTime<Utc>,
+    end: DateTime<Utc>,
+    detail: Option<bool>,
+) -> Result<VolumeCost, UnexpectedOnlyError> {
+    let costs =
+        calculate_volume_state_costs(transaction, scope, begin, end).await?;
+    if !detail.unwrap_or(false) {
+        return Ok(VolumeCost::Normal(VolumeCostSimple {
+            total: volume_cost_total(&costs),
+        }));
+    }
+    Ok(match scope {
+        VolumeCostScope::Volume(_) => {
+            let cost = volume_cost_for_volumes(&costs);
+            VolumeCost::Volume(VolumeCostVolume {
+                total: cost.total,
+                volume_types: cost.volume_types,
+            })
+        }
+        VolumeCostScope::User(_) => {
+            VolumeCost::User(volume_cost_for_volumes(&costs))
+        }
+        VolumeCostScope::Project(_) => {
+            VolumeCost::Project(volume_cost_for_users(&costs))
+        }
+        VolumeCostScope::All => {
+            let mut cost = VolumeCostAll::default();
+            let mut projects: HashMap<String, Vec<VolumeStateCost>> =
+                HashMap::new();
+            for state_cost in costs {
+                add_volume_type_cost(
+                    &mut cost.total,
+                    &mut cost.volume_types,
+                    &state_cost,
+                );
+                projects
+                    .entry(state_cost.project.name.clone())
+                    .or_default()
+                    .push(state_cost);
+            }
+            cost.projects = projects
+                .into_iter()
+                .map(|(name, costs)| (name, volume_cost_for_users(&costs)))
+                .collect();
+            VolumeCost::All(cost)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn volume_cost_is_prorated_per_gib_and_year() {
+        let year = 365 * 24 * 60 * 60;
+        assert_eq!(calculate_volume_size_cost(10, year, 2.), 20.);
+        assert_eq!(calculate_volume_size_cost(10, year / 5, 2.), 4.);
+        assert!(is_billed_volume_status("in-use"));
+        assert!(!is_billed_volume_status("error_deleting"));
+    }
+}
@@ -0,0 +1,276 @@
+use std::collections::{HashMap, HashSet, hash_map::Entry};
+
+use actix_web::{
+    HttpResponse,
+    web::{Data, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{
+    accounting::{ServerState, ServerStateCompaction},
+    user::User,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, MySql, MySqlPool, Transaction};
+
+use super::delete::delete_server_state_from_db;
+use crate::{
+    authorization::require_admin_user,
+    configuration::AccountingSettings,
+    database::accounting::{
+        daily_usage::invalidate_daily_usages_in_db,
+        server_state::{
+            select_all_server_states_from_db, select_maybe_server_state_from_db,
+        },
+        server_state_summary::{
+            NewServerStateSummary, upsert_server_state_summary_into_db,
+        },
+    },
+    error::{MinimalApiError, NormalApiError, UnexpectedOnlyError},
+    routes::accounting::{
+        billing_policy::BillingPolicies,
+        server_cost::get::get_flavor_price_periods,
+    },
+    utils::{fiscal_year, start_of_the_year},
+};
+
+#[tracing::instrument(name = "server_state_compact")]
+pub async fn server_state_compact(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    accounting: Data<AccountingSettings>,
+) -> Result<HttpResponse, NormalApiError> {
+    require_admin_user(&user)?;
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let compaction =
+        compact_server_states(&mut transaction, accounting.retention_years)
+            .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(compaction))
+}
+
+/// Merges adjacent identical server states and, when a retention policy is
+/// given, archives the states of older closed fiscal years.
+#[tracing::instrument(name = "compact_server_states", skip(transaction))]
+pub async fn compact_server_states(
+    transaction: &mut Transaction<'_, MySql>,
+    retention_years: Option<u32>,
+) -> Result<ServerStateCompaction, MinimalApiError> {
+    let merged_state_count = merge_adjacent_server_states(transaction).await?;
+    let (archived_state_count, archived_until) = match retention_years {
+        Some(retention_years) => {
+            let archived_until = fiscal_year(Utc::now())
+                .saturating_sub(retention_years.saturating_add(1));
+            (
+                archive_server_states(transaction, archived_until).await?,
+                Some(archived_until),
+            )
+        }
+        None => (0, None),
+    };
+    Ok(ServerStateCompaction {
+        merged_state_count,
+        archived_state_count,
+        archived_until,
+    })
+}
+
+fn is_continued_by(state: &ServerState, next: &ServerState) -> bool {
+    state.end == Some(next.begin)
+        && state.instance_id == next.instance_id
+        && state.instance_name == next.instance_name
+        && state.flavor == next.flavor
+        && state.status == next.status
+        && state.user == next.user
+}
+
+async fn merge_adjacent_server_states(
+    transaction: &mut Transaction<'_, MySql>,
+) -> Result<u32, MinimalApiError> {
+    let mut states = select_all_server_states_from_db(transaction).await?;
+    states.sort_by_key(|s| (s.instance_id, s.begin, s.id));
+
+    let mut merged_state_count = 0;
+    let mut kept_states: Vec<ServerState> = Vec::new();
+    let mut extended_state_ids = HashSet::new();
+    for state in states {
+        if let Some(kept_state) = kept_states.last_mut()
+            && is_continued_by(kept_state, &state)
+        {
+            delete_server_state_from_db(transaction, state.id as u64).await?;
+            kept_state.end = state.end;
+            extended_state_ids.insert(kept_state.id);
+            merged_state_count += 1;
+            continue;
+        }
+        kept_states.push(state);
+    }
+    for state in kept_states
+        .iter()
+        .filter(|s| extended_state_ids.contains(&s.id))
+    {
+        update_server_state_period_in_db(
+            transaction,
+            state.id as u64,
+            state.begin.to_utc(),
+            state.end.map(|end| end.to_utc()),
+        )
+        .await?;
+    }
+    Ok(merged_state_count)
+}
+
+type Period = (DateTime<Utc>, DateTime<Utc>);
+
+/// Archives the states of all fiscal years up to the given one into
+/// summaries per server, flavor, user and price period, so that costs stay
+/// the same. States reaching into later fiscal years are kept, but begin
+/// with the first of them afterwards.
+async fn archive_server_states(
+    transaction: &mut Transaction<'_, MySql>,
+    archived_until: u32,
+) -> Result<u32, MinimalApiError> {
+    let cutoff = start_of_the_year(archived_until + 1);
+    let states = select_all_server_states_from_db(transaction)
+        .await?
+        .into_iter()
+        .filter(|s| s.begin < cutoff)
+        .collect::<Vec<_>>();
+
+    let policies = BillingPolicies::load(transaction).await?;
+    let mut price_periods: HashMap<u32, Vec<Period>> = HashMap::new();
+    let mut summaries: HashMap<_, NewServerStateSummary> = HashMap::new();
+    for state in &states {
+        let begin = state.begin.to_utc();
+        let end = state.end.map_or(cutoff, |end| end.to_utc().min(cutoff));
+        let mut year = fiscal_year(begin);
+        while start_of_the_year(year) < end {
+            if let Entry::Vacant(entry) = price_periods.entry(year) {
+                let year_end = start_of_the_year(year + 1);
+                let starts = get_flavor_price_periods(
+                    transaction,
+                    start_of_the_year(year),
+                    year_end,
+                )
+                .await?
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>();
+                let ends = starts.iter().skip(1).cloned().chain([year_end]);
+                entry.insert(starts.iter().cloned().zip(ends).collect());
+            }
+            for (period_begin, period_end) in &price_periods[&year] {
+                let covered_begin = begin.max(*period_begin);
+                let covered_end = end.min(*period_end);
+                if covered_end <= covered_begin {
+                    continue;
+                }
+                let summary = summaries
+                    .entry((
+                        state.instance_id,
+                        state.flavor,
+                        state.user,
+                        *period_begin,
+                    ))
+                    .or_insert(NewServerStateSummary {
+                        year,
+                        begin: *period_begin,
+                        end: *period_end,
+                        instance_id: state.instance_id,
+                        flavor: state.flavor,
+                        user: state.user,
+                        seconds: 0,
+                    });
+                summary.seconds += policies
+                    .billed_seconds(&state.status, covered_begin, covered_end)
+                    .round() as i64;
+            }
+            year += 1;
+        }
+    }
+
+    for summary in summaries.values() {
+        upsert_server_state_summary_into_db(transaction, summary).await?;
+    }
+    for state in &states {
+        if state.end.is_some_and(|end| end <= cutoff) {
+            delete_server_state_from_db(transaction, state.id as u64).await?;
+        } else {
+            update_server_state_period_in_db(
+                transaction,
+                state.id as u64,
+                cutoff,
+                state.end.map(|end| end.to_utc()),
+            )
+            .await?;
+        }
+    }
+    Ok(states.len() as u32)
+}
+
+#[tracing::instrument(
+    name = "update_server_state_period_in_db",
+    skip(transaction)
+)]
+async fn update_server_state_period_in_db(
+    transaction: &mut Transaction<'_, MySql>,
+    server_state_id: u64,
+    begin: DateTime<Utc>,
+    end: Option<DateTime<Utc>>,
+) -> Result<(), UnexpectedOnlyError> {
+    if let Some(state) =
+        select_maybe_server_state_from_db(transaction, server_state_id).await?
+    {
+        invalidate_daily_usages_in_db(
+            transaction,
+            state.begin.to_utc(),
+            state.end.map(|end| end.to_utc()),
+        )
+        .await?;
+    }
+    invalidate_daily_usages_in_db(transaction, begin, end).await?;
+    let query = sqlx::query!(
+        r#"
+        UPDATE accounting_state
+        SET
+            begin = ?,
+            end = ?
+        WHERE id = ?
+        "#,
+        begin,
+        end,
+        server_state_id,
+    );
+    transaction
+        .execute(query)
+        .await
+        .context("Failed to execute update query")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeDelta, TimeZone};
+
+    use super::*;
+
+    #[test]
+    fn fiscal_year_starts_with_start_of_the_year() {
+        assert_eq!(fiscal_year(start_of_the_year(2025)), 2025);
+        assert_eq!(
+            fiscal_year(start_of_the_year(2025) - TimeDelta::seconds(1)),
+            2024
+        );
+        assert_eq!(
+            fiscal_year(Utc.with_ymd_and_hms(2025, 12, 31, 23, 0, 0).unwrap()),
+            2025
+        );
+    }
+}
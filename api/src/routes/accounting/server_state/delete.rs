@@ -9,6 +9,10 @@ use sqlx::{Executor, MySql, MySqlPool, Transaction};
 use super::ServerStateIdParam;
 use crate::{
     authorization::require_admin_user,
+    database::accounting::{
+        daily_usage::invalidate_daily_usages_in_db,
+        server_state::select_maybe_server_state_from_db,
+    },
     error::{MinimalApiError, NormalApiError},
 };
 
@@ -36,10 +40,20 @@ pub async fn server_state_delete(
 }
 
 #[tracing::instrument(name = "delete_server_state_from_db", skip(transaction))]
-async fn delete_server_state_from_db(
+pub(super) async fn delete_server_state_from_db(
     transaction: &mut Transaction<'_, MySql>,
     server_state_id: u64,
 ) -> Result<(), MinimalApiError> {
+    if let Some(state) =
+        select_maybe_server_state_from_db(transaction, server_state_id).await?
+    {
+        invalidate_daily_usages_in_db(
+            transaction,
+            state.begin.to_utc(),
+            state.end.map(|end| end.to_utc()),
+        )
+        .await?;
+    }
     let query1 = sqlx::query!(
         r#"
         DELETE IGNORE FROM accounting_serverstate
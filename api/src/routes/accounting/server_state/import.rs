@@ -2,27 +2,47 @@ use std::{collections::HashMap, hash::Hash};
 
 use actix_web::{
     HttpResponse,
-    web::{Data, ReqData},
+    web::{Data, Query, ReqData},
 };
 use anyhow::{Context, anyhow};
-use avina_wire::{accounting::ServerStateImport, user::User};
-use chrono::Utc;
+use avina_wire::{
+    accounting::{
+        ServerState, ServerStateImport, ServerStateImportDiff,
+        ServerStateImportEndedState, ServerStateImportNewState,
+        ServerStateImportParams, ServerStateImportSkippedServer,
+        UnmatchedServerResolution,
+    },
+    user::User,
+};
+use chrono::{DateTime, Utc};
 use sqlx::{Executor, FromRow, MySql, MySqlPool, Transaction};
+use uuid::Uuid;
 
 use crate::{
     authorization::require_admin_user,
-    database::accounting::server_state::{
-        NewServerState, insert_server_state_into_db,
-        select_unfinished_server_states_from_db,
+    database::accounting::{
+        daily_usage::invalidate_daily_usages_in_db,
+        server_state::{
+            NewServerState, insert_server_state_into_db,
+            select_latest_server_state_change_from_db,
+            select_server_states_by_server_from_db,
+            select_unfinished_server_states_from_db,
+        },
+        unmatched_server::{
+            NewUnmatchedServer,
+            select_maybe_unmatched_server_by_instance_from_db,
+            upsert_unmatched_server_into_db,
+        },
     },
     error::{
         NotFoundOrUnexpectedApiError, OptionApiError, UnexpectedOnlyError,
     },
     openstack::{OpenStack, ServerDetailed},
+    routes::accounting::daily_usage::backfill::aggregate_daily_usages,
 };
 
 // NOTE: the hashmap cannot contain (None, None).
-fn union_hash_zip<K, V, W>(
+pub(crate) fn union_hash_zip<K, V, W>(
     hm1: HashMap<K, V>,
     hm2: HashMap<K, W>,
 ) -> HashMap<K, (Option<V>, Option<W>)>
@@ -48,6 +68,7 @@ pub async fn server_state_import(
     user: ReqData<User>,
     db_pool: Data<MySqlPool>,
     openstack: Data<OpenStack>,
+    params: Query<ServerStateImportParams>,
     // TODO: is the NormalApiError::ValidationError used?
     // Maybe we need a AuthOrUnexpectedError type.
 ) -> Result<HttpResponse, OptionApiError> {
@@ -56,7 +77,30 @@ pub async fn server_state_import(
         .begin()
         .await
         .context("Failed to begin transaction")?;
+    let diff = import_server_states(&mut transaction, &openstack).await?;
+    if params.dry_run.unwrap_or(false) {
+        transaction
+            .rollback()
+            .await
+            .context("Failed to roll back transaction")?;
+        return Ok(HttpResponse::Ok()
+            .content_type("application/json")
+            .json(diff));
+    }
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(ServerStateImport::from(&diff)))
+}
 
+#[tracing::instrument(name = "import_server_states", skip(openstack))]
+pub async fn import_server_states(
+    transaction: &mut Transaction<'_, MySql>,
+    openstack: &OpenStack,
+) -> Result<ServerStateImportDiff, OptionApiError> {
     // TODO: should we add additional context to the error here?
     let servers = openstack
         .get_servers()
@@ -65,38 +109,96 @@ pub async fn server_state_import(
         .cloned()
         .map(|s| (s.id, s))
         .collect::<HashMap<_, _>>();
-    let states = select_unfinished_server_states_from_db(&mut transaction)
+    let states = select_unfinished_server_states_from_db(transaction)
         .await?
         .iter()
         .cloned()
         .map(|s| (s.instance_id, s))
         .collect::<HashMap<_, _>>();
+    let now = Utc::now();
+
+    // NOTE: servers that vanished since the last import, as well as servers
+    // that were created and deleted in between, are only listed by Nova
+    // when asking for deleted servers explicitly.
+    let vanished_since = states
+        .values()
+        .filter(|s| !servers.contains_key(&s.instance_id))
+        .map(|s| s.begin.to_utc())
+        .min();
+    let latest_change =
+        select_latest_server_state_change_from_db(transaction).await?;
+    let deleted_servers =
+        match [vanished_since, latest_change].into_iter().flatten().min() {
+            Some(since) => openstack
+                .get_deleted_servers(since)
+                .await?
+                .into_iter()
+                .map(|s| (s.id, s))
+                .collect::<HashMap<_, _>>(),
+            None => HashMap::new(),
+        };
 
     let servers_and_states = union_hash_zip(servers, states);
 
-    let mut new_state_count = 0;
-    let mut end_state_count = 0;
+    let mut diff = ServerStateImportDiff {
+        new_states: vec![],
+        ended_states: vec![],
+        skipped_servers: vec![],
+    };
 
     for server_and_state in servers_and_states.values() {
         match server_and_state {
             (Some(server), Some(state)) => {
-                if server.status != state.status {
-                    end_server_state_in_db(&mut transaction, state.id as u64)
+                // NOTE: a resized, renamed or moved server has to be billed
+                // differently from now on, just like a status change.
+                let changes =
+                    changed_attributes(transaction, server, state).await?;
+                if !changes.is_empty() {
+                    // NOTE: Nova does not tell when exactly the change
+                    // happened, but it was the latest update of the server.
+                    let changed_at =
+                        server.updated.min(now).max(state.begin.to_utc());
+                    end_server_state(transaction, state, changed_at, &mut diff)
                         .await?;
-                    end_state_count += 1;
-                    new_state_count +=
-                        create_server_state_in_db(&mut transaction, server)
-                            .await?;
+                    create_server_state(
+                        transaction,
+                        server,
+                        changed_at,
+                        None,
+                        Some(state.status.clone()),
+                        Some(changes.join(", ")),
+                        &mut diff,
+                    )
+                    .await?;
                 }
             }
             (Some(server), None) => {
-                new_state_count +=
-                    create_server_state_in_db(&mut transaction, server).await?;
+                let begin = first_unbilled_time_of_server(
+                    transaction,
+                    server.id,
+                    server.launched_at.unwrap_or(server.created),
+                )
+                .await?
+                .min(now);
+                create_server_state(
+                    transaction,
+                    server,
+                    begin,
+                    None,
+                    None,
+                    None,
+                    &mut diff,
+                )
+                .await?;
             }
             (None, Some(state)) => {
-                end_server_state_in_db(&mut transaction, state.id as u64)
-                    .await?;
-                end_state_count += 1;
+                let end = deleted_servers
+                    .get(&state.instance_id)
+                    .map(|s| s.terminated_at.unwrap_or(s.updated))
+                    .unwrap_or(now)
+                    .min(now)
+                    .max(state.begin.to_utc());
+                end_server_state(transaction, state, end, &mut diff).await?;
             }
             (None, None) => {
                 return Err(anyhow!(
@@ -107,22 +209,142 @@ pub async fn server_state_import(
         }
     }
 
-    transaction
-        .commit()
-        .await
-        .context("Failed to commit transaction")?;
-    Ok(HttpResponse::Ok().content_type("application/json").json(
-        ServerStateImport {
-            new_state_count,
-            end_state_count,
-        },
-    ))
+    for deleted_server in deleted_servers.values() {
+        if servers_and_states.contains_key(&deleted_server.id) {
+            continue;
+        }
+        // NOTE: servers that were never launched did not consume anything.
+        let Some(launched_at) = deleted_server.launched_at else {
+            continue;
+        };
+        if !select_server_states_by_server_from_db(
+            transaction,
+            deleted_server.id,
+            false,
+        )
+        .await?
+        .is_empty()
+        {
+            continue;
+        }
+        // NOTE: Nova only reports the server as DELETED now, but it was
+        // running between its launch and its termination.
+        let server = ServerDetailed {
+            status: "ACTIVE".to_string(),
+            ..deleted_server.clone()
+        };
+        let end = server.terminated_at.unwrap_or(server.updated).min(now);
+        create_server_state(
+            transaction,
+            &server,
+            launched_at.min(end),
+            Some(end),
+            None,
+            None,
+            &mut diff,
+        )
+        .await?;
+    }
+
+    // NOTE: the days before the import are closed now, unless states are
+    // changed later on, which drops their aggregates again.
+    aggregate_daily_usages(transaction, now).await?;
+
+    Ok(diff)
+}
+
+/// Servers are billed from their launch, but never before the end of their
+/// last closed state, e.g. when the state after a resize to a flavor that was
+/// not imported yet could not be created right away.
+pub(crate) async fn first_unbilled_time_of_server(
+    transaction: &mut Transaction<'_, MySql>,
+    server_id: Uuid,
+    launched_at: DateTime<Utc>,
+) -> Result<DateTime<Utc>, UnexpectedOnlyError> {
+    let last_end =
+        select_server_states_by_server_from_db(transaction, server_id, false)
+            .await?
+            .iter()
+            .filter_map(|state| state.end)
+            .map(|end| end.to_utc())
+            .max();
+    Ok(last_end.map_or(launched_at, |end| end.max(launched_at)))
+}
+
+/// Returns which of the billed attributes of the server differ from its
+/// current state.
+async fn changed_attributes(
+    transaction: &mut Transaction<'_, MySql>,
+    server: &ServerDetailed,
+    state: &ServerState,
+) -> Result<Vec<&'static str>, OptionApiError> {
+    let mut changes = vec![];
+    if server.status != state.status {
+        changes.push("status");
+    }
+    let (flavor_id, user_id) =
+        select_flavor_and_user_ids_of_server(transaction, server).await?;
+    if flavor_id != Some(state.flavor as u64) {
+        changes.push("flavor");
+    }
+    if server.name != state.instance_name {
+        changes.push("name");
+    }
+    if user_id != Some(state.user as u64) {
+        changes.push("user");
+    }
+    Ok(changes)
+}
+
+async fn end_server_state(
+    transaction: &mut Transaction<'_, MySql>,
+    state: &ServerState,
+    end: DateTime<Utc>,
+    diff: &mut ServerStateImportDiff,
+) -> Result<(), OptionApiError> {
+    end_server_state_in_db(transaction, state.id as u64, end).await?;
+    diff.ended_states.push(ServerStateImportEndedState {
+        id: state.id,
+        instance_id: state.instance_id,
+        instance_name: state.instance_name.clone(),
+        status: state.status.clone(),
+    });
+    Ok(())
+}
+
+async fn create_server_state(
+    transaction: &mut Transaction<'_, MySql>,
+    server: &ServerDetailed,
+    begin: DateTime<Utc>,
+    end: Option<DateTime<Utc>>,
+    old_status: Option<String>,
+    changes: Option<String>,
+    diff: &mut ServerStateImportDiff,
+) -> Result<(), OptionApiError> {
+    match create_server_state_in_db(transaction, server, begin, end).await? {
+        None => diff.new_states.push(ServerStateImportNewState {
+            instance_id: server.id,
+            instance_name: server.name.clone(),
+            old_status,
+            new_status: server.status.clone(),
+            changes,
+        }),
+        Some(reason) => {
+            diff.skipped_servers.push(ServerStateImportSkippedServer {
+                instance_id: server.id,
+                instance_name: server.name.clone(),
+                reason,
+            })
+        }
+    }
+    Ok(())
 }
 
 #[tracing::instrument(name = "end_server_state_in_db", skip(transaction))]
 pub async fn end_server_state_in_db(
     transaction: &mut Transaction<'_, MySql>,
     server_state_id: u64,
+    end: DateTime<Utc>,
 ) -> Result<(), NotFoundOrUnexpectedApiError> {
     let query = sqlx::query!(
         r#"
@@ -131,48 +353,96 @@ pub async fn end_server_state_in_db(
             end = ?
         WHERE id = ?
         "#,
-        Utc::now(),
+        end,
         server_state_id,
     );
     transaction
         .execute(query)
         .await
         .context("Failed to execute update first query")?;
+    // NOTE: the state was aggregated as running until the end of the last
+    // aggregated day.
+    invalidate_daily_usages_in_db(transaction, end, None).await?;
     Ok(())
 }
 
-#[tracing::instrument(name = "create_server_state_in_db", skip(transaction))]
-pub async fn create_server_state_in_db(
+/// Looks up the flavor and user of the server by their OpenStack IDs and
+/// falls back to what an unknown server has been mapped to.
+async fn select_flavor_and_user_ids_of_server(
     transaction: &mut Transaction<'_, MySql>,
     server: &ServerDetailed,
-) -> Result<u32, OptionApiError> {
-    let Some(flavor_id) = select_maybe_flavor_id_by_openstack_id_from_db(
+) -> Result<(Option<u64>, Option<u64>), OptionApiError> {
+    let flavor_id = select_maybe_flavor_id_by_openstack_id_from_db(
         transaction,
         server.flavor.id.clone(),
     )
-    .await?
-    else {
-        tracing::warn!(
-            "Flavor {} not found, skipping server state creation.",
-            server.flavor.id.clone()
-        );
-        return Ok(0);
-    };
-    let Some(user_id) = select_maybe_user_id_by_openstack_id_from_db(
+    .await?;
+    let user_id = select_maybe_user_id_by_openstack_id_from_db(
         transaction,
         server.tenant_id.clone(),
     )
+    .await?;
+    if flavor_id.is_some() && user_id.is_some() {
+        return Ok((flavor_id, user_id));
+    }
+    let mapping = select_maybe_unmatched_server_by_instance_from_db(
+        transaction,
+        server.id,
+    )
     .await?
-    else {
-        tracing::warn!(
-            "User {} not found, skipping server state creation.",
-            server.tenant_id.clone()
-        );
-        return Ok(0);
+    .filter(|u| u.resolution == Some(UnmatchedServerResolution::Mapped));
+    Ok(match mapping {
+        Some(mapping) => (
+            flavor_id.or(mapping.flavor.map(|f| f as u64)),
+            user_id.or(mapping.user.map(|u| u as u64)),
+        ),
+        None => (flavor_id, user_id),
+    })
+}
+
+/// Returns why no state was created for the server, if it had to be skipped.
+/// Such servers are recorded as unmatched servers.
+#[tracing::instrument(name = "create_server_state_in_db", skip(transaction))]
+pub async fn create_server_state_in_db(
+    transaction: &mut Transaction<'_, MySql>,
+    server: &ServerDetailed,
+    begin: DateTime<Utc>,
+    end: Option<DateTime<Utc>>,
+) -> Result<Option<String>, OptionApiError> {
+    let (flavor_id, user_id) =
+        select_flavor_and_user_ids_of_server(transaction, server).await?;
+    let (Some(flavor_id), Some(user_id)) = (flavor_id, user_id) else {
+        let mut unknown = vec![];
+        if flavor_id.is_none() {
+            unknown.push(format!("flavor {}", server.flavor.id));
+        }
+        if user_id.is_none() {
+            unknown.push(format!("user {}", server.tenant_id));
+        }
+        let reason = format!("Unknown {}", unknown.join(" and "));
+        tracing::warn!("{reason}, skipping server state creation.");
+        // NOTE: keep the server around, so that its usage can still be
+        // billed once it has been resolved.
+        upsert_unmatched_server_into_db(
+            transaction,
+            &NewUnmatchedServer {
+                instance_id: server.id,
+                instance_name: server.name.clone(),
+                status: server.status.clone(),
+                flavor_openstack_id: server.flavor.id.clone(),
+                user_openstack_id: server.tenant_id.clone(),
+                reason: reason.clone(),
+                data: serde_json::to_string(server)
+                    .context("Failed to serialize server")?,
+                seen: Utc::now(),
+            },
+        )
+        .await?;
+        return Ok(Some(reason));
     };
     let server_state = NewServerState {
-        begin: Utc::now(),
-        end: None,
+        begin,
+        end,
         instance_id: server.id,
         instance_name: server.name.clone(),
         flavor: flavor_id as u32,
@@ -180,7 +450,7 @@ pub async fn create_server_state_in_db(
         user: user_id as u32,
     };
     let _ = insert_server_state_into_db(transaction, &server_state).await?;
-    Ok(1)
+    Ok(None)
 }
 
 #[tracing::instrument(
@@ -14,8 +14,10 @@ mod modify;
 use modify::server_state_modify;
 mod delete;
 use delete::server_state_delete;
-mod import;
+pub(crate) mod import;
 use import::server_state_import;
+pub(crate) mod compact;
+use compact::server_state_compact;
 
 pub fn server_states_scope() -> Scope {
     scope("/serverstates")
@@ -26,6 +28,7 @@ pub fn server_states_scope() -> Scope {
         .route("/{server_state_id}/", patch().to(server_state_modify))
         .route("/{server_state_id}/", delete().to(server_state_delete))
         .route("/import/", get().to(server_state_import))
+        .route("/compact/", post().to(server_state_compact))
 }
 
 // TODO: wouldn't a general IdParam be better?
@@ -12,7 +12,10 @@ use sqlx::{Executor, MySql, MySqlPool, Transaction};
 use super::ServerStateIdParam;
 use crate::{
     authorization::require_admin_user,
-    database::accounting::server_state::select_server_state_from_db,
+    database::accounting::{
+        daily_usage::invalidate_daily_usages_in_db,
+        server_state::select_server_state_from_db,
+    },
     error::{NotFoundOrUnexpectedApiError, OptionApiError},
 };
 
@@ -102,6 +105,14 @@ pub async fn update_server_state_in_db(
         .execute(query2)
         .await
         .context("Failed to execute update second query")?;
+    for (begin, end) in [(row.begin, row.end), (begin, end)] {
+        invalidate_daily_usages_in_db(
+            transaction,
+            begin.to_utc(),
+            end.map(|end| end.to_utc()),
+        )
+        .await?;
+    }
     let price = ServerState {
         id: data.id,
         begin,
@@ -0,0 +1,38 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Query, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{accounting::UnmatchedServerListParams, user::User};
+use sqlx::MySqlPool;
+
+use crate::{
+    authorization::require_admin_user,
+    database::accounting::unmatched_server::select_unmatched_servers_from_db,
+    error::OptionApiError,
+};
+
+#[tracing::instrument(name = "unmatched_server_list")]
+pub async fn unmatched_server_list(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    params: Query<UnmatchedServerListParams>,
+) -> Result<HttpResponse, OptionApiError> {
+    require_admin_user(&user)?;
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let unmatched_servers = select_unmatched_servers_from_db(
+        &mut transaction,
+        params.all.unwrap_or(false),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(unmatched_servers))
+}
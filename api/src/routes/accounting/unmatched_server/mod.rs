@@ -0,0 +1,24 @@
+use actix_web::{
+    Scope,
+    web::{get, post, scope},
+};
+use serde::Deserialize;
+
+mod list;
+use list::unmatched_server_list;
+mod resolve;
+use resolve::unmatched_server_resolve;
+
+pub fn unmatched_servers_scope() -> Scope {
+    scope("/unmatchedservers")
+        .route("/", get().to(unmatched_server_list))
+        .route(
+            "/{unmatched_server_id}/resolve/",
+            post().to(unmatched_server_resolve),
+        )
+}
+
+#[derive(Deserialize, Debug)]
+struct UnmatchedServerIdParam {
+    unmatched_server_id: u32,
+}
@@ -0,0 +1,185 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, Path, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{
+    accounting::{
+        UnmatchedServer, UnmatchedServerResolution, UnmatchedServerResolveData,
+    },
+    user::User,
+};
+use chrono::Utc;
+use sqlx::{MySql, MySqlPool, Transaction};
+
+use super::UnmatchedServerIdParam;
+use crate::{
+    authorization::require_admin_user,
+    database::{
+        accounting::{
+            server_state::{
+                NewServerState, insert_server_state_into_db,
+                select_server_states_by_server_from_db,
+            },
+            unmatched_server::{
+                resolve_unmatched_server_in_db, select_unmatched_server_from_db,
+            },
+        },
+        resources::flavor::select_maybe_flavor_from_db,
+        user::user::select_maybe_user_from_db,
+    },
+    error::OptionApiError,
+    openstack::ServerDetailed,
+    routes::accounting::server_state::import::{
+        first_unbilled_time_of_server,
+        select_maybe_flavor_id_by_openstack_id_from_db,
+        select_maybe_user_id_by_openstack_id_from_db,
+    },
+};
+
+#[tracing::instrument(name = "unmatched_server_resolve")]
+pub async fn unmatched_server_resolve(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    params: Path<UnmatchedServerIdParam>,
+    data: Json<UnmatchedServerResolveData>,
+) -> Result<HttpResponse, OptionApiError> {
+    require_admin_user(&user)?;
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let unmatched_server = select_unmatched_server_from_db(
+        &mut transaction,
+        params.unmatched_server_id as u64,
+    )
+    .await?;
+    if unmatched_server.resolution.is_some() {
+        return Err(OptionApiError::ValidationError(
+            "Unmatched server has already been resolved".to_string(),
+        ));
+    }
+    if data.ignore {
+        if data.flavor.is_some() || data.user.is_some() {
+            return Err(OptionApiError::ValidationError(
+                "Cannot map and ignore a server at the same time".to_string(),
+            ));
+        }
+        resolve_unmatched_server_in_db(
+            &mut transaction,
+            unmatched_server.id as u64,
+            UnmatchedServerResolution::Ignored,
+            None,
+            None,
+        )
+        .await?;
+    } else {
+        map_unmatched_server(&mut transaction, &unmatched_server, &data)
+            .await?;
+    }
+    let unmatched_server = select_unmatched_server_from_db(
+        &mut transaction,
+        params.unmatched_server_id as u64,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(unmatched_server))
+}
+
+/// Maps the server to a flavor and user and backfills its state from its
+/// launch, but not before the end of its last state, further states are
+/// created by the import.
+async fn map_unmatched_server(
+    transaction: &mut Transaction<'_, MySql>,
+    unmatched_server: &UnmatchedServer,
+    data: &UnmatchedServerResolveData,
+) -> Result<(), OptionApiError> {
+    let flavor = match data.flavor {
+        Some(flavor) => select_maybe_flavor_from_db(transaction, flavor as u64)
+            .await?
+            .map(|f| f.id),
+        None => select_maybe_flavor_id_by_openstack_id_from_db(
+            transaction,
+            unmatched_server.flavor_openstack_id.clone(),
+        )
+        .await?
+        .map(|f| f as u32),
+    };
+    let Some(flavor) = flavor else {
+        return Err(OptionApiError::ValidationError(
+            "Flavor of the server is unknown, an existing one has to be given"
+                .to_string(),
+        ));
+    };
+    let user = match data.user {
+        Some(user) => select_maybe_user_from_db(transaction, user as u64)
+            .await?
+            .map(|u| u.id),
+        None => select_maybe_user_id_by_openstack_id_from_db(
+            transaction,
+            unmatched_server.user_openstack_id.clone(),
+        )
+        .await?
+        .map(|u| u as u32),
+    };
+    let Some(user) = user else {
+        return Err(OptionApiError::ValidationError(
+            "User of the server is unknown, an existing one has to be given"
+                .to_string(),
+        ));
+    };
+    if select_server_states_by_server_from_db(
+        transaction,
+        unmatched_server.instance_id,
+        false,
+    )
+    .await?
+    .iter()
+    .any(|s| s.end.is_none())
+    {
+        return Err(OptionApiError::ValidationError(
+            "Server already has an unfinished state".to_string(),
+        ));
+    }
+    // NOTE: bill the server from its launch like the import does, falling
+    // back to when it was first seen if the stored server data is unusable.
+    let launched_at =
+        serde_json::from_str::<ServerDetailed>(&unmatched_server.data)
+            .map_or(unmatched_server.first_seen.to_utc(), |server| {
+                server.launched_at.unwrap_or(server.created)
+            });
+    let begin = first_unbilled_time_of_server(
+        transaction,
+        unmatched_server.instance_id,
+        launched_at,
+    )
+    .await?
+    .min(Utc::now());
+    insert_server_state_into_db(
+        transaction,
+        &NewServerState {
+            begin,
+            end: None,
+            instance_id: unmatched_server.instance_id,
+            instance_name: unmatched_server.instance_name.clone(),
+            flavor,
+            status: unmatched_server.status.clone(),
+            user,
+        },
+    )
+    .await?;
+    resolve_unmatched_server_in_db(
+        transaction,
+        unmatched_server.id as u64,
+        UnmatchedServerResolution::Mapped,
+        Some(flavor),
+        Some(user),
+    )
+    .await?;
+    Ok(())
+}
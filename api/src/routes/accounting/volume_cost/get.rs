@@ -0,0 +1,90 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Query, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{accounting::VolumeCostParams, user::User};
+use chrono::{Datelike, TimeZone, Utc};
+use sqlx::MySqlPool;
+
+use crate::{
+    authorization::{
+        require_admin_user, require_master_user_or_return_not_found,
+        require_user_or_project_master_or_not_found,
+    },
+    database::{
+        accounting::volume_state::select_volume_states_by_volume_from_db,
+        user::user::select_user_from_db,
+    },
+    error::OptionApiError,
+    routes::accounting::server_cost::volume::{
+        VolumeCostScope, calculate_volume_cost,
+    },
+};
+
+#[tracing::instrument(name = "volume_cost")]
+pub async fn volume_cost(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    params: Query<VolumeCostParams>,
+) -> Result<HttpResponse, OptionApiError> {
+    let end = params.end.unwrap_or(Utc::now().fixed_offset());
+    let begin = params.begin.unwrap_or(
+        Utc.with_ymd_and_hms(Utc::now().year(), 1, 1, 1, 0, 0)
+            .unwrap()
+            .fixed_offset(),
+    );
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let scope = if params.all.unwrap_or(false) {
+        require_admin_user(&user)?;
+        VolumeCostScope::All
+    } else if let Some(project_id) = params.project {
+        require_master_user_or_return_not_found(&user, project_id)?;
+        VolumeCostScope::Project(project_id)
+    } else if let Some(user_id) = params.user {
+        let user_queried =
+            select_user_from_db(&mut transaction, user_id as u64).await?;
+        require_user_or_project_master_or_not_found(
+            &user,
+            user_id,
+            user_queried.project,
+        )?;
+        VolumeCostScope::User(user_id)
+    } else if let Some(volume_id) = params.volume {
+        let volume_states =
+            select_volume_states_by_volume_from_db(&mut transaction, volume_id)
+                .await?;
+        let Some(volume_state) = volume_states.first() else {
+            return Err(OptionApiError::NotFoundError);
+        };
+        let volume_state_user =
+            select_user_from_db(&mut transaction, volume_state.user as u64)
+                .await?;
+        require_user_or_project_master_or_not_found(
+            &user,
+            volume_state_user.id,
+            volume_state_user.project,
+        )?;
+        VolumeCostScope::Volume(volume_id)
+    } else {
+        VolumeCostScope::User(user.id)
+    };
+    let cost = calculate_volume_cost(
+        &mut transaction,
+        scope,
+        begin.into(),
+        end.into(),
+        params.detail,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(cost))
+}
@@ -0,0 +1,11 @@
+use actix_web::{
+    Scope,
+    web::{get, scope},
+};
+
+mod get;
+use get::volume_cost;
+
+pub fn volume_cost_scope() -> Scope {
+    scope("/volumecost").route("/", get().to(volume_cost))
+}
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use actix_web::{
+    HttpResponse,
+    web::{Data, Query, ReqData},
+};
+use anyhow::{Context, anyhow};
+use avina_wire::{
+    accounting::{VolumeState, VolumeStateImport, VolumeStateImportParams},
+    user::User,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{MySql, MySqlPool, Transaction};
+
+use crate::{
+    authorization::require_admin_user,
+    database::accounting::volume_state::{
+        NewVolumeState, end_volume_state_in_db, insert_volume_state_into_db,
+        select_unfinished_volume_states_from_db,
+    },
+    error::OptionApiError,
+    openstack::{OpenStack, VolumeDetailed},
+    routes::accounting::server_state::import::{
+        select_maybe_user_id_by_openstack_id_from_db, union_hash_zip,
+    },
+};
+
+#[tracing::instrument(name = "volume_state_import", skip(openstack))]
+pub async fn volume_state_import(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    openstack: Data<OpenStack>,
+    params: Query<VolumeStateImportParams>,
+) -> Result<HttpResponse, OptionApiError> {
+    require_admin_user(&user)?;
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let import = import_volume_states(&mut transaction, &openstack).await?;
+    if params.dry_run.unwrap_or(false) {
+        transaction
+            .rollback()
+            .await
+            .context("Failed to roll back transaction")?;
+    } else {
+        transaction
+            .commit()
+            .await
+            .context("Failed to commit transaction")?;
+    }
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(import))
+}
+
+#[tracing::instrument(name = "import_volume_states", skip(openstack))]
+pub async fn import_volume_states(
+    transaction: &mut Transaction<'_, MySql>,
+    openstack: &OpenStack,
+) -> Result<VolumeStateImport, OptionApiError> {
+    let volumes = openstack
+        .get_volumes()
+        .await?
+        .into_iter()
+        .map(|v| (v.id, v))
+        .collect::<HashMap<_, _>>();
+    let states = select_unfinished_volume_states_from_db(transaction)
+        .await?
+        .into_iter()
+        .map(|s| (s.volume_id, s))
+        .collect::<HashMap<_, _>>();
+    let now = Utc::now();
+
+    let mut import = VolumeStateImport {
+        new_state_count: 0,
+        end_state_count: 0,
+        skipped_volume_count: 0,
+    };
+
+    for volume_and_state in union_hash_zip(volumes, states).values() {
+        match volume_and_state {
+            (Some(volume), Some(state)) => {
+                let user_id = select_maybe_user_id_by_openstack_id_from_db(
+                    transaction,
+                    volume.tenant_id.clone(),
+                )
+                .await?;
+                if !volume_changed(volume, state, user_id) {
+                    continue;
+                }
+                // NOTE: Cinder does not tell when exactly the change
+                // happened, but it was the latest update of the volume.
+                let changed_at = volume
+                    .updated_at
+                    .unwrap_or(now)
+                    .min(now)
+                    .max(state.begin.to_utc());
+                end_volume_state_in_db(
+                    transaction,
+                    state.id as u64,
+                    changed_at,
+                )
+                .await?;
+                import.end_state_count += 1;
+                create_volume_state(
+                    transaction,
+                    volume,
+                    user_id,
+                    changed_at,
+                    &mut import,
+                )
+                .await?;
+            }
+            (Some(volume), None) => {
+                let user_id = select_maybe_user_id_by_openstack_id_from_db(
+                    transaction,
+                    volume.tenant_id.clone(),
+                )
+                .await?;
+                create_volume_state(
+                    transaction,
+                    volume,
+                    user_id,
+                    volume.created_at.min(now),
+                    &mut import,
+                )
+                .await?;
+            }
+            (None, Some(state)) => {
+                // NOTE: Cinder does not list deleted volumes, so they are
+                // accounted until they vanished.
+                end_volume_state_in_db(transaction, state.id as u64, now)
+                    .await?;
+                import.end_state_count += 1;
+            }
+            (None, None) => {
+                return Err(anyhow!(
+                    "Volume state hash map contains invalid none-none pair."
+                )
+                .into());
+            }
+        }
+    }
+
+    Ok(import)
+}
+
+/// Returns whether any of the billed attributes of the volume differ from
+/// its current state.
+fn volume_changed(
+    volume: &VolumeDetailed,
+    state: &VolumeState,
+    user_id: Option<u64>,
+) -> bool {
+    volume.status != state.status
+        || volume.size != state.size
+        || volume.volume_type.clone().unwrap_or_default() != state.volume_type
+        || volume.name.clone().unwrap_or_default() != state.volume_name
+        || user_id != Some(state.user as u64)
+}
+
+async fn create_volume_state(
+    transaction: &mut Transaction<'_, MySql>,
+    volume: &VolumeDetailed,
+    user_id: Option<u64>,
+    begin: DateTime<Utc>,
+    import: &mut VolumeStateImport,
+) -> Result<(), OptionApiError> {
+    let Some(user_id) = user_id else {
+        tracing::warn!(
+            "Unknown user {} of volume {}, skipping volume state creation.",
+            volume.tenant_id,
+            volume.id
+        );
+        import.skipped_volume_count += 1;
+        return Ok(());
+    };
+    let volume_state = NewVolumeState {
+        begin,
+        end: None,
+        volume_id: volume.id,
+        volume_name: volume.name.clone().unwrap_or_default(),
+        size: volume.size,
+        volume_type: volume.volume_type.clone().unwrap_or_default(),
+        status: volume.status.clone(),
+        user: user_id as u32,
+    };
+    let _ = insert_volume_state_into_db(transaction, &volume_state).await?;
+    import.new_state_count += 1;
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Query, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{accounting::VolumeStateListParams, user::User};
+use sqlx::MySqlPool;
+
+use crate::{
+    authorization::{
+        require_admin_user, require_master_user_or_return_not_found,
+        require_user_or_project_master_or_not_found,
+    },
+    database::{
+        accounting::volume_state::{
+            select_all_volume_states_from_db,
+            select_volume_states_by_project_from_db,
+            select_volume_states_by_user_from_db,
+            select_volume_states_by_volume_from_db,
+        },
+        user::user::select_user_from_db,
+    },
+    error::OptionApiError,
+};
+
+#[tracing::instrument(name = "volume_state_list")]
+pub async fn volume_state_list(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    params: Query<VolumeStateListParams>,
+) -> Result<HttpResponse, OptionApiError> {
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let mut volume_states = if params.all.unwrap_or(false) {
+        require_admin_user(&user)?;
+        select_all_volume_states_from_db(&mut transaction).await?
+    } else if let Some(project_id) = params.project {
+        require_master_user_or_return_not_found(&user, project_id)?;
+        select_volume_states_by_project_from_db(
+            &mut transaction,
+            project_id as u64,
+        )
+        .await?
+    } else if let Some(user_id) = params.user {
+        let user1 = select_user_from_db(&mut transaction, user_id as u64)
+            .await
+            .context("Failed to select user")?;
+        require_user_or_project_master_or_not_found(
+            &user,
+            user1.id,
+            user1.project,
+        )?;
+        select_volume_states_by_user_from_db(&mut transaction, user1.id as u64)
+            .await?
+    } else if let Some(volume_id) = params.volume {
+        let volume_states =
+            select_volume_states_by_volume_from_db(&mut transaction, volume_id)
+                .await?;
+        let Some(volume_state) = volume_states.first() else {
+            return Err(OptionApiError::NotFoundError);
+        };
+        let volume_state_user =
+            select_user_from_db(&mut transaction, volume_state.user as u64)
+                .await?;
+        require_user_or_project_master_or_not_found(
+            &user,
+            volume_state_user.id,
+            volume_state_user.project,
+        )?;
+        volume_states
+    } else {
+        select_volume_states_by_user_from_db(&mut transaction, user.id as u64)
+            .await?
+    };
+    if let Some(volume_id) = params.volume {
+        volume_states.retain(|s| s.volume_id == volume_id);
+    }
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(volume_states))
+}
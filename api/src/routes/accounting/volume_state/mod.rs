@@ -0,0 +1,25 @@
+use actix_web::{
+    Scope,
+    web::{get, scope},
+};
+use serde::Deserialize;
+
+mod list;
+use list::volume_state_list;
+mod get;
+use get::volume_state_get;
+pub(crate) mod import;
+use import::volume_state_import;
+
+pub fn volume_states_scope() -> Scope {
+    scope("/volumestates")
+        .route("", get().to(volume_state_list))
+        .route("/{volume_state_id}", get().to(volume_state_get))
+        .route("/import/", get().to(volume_state_import))
+}
+
+#[derive(Deserialize, Debug)]
+struct VolumeStateIdParam {
+    #[allow(unused)]
+    volume_state_id: u32,
+}
@@ -0,0 +1,47 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Query, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{audit::AuditLogListParams, user::User};
+use sqlx::MySqlPool;
+
+use crate::{
+    authorization::{Action, Target, require},
+    database::audit::{AuditLogFilter, select_audit_log_entries_from_db},
+    error::OptionApiError,
+};
+
+#[tracing::instrument(name = "audit_log_list")]
+pub async fn audit_log_list(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    params: Query<AuditLogListParams>,
+) -> Result<HttpResponse, OptionApiError> {
+    require(
+        &user,
+        Action::ViewAuditLog,
+        Target::Global,
+        "Admin privileges required",
+    )?;
+    let filter = AuditLogFilter {
+        begin: params.begin.map(|b| b.to_utc()),
+        end: params.end.map(|e| e.to_utc()),
+        actor: params.actor,
+        resource_type: params.resource_type.clone(),
+        resource_id: params.resource_id,
+    };
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let entries =
+        select_audit_log_entries_from_db(&mut transaction, &filter).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(entries))
+}
@@ -0,0 +1,11 @@
+use actix_web::{
+    Scope,
+    web::{get, scope},
+};
+
+mod list;
+use list::audit_log_list;
+
+pub fn audit_scope() -> Scope {
+    scope("/audit").route("/", get().to(audit_log_list))
+}
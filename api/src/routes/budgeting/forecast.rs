@@ -0,0 +1,325 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Query, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{
+    budgeting::{BudgetForecast, BudgetForecastParams},
+    user::{User, UserClass},
+};
+use chrono::{DateTime, TimeDelta, Utc};
+use sqlx::{MySql, MySqlPool, Transaction};
+
+use crate::{
+    authorization::{
+        require_project_user_or_return_not_found,
+        require_user_or_project_master_or_not_found,
+    },
+    database::{
+        accounting::server_state::select_unfinished_server_states_by_user_from_db,
+        budgeting::{
+            project_budget::select_maybe_project_budget_by_project_and_year_from_db,
+            user_budget::select_maybe_user_budget_by_user_and_year_from_db,
+        },
+        user::{
+            project::select_user_class_by_project_from_db,
+            user::{
+                select_user_class_by_user_from_db, select_user_from_db,
+                select_users_by_project_from_db,
+            },
+        },
+    },
+    error::{OptionApiError, UnexpectedOnlyError},
+    routes::accounting::{
+        billing_policy::BillingPolicies,
+        server_cost::{
+            floating_ip::{FloatingIpCostScope, FloatingIpStates},
+            get::{Prices, get_flavor_price_periods},
+            total::{
+                calculate_total_cost_for_project, calculate_total_cost_for_user,
+            },
+            volume::{VolumeCostScope, VolumeStates},
+        },
+    },
+    utils::{fiscal_year, start_of_the_year},
+};
+
+const SECONDS_PER_YEAR: f64 = (365 * 24 * 60 * 60) as f64;
+
+/// Returns the cost per second of the servers of the user running at the
+/// moment, billed at the given prices and the billing policies in effect at
+/// the given time.
+async fn calculate_running_cost_rate_for_user(
+    transaction: &mut Transaction<'_, MySql>,
+    user_id: u64,
+    user_class: UserClass,
+    prices: &Prices,
+    at: DateTime<Utc>,
+) -> Result<f64, UnexpectedOnlyError> {
+    let states =
+        select_unfinished_server_states_by_user_from_db(transaction, user_id)
+            .await?;
+    let policies = BillingPolicies::load(transaction).await?;
+    Ok(states
+        .iter()
+        .filter_map(|state| {
+            prices
+                .get(&user_class)
+                .and_then(|prices| prices.get(&state.flavor_name))
+                .map(|price| price * policies.multiplier(&state.status, at))
+        })
+        .sum::<f64>()
+        / SECONDS_PER_YEAR)
+}
+
+/// Returns the cost per second of the volumes within the scope allocated at
+/// the given time.
+async fn calculate_volume_cost_rate(
+    transaction: &mut Transaction<'_, MySql>,
+    scope: VolumeCostScope,
+    at: DateTime<Utc>,
+) -> Result<f64, UnexpectedOnlyError> {
+    Ok(VolumeStates::load(transaction, scope, at, at)
+        .await?
+        .rate(at))
+}
+
+/// Returns the cost per second of the floating IPs within the scope
+/// allocated at the given time.
+async fn calculate_floating_ip_cost_rate(
+    transaction: &mut Transaction<'_, MySql>,
+    scope: FloatingIpCostScope,
+    at: DateTime<Utc>,
+) -> Result<f64, UnexpectedOnlyError> {
+    Ok(FloatingIpStates::load(transaction, scope, at, at)
+        .await?
+        .rate(at))
+}
+
+async fn current_prices(
+    transaction: &mut Transaction<'_, MySql>,
+    at: DateTime<Utc>,
+) -> Result<Prices, UnexpectedOnlyError> {
+    let year_end = start_of_the_year(fiscal_year(at) + 1);
+    Ok(get_flavor_price_periods(transaction, at, year_end.max(at))
+        .await?
+        .shift_remove(&at)
+        .unwrap_or_default())
+}
+
+/// Extends the cost up to the given time by the running cost until the end
+/// of the fiscal year, and predicts when the budget runs out, which is the
+/// given time itself when it is already exhausted.
+fn forecast(
+    cost: f64,
+    rate: f64,
+    budget: Option<u32>,
+    at: DateTime<Utc>,
+) -> BudgetForecast {
+    let year_end = start_of_the_year(fiscal_year(at) + 1);
+    let remaining_seconds = (year_end - at).num_seconds().max(0) as f64;
+    let running_cost = rate * remaining_seconds;
+    let projected_total = cost + running_cost;
+    let projected_overrun =
+        budget.map_or(0., |budget| (projected_total - budget as f64).max(0.));
+    let exhausted_at = budget.and_then(|budget| {
+        let left = budget as f64 - cost;
+        if left <= 0. {
+            Some(at)
+        } else if rate > 0. && left / rate < remaining_seconds {
+            Some(at + TimeDelta::seconds((left / rate).ceil() as i64))
+        } else {
+            None
+        }
+    });
+    BudgetForecast {
+        cost,
+        running_cost,
+        projected_total,
+        budget,
+        projected_overrun,
+        exhausted_at: exhausted_at.map(|at| at.fixed_offset()),
+    }
+}
+
+pub async fn forecast_user_budget(
+    transaction: &mut Transaction<'_, MySql>,
+    user_id: u64,
+    cost: f64,
+    budget: Option<u32>,
+    at: DateTime<Utc>,
+) -> Result<BudgetForecast, UnexpectedOnlyError> {
+    let mut rate = 0.;
+    if let Some(user_class) =
+        select_user_class_by_user_from_db(transaction, user_id).await?
+    {
+        let prices = current_prices(transaction, at).await?;
+        rate += calculate_running_cost_rate_for_user(
+            transaction,
+            user_id,
+            user_class,
+            &prices,
+            at,
+        )
+        .await?;
+    }
+    rate += calculate_volume_cost_rate(
+        transaction,
+        VolumeCostScope::User(user_id as u32),
+        at,
+    )
+    .await?;
+    rate += calculate_floating_ip_cost_rate(
+        transaction,
+        FloatingIpCostScope::User(user_id as u32),
+        at,
+    )
+    .await?;
+    Ok(forecast(cost, rate, budget, at))
+}
+
+pub async fn forecast_project_budget(
+    transaction: &mut Transaction<'_, MySql>,
+    project_id: u64,
+    cost: f64,
+    budget: Option<u32>,
+    at: DateTime<Utc>,
+) -> Result<BudgetForecast, UnexpectedOnlyError> {
+    let mut rate = 0.;
+    if let Some(user_class) =
+        select_user_class_by_project_from_db(transaction, project_id).await?
+    {
+        let prices = current_prices(transaction, at).await?;
+        for user in
+            select_users_by_project_from_db(transaction, project_id).await?
+        {
+            rate += calculate_running_cost_rate_for_user(
+                transaction,
+                user.id as u64,
+                user_class,
+                &prices,
+                at,
+            )
+            .await?;
+        }
+    }
+    rate += calculate_volume_cost_rate(
+        transaction,
+        VolumeCostScope::Project(project_id as u32),
+        at,
+    )
+    .await?;
+    rate += calculate_floating_ip_cost_rate(
+        transaction,
+        FloatingIpCostScope::Project(project_id as u32),
+        at,
+    )
+    .await?;
+    Ok(forecast(cost, rate, budget, at))
+}
+
+#[tracing::instrument(name = "budget_forecast")]
+pub async fn budget_forecast(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    params: Query<BudgetForecastParams>,
+) -> Result<HttpResponse, OptionApiError> {
+    let now = Utc::now();
+    let year = fiscal_year(now);
+    let begin = start_of_the_year(year);
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let forecast = if let Some(project_id) = params.project {
+        require_project_user_or_return_not_found(&user, project_id)?;
+        let cost = calculate_total_cost_for_project(
+            &mut transaction,
+            project_id,
+            begin,
+            now,
+        )
+        .await?;
+        let budget = select_maybe_project_budget_by_project_and_year_from_db(
+            &mut transaction,
+            project_id as u64,
+            year,
+        )
+        .await?;
+        forecast_project_budget(
+            &mut transaction,
+            project_id as u64,
+            cost.total,
+            budget.map(|budget| budget.amount),
+            now,
+        )
+        .await?
+    } else {
+        let user_id = params.user.unwrap_or(user.id);
+        let user_queried =
+            select_user_from_db(&mut transaction, user_id as u64).await?;
+        require_user_or_project_master_or_not_found(
+            &user,
+            user_id,
+            user_queried.project,
+        )?;
+        let cost = calculate_total_cost_for_user(
+            &mut transaction,
+            user_id,
+            begin,
+            now,
+        )
+        .await?;
+        let budget = select_maybe_user_budget_by_user_and_year_from_db(
+            &mut transaction,
+            user_id as u64,
+            year,
+        )
+        .await?;
+        forecast_user_budget(
+            &mut transaction,
+            user_id as u64,
+            cost.total,
+            budget.map(|budget| budget.amount),
+            now,
+        )
+        .await?
+    };
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(forecast))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forecast_predicts_when_budget_runs_out() {
+        let at = start_of_the_year(2025);
+        let rate = 1. / (24 * 60 * 60) as f64;
+        let forecast = forecast(10., rate, Some(20), at);
+        assert!((forecast.running_cost - 365.).abs() < 1e-6);
+        assert!((forecast.projected_overrun - 355.).abs() < 1e-6);
+        assert_eq!(
+            forecast.exhausted_at,
+            Some((at + TimeDelta::days(10)).fixed_offset())
+        );
+        assert_eq!(
+            super::forecast(30., 0., Some(20), at).exhausted_at,
+            Some(at.fixed_offset())
+        );
+        assert_eq!(super::forecast(10., rate, None, at).exhausted_at, None);
+    }
+
+    #[test]
+    fn forecast_before_start_of_the_year_ends_with_fiscal_year() {
+        let at = start_of_the_year(2025) - TimeDelta::minutes(30);
+        let forecast = forecast(0., 1., None, at);
+        assert_eq!(forecast.running_cost, (30 * 60) as f64);
+    }
+}
@@ -11,6 +11,8 @@ mod bulk_create;
 use bulk_create::budget_bulk_create;
 mod over_tree;
 use over_tree::budget_over_tree;
+pub(crate) mod forecast;
+use forecast::budget_forecast;
 
 pub fn budgeting_scope() -> Scope {
     scope("/budgeting")
@@ -18,4 +20,5 @@ pub fn budgeting_scope() -> Scope {
         .service(user_budgets_scope())
         .route("/budgetbulkcreate/", post().to(budget_bulk_create))
         .route("/budgetovertree/", get().to(budget_over_tree))
+        .route("/budgetforecast/", get().to(budget_forecast))
 }
@@ -39,9 +39,9 @@ use crate::{
     error::{
         NotFoundOrUnexpectedApiError, OptionApiError, UnexpectedOnlyError,
     },
-    routes::server_cost::get::{
-        calculate_server_cost_for_all_detail,
-        calculate_server_cost_for_project_detail,
+    routes::accounting::server_cost::total::{
+        calculate_total_cost_for_all_detail,
+        calculate_total_cost_for_project_detail,
     },
     utils::start_of_the_year,
 };
@@ -73,9 +73,9 @@ async fn budget_over_tree_for_user(
         year as u32,
     )
     .await?;
-    let project_cost = calculate_server_cost_for_project_detail(
+    let project_cost = calculate_total_cost_for_project_detail(
         transaction,
-        user.project as u64,
+        user.project,
         begin,
         end,
     )
@@ -174,9 +174,9 @@ async fn budget_over_tree_for_project(
     .cloned()
     .map(|b| (b.username.clone(), b))
     .collect::<HashMap<_, _>>();
-    let project_cost = calculate_server_cost_for_project_detail(
+    let project_cost = calculate_total_cost_for_project_detail(
         transaction,
-        project_id,
+        project_id as u32,
         begin,
         end,
     )
@@ -257,7 +257,7 @@ async fn budget_over_tree_for_all(
             .map(|b| (b.username.clone(), b))
             .collect::<HashMap<_, _>>();
     let all_cost =
-        calculate_server_cost_for_all_detail(transaction, begin, end).await?;
+        calculate_total_cost_for_all_detail(transaction, begin, end).await?;
     let mut tree = BudgetOverTree {
         cost: Some(all_cost.total),
         projects: HashMap::new(),
@@ -13,7 +13,7 @@ use sqlx::{Executor, MySql, MySqlPool, Transaction};
 use super::ProjectBudgetIdParam;
 use crate::{
     authorization::{
-        require_admin_user, require_master_user_or_return_not_found,
+        Action, Target, require_admin_user, require_or_return_not_found,
     },
     database::budgeting::project_budget::select_project_budget_from_db,
     error::{NotFoundOrUnexpectedApiError, OptionApiError},
@@ -49,7 +49,11 @@ pub async fn project_budget_modify(
         params.project_budget_id as u64,
     )
     .await?;
-    require_master_user_or_return_not_found(&user, project_budget.project)?;
+    require_or_return_not_found(
+        &user,
+        Action::ModifyBudget,
+        Target::Project(project_budget.project),
+    )?;
 
     let year = Utc::now().year();
     if project_budget.year < year as u32 && !data.force {
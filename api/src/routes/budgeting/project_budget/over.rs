@@ -2,7 +2,7 @@ use actix_web::{
     HttpResponse,
     web::{Data, Query, ReqData},
 };
-use anyhow::{Context, anyhow};
+use anyhow::Context;
 use avina_wire::{
     budgeting::{
         ProjectBudgetOverDetail, ProjectBudgetOverParams,
@@ -24,8 +24,9 @@ use crate::{
         select_project_budgets_by_year_from_db,
     },
     error::{OptionApiError, UnexpectedOnlyError},
-    routes::accounting::server_cost::get::{
-        ServerCostForProject, calculate_server_cost_for_project,
+    routes::{
+        accounting::server_cost::total::calculate_total_cost_for_project,
+        budgeting::forecast::forecast_project_budget,
     },
     utils::start_of_the_year,
 };
@@ -54,17 +55,13 @@ pub async fn calculate_project_budget_over_for_budget_normal(
         return Ok(overs);
     }
     let begin = start_of_the_year(year);
-    let ServerCostForProject::Normal(cost) = calculate_server_cost_for_project(
+    let cost = calculate_total_cost_for_project(
         transaction,
-        budget.project as u64,
+        budget.project,
         begin,
         end,
-        None,
     )
-    .await?
-    else {
-        return Err(anyhow!("Unexpected ServerCostForProject variant.").into());
-    };
+    .await?;
     let over = ProjectBudgetOverSimple {
         budget_id: budget_id as u32,
         project_id: budget.project,
@@ -91,17 +88,21 @@ pub async fn calculate_project_budget_over_for_budget_detail(
         return Ok(overs);
     }
     let begin = start_of_the_year(year);
-    let ServerCostForProject::Normal(cost) = calculate_server_cost_for_project(
+    let cost = calculate_total_cost_for_project(
         transaction,
-        budget.project as u64,
+        budget.project,
         begin,
         end,
-        None,
     )
-    .await?
-    else {
-        return Err(anyhow!("Unexpected ServerCostForProject variant.").into());
-    };
+    .await?;
+    let forecast = forecast_project_budget(
+        transaction,
+        budget.project as u64,
+        cost.total,
+        Some(budget.amount),
+        end,
+    )
+    .await?;
     let over = ProjectBudgetOverDetail {
         budget_id: budget_id as u32,
         project_id: budget.project,
@@ -109,6 +110,9 @@ pub async fn calculate_project_budget_over_for_budget_detail(
         over: cost.total >= budget.amount as f64,
         cost: cost.total,
         budget: budget.amount,
+        projected_total: forecast.projected_total,
+        projected_overrun: forecast.projected_overrun,
+        exhausted_at: forecast.exhausted_at,
     };
     overs.push(over);
     Ok(overs)
@@ -157,17 +161,13 @@ pub async fn calculate_project_budget_over_for_project_normal(
         return Ok(overs);
     };
     let begin = start_of_the_year(year);
-    let ServerCostForProject::Normal(cost) = calculate_server_cost_for_project(
+    let cost = calculate_total_cost_for_project(
         transaction,
-        budget.project as u64,
+        budget.project,
         begin,
         end,
-        None,
     )
-    .await?
-    else {
-        return Err(anyhow!("Unexpected ServerCostForProject variant.").into());
-    };
+    .await?;
     let over = ProjectBudgetOverSimple {
         budget_id: budget.id,
         project_id: budget.project,
@@ -195,17 +195,21 @@ pub async fn calculate_project_budget_over_for_project_detail(
         return Ok(overs);
     };
     let begin = start_of_the_year(year);
-    let ServerCostForProject::Normal(cost) = calculate_server_cost_for_project(
+    let cost = calculate_total_cost_for_project(
         transaction,
-        budget.project as u64,
+        budget.project,
         begin,
         end,
-        None,
     )
-    .await?
-    else {
-        return Err(anyhow!("Unexpected ServerCostForProject variant.").into());
-    };
+    .await?;
+    let forecast = forecast_project_budget(
+        transaction,
+        budget.project as u64,
+        cost.total,
+        Some(budget.amount),
+        end,
+    )
+    .await?;
     let over = ProjectBudgetOverDetail {
         budget_id: budget.id,
         project_id: budget.project,
@@ -213,6 +217,9 @@ pub async fn calculate_project_budget_over_for_project_detail(
         over: cost.total >= budget.amount as f64,
         cost: cost.total,
         budget: budget.amount,
+        projected_total: forecast.projected_total,
+        projected_overrun: forecast.projected_overrun,
+        exhausted_at: forecast.exhausted_at,
     };
     overs.push(over);
     Ok(overs)
@@ -254,20 +261,13 @@ pub async fn calculate_project_budget_over_for_all_normal(
         select_project_budgets_by_year_from_db(transaction, year).await?;
     let begin = start_of_the_year(year);
     for budget in budgets {
-        let ServerCostForProject::Normal(cost) =
-            calculate_server_cost_for_project(
-                transaction,
-                budget.project as u64,
-                begin,
-                end,
-                None,
-            )
-            .await?
-        else {
-            return Err(
-                anyhow!("Unexpected ServerCostForProject variant.").into()
-            );
-        };
+        let cost = calculate_total_cost_for_project(
+            transaction,
+            budget.project,
+            begin,
+            end,
+        )
+        .await?;
         let over = ProjectBudgetOverSimple {
             budget_id: budget.id,
             project_id: budget.project,
@@ -289,20 +289,21 @@ pub async fn calculate_project_budget_over_for_all_detail(
         select_project_budgets_by_year_from_db(transaction, year).await?;
     let begin = start_of_the_year(year);
     for budget in budgets {
-        let ServerCostForProject::Normal(cost) =
-            calculate_server_cost_for_project(
-                transaction,
-                budget.project as u64,
-                begin,
-                end,
-                None,
-            )
-            .await?
-        else {
-            return Err(
-                anyhow!("Unexpected ServerCostForProject variant.").into()
-            );
-        };
+        let cost = calculate_total_cost_for_project(
+            transaction,
+            budget.project,
+            begin,
+            end,
+        )
+        .await?;
+        let forecast = forecast_project_budget(
+            transaction,
+            budget.project as u64,
+            cost.total,
+            Some(budget.amount),
+            end,
+        )
+        .await?;
         let over = ProjectBudgetOverDetail {
             budget_id: budget.id,
             project_id: budget.project,
@@ -310,6 +311,9 @@ pub async fn calculate_project_budget_over_for_all_detail(
             over: cost.total >= budget.amount as f64,
             cost: cost.total,
             budget: budget.amount,
+            projected_total: forecast.projected_total,
+            projected_overrun: forecast.projected_overrun,
+            exhausted_at: forecast.exhausted_at,
         };
         overs.push(over);
     }
@@ -13,7 +13,7 @@ use sqlx::{Executor, MySql, MySqlPool, Transaction};
 use super::UserBudgetIdParam;
 use crate::{
     authorization::{
-        require_admin_user, require_master_user_or_return_not_found,
+        Action, Target, require_admin_user, require_or_return_not_found,
     },
     database::{
         budgeting::{
@@ -55,7 +55,14 @@ pub async fn user_budget_modify(
         select_user_budget_from_db(&mut transaction, data.id as u64).await?;
     let user_budget_user =
         select_user_from_db(&mut transaction, user_budget.user as u64).await?;
-    require_master_user_or_return_not_found(&user, user_budget_user.project)?;
+    require_or_return_not_found(
+        &user,
+        Action::ModifyBudget,
+        Target::User {
+            id: user_budget_user.id,
+            project: user_budget_user.project,
+        },
+    )?;
 
     let year = Utc::now().year();
     if user_budget.year < year as u32 && !data.force {
@@ -2,7 +2,7 @@ use actix_web::{
     HttpResponse,
     web::{Data, Query, ReqData},
 };
-use anyhow::{Context, anyhow};
+use anyhow::Context;
 use avina_wire::{
     budgeting::{
         UserBudgetOverCombined, UserBudgetOverCombinedDetail,
@@ -33,12 +33,10 @@ use crate::{
     },
     error::{OptionApiError, UnexpectedOnlyError},
     routes::{
-        accounting::server_cost::get::{
-            ServerCostForUser, calculate_server_cost_for_user,
-        },
-        server_cost::get::{
-            ServerCostForProject, calculate_server_cost_for_project,
+        accounting::server_cost::total::{
+            calculate_total_cost_for_project, calculate_total_cost_for_user,
         },
+        budgeting::forecast::forecast_user_budget,
     },
     utils::start_of_the_year,
 };
@@ -68,17 +66,9 @@ pub async fn calculate_user_budget_over_for_budget_normal(
         return Ok(overs);
     }
     let begin = start_of_the_year(year);
-    let ServerCostForUser::Normal(cost) = calculate_server_cost_for_user(
-        transaction,
-        budget.user as u64,
-        begin,
-        end,
-        None,
-    )
-    .await?
-    else {
-        return Err(anyhow!("Unexpected ServerCostForProject variant.").into());
-    };
+    let cost =
+        calculate_total_cost_for_user(transaction, budget.user, begin, end)
+            .await?;
     let over = UserBudgetOverSimple {
         budget_id: budget_id as u32,
         user_id: budget.user,
@@ -115,29 +105,12 @@ pub async fn calculate_user_budget_over_for_budget_combined(
         return Ok(overs);
     }
     let begin = start_of_the_year(year);
-    let ServerCostForUser::Normal(cost) = calculate_server_cost_for_user(
-        transaction,
-        budget.user as u64,
-        begin,
-        end,
-        None,
-    )
-    .await?
-    else {
-        return Err(anyhow!("Unexpected ServerCostForProject variant.").into());
-    };
-    let ServerCostForProject::Normal(project_cost) =
-        calculate_server_cost_for_project(
-            transaction,
-            user.project as u64,
-            begin,
-            end,
-            None,
-        )
-        .await?
-    else {
-        return Err(anyhow!("Unexpected ServerCostForProject variant.").into());
-    };
+    let cost =
+        calculate_total_cost_for_user(transaction, budget.user, begin, end)
+            .await?;
+    let project_cost =
+        calculate_total_cost_for_project(transaction, user.project, begin, end)
+            .await?;
     let over = UserBudgetOverCombined {
         budget_id: budget_id as u32,
         user_id: budget.user,
@@ -173,17 +146,17 @@ pub async fn calculate_user_budget_over_for_budget_detail(
         return Ok(overs);
     }
     let begin = start_of_the_year(year);
-    let ServerCostForUser::Normal(cost) = calculate_server_cost_for_user(
+    let cost =
+        calculate_total_cost_for_user(transaction, budget.user, begin, end)
+            .await?;
+    let forecast = forecast_user_budget(
         transaction,
         budget.user as u64,
-        begin,
+        cost.total,
+        Some(budget.amount),
         end,
-        None,
     )
-    .await?
-    else {
-        return Err(anyhow!("Unexpected ServerCostForProject variant.").into());
-    };
+    .await?;
     let over = UserBudgetOverDetail {
         budget_id: budget_id as u32,
         user_id: budget.user,
@@ -191,6 +164,9 @@ pub async fn calculate_user_budget_over_for_budget_detail(
         over: cost.total >= budget.amount as f64,
         cost: cost.total,
         budget: budget.amount,
+        projected_total: forecast.projected_total,
+        projected_overrun: forecast.projected_overrun,
+        exhausted_at: forecast.exhausted_at,
     };
     overs.push(over);
     Ok(overs)
@@ -222,29 +198,12 @@ pub async fn calculate_user_budget_over_for_budget_combined_detail(
         return Ok(overs);
     }
     let begin = start_of_the_year(year);
-    let ServerCostForUser::Normal(cost) = calculate_server_cost_for_user(
-        transaction,
-        budget.user as u64,
-        begin,
-        end,
-        None,
-    )
-    .await?
-    else {
-        return Err(anyhow!("Unexpected ServerCostForProject variant.").into());
-    };
-    let ServerCostForProject::Normal(project_cost) =
-        calculate_server_cost_for_project(
-            transaction,
-            user.project as u64,
-            begin,
-            end,
-            None,
-        )
-        .await?
-    else {
-        return Err(anyhow!("Unexpected ServerCostForProject variant.").into());
-    };
+    let cost =
+        calculate_total_cost_for_user(transaction, budget.user, begin, end)
+            .await?;
+    let project_cost =
+        calculate_total_cost_for_project(transaction, user.project, begin, end)
+            .await?;
     let over = UserBudgetOverCombinedDetail {
         budget_id: budget_id as u32,
         user_id: budget.user,
@@ -328,17 +287,9 @@ pub async fn calculate_user_budget_over_for_user_normal(
         return Ok(overs);
     };
     let begin = start_of_the_year(year);
-    let ServerCostForUser::Normal(cost) = calculate_server_cost_for_user(
-        transaction,
-        budget.user as u64,
-        begin,
-        end,
-        None,
-    )
-    .await?
-    else {
-        return Err(anyhow!("Unexpected ServerCostForProject variant.").into());
-    };
+    let cost =
+        calculate_total_cost_for_user(transaction, budget.user, begin, end)
+            .await?;
     let over = UserBudgetOverSimple {
         budget_id: budget.id,
         user_id: budget.user,
@@ -379,29 +330,12 @@ pub async fn calculate_user_budget_over_for_user_combined(
         return Ok(overs);
     }
     let begin = start_of_the_year(year);
-    let ServerCostForUser::Normal(cost) = calculate_server_cost_for_user(
-        transaction,
-        budget.user as u64,
-        begin,
-        end,
-        None,
-    )
-    .await?
-    else {
-        return Err(anyhow!("Unexpected ServerCostForProject variant.").into());
-    };
-    let ServerCostForProject::Normal(project_cost) =
-        calculate_server_cost_for_project(
-            transaction,
-            user.project as u64,
-            begin,
-            end,
-            None,
-        )
-        .await?
-    else {
-        return Err(anyhow!("Unexpected ServerCostForProject variant.").into());
-    };
+    let cost =
+        calculate_total_cost_for_user(transaction, budget.user, begin, end)
+            .await?;
+    let project_cost =
+        calculate_total_cost_for_project(transaction, user.project, begin, end)
+            .await?;
     let over = UserBudgetOverCombined {
         budget_id: budget.id,
         user_id: budget.user,
@@ -438,17 +372,17 @@ pub async fn calculate_user_budget_over_for_user_detail(
         return Ok(overs);
     };
     let begin = start_of_the_year(year);
-    let ServerCostForUser::Normal(cost) = calculate_server_cost_for_user(
+    let cost =
+        calculate_total_cost_for_user(transaction, budget.user, begin, end)
+            .await?;
+    let forecast = forecast_user_budget(
         transaction,
         budget.user as u64,
-        begin,
+        cost.total,
+        Some(budget.amount),
         end,
-        None,
     )
-    .await?
-    else {
-        return Err(anyhow!("Unexpected ServerCostForProject variant.").into());
-    };
+    .await?;
     let over = UserBudgetOverDetail {
         budget_id: budget.id,
         user_id: budget.user,
@@ -456,6 +390,9 @@ pub async fn calculate_user_budget_over_for_user_detail(
         over: cost.total >= budget.amount as f64,
         cost: cost.total,
         budget: budget.amount,
+        projected_total: forecast.projected_total,
+        projected_overrun: forecast.projected_overrun,
+        exhausted_at: forecast.exhausted_at,
     };
     overs.push(over);
     Ok(overs)
@@ -491,29 +428,12 @@ pub async fn calculate_user_budget_over_for_user_combined_detail(
         return Ok(overs);
     }
     let begin = start_of_the_year(year);
-    let ServerCostForUser::Normal(cost) = calculate_server_cost_for_user(
-        transaction,
-        budget.user as u64,
-        begin,
-        end,
-        None,
-    )
-    .await?
-    else {
-        return Err(anyhow!("Unexpected ServerCostForProject variant.").into());
-    };
-    let ServerCostForProject::Normal(project_cost) =
-        calculate_server_cost_for_project(
-            transaction,
-            user.project as u64,
-            begin,
-            end,
-            None,
-        )
-        .await?
-    else {
-        return Err(anyhow!("Unexpected ServerCostForProject variant.").into());
-    };
+    let cost =
+        calculate_total_cost_for_user(transaction, budget.user, begin, end)
+            .await?;
+    let project_cost =
+        calculate_total_cost_for_project(transaction, user.project, begin, end)
+            .await?;
     let over = UserBudgetOverCombinedDetail {
         budget_id: budget.id,
         user_id: budget.user,
@@ -595,19 +515,9 @@ pub async fn calculate_user_budget_over_for_project_normal(
     .await?;
     let begin = start_of_the_year(year);
     for budget in budgets {
-        let ServerCostForUser::Normal(cost) = calculate_server_cost_for_user(
-            transaction,
-            budget.user as u64,
-            begin,
-            end,
-            None,
-        )
-        .await?
-        else {
-            return Err(
-                anyhow!("Unexpected ServerCostForProject variant.").into()
-            );
-        };
+        let cost =
+            calculate_total_cost_for_user(transaction, budget.user, begin, end)
+                .await?;
         let over = UserBudgetOverSimple {
             budget_id: budget.id,
             user_id: budget.user,
@@ -648,33 +558,16 @@ pub async fn calculate_user_budget_over_for_project_combined(
             )
             .await?;
         let begin = start_of_the_year(year);
-        let ServerCostForUser::Normal(cost) = calculate_server_cost_for_user(
+        let cost =
+            calculate_total_cost_for_user(transaction, budget.user, begin, end)
+                .await?;
+        let project_cost = calculate_total_cost_for_project(
             transaction,
-            budget.user as u64,
+            user.project,
             begin,
             end,
-            None,
         )
-        .await?
-        else {
-            return Err(
-                anyhow!("Unexpected ServerCostForProject variant.").into()
-            );
-        };
-        let ServerCostForProject::Normal(project_cost) =
-            calculate_server_cost_for_project(
-                transaction,
-                user.project as u64,
-                begin,
-                end,
-                None,
-            )
-            .await?
-        else {
-            return Err(
-                anyhow!("Unexpected ServerCostForProject variant.").into()
-            );
-        };
+        .await?;
         let over = UserBudgetOverCombined {
             budget_id: budget.id,
             user_id: budget.user,
@@ -710,19 +603,17 @@ pub async fn calculate_user_budget_over_for_project_detail(
     .await?;
     let begin = start_of_the_year(year);
     for budget in budgets {
-        let ServerCostForUser::Normal(cost) = calculate_server_cost_for_user(
+        let cost =
+            calculate_total_cost_for_user(transaction, budget.user, begin, end)
+                .await?;
+        let forecast = forecast_user_budget(
             transaction,
             budget.user as u64,
-            begin,
+            cost.total,
+            Some(budget.amount),
             end,
-            None,
         )
-        .await?
-        else {
-            return Err(
-                anyhow!("Unexpected ServerCostForProject variant.").into()
-            );
-        };
+        .await?;
         let over = UserBudgetOverDetail {
             budget_id: budget.id,
             user_id: budget.user,
@@ -730,6 +621,9 @@ pub async fn calculate_user_budget_over_for_project_detail(
             over: cost.total >= budget.amount as f64,
             cost: cost.total,
             budget: budget.amount,
+            projected_total: forecast.projected_total,
+            projected_overrun: forecast.projected_overrun,
+            exhausted_at: forecast.exhausted_at,
         };
         overs.push(over);
     }
@@ -765,33 +659,16 @@ pub async fn calculate_user_budget_over_for_project_combined_detail(
             )
             .await?;
         let begin = start_of_the_year(year);
-        let ServerCostForUser::Normal(cost) = calculate_server_cost_for_user(
+        let cost =
+            calculate_total_cost_for_user(transaction, budget.user, begin, end)
+                .await?;
+        let project_cost = calculate_total_cost_for_project(
             transaction,
-            budget.user as u64,
+            user.project,
             begin,
             end,
-            None,
         )
-        .await?
-        else {
-            return Err(
-                anyhow!("Unexpected ServerCostForProject variant.").into()
-            );
-        };
-        let ServerCostForProject::Normal(project_cost) =
-            calculate_server_cost_for_project(
-                transaction,
-                user.project as u64,
-                begin,
-                end,
-                None,
-            )
-            .await?
-        else {
-            return Err(
-                anyhow!("Unexpected ServerCostForProject variant.").into()
-            );
-        };
+        .await?;
         let over = UserBudgetOverCombinedDetail {
             budget_id: budget.id,
             user_id: budget.user,
@@ -869,19 +746,9 @@ pub async fn calculate_user_budget_over_for_all_normal(
         select_user_budgets_by_year_from_db(transaction, year).await?;
     let begin = start_of_the_year(year);
     for budget in budgets {
-        let ServerCostForUser::Normal(cost) = calculate_server_cost_for_user(
-            transaction,
-            budget.user as u64,
-            begin,
-            end,
-            None,
-        )
-        .await?
-        else {
-            return Err(
-                anyhow!("Unexpected ServerCostForProject variant.").into()
-            );
-        };
+        let cost =
+            calculate_total_cost_for_user(transaction, budget.user, begin, end)
+                .await?;
         let over = UserBudgetOverSimple {
             budget_id: budget.id,
             user_id: budget.user,
@@ -917,33 +784,16 @@ pub async fn calculate_user_budget_over_for_all_combined(
             )
             .await?;
         let begin = start_of_the_year(year);
-        let ServerCostForUser::Normal(cost) = calculate_server_cost_for_user(
+        let cost =
+            calculate_total_cost_for_user(transaction, budget.user, begin, end)
+                .await?;
+        let project_cost = calculate_total_cost_for_project(
             transaction,
-            budget.user as u64,
+            user.project,
             begin,
             end,
-            None,
         )
-        .await?
-        else {
-            return Err(
-                anyhow!("Unexpected ServerCostForProject variant.").into()
-            );
-        };
-        let ServerCostForProject::Normal(project_cost) =
-            calculate_server_cost_for_project(
-                transaction,
-                user.project as u64,
-                begin,
-                end,
-                None,
-            )
-            .await?
-        else {
-            return Err(
-                anyhow!("Unexpected ServerCostForProject variant.").into()
-            );
-        };
+        .await?;
         let over = UserBudgetOverCombined {
             budget_id: budget.id,
             user_id: budget.user,
@@ -974,19 +824,17 @@ pub async fn calculate_user_budget_over_for_all_detail(
         select_user_budgets_by_year_from_db(transaction, year).await?;
     let begin = start_of_the_year(year);
     for budget in budgets {
-        let ServerCostForUser::Normal(cost) = calculate_server_cost_for_user(
+        let cost =
+            calculate_total_cost_for_user(transaction, budget.user, begin, end)
+                .await?;
+        let forecast = forecast_user_budget(
             transaction,
             budget.user as u64,
-            begin,
+            cost.total,
+            Some(budget.amount),
             end,
-            None,
         )
-        .await?
-        else {
-            return Err(
-                anyhow!("Unexpected ServerCostForProject variant.").into()
-            );
-        };
+        .await?;
         let over = UserBudgetOverDetail {
             budget_id: budget.id,
             user_id: budget.user,
@@ -994,6 +842,9 @@ pub async fn calculate_user_budget_over_for_all_detail(
             over: cost.total >= budget.amount as f64,
             cost: cost.total,
             budget: budget.amount,
+            projected_total: forecast.projected_total,
+            projected_overrun: forecast.projected_overrun,
+            exhausted_at: forecast.exhausted_at,
         };
         overs.push(over);
     }
@@ -1024,33 +875,16 @@ pub async fn calculate_user_budget_over_for_all_combined_detail(
             )
             .await?;
         let begin = start_of_the_year(year);
-        let ServerCostForUser::Normal(cost) = calculate_server_cost_for_user(
+        let cost =
+            calculate_total_cost_for_user(transaction, budget.user, begin, end)
+                .await?;
+        let project_cost = calculate_total_cost_for_project(
             transaction,
-            budget.user as u64,
+            user.project,
             begin,
             end,
-            None,
         )
-        .await?
-        else {
-            return Err(
-                anyhow!("Unexpected ServerCostForProject variant.").into()
-            );
-        };
-        let ServerCostForProject::Normal(project_cost) =
-            calculate_server_cost_for_project(
-                transaction,
-                user.project as u64,
-                begin,
-                end,
-                None,
-            )
-            .await?
-        else {
-            return Err(
-                anyhow!("Unexpected ServerCostForProject variant.").into()
-            );
-        };
+        .await?;
         let over = UserBudgetOverCombinedDetail {
             budget_id: budget.id,
             user_id: budget.user,
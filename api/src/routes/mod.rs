@@ -1,19 +1,27 @@
-mod accounting;
+pub(crate) mod accounting;
+mod audit;
 mod budgeting;
 mod health_check;
 mod hello;
 mod pricing;
 mod quota;
-mod resources;
+mod readiness;
+pub(crate) mod resources;
+mod scheduler;
+mod token_cache;
 pub mod user;
 
 pub use accounting::*;
+pub use audit::*;
 pub use budgeting::*;
 pub use health_check::*;
 pub use hello::*;
 pub use pricing::*;
 pub use quota::*;
+pub use readiness::*;
 pub use resources::*;
+pub use scheduler::*;
+pub use token_cache::*;
 pub use user::*;
 
 // TODO: missing endpoints
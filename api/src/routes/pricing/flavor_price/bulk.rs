@@ -0,0 +1,164 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{
+    pricing::{FlavorPrice, FlavorPriceBulkChange, FlavorPriceBulkCreateData},
+    user::{User, UserClass},
+};
+use chrono::Utc;
+use sqlx::{MySql, MySqlPool, Transaction};
+use strum::IntoEnumIterator;
+
+use crate::{
+    authorization::require_admin_user,
+    database::{
+        pricing::flavor_price::{NewFlavorPrice, insert_flavor_price_into_db},
+        resources::{
+            flavor::select_all_flavors_from_db,
+            flavor_group::select_maybe_flavor_group_from_db,
+        },
+    },
+    error::OptionApiError,
+    routes::accounting::server_cost::get::get_flavor_price_periods,
+};
+
+#[tracing::instrument(name = "flavor_price_bulk_create")]
+pub async fn flavor_price_bulk_create(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    data: Json<FlavorPriceBulkCreateData>,
+) -> Result<HttpResponse, OptionApiError> {
+    require_admin_user(&user)?;
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let changes = plan_flavor_price_changes(&mut transaction, &data).await?;
+    let mut flavor_prices = Vec::new();
+    for change in changes {
+        let new_flavor_price = NewFlavorPrice {
+            flavor_id: change.flavor as u64,
+            user_class: change.user_class,
+            unit_price: change.new_price,
+            start_time: data.start_time.to_utc(),
+        };
+        let id =
+            insert_flavor_price_into_db(&mut transaction, &new_flavor_price)
+                .await?;
+        flavor_prices.push(FlavorPrice {
+            id: id as u32,
+            flavor: change.flavor,
+            flavor_name: change.flavor_name,
+            user_class: change.user_class,
+            unit_price: change.new_price,
+            start_time: data.start_time,
+        });
+    }
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Created()
+        .content_type("application/json")
+        .json(flavor_prices))
+}
+
+/// Resolves the selected flavors and user classes and computes their new
+/// prices from the prices in effect at the start time.
+#[tracing::instrument(name = "plan_flavor_price_changes", skip(transaction))]
+pub(super) async fn plan_flavor_price_changes(
+    transaction: &mut Transaction<'_, MySql>,
+    data: &FlavorPriceBulkCreateData,
+) -> Result<Vec<FlavorPriceBulkChange>, OptionApiError> {
+    if data.unit_price.is_some() == data.percentage.is_some() {
+        return Err(OptionApiError::ValidationError(
+            "Either unit price or percentage has to be given".to_string(),
+        ));
+    }
+    if data.start_time.to_utc() <= Utc::now() {
+        return Err(OptionApiError::ValidationError(
+            "Start time has to be in the future".to_string(),
+        ));
+    }
+
+    let flavors = select_all_flavors_from_db(transaction).await?;
+    let mut selected_flavors = Vec::new();
+    if let Some(flavor_group_id) = data.flavor_group {
+        if select_maybe_flavor_group_from_db(
+            transaction,
+            flavor_group_id as u64,
+        )
+        .await?
+        .is_none()
+        {
+            return Err(OptionApiError::NotFoundError);
+        }
+        selected_flavors.extend(
+            flavors
+                .iter()
+                .filter(|flavor| flavor.group == Some(flavor_group_id)),
+        );
+    }
+    for flavor_id in &data.flavors {
+        let Some(flavor) = flavors.iter().find(|f| f.id == *flavor_id) else {
+            return Err(OptionApiError::NotFoundError);
+        };
+        selected_flavors.push(flavor);
+    }
+    selected_flavors.sort_by_key(|flavor| flavor.id);
+    selected_flavors.dedup_by_key(|flavor| flavor.id);
+    if selected_flavors.is_empty() {
+        return Err(OptionApiError::ValidationError(
+            "No flavors selected".to_string(),
+        ));
+    }
+    let user_classes = if data.user_classes.is_empty() {
+        UserClass::iter().collect::<Vec<_>>()
+    } else {
+        let mut user_classes = data.user_classes.clone();
+        user_classes.sort_by_key(|user_class| *user_class as u32);
+        user_classes.dedup();
+        user_classes
+    };
+
+    let start_time = data.start_time.to_utc();
+    let price_periods =
+        get_flavor_price_periods(transaction, start_time, start_time).await?;
+    let prices = price_periods
+        .get(&start_time)
+        .context("Failed to find price period for start time")?;
+
+    let mut changes = Vec::new();
+    for flavor in selected_flavors {
+        for user_class in &user_classes {
+            let current_price = prices
+                .get(user_class)
+                .and_then(|class_prices| class_prices.get(&flavor.name))
+                .cloned()
+                .unwrap_or(0.0);
+            let new_price = match (data.unit_price, data.percentage) {
+                (Some(unit_price), _) => unit_price,
+                // NOTE: relative changes are rounded to cents
+                (_, Some(percentage)) => {
+                    (current_price * (100. + percentage)).round() / 100.
+                }
+                (None, None) => unreachable!(),
+            };
+            if new_price < 0. {
+                return Err(OptionApiError::ValidationError(
+                    "Unit price must not be negative".to_string(),
+                ));
+            }
+            changes.push(FlavorPriceBulkChange {
+                flavor: flavor.id,
+                flavor_name: flavor.name.clone(),
+                user_class: *user_class,
+                current_price,
+                new_price,
+            });
+        }
+    }
+    Ok(changes)
+}
@@ -0,0 +1,75 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Query, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{
+    pricing::{FlavorPriceEffective, FlavorPriceEffectiveParams},
+    user::{Project, User},
+};
+use chrono::Utc;
+use sqlx::MySqlPool;
+
+use crate::{
+    database::resources::flavor::select_all_flavors_from_db,
+    error::OptionApiError,
+    routes::accounting::server_cost::get::get_flavor_price_periods,
+};
+
+#[tracing::instrument(name = "flavor_price_effective")]
+pub async fn flavor_price_effective(
+    user: ReqData<User>,
+    project: ReqData<Project>,
+    db_pool: Data<MySqlPool>,
+    params: Query<FlavorPriceEffectiveParams>,
+) -> Result<HttpResponse, OptionApiError> {
+    let time = params.time.map(|t| t.to_utc()).unwrap_or(Utc::now());
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let mut flavors = select_all_flavors_from_db(&mut transaction).await?;
+    if let Some(flavor_id) = params.flavor {
+        flavors.retain(|flavor| flavor.id == flavor_id);
+        if flavors.is_empty() {
+            return Err(OptionApiError::NotFoundError);
+        }
+    }
+    // NOTE: resolving a period that begins and ends at the same point in
+    // time yields exactly the prices active at that time.
+    let price_periods =
+        get_flavor_price_periods(&mut transaction, time, time).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+
+    let prices = price_periods
+        .get(&time)
+        .context("Failed to find price period for requested time")?;
+    let mut effective_prices = Vec::new();
+    for flavor in flavors {
+        for (user_class, class_prices) in prices {
+            if params.user_class.is_some_and(|uc| uc != *user_class) {
+                continue;
+            }
+            effective_prices.push(FlavorPriceEffective {
+                flavor: flavor.id,
+                flavor_name: flavor.name.clone(),
+                user_class: *user_class,
+                unit_price: class_prices
+                    .get(&flavor.name)
+                    .cloned()
+                    .unwrap_or(0.0),
+            });
+        }
+    }
+    effective_prices.sort_by(|a, b| {
+        a.flavor_name
+            .cmp(&b.flavor_name)
+            .then((a.user_class as u32).cmp(&(b.user_class as u32)))
+    });
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(effective_prices))
+}
@@ -0,0 +1,87 @@
+use std::collections::HashSet;
+
+use actix_web::{
+    HttpResponse,
+    web::{Data, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{
+    pricing::FlavorPriceInitialize,
+    user::{User, UserClass},
+};
+use chrono::Utc;
+use sqlx::{MySql, MySqlPool, Transaction};
+use strum::IntoEnumIterator;
+
+use crate::{
+    authorization::require_admin_user,
+    configuration::AccountingSettings,
+    database::{
+        pricing::flavor_price::{
+            NewFlavorPrice, insert_flavor_price_into_db,
+            select_all_flavor_prices_from_db,
+        },
+        resources::flavor::select_all_flavors_from_db,
+    },
+    error::NormalApiError,
+};
+
+#[tracing::instrument(name = "flavor_price_initialize")]
+pub async fn flavor_price_initialize(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    accounting: Data<AccountingSettings>,
+) -> Result<HttpResponse, NormalApiError> {
+    require_admin_user(&user)?;
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let flavor_price_initialize = initialize_flavor_prices(
+        &mut transaction,
+        accounting.default_flavor_price.unwrap_or(0.),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(flavor_price_initialize))
+}
+
+/// Creates a price for every pair of flavor and user class that does not
+/// have any price yet, existing prices are left untouched.
+#[tracing::instrument(name = "initialize_flavor_prices", skip(transaction))]
+pub async fn initialize_flavor_prices(
+    transaction: &mut Transaction<'_, MySql>,
+    unit_price: f64,
+) -> Result<FlavorPriceInitialize, NormalApiError> {
+    let existing_prices = select_all_flavor_prices_from_db(transaction)
+        .await?
+        .into_iter()
+        .map(|price| (price.flavor, price.user_class))
+        .collect::<HashSet<_>>();
+    let flavors = select_all_flavors_from_db(transaction).await?;
+    let start_time = Utc::now();
+    let mut new_flavor_price_count = 0;
+    for flavor in flavors {
+        for user_class in UserClass::iter() {
+            if existing_prices.contains(&(flavor.id, user_class)) {
+                continue;
+            }
+            let new_flavor_price = NewFlavorPrice {
+                flavor_id: flavor.id as u64,
+                user_class,
+                unit_price,
+                start_time,
+            };
+            insert_flavor_price_into_db(transaction, &new_flavor_price).await?;
+            new_flavor_price_count += 1;
+        }
+    }
+    Ok(FlavorPriceInitialize {
+        new_flavor_price_count,
+    })
+}
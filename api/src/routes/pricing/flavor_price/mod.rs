@@ -14,11 +14,29 @@ mod modify;
 use modify::flavor_price_modify;
 mod delete;
 use delete::flavor_price_delete;
+mod effective;
+use effective::flavor_price_effective;
+mod timeline;
+use timeline::flavor_price_timeline;
+mod initialize;
+use initialize::flavor_price_initialize;
+mod bulk;
+use bulk::flavor_price_bulk_create;
+mod preview;
+use preview::flavor_price_bulk_preview;
 
 pub fn flavor_prices_scope() -> Scope {
     scope("/flavorprices")
         .route("/", post().to(flavor_price_create))
         .route("", get().to(flavor_price_list))
+        .route("/effective/", get().to(flavor_price_effective))
+        .route("/timeline/", get().to(flavor_price_timeline))
+        .route("/initialize/", post().to(flavor_price_initialize))
+        // NOTE: only kept for older clients, as GET requests are not recorded
+        // in the audit log.
+        .route("/initialize/", get().to(flavor_price_initialize))
+        .route("/bulk/", post().to(flavor_price_bulk_create))
+        .route("/bulk/preview/", post().to(flavor_price_bulk_preview))
         .route("/{flavor_price_id}", get().to(flavor_price_get))
         // TODO: what about PUT?
         .route("/{flavor_price_id}/", patch().to(flavor_price_modify))
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{
+    pricing::{
+        FlavorPriceBulkPreview, FlavorPriceBulkPreviewData,
+        FlavorPriceBulkProjectImpact,
+    },
+    user::User,
+};
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use sqlx::{MySql, MySqlPool, Transaction};
+
+use super::bulk::plan_flavor_price_changes;
+use crate::{
+    authorization::require_admin_user,
+    database::user::project::select_all_projects_from_db,
+    error::OptionApiError,
+    routes::accounting::server_cost::get::{
+        PricePeriods, calculate_server_cost_for_all_detail_with_price_periods,
+        get_flavor_price_periods,
+    },
+};
+
+#[tracing::instrument(name = "flavor_price_bulk_preview")]
+pub async fn flavor_price_bulk_preview(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    data: Json<FlavorPriceBulkPreviewData>,
+) -> Result<HttpResponse, OptionApiError> {
+    require_admin_user(&user)?;
+    let end = data.end.unwrap_or(Utc::now().fixed_offset());
+    let begin = data.begin.unwrap_or(
+        Utc.with_ymd_and_hms(Utc::now().year(), 1, 1, 1, 0, 0)
+            .unwrap()
+            .fixed_offset(),
+    );
+    if begin > end {
+        return Err(OptionApiError::ValidationError(
+            "Begin must not be after end".to_string(),
+        ));
+    }
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let changes =
+        plan_flavor_price_changes(&mut transaction, &data.change).await?;
+    let projects = select_all_projects_from_db(&mut transaction).await?;
+    // NOTE: the new prices are applied to the stored price periods in memory,
+    // as if they had been in effect during the whole reference period.
+    // Prices that are not selected stay as they were and cancel out in the
+    // difference.
+    let current_price_periods =
+        get_flavor_price_periods(&mut transaction, begin.into(), end.into())
+            .await?;
+    let mut new_price_periods = current_price_periods.clone();
+    for prices in new_price_periods.values_mut() {
+        for change in &changes {
+            prices
+                .entry(change.user_class)
+                .or_default()
+                .insert(change.flavor_name.clone(), change.new_price);
+        }
+    }
+    let current_costs = calculate_project_costs_with_price_periods(
+        &mut transaction,
+        &current_price_periods,
+        begin.into(),
+        end.into(),
+    )
+    .await?;
+    let new_costs = calculate_project_costs_with_price_periods(
+        &mut transaction,
+        &new_price_periods,
+        begin.into(),
+        end.into(),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+
+    let mut impacts = Vec::new();
+    for project in projects {
+        let current_cost =
+            current_costs.get(&project.name).cloned().unwrap_or(0.0);
+        let new_cost = new_costs.get(&project.name).cloned().unwrap_or(0.0);
+        if current_cost == 0. && new_cost == 0. {
+            continue;
+        }
+        impacts.push(FlavorPriceBulkProjectImpact {
+            project: project.id,
+            project_name: project.name,
+            current_cost,
+            new_cost,
+            difference: new_cost - current_cost,
+        });
+    }
+    impacts.sort_by(|a, b| a.project_name.cmp(&b.project_name));
+    let preview = FlavorPriceBulkPreview {
+        begin,
+        end,
+        prices: changes,
+        projects: impacts,
+    };
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(preview))
+}
+
+async fn calculate_project_costs_with_price_periods(
+    transaction: &mut Transaction<'_, MySql>,
+    price_periods: &PricePeriods,
+    begin: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<HashMap<String, f64>, OptionApiError> {
+    let cost = calculate_server_cost_for_all_detail_with_price_periods(
+        transaction,
+        price_periods,
+        begin,
+        end,
+    )
+    .await?;
+    Ok(cost
+        .projects
+        .into_iter()
+        .map(|(project_name, project_cost)| (project_name, project_cost.total))
+        .collect())
+}
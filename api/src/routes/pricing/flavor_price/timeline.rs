@@ -0,0 +1,101 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Query, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{
+    pricing::{FlavorPricePeriod, FlavorPriceTimelineParams},
+    user::{Project, User},
+};
+use chrono::{Datelike, TimeZone, Utc};
+use sqlx::MySqlPool;
+
+use crate::{
+    database::resources::flavor::select_all_flavors_from_db,
+    error::OptionApiError,
+    routes::accounting::server_cost::get::get_flavor_price_periods,
+};
+
+#[tracing::instrument(name = "flavor_price_timeline")]
+pub async fn flavor_price_timeline(
+    user: ReqData<User>,
+    project: ReqData<Project>,
+    db_pool: Data<MySqlPool>,
+    params: Query<FlavorPriceTimelineParams>,
+) -> Result<HttpResponse, OptionApiError> {
+    let end = params.end.unwrap_or(Utc::now().fixed_offset());
+    let begin = params.begin.unwrap_or(
+        Utc.with_ymd_and_hms(Utc::now().year(), 1, 1, 1, 0, 0)
+            .unwrap()
+            .fixed_offset(),
+    );
+    if begin > end {
+        return Err(OptionApiError::ValidationError(
+            "Begin must not be after end".to_string(),
+        ));
+    }
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let mut flavors = select_all_flavors_from_db(&mut transaction).await?;
+    if let Some(flavor_id) = params.flavor {
+        flavors.retain(|flavor| flavor.id == flavor_id);
+        if flavors.is_empty() {
+            return Err(OptionApiError::NotFoundError);
+        }
+    }
+    let price_periods =
+        get_flavor_price_periods(&mut transaction, begin.into(), end.into())
+            .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+
+    let mut end_times =
+        price_periods.keys().skip(1).cloned().collect::<Vec<_>>();
+    end_times.push(end.into());
+
+    let mut timeline: Vec<FlavorPricePeriod> = Vec::new();
+    for flavor in flavors {
+        for ((start_time, prices), end_time) in
+            price_periods.iter().zip(end_times.iter())
+        {
+            for (user_class, class_prices) in prices {
+                if params.user_class.is_some_and(|uc| uc != *user_class) {
+                    continue;
+                }
+                let unit_price =
+                    class_prices.get(&flavor.name).cloned().unwrap_or(0.0);
+                // NOTE: periods are also split at user class changes, so
+                // consecutive periods with an unchanged price are merged.
+                if let Some(period) = timeline.iter_mut().rev().find(|p| {
+                    p.flavor == flavor.id && p.user_class == *user_class
+                }) && period.unit_price == unit_price
+                    && period.end == start_time.fixed_offset()
+                {
+                    period.end = end_time.fixed_offset();
+                    continue;
+                }
+                timeline.push(FlavorPricePeriod {
+                    flavor: flavor.id,
+                    flavor_name: flavor.name.clone(),
+                    user_class: *user_class,
+                    unit_price,
+                    begin: start_time.fixed_offset(),
+                    end: end_time.fixed_offset(),
+                });
+            }
+        }
+    }
+    timeline.sort_by(|a, b| {
+        a.flavor_name
+            .cmp(&b.flavor_name)
+            .then((a.user_class as u32).cmp(&(b.user_class as u32)))
+            .then(a.begin.cmp(&b.begin))
+    });
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(timeline))
+}
@@ -0,0 +1,54 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{
+    pricing::{FloatingIpPrice, FloatingIpPriceCreateData},
+    user::User,
+};
+use sqlx::MySqlPool;
+
+use crate::{
+    authorization::require_admin_user,
+    database::pricing::floating_ip_price::{
+        NewFloatingIpPrice, insert_floating_ip_price_into_db,
+    },
+    error::{NormalApiError, OptionApiError},
+};
+
+#[tracing::instrument(name = "floating_ip_price_create")]
+pub async fn floating_ip_price_create(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    data: Json<FloatingIpPriceCreateData>,
+) -> Result<HttpResponse, OptionApiError> {
+    require_admin_user(&user)?;
+    let new_floating_ip_price: NewFloatingIpPrice = data
+        .clone()
+        .try_into()
+        .map_err(NormalApiError::ValidationError)?;
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let id = insert_floating_ip_price_into_db(
+        &mut transaction,
+        &new_floating_ip_price,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    let floating_ip_price_created = FloatingIpPrice {
+        id: id as u32,
+        pool: new_floating_ip_price.pool,
+        user_class: new_floating_ip_price.user_class,
+        unit_price: new_floating_ip_price.unit_price,
+        start_time: new_floating_ip_price.start_time.fixed_offset(),
+    };
+    Ok(HttpResponse::Created()
+        .content_type("application/json")
+        .json(floating_ip_price_created))
+}
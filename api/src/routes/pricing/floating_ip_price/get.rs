@@ -0,0 +1,37 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Path, ReqData},
+};
+use anyhow::Context;
+use avina_wire::user::User;
+use sqlx::MySqlPool;
+
+use super::FloatingIpPriceIdParam;
+use crate::{
+    database::pricing::floating_ip_price::select_floating_ip_price_from_db,
+    error::OptionApiError,
+};
+
+#[tracing::instrument(name = "floating_ip_price_get")]
+pub async fn floating_ip_price_get(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    params: Path<FloatingIpPriceIdParam>,
+) -> Result<HttpResponse, OptionApiError> {
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let floating_ip_price = select_floating_ip_price_from_db(
+        &mut transaction,
+        params.floating_ip_price_id as u64,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(floating_ip_price))
+}
@@ -0,0 +1,32 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, ReqData},
+};
+use anyhow::Context;
+use avina_wire::user::User;
+use sqlx::MySqlPool;
+
+use crate::{
+    database::pricing::floating_ip_price::select_all_floating_ip_prices_from_db,
+    error::NormalApiError,
+};
+
+#[tracing::instrument(name = "floating_ip_price_list")]
+pub async fn floating_ip_price_list(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+) -> Result<HttpResponse, NormalApiError> {
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let floating_ip_prices =
+        select_all_floating_ip_prices_from_db(&mut transaction).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(floating_ip_prices))
+}
@@ -0,0 +1,97 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, Path, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{
+    pricing::{FloatingIpPrice, FloatingIpPriceModifyData},
+    user::User,
+};
+use sqlx::{Executor, MySql, MySqlPool, Transaction};
+
+use super::FloatingIpPriceIdParam;
+use crate::{
+    authorization::require_admin_user,
+    database::pricing::floating_ip_price::select_floating_ip_price_from_db,
+    error::{NotFoundOrUnexpectedApiError, OptionApiError},
+};
+
+#[tracing::instrument(name = "floating_ip_price_modify")]
+pub async fn floating_ip_price_modify(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    data: Json<FloatingIpPriceModifyData>,
+    params: Path<FloatingIpPriceIdParam>,
+) -> Result<HttpResponse, OptionApiError> {
+    require_admin_user(&user)?;
+    if data.id != params.floating_ip_price_id {
+        return Err(OptionApiError::ValidationError(
+            "ID in URL does not match ID in body".to_string(),
+        ));
+    }
+    if data
+        .unit_price
+        .is_some_and(|unit_price| !unit_price.is_finite() || unit_price < 0.)
+    {
+        return Err(OptionApiError::ValidationError(
+            "Floating IP price must not be negative".to_string(),
+        ));
+    }
+    if data.pool.as_ref().is_some_and(|pool| pool.is_empty()) {
+        return Err(OptionApiError::ValidationError(
+            "Pool of floating IP price must not be empty".to_string(),
+        ));
+    }
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let floating_ip_price =
+        update_floating_ip_price_in_db(&mut transaction, &data).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(floating_ip_price))
+}
+
+#[tracing::instrument(
+    name = "update_floating_ip_price_in_db",
+    skip(data, transaction)
+)]
+pub async fn update_floating_ip_price_in_db(
+    transaction: &mut Transaction<'_, MySql>,
+    data: &FloatingIpPriceModifyData,
+) -> Result<FloatingIpPrice, NotFoundOrUnexpectedApiError> {
+    let row =
+        select_floating_ip_price_from_db(transaction, data.id as u64).await?;
+    let pool = data.pool.clone().unwrap_or(row.pool);
+    let user_class = data.user_class.unwrap_or(row.user_class);
+    let unit_price = data.unit_price.unwrap_or(row.unit_price);
+    let start_time = data.start_time.unwrap_or(row.start_time);
+    let query = sqlx::query!(
+        r#"
+        UPDATE pricing_floatingipprice
+        SET pool = ?, user_class = ?, unit_price = ?, start_time = ?
+        WHERE id = ?
+        "#,
+        pool,
+        user_class as u32,
+        unit_price,
+        start_time.to_utc(),
+        data.id,
+    );
+    transaction
+        .execute(query)
+        .await
+        .context("Failed to execute update query")?;
+    Ok(FloatingIpPrice {
+        id: data.id,
+        pool,
+        user_class,
+        unit_price,
+        start_time,
+    })
+}
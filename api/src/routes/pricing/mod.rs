@@ -2,7 +2,14 @@ use actix_web::{Scope, web::scope};
 
 mod flavor_price;
 use flavor_price::flavor_prices_scope;
+mod floating_ip_price;
+use floating_ip_price::floating_ip_prices_scope;
+mod volume_price;
+use volume_price::volume_prices_scope;
 
 pub fn pricing_scope() -> Scope {
-    scope("/pricing").service(flavor_prices_scope())
+    scope("/pricing")
+        .service(flavor_prices_scope())
+        .service(volume_prices_scope())
+        .service(floating_ip_prices_scope())
 }
@@ -0,0 +1,51 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{
+    pricing::{VolumePrice, VolumePriceCreateData},
+    user::User,
+};
+use sqlx::MySqlPool;
+
+use crate::{
+    authorization::require_admin_user,
+    database::pricing::volume_price::{
+        NewVolumePrice, insert_volume_price_into_db,
+    },
+    error::{NormalApiError, OptionApiError},
+};
+
+#[tracing::instrument(name = "volume_price_create")]
+pub async fn volume_price_create(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    data: Json<VolumePriceCreateData>,
+) -> Result<HttpResponse, OptionApiError> {
+    require_admin_user(&user)?;
+    let new_volume_price: NewVolumePrice = data
+        .clone()
+        .try_into()
+        .map_err(NormalApiError::ValidationError)?;
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let id = insert_volume_price_into_db(&mut transaction, &new_volume_price)
+        .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    let volume_price_created = VolumePrice {
+        id: id as u32,
+        volume_type: new_volume_price.volume_type,
+        user_class: new_volume_price.user_class,
+        unit_price: new_volume_price.unit_price,
+        start_time: new_volume_price.start_time.fixed_offset(),
+    };
+    Ok(HttpResponse::Created()
+        .content_type("application/json")
+        .json(volume_price_created))
+}
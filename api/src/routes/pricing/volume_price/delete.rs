@@ -0,0 +1,60 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Path, ReqData},
+};
+use anyhow::Context;
+use avina_wire::user::User;
+use sqlx::{Executor, MySql, MySqlPool, Transaction};
+
+use super::VolumePriceIdParam;
+use crate::{
+    authorization::require_admin_user,
+    error::{MinimalApiError, NormalApiError},
+};
+
+#[tracing::instrument(name = "volume_price_delete")]
+pub async fn volume_price_delete(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    params: Path<VolumePriceIdParam>,
+) -> Result<HttpResponse, NormalApiError> {
+    require_admin_user(&user)?;
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    delete_volume_price_from_db(
+        &mut transaction,
+        params.volume_price_id as u64,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[tracing::instrument(name = "delete_volume_price_from_db", skip(transaction))]
+async fn delete_volume_price_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    volume_price_id: u64,
+) -> Result<(), MinimalApiError> {
+    let query = sqlx::query!(
+        r#"
+        DELETE IGNORE FROM pricing_volumeprice
+        WHERE id = ?
+        "#,
+        volume_price_id
+    );
+    let result = transaction
+        .execute(query)
+        .await
+        .context("Failed to execute delete query")?;
+    if result.rows_affected() == 0 {
+        return Err(MinimalApiError::ValidationError(
+            "Failed to delete volume price.".to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use actix_web::{
+    Scope,
+    web::{delete, get, patch, post, scope},
+};
+use serde::Deserialize;
+
+mod create;
+use create::volume_price_create;
+mod list;
+use list::volume_price_list;
+mod get;
+use get::volume_price_get;
+mod modify;
+use modify::volume_price_modify;
+mod delete;
+use delete::volume_price_delete;
+
+pub fn volume_prices_scope() -> Scope {
+    scope("/volumeprices")
+        .route("/", post().to(volume_price_create))
+        .route("", get().to(volume_price_list))
+        .route("/{volume_price_id}", get().to(volume_price_get))
+        .route("/{volume_price_id}/", patch().to(volume_price_modify))
+        .route("/{volume_price_id}/", delete().to(volume_price_delete))
+}
+
+#[derive(Deserialize, Debug)]
+struct VolumePriceIdParam {
+    #[allow(unused)]
+    volume_price_id: u32,
+}
@@ -0,0 +1,100 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, Path, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{
+    pricing::{VolumePrice, VolumePriceModifyData},
+    user::User,
+};
+use sqlx::{Executor, MySql, MySqlPool, Transaction};
+
+use super::VolumePriceIdParam;
+use crate::{
+    authorization::require_admin_user,
+    database::pricing::volume_price::select_volume_price_from_db,
+    error::{NotFoundOrUnexpectedApiError, OptionApiError},
+};
+
+#[tracing::instrument(name = "volume_price_modify")]
+pub async fn volume_price_modify(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    data: Json<VolumePriceModifyData>,
+    params: Path<VolumePriceIdParam>,
+) -> Result<HttpResponse, OptionApiError> {
+    require_admin_user(&user)?;
+    if data.id != params.volume_price_id {
+        return Err(OptionApiError::ValidationError(
+            "ID in URL does not match ID in body".to_string(),
+        ));
+    }
+    if data
+        .unit_price
+        .is_some_and(|unit_price| !unit_price.is_finite() || unit_price < 0.)
+    {
+        return Err(OptionApiError::ValidationError(
+            "Volume price must not be negative".to_string(),
+        ));
+    }
+    if data
+        .volume_type
+        .as_ref()
+        .is_some_and(|volume_type| volume_type.is_empty())
+    {
+        return Err(OptionApiError::ValidationError(
+            "Volume type of volume price must not be empty".to_string(),
+        ));
+    }
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let volume_price =
+        update_volume_price_in_db(&mut transaction, &data).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(volume_price))
+}
+
+#[tracing::instrument(
+    name = "update_volume_price_in_db",
+    skip(data, transaction)
+)]
+pub async fn update_volume_price_in_db(
+    transaction: &mut Transaction<'_, MySql>,
+    data: &VolumePriceModifyData,
+) -> Result<VolumePrice, NotFoundOrUnexpectedApiError> {
+    let row = select_volume_price_from_db(transaction, data.id as u64).await?;
+    let volume_type = data.volume_type.clone().unwrap_or(row.volume_type);
+    let user_class = data.user_class.unwrap_or(row.user_class);
+    let unit_price = data.unit_price.unwrap_or(row.unit_price);
+    let start_time = data.start_time.unwrap_or(row.start_time);
+    let query = sqlx::query!(
+        r#"
+        UPDATE pricing_volumeprice
+        SET volume_type = ?, user_class = ?, unit_price = ?, start_time = ?
+        WHERE id = ?
+        "#,
+        volume_type,
+        user_class as u32,
+        unit_price,
+        start_time.to_utc(),
+        data.id,
+    );
+    transaction
+        .execute(query)
+        .await
+        .context("Failed to execute update query")?;
+    Ok(VolumePrice {
+        id: data.id,
+        volume_type,
+        user_class,
+        unit_price,
+        start_time,
+    })
+}
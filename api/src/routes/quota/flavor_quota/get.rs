@@ -8,7 +8,7 @@ use sqlx::MySqlPool;
 
 use super::FlavorQuotaIdParam;
 use crate::{
-    authorization::require_admin_user,
+    authorization::{Action, Target, can, require_admin_user},
     database::quota::flavor_quota::select_flavor_quota_from_db,
     error::OptionApiError,
 };
@@ -34,7 +34,9 @@ pub async fn flavor_quota_get(
         .commit()
         .await
         .context("Failed to commit transaction")?;
-    if flavor_quota.user != user.id && !user.is_staff {
+    if flavor_quota.user != user.id
+        && !can(&user, Action::Administrate, Target::Global)
+    {
         return Err(OptionApiError::NotFoundError);
     }
     Ok(HttpResponse::Ok()
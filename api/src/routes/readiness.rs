@@ -0,0 +1,29 @@
+use actix_web::{HttpResponse, web::Data};
+use serde::Serialize;
+
+use crate::openstack::{OpenStack, TokenStatus};
+
+#[derive(Serialize)]
+struct Readiness {
+    ready: bool,
+    openstack_token: TokenStatus,
+}
+
+// NOTE: unlike the health check this reports whether requests can currently
+// be served, i.e. whether a valid OpenStack service token is available or
+// can be renewed.
+#[tracing::instrument(name = "readiness", skip(openstack))]
+pub async fn readiness(openstack: Data<OpenStack>) -> HttpResponse {
+    let openstack_token = openstack.token_status().await;
+    let readiness = Readiness {
+        ready: openstack_token.valid,
+        openstack_token,
+    };
+    if readiness.ready {
+        HttpResponse::Ok()
+    } else {
+        HttpResponse::ServiceUnavailable()
+    }
+    .content_type("application/json")
+    .json(readiness)
+}
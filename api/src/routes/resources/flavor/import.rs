@@ -7,7 +7,7 @@ use avina_wire::{
     resources::{FlavorCreateData, FlavorImport},
     user::User,
 };
-use sqlx::MySqlPool;
+use sqlx::{MySql, MySqlPool, Transaction};
 
 use crate::{
     authorization::require_admin_user,
@@ -30,7 +30,22 @@ pub async fn flavor_import(
         .begin()
         .await
         .context("Failed to begin transaction")?;
-    let existing_flavor_names = select_all_flavors_from_db(&mut transaction)
+    let flavor_import = import_flavors(&mut transaction, &openstack).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(flavor_import))
+}
+
+#[tracing::instrument(name = "import_flavors", skip(openstack))]
+pub async fn import_flavors(
+    transaction: &mut Transaction<'_, MySql>,
+    openstack: &OpenStack,
+) -> Result<FlavorImport, NormalApiError> {
+    let existing_flavor_names = select_all_flavors_from_db(transaction)
         .await?
         .iter()
         .map(|f| f.name.clone())
@@ -49,14 +64,7 @@ pub async fn flavor_import(
             group: None,
             weight: None,
         };
-        let _ = insert_flavor_into_db(&mut transaction, &data).await?;
+        let _ = insert_flavor_into_db(transaction, &data).await?;
     }
-    transaction
-        .commit()
-        .await
-        .context("Failed to commit transaction")?;
-    let flavor_import = FlavorImport { new_flavor_count };
-    Ok(HttpResponse::Ok()
-        .content_type("application/json")
-        .json(flavor_import))
+    Ok(FlavorImport { new_flavor_count })
 }
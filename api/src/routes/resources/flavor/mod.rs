@@ -14,7 +14,7 @@ mod modify;
 use modify::flavor_modify;
 mod delete;
 use delete::flavor_delete;
-mod import;
+pub(crate) mod import;
 use import::flavor_import;
 pub mod usage;
 use usage::flavor_usage;
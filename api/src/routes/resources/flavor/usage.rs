@@ -26,7 +26,7 @@ use crate::{
             select_user_from_db, select_users_by_project_from_db,
         },
     },
-    error::{OptionApiError, UnexpectedOnlyError},
+    error::{OpenStackError, OptionApiError},
     openstack::OpenStack,
 };
 
@@ -63,7 +63,7 @@ pub async fn calculate_flavor_usage_for_user_simple(
     transaction: &mut Transaction<'_, MySql>,
     openstack: Data<OpenStack>,
     user_id: u64,
-) -> Result<Vec<FlavorUsageSimple>, UnexpectedOnlyError> {
+) -> Result<Vec<FlavorUsageSimple>, OpenStackError> {
     let Some(user) =
         select_maybe_user_detail_from_db(transaction, user_id).await?
     else {
@@ -86,7 +86,7 @@ pub async fn calculate_flavor_usage_for_user_simple_inner(
     openstack: Data<OpenStack>,
     user: User,
     flavors: Vec<Flavor>,
-) -> Result<Vec<FlavorUsageSimple>, UnexpectedOnlyError> {
+) -> Result<Vec<FlavorUsageSimple>, OpenStackError> {
     let os_servers =
         openstack.get_servers_of_project(user.openstack_id).await?;
     let flavor_by_uuid: HashMap<_, _> = flavors
@@ -138,7 +138,7 @@ pub async fn calculate_flavor_usage_for_user_aggregate(
     transaction: &mut Transaction<'_, MySql>,
     openstack: Data<OpenStack>,
     user_id: u64,
-) -> Result<Vec<FlavorUsageAggregate>, UnexpectedOnlyError> {
+) -> Result<Vec<FlavorUsageAggregate>, OpenStackError> {
     Ok(aggregate_flavor_usage(
         calculate_flavor_usage_for_user_simple(transaction, openstack, user_id)
             .await?,
@@ -150,7 +150,7 @@ pub async fn calculate_flavor_usage_for_user(
     openstack: Data<OpenStack>,
     user_id: u64,
     aggregate: bool,
-) -> Result<FlavorUsage, UnexpectedOnlyError> {
+) -> Result<FlavorUsage, OpenStackError> {
     Ok(if aggregate {
         FlavorUsage::Aggregate(
             calculate_flavor_usage_for_user_aggregate(
@@ -176,7 +176,7 @@ pub async fn calculate_flavor_usage_for_project_simple(
     transaction: &mut Transaction<'_, MySql>,
     openstack: Data<OpenStack>,
     project_id: u64,
-) -> Result<Vec<FlavorUsageSimple>, UnexpectedOnlyError> {
+) -> Result<Vec<FlavorUsageSimple>, OpenStackError> {
     let users =
         select_users_by_project_from_db(transaction, project_id).await?;
     let flavors = select_lrz_flavors_from_db(transaction).await?;
@@ -201,7 +201,7 @@ pub async fn calculate_flavor_usage_for_project_aggregate(
     transaction: &mut Transaction<'_, MySql>,
     openstack: Data<OpenStack>,
     project_id: u64,
-) -> Result<Vec<FlavorUsageAggregate>, UnexpectedOnlyError> {
+) -> Result<Vec<FlavorUsageAggregate>, OpenStackError> {
     Ok(aggregate_flavor_usage(
         calculate_flavor_usage_for_project_simple(
             transaction,
@@ -217,7 +217,7 @@ pub async fn calculate_flavor_usage_for_project(
     openstack: Data<OpenStack>,
     project_id: u64,
     aggregate: bool,
-) -> Result<FlavorUsage, UnexpectedOnlyError> {
+) -> Result<FlavorUsage, OpenStackError> {
     Ok(if aggregate {
         FlavorUsage::Aggregate(
             calculate_flavor_usage_for_project_aggregate(
@@ -242,7 +242,7 @@ pub async fn calculate_flavor_usage_for_project(
 pub async fn calculate_flavor_usage_for_all_simple(
     transaction: &mut Transaction<'_, MySql>,
     openstack: Data<OpenStack>,
-) -> Result<Vec<FlavorUsageSimple>, UnexpectedOnlyError> {
+) -> Result<Vec<FlavorUsageSimple>, OpenStackError> {
     let users = select_all_users_from_db(transaction).await?;
     let flavors = select_lrz_flavors_from_db(transaction).await?;
     let mut handles = Vec::with_capacity(users.len());
@@ -265,7 +265,7 @@ pub async fn calculate_flavor_usage_for_all_simple(
 pub async fn calculate_flavor_usage_for_all_aggregate(
     transaction: &mut Transaction<'_, MySql>,
     openstack: Data<OpenStack>,
-) -> Result<Vec<FlavorUsageAggregate>, UnexpectedOnlyError> {
+) -> Result<Vec<FlavorUsageAggregate>, OpenStackError> {
     Ok(aggregate_flavor_usage(
         calculate_flavor_usage_for_all_simple(transaction, openstack).await?,
     ))
@@ -275,7 +275,7 @@ pub async fn calculate_flavor_usage_for_all(
     transaction: &mut Transaction<'_, MySql>,
     openstack: Data<OpenStack>,
     aggregate: bool,
-) -> Result<FlavorUsage, UnexpectedOnlyError> {
+) -> Result<FlavorUsage, OpenStackError> {
     Ok(if aggregate {
         FlavorUsage::Aggregate(
             calculate_flavor_usage_for_all_aggregate(transaction, openstack)
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use actix_web::{
+    HttpResponse,
+    web::{Data, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{
+    resources::{FlavorGroupCreateData, FlavorGroupInitialize},
+    user::User,
+};
+use sqlx::{MySql, MySqlPool, Transaction};
+
+use crate::{
+    authorization::require_admin_user,
+    database::resources::{
+        flavor::{select_all_flavors_from_db, update_flavor_group_in_db},
+        flavor_group::{
+            insert_flavor_group_into_db, select_all_flavor_groups_from_db,
+        },
+    },
+    error::NormalApiError,
+};
+
+#[tracing::instrument(name = "flavor_group_initialize")]
+pub async fn flavor_group_initialize(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+) -> Result<HttpResponse, NormalApiError> {
+    require_admin_user(&user)?;
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let flavor_group_initialize =
+        initialize_flavor_groups(&mut transaction, user.project as u64).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(flavor_group_initialize))
+}
+
+/// Flavors are named `<group>.<size>`, e.g. `nvidia-v100.2` belongs to the
+/// group `nvidia-v100`.
+fn flavor_group_name(flavor_name: &str) -> Option<&str> {
+    match flavor_name.rsplit_once('.') {
+        Some((group_name, size))
+            if !group_name.is_empty() && !size.is_empty() =>
+        {
+            Some(group_name)
+        }
+        _ => None,
+    }
+}
+
+/// Puts every flavor without a group into the group derived from its name,
+/// creating the groups that do not exist yet.
+#[tracing::instrument(name = "initialize_flavor_groups", skip(transaction))]
+pub async fn initialize_flavor_groups(
+    transaction: &mut Transaction<'_, MySql>,
+    project_id: u64,
+) -> Result<FlavorGroupInitialize, NormalApiError> {
+    let mut groups = select_all_flavor_groups_from_db(transaction)
+        .await?
+        .into_iter()
+        .map(|group| (group.name, group.id))
+        .collect::<HashMap<_, _>>();
+    let flavors = select_all_flavors_from_db(transaction).await?;
+    let mut new_flavor_group_count = 0;
+    let mut new_flavor_count = 0;
+    for flavor in flavors {
+        if flavor.group.is_some() {
+            continue;
+        }
+        let Some(group_name) = flavor_group_name(&flavor.name) else {
+            continue;
+        };
+        let group_id = match groups.get(group_name) {
+            Some(group_id) => *group_id,
+            None => {
+                let data = FlavorGroupCreateData::new(group_name.to_string());
+                let group_id =
+                    insert_flavor_group_into_db(transaction, &data, project_id)
+                        .await? as u32;
+                groups.insert(group_name.to_string(), group_id);
+                new_flavor_group_count += 1;
+                group_id
+            }
+        };
+        update_flavor_group_in_db(transaction, flavor.id, Some(group_id))
+            .await?;
+        new_flavor_count += 1;
+    }
+    Ok(FlavorGroupInitialize {
+        new_flavor_group_count,
+        new_flavor_count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flavor_group_name_strips_size() {
+        assert_eq!(flavor_group_name("nvidia-v100.2"), Some("nvidia-v100"));
+        assert_eq!(flavor_group_name("lrz.medium.large"), Some("lrz.medium"));
+        assert_eq!(flavor_group_name("tiny"), None);
+        assert_eq!(flavor_group_name(".small"), None);
+        assert_eq!(flavor_group_name("lrz."), None);
+    }
+}
@@ -21,7 +21,7 @@ use crate::{
         require_user_or_project_master_or_not_found,
     },
     database::user::user::select_user_from_db,
-    error::{OptionApiError, UnexpectedOnlyError},
+    error::{OpenStackError, OptionApiError},
     openstack::OpenStack,
     routes::resources::flavor::usage::{
         calculate_flavor_usage_for_all_simple,
@@ -88,7 +88,7 @@ pub async fn calculate_flavor_group_usage_for_user_simple(
     transaction: &mut Transaction<'_, MySql>,
     openstack: Data<OpenStack>,
     user_id: u64,
-) -> Result<Vec<FlavorGroupUsageSimple>, UnexpectedOnlyError> {
+) -> Result<Vec<FlavorGroupUsageSimple>, OpenStackError> {
     Ok(flavor_usage_to_flavor_group_usage(
         calculate_flavor_usage_for_user_simple(transaction, openstack, user_id)
             .await?,
@@ -99,7 +99,7 @@ pub async fn calculate_flavor_group_usage_for_user_aggregate(
     transaction: &mut Transaction<'_, MySql>,
     openstack: Data<OpenStack>,
     user_id: u64,
-) -> Result<Vec<FlavorGroupUsageAggregate>, UnexpectedOnlyError> {
+) -> Result<Vec<FlavorGroupUsageAggregate>, OpenStackError> {
     Ok(aggregate_flavor_group_usage(
         calculate_flavor_group_usage_for_user_simple(
             transaction,
@@ -115,7 +115,7 @@ pub async fn calculate_flavor_group_usage_for_user(
     openstack: Data<OpenStack>,
     user_id: u64,
     aggregate: bool,
-) -> Result<FlavorGroupUsage, UnexpectedOnlyError> {
+) -> Result<FlavorGroupUsage, OpenStackError> {
     Ok(if aggregate {
         FlavorGroupUsage::Aggregate(
             calculate_flavor_group_usage_for_user_aggregate(
@@ -141,7 +141,7 @@ pub async fn calculate_flavor_group_usage_for_project_simple(
     transaction: &mut Transaction<'_, MySql>,
     openstack: Data<OpenStack>,
     project_id: u64,
-) -> Result<Vec<FlavorGroupUsageSimple>, UnexpectedOnlyError> {
+) -> Result<Vec<FlavorGroupUsageSimple>, OpenStackError> {
     Ok(flavor_usage_to_flavor_group_usage(
         calculate_flavor_usage_for_project_simple(
             transaction,
@@ -156,7 +156,7 @@ pub async fn calculate_flavor_group_usage_for_project_aggregate(
     transaction: &mut Transaction<'_, MySql>,
     openstack: Data<OpenStack>,
     project_id: u64,
-) -> Result<Vec<FlavorGroupUsageAggregate>, UnexpectedOnlyError> {
+) -> Result<Vec<FlavorGroupUsageAggregate>, OpenStackError> {
     Ok(aggregate_flavor_group_usage(
         calculate_flavor_group_usage_for_project_simple(
             transaction,
@@ -172,7 +172,7 @@ pub async fn calculate_flavor_group_usage_for_project(
     openstack: Data<OpenStack>,
     project_id: u64,
     aggregate: bool,
-) -> Result<FlavorGroupUsage, UnexpectedOnlyError> {
+) -> Result<FlavorGroupUsage, OpenStackError> {
     Ok(if aggregate {
         FlavorGroupUsage::Aggregate(
             calculate_flavor_group_usage_for_project_aggregate(
@@ -197,7 +197,7 @@ pub async fn calculate_flavor_group_usage_for_project(
 pub async fn calculate_flavor_group_usage_for_all_simple(
     transaction: &mut Transaction<'_, MySql>,
     openstack: Data<OpenStack>,
-) -> Result<Vec<FlavorGroupUsageSimple>, UnexpectedOnlyError> {
+) -> Result<Vec<FlavorGroupUsageSimple>, OpenStackError> {
     Ok(flavor_usage_to_flavor_group_usage(
         calculate_flavor_usage_for_all_simple(transaction, openstack).await?,
     ))
@@ -206,7 +206,7 @@ pub async fn calculate_flavor_group_usage_for_all_simple(
 pub async fn calculate_flavor_group_usage_for_all_aggregate(
     transaction: &mut Transaction<'_, MySql>,
     openstack: Data<OpenStack>,
-) -> Result<Vec<FlavorGroupUsageAggregate>, UnexpectedOnlyError> {
+) -> Result<Vec<FlavorGroupUsageAggregate>, OpenStackError> {
     Ok(aggregate_flavor_group_usage(
         calculate_flavor_group_usage_for_all_simple(transaction, openstack)
             .await?,
@@ -217,7 +217,7 @@ pub async fn calculate_flavor_group_usage_for_all(
     transaction: &mut Transaction<'_, MySql>,
     openstack: Data<OpenStack>,
     aggregate: bool,
-) -> Result<FlavorGroupUsage, UnexpectedOnlyError> {
+) -> Result<FlavorGroupUsage, OpenStackError> {
     Ok(if aggregate {
         FlavorGroupUsage::Aggregate(
             calculate_flavor_group_usage_for_all_aggregate(
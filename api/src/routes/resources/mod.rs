@@ -2,7 +2,7 @@ use actix_web::{Scope, web::scope};
 
 mod flavor_group;
 use flavor_group::flavor_groups_scope;
-mod flavor;
+pub(crate) mod flavor;
 use flavor::flavors_scope;
 mod usage;
 use usage::usage_scope;
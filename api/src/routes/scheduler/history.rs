@@ -0,0 +1,33 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Query, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{scheduler::JobRunListParams, user::User};
+use sqlx::MySqlPool;
+
+use crate::{
+    authorization::require_admin_user,
+    database::scheduler::select_job_runs_from_db, error::OptionApiError,
+};
+
+#[tracing::instrument(name = "scheduler_job_run_list")]
+pub async fn scheduler_job_run_list(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    params: Query<JobRunListParams>,
+) -> Result<HttpResponse, OptionApiError> {
+    require_admin_user(&user)?;
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let runs = select_job_runs_from_db(&mut transaction, params.job).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(runs))
+}
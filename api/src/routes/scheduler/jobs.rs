@@ -0,0 +1,59 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{
+    scheduler::{Job, JobName},
+    user::User,
+};
+use chrono::Utc;
+use sqlx::MySqlPool;
+use strum::IntoEnumIterator;
+
+use crate::{
+    authorization::require_admin_user,
+    database::scheduler::select_last_job_run_from_db, error::OptionApiError,
+    scheduler::Scheduler,
+};
+
+#[tracing::instrument(name = "scheduler_job_list", skip(scheduler))]
+pub async fn scheduler_job_list(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    scheduler: Data<Scheduler>,
+) -> Result<HttpResponse, OptionApiError> {
+    require_admin_user(&user)?;
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let now = Utc::now();
+    let mut jobs = vec![];
+    for name in JobName::iter() {
+        let last_run =
+            select_last_job_run_from_db(&mut transaction, name).await?;
+        let schedule = scheduler.schedule(name);
+        jobs.push(Job {
+            name,
+            schedule: schedule.map(|s| s.to_string()),
+            next_run: schedule
+                .and_then(|s| {
+                    s.next_run(
+                        last_run.as_ref().map(|r| r.started_at.to_utc()),
+                        now,
+                    )
+                })
+                .map(|n| n.max(now).fixed_offset()),
+            last_status: last_run.as_ref().map(|r| r.status),
+            last_started_at: last_run.map(|r| r.started_at),
+        });
+    }
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(jobs))
+}
@@ -0,0 +1,18 @@
+use actix_web::{
+    Scope,
+    web::{get, post, scope},
+};
+
+mod history;
+use history::scheduler_job_run_list;
+mod jobs;
+use jobs::scheduler_job_list;
+mod run;
+use run::scheduler_job_run;
+
+pub fn scheduler_scope() -> Scope {
+    scope("/scheduler")
+        .route("/jobs/", get().to(scheduler_job_list))
+        .route("/jobs/{job}/run/", post().to(scheduler_job_run))
+        .route("/runs/", get().to(scheduler_job_run_list))
+}
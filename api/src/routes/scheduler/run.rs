@@ -0,0 +1,46 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Path, ReqData},
+};
+use anyhow::Context;
+use avina_wire::{scheduler::JobName, user::User};
+use serde::Deserialize;
+use sqlx::MySqlPool;
+
+use crate::{
+    authorization::require_admin_user, configuration::AccountingSettings,
+    error::OptionApiError, openstack::OpenStack, scheduler::run_job,
+};
+
+#[derive(Deserialize, Debug)]
+pub struct JobParams {
+    job: JobName,
+}
+
+#[tracing::instrument(name = "scheduler_job_run", skip(openstack))]
+pub async fn scheduler_job_run(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    openstack: Data<OpenStack>,
+    accounting: Data<AccountingSettings>,
+    params: Path<JobParams>,
+) -> Result<HttpResponse, OptionApiError> {
+    require_admin_user(&user)?;
+    // NOTE: run the job in its own task, so it is not cancelled halfway when
+    // the client disconnects, and a panic in the job becomes an error here.
+    let job = params.job;
+    let user_id = user.id;
+    let handle = tokio::spawn(async move {
+        run_job(&db_pool, &openstack, &accounting, job, Some(user_id), None)
+            .await
+    });
+    let Some(run) = handle.await.context("Failed to run job")?? else {
+        return Err(OptionApiError::ValidationError(format!(
+            "Job {} is already running",
+            params.job
+        )));
+    };
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(run))
+}
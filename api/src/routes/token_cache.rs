@@ -0,0 +1,60 @@
+use actix_web::{
+    HttpRequest, HttpResponse, Scope,
+    web::{Data, ReqData, delete, get, scope},
+};
+use avina_wire::user::User;
+
+use crate::{
+    authentication::Token,
+    authorization::require_admin_user,
+    error::{AuthOnlyError, NormalApiError},
+    token_cache::TokenCache,
+};
+
+pub fn token_cache_scope() -> Scope {
+    scope("/tokencache")
+        .route("", get().to(token_cache_stats))
+        .route("/", delete().to(token_cache_revoke))
+}
+
+#[tracing::instrument(name = "token_cache_stats", skip(token_cache))]
+async fn token_cache_stats(
+    user: ReqData<User>,
+    token_cache: Data<TokenCache>,
+) -> Result<HttpResponse, AuthOnlyError> {
+    require_admin_user(&user)?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(token_cache.stats()))
+}
+
+// NOTE: Without an X-Subject-Token header the caller drops their own token,
+// e.g. right after revoking it in Keystone. Dropping other tokens is
+// restricted to admins.
+#[tracing::instrument(
+    name = "token_cache_revoke",
+    skip(token, request, token_cache)
+)]
+async fn token_cache_revoke(
+    user: ReqData<User>,
+    token: ReqData<Token>,
+    request: HttpRequest,
+    token_cache: Data<TokenCache>,
+) -> Result<HttpResponse, NormalApiError> {
+    let subject = match request.headers().get("X-Subject-Token") {
+        Some(subject) => subject
+            .to_str()
+            .map_err(|_| {
+                NormalApiError::ValidationError(
+                    "Subject token is not a valid string".to_string(),
+                )
+            })?
+            .to_string(),
+        None => token.0.clone(),
+    };
+    if subject != token.0 {
+        require_admin_user(&user)?;
+    }
+    token_cache.revoke(subject.as_str());
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,82 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, ReqData},
+};
+use anyhow::Context;
+use avina_wire::user::{ApiKey, ApiKeyCreateData, ApiKeyCreated, User};
+use chrono::Utc;
+use sqlx::MySqlPool;
+
+use crate::{
+    api_key::{api_key_visible_prefix, generate_api_key, hash_api_key},
+    authorization::require_admin_user,
+    database::user::{
+        api_key::{NewApiKey, insert_api_key_into_db},
+        user::select_user_name_from_db,
+    },
+    error::OptionApiError,
+};
+
+#[tracing::instrument(name = "api_key_create")]
+pub async fn api_key_create(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    data: Json<ApiKeyCreateData>,
+) -> Result<HttpResponse, OptionApiError> {
+    let owner = data.user.unwrap_or(user.id);
+    if owner != user.id {
+        require_admin_user(&user)?;
+    }
+    if data.scopes.is_empty() {
+        return Err(OptionApiError::ValidationError(
+            "At least one scope is required".to_string(),
+        ));
+    }
+    let created_at = Utc::now();
+    let expires_at = data.expires_at.map(|e| e.to_utc());
+    if let Some(expires_at) = expires_at
+        && expires_at <= created_at
+    {
+        return Err(OptionApiError::ValidationError(
+            "Expiry date must be in the future".to_string(),
+        ));
+    }
+    let key = generate_api_key();
+    let new_api_key = NewApiKey {
+        name: data.name.clone(),
+        prefix: api_key_visible_prefix(&key),
+        key_hash: hash_api_key(&key),
+        scopes: data.scopes.clone(),
+        created_at,
+        expires_at,
+        user: owner,
+    };
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let username =
+        select_user_name_from_db(&mut transaction, owner as u64).await?;
+    let id = insert_api_key_into_db(&mut transaction, &new_api_key).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    let api_key_created = ApiKeyCreated {
+        api_key: ApiKey {
+            id: id as u32,
+            name: new_api_key.name,
+            prefix: new_api_key.prefix,
+            scopes: new_api_key.scopes,
+            user: owner,
+            username,
+            created_at: created_at.fixed_offset(),
+            expires_at: expires_at.map(|e| e.fixed_offset()),
+            last_used_at: None,
+        },
+        key,
+    };
+    Ok(HttpResponse::Created()
+        .content_type("application/json")
+        .json(api_key_created))
+}
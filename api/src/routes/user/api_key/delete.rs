@@ -0,0 +1,64 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Path, ReqData},
+};
+use anyhow::Context;
+use avina_wire::user::User;
+use sqlx::{Executor, MySql, MySqlPool, Transaction};
+
+use super::ApiKeyIdParam;
+use crate::{
+    authorization::require_admin_user_or_return_not_found,
+    database::user::api_key::select_api_key_from_db,
+    error::{MinimalApiError, OptionApiError},
+};
+
+// NOTE: revoking is deleting, a key cannot be restored anyway since only its
+// hash is stored.
+#[tracing::instrument(name = "api_key_delete")]
+pub async fn api_key_delete(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    params: Path<ApiKeyIdParam>,
+) -> Result<HttpResponse, OptionApiError> {
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let api_key =
+        select_api_key_from_db(&mut transaction, params.api_key_id as u64)
+            .await?;
+    if api_key.user != user.id {
+        require_admin_user_or_return_not_found(&user)?;
+    }
+    delete_api_key_from_db(&mut transaction, params.api_key_id as u64).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[tracing::instrument(name = "delete_api_key_from_db", skip(transaction))]
+async fn delete_api_key_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    api_key_id: u64,
+) -> Result<(), MinimalApiError> {
+    let query = sqlx::query!(
+        r#"
+        DELETE IGNORE FROM user_apikey
+        WHERE id = ?
+        "#,
+        api_key_id
+    );
+    let result = transaction
+        .execute(query)
+        .await
+        .context("Failed to execute delete query")?;
+    if result.rows_affected() == 0 {
+        return Err(MinimalApiError::ValidationError(
+            "Failed to delete API key.".to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Path, ReqData},
+};
+use anyhow::Context;
+use avina_wire::user::User;
+use sqlx::MySqlPool;
+
+use super::ApiKeyIdParam;
+use crate::{
+    authorization::require_user_or_project_master_or_not_found,
+    database::user::{
+        api_key::select_api_key_from_db, user::select_user_from_db,
+    },
+    error::OptionApiError,
+};
+
+#[tracing::instrument(name = "api_key_get")]
+pub async fn api_key_get(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    params: Path<ApiKeyIdParam>,
+) -> Result<HttpResponse, OptionApiError> {
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let api_key =
+        select_api_key_from_db(&mut transaction, params.api_key_id as u64)
+            .await?;
+    let api_key_user =
+        select_user_from_db(&mut transaction, api_key.user as u64).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    require_user_or_project_master_or_not_found(
+        &user,
+        api_key_user.id,
+        api_key_user.project,
+    )?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(api_key))
+}
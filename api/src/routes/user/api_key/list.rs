@@ -0,0 +1,57 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Query, ReqData},
+};
+use anyhow::Context;
+use avina_wire::user::{ApiKeyListParams, User};
+use sqlx::MySqlPool;
+
+use crate::{
+    authorization::{
+        require_admin_user, require_user_or_project_master_or_not_found,
+    },
+    database::user::{
+        api_key::{
+            select_all_api_keys_from_db, select_api_keys_by_user_from_db,
+        },
+        user::select_user_from_db,
+    },
+    error::OptionApiError,
+};
+
+#[tracing::instrument(name = "api_key_list")]
+pub async fn api_key_list(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    params: Query<ApiKeyListParams>,
+) -> Result<HttpResponse, OptionApiError> {
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let api_keys = if params.all.unwrap_or(false) {
+        require_admin_user(&user)?;
+        select_all_api_keys_from_db(&mut transaction).await?
+    } else if let Some(user_id) = params.user {
+        let user1 = select_user_from_db(&mut transaction, user_id as u64)
+            .await
+            .context("Failed to select user")?;
+        require_user_or_project_master_or_not_found(
+            &user,
+            user1.id,
+            user1.project,
+        )?;
+        select_api_keys_by_user_from_db(&mut transaction, user1.id as u64)
+            .await?
+    } else {
+        select_api_keys_by_user_from_db(&mut transaction, user.id as u64)
+            .await?
+    };
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(api_keys))
+}
@@ -0,0 +1,27 @@
+use actix_web::{
+    Scope,
+    web::{delete, get, post, scope},
+};
+use serde::Deserialize;
+
+mod create;
+use create::api_key_create;
+mod list;
+use list::api_key_list;
+mod get;
+use get::api_key_get;
+mod delete;
+use delete::api_key_delete;
+
+pub fn api_keys_scope() -> Scope {
+    scope("/apikeys")
+        .route("/", post().to(api_key_create))
+        .route("", get().to(api_key_list))
+        .route("/{api_key_id}", get().to(api_key_get))
+        .route("/{api_key_id}/", delete().to(api_key_delete))
+}
+
+#[derive(Deserialize, Debug)]
+struct ApiKeyIdParam {
+    api_key_id: u32,
+}
@@ -5,8 +5,8 @@ use actix_web::{
     web::{Data, ReqData},
 };
 use anyhow::Context;
-use avina_wire::user::{User, UserClass, UserImport};
-use sqlx::MySqlPool;
+use avina_wire::user::{Role, User, UserClass, UserImport};
+use sqlx::{MySql, MySqlPool, Transaction};
 
 use crate::{
     authorization::require_admin_user,
@@ -32,12 +32,26 @@ pub async fn user_import(
         .begin()
         .await
         .context("Failed to begin transaction")?;
+    let user_import = import_users(&mut transaction, &openstack).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(user_import))
+}
 
+#[tracing::instrument(name = "import_users", skip(openstack))]
+pub async fn import_users(
+    transaction: &mut Transaction<'_, MySql>,
+    openstack: &OpenStack,
+) -> Result<UserImport, NormalApiError> {
     let os_domains = openstack.get_domains().await?;
     let os_projects = openstack.get_projects().await?;
 
-    let users = select_all_users_from_db(&mut transaction).await?;
-    let mut projects = select_all_projects_from_db(&mut transaction).await?;
+    let users = select_all_users_from_db(transaction).await?;
+    let mut projects = select_all_projects_from_db(transaction).await?;
     let usernames: Vec<String> = users.iter().map(|u| u.name.clone()).collect();
     let project_names: Vec<String> =
         projects.iter().map(|u| u.name.clone()).collect();
@@ -57,7 +71,7 @@ pub async fn user_import(
                 // TODO: get userclass from ldap
                 user_class: UserClass::NA,
             };
-            insert_project_into_db(&mut transaction, &new_project).await?;
+            insert_project_into_db(transaction, &new_project).await?;
             // TODO: create project budget
 
             new_project_count += 1;
@@ -65,7 +79,7 @@ pub async fn user_import(
     }
 
     if new_project_count > 0 {
-        projects = select_all_projects_from_db(&mut transaction).await?;
+        projects = select_all_projects_from_db(transaction).await?;
     }
 
     let project_by_name = projects
@@ -90,24 +104,18 @@ pub async fn user_import(
             openstack_id: os_project.id.clone(),
             project_id: project.id,
             // TODO: get role from ldap
-            role: 1,
+            role: Role::User,
             is_staff: false,
             is_active: false,
         };
-        insert_user_into_db(&mut transaction, &new_user).await?;
+        insert_user_into_db(transaction, &new_user).await?;
         // TODO: create user budget
 
         new_user_count += 1;
     }
 
-    transaction
-        .commit()
-        .await
-        .context("Failed to commit transaction")?;
-    Ok(HttpResponse::Ok()
-        .content_type("application/json")
-        .json(UserImport {
-            new_project_count,
-            new_user_count,
-        }))
+    Ok(UserImport {
+        new_project_count,
+        new_user_count,
+    })
 }
@@ -3,20 +3,26 @@ use actix_web::{
     web::{get, scope},
 };
 
+pub mod api_key;
+use api_key::api_keys_scope;
 pub mod project;
 use project::projects_scope;
+pub mod project_class_change;
+use project_class_change::project_class_changes_scope;
 #[allow(clippy::module_inception)]
 pub mod user;
 use user::users_scope;
 mod me;
 use me::user_me;
-mod import;
+pub(crate) mod import;
 use import::user_import;
 
 pub fn user_scope() -> Scope {
     scope("/user")
         .service(projects_scope())
+        .service(project_class_changes_scope())
         .service(users_scope())
+        .service(api_keys_scope())
         .route("/me", get().to(user_me))
         .route("/import", get().to(user_import))
 }
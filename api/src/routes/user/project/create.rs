@@ -4,10 +4,14 @@ use actix_web::{
 };
 use anyhow::Context;
 use avina_wire::user::{Project, ProjectCreateData, User, UserClass};
+use chrono::DateTime;
 use sqlx::{Executor, MySql, MySqlPool, Transaction};
 
 use crate::{
     authorization::require_admin_user,
+    database::user::project_class_change::{
+        NewProjectClassChange, insert_project_class_change_into_db,
+    },
     error::{MinimalApiError, NormalApiError},
 };
 
@@ -89,5 +93,16 @@ pub async fn insert_project_into_db(
         ));
     }
     let id = result.last_insert_id();
+    // NOTE: the class a project is created with applies to all its servers
+    // until it is changed.
+    insert_project_class_change_into_db(
+        transaction,
+        &NewProjectClassChange {
+            project_id: id,
+            user_class: new_project.user_class,
+            start_time: DateTime::UNIX_EPOCH,
+        },
+    )
+    .await?;
     Ok(id)
 }
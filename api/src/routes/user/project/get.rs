@@ -8,7 +8,9 @@ use sqlx::MySqlPool;
 
 use super::ProjectIdParam;
 use crate::{
-    authorization::require_admin_user_or_return_not_found,
+    authorization::{
+        Action, Target, can, require_admin_user_or_return_not_found,
+    },
     database::{
         resources::flavor_group::select_minimal_flavor_groups_by_project_id_from_db,
         user::{
@@ -38,7 +40,7 @@ pub async fn project_get(
         select_project_from_db(&mut transaction, params.project_id as u64)
             .await?;
 
-    let project = if user.is_staff {
+    let project = if can(&user, Action::Administrate, Target::Global) {
         let users = select_minimal_users_by_project_id_from_db(
             &mut transaction,
             row.id as u64,
@@ -4,13 +4,20 @@ use actix_web::{
 };
 use anyhow::Context;
 use avina_wire::user::{Project, ProjectModifyData, User};
+use chrono::{DateTime, Utc};
 use sqlx::{Executor, MySql, MySqlPool, Transaction};
 
 use super::ProjectIdParam;
 use crate::{
     authorization::require_admin_user,
-    database::user::project::select_project_from_db,
-    error::{NotFoundOrUnexpectedApiError, OptionApiError},
+    database::user::{
+        project::select_project_from_db,
+        project_class_change::{
+            NewProjectClassChange, insert_project_class_change_into_db,
+            select_project_class_changes_by_project_from_db,
+        },
+    },
+    error::OptionApiError,
 };
 
 #[tracing::instrument(name = "project_modify")]
@@ -45,7 +52,7 @@ pub async fn project_modify(
 pub async fn update_project_in_db(
     transaction: &mut Transaction<'_, MySql>,
     data: &ProjectModifyData,
-) -> Result<Project, NotFoundOrUnexpectedApiError> {
+) -> Result<Project, OptionApiError> {
     let row = select_project_from_db(transaction, data.id as u64).await?;
     let name = data.name.clone().unwrap_or(row.name);
     let openstack_id = data.openstack_id.clone().unwrap_or(row.openstack_id);
@@ -65,6 +72,36 @@ pub async fn update_project_in_db(
         .execute(query)
         .await
         .context("Failed to execute update query")?;
+    if user_class != row.user_class {
+        // NOTE: projects created before the history existed have none yet,
+        // so their previous class is recorded to keep applying to the past.
+        if select_project_class_changes_by_project_from_db(
+            transaction,
+            data.id as u64,
+        )
+        .await?
+        .is_empty()
+        {
+            insert_project_class_change_into_db(
+                transaction,
+                &NewProjectClassChange {
+                    project_id: data.id as u64,
+                    user_class: row.user_class,
+                    start_time: DateTime::UNIX_EPOCH,
+                },
+            )
+            .await?;
+        }
+        insert_project_class_change_into_db(
+            transaction,
+            &NewProjectClassChange {
+                project_id: data.id as u64,
+                user_class,
+                start_time: Utc::now(),
+            },
+        )
+        .await?;
+    }
     let project = Project {
         id: data.id,
         name,
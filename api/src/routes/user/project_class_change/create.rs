@@ -0,0 +1,66 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, ReqData},
+};
+use anyhow::Context;
+use avina_wire::user::{
+    ProjectClassChange, ProjectClassChangeCreateData, User,
+};
+use sqlx::MySqlPool;
+
+use crate::{
+    authorization::require_admin_user,
+    database::user::{
+        project::select_maybe_project_from_db,
+        project_class_change::{
+            NewProjectClassChange, insert_project_class_change_into_db,
+        },
+    },
+    error::{NormalApiError, OptionApiError},
+};
+
+#[tracing::instrument(name = "project_class_change_create")]
+pub async fn project_class_change_create(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    data: Json<ProjectClassChangeCreateData>,
+) -> Result<HttpResponse, OptionApiError> {
+    require_admin_user(&user)?;
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let Some(project) =
+        select_maybe_project_from_db(&mut transaction, data.project as u64)
+            .await?
+    else {
+        return Err(NormalApiError::ValidationError(
+            "Project of project class change does not exist".to_string(),
+        )
+        .into());
+    };
+    let new_project_class_change = NewProjectClassChange {
+        project_id: project.id as u64,
+        user_class: data.user_class,
+        start_time: data.start_time.to_utc(),
+    };
+    let id = insert_project_class_change_into_db(
+        &mut transaction,
+        &new_project_class_change,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    let project_class_change_created = ProjectClassChange {
+        id: id as u32,
+        project: project.id,
+        project_name: project.name,
+        user_class: new_project_class_change.user_class,
+        start_time: new_project_class_change.start_time.fixed_offset(),
+    };
+    Ok(HttpResponse::Created()
+        .content_type("application/json")
+        .json(project_class_change_created))
+}
@@ -0,0 +1,63 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Path, ReqData},
+};
+use anyhow::Context;
+use avina_wire::user::User;
+use sqlx::{Executor, MySql, MySqlPool, Transaction};
+
+use super::ProjectClassChangeIdParam;
+use crate::{
+    authorization::require_admin_user,
+    error::{MinimalApiError, NormalApiError},
+};
+
+#[tracing::instrument(name = "project_class_change_delete")]
+pub async fn project_class_change_delete(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    params: Path<ProjectClassChangeIdParam>,
+) -> Result<HttpResponse, NormalApiError> {
+    require_admin_user(&user)?;
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    delete_project_class_change_from_db(
+        &mut transaction,
+        params.project_class_change_id as u64,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[tracing::instrument(
+    name = "delete_project_class_change_from_db",
+    skip(transaction)
+)]
+async fn delete_project_class_change_from_db(
+    transaction: &mut Transaction<'_, MySql>,
+    project_class_change_id: u64,
+) -> Result<(), MinimalApiError> {
+    let query = sqlx::query!(
+        r#"
+        DELETE IGNORE FROM user_project_class_history
+        WHERE id = ?
+        "#,
+        project_class_change_id
+    );
+    let result = transaction
+        .execute(query)
+        .await
+        .context("Failed to execute delete query")?;
+    if result.rows_affected() == 0 {
+        return Err(MinimalApiError::ValidationError(
+            "Failed to delete project class change.".to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Path, ReqData},
+};
+use anyhow::Context;
+use avina_wire::user::User;
+use sqlx::MySqlPool;
+
+use super::ProjectClassChangeIdParam;
+use crate::{
+    authorization::require_project_user_or_return_not_found,
+    database::user::project_class_change::select_project_class_change_from_db,
+    error::OptionApiError,
+};
+
+#[tracing::instrument(name = "project_class_change_get")]
+pub async fn project_class_change_get(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    params: Path<ProjectClassChangeIdParam>,
+) -> Result<HttpResponse, OptionApiError> {
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let project_class_change = select_project_class_change_from_db(
+        &mut transaction,
+        params.project_class_change_id as u64,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    require_project_user_or_return_not_found(
+        &user,
+        project_class_change.project,
+    )?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(project_class_change))
+}
@@ -0,0 +1,52 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Query, ReqData},
+};
+use anyhow::Context;
+use avina_wire::user::{ProjectClassChangeListParams, User};
+use sqlx::MySqlPool;
+
+use crate::{
+    authorization::{require_admin_user, require_project_user},
+    database::user::project_class_change::{
+        select_all_project_class_changes_from_db,
+        select_project_class_changes_by_project_from_db,
+    },
+    error::NormalApiError,
+};
+
+#[tracing::instrument(name = "project_class_change_list")]
+pub async fn project_class_change_list(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    params: Query<ProjectClassChangeListParams>,
+) -> Result<HttpResponse, NormalApiError> {
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let project_class_changes = if params.all.unwrap_or(false) {
+        require_admin_user(&user)?;
+        select_all_project_class_changes_from_db(&mut transaction).await?
+    } else if let Some(project_id) = params.project {
+        require_project_user(&user, project_id)?;
+        select_project_class_changes_by_project_from_db(
+            &mut transaction,
+            project_id as u64,
+        )
+        .await?
+    } else {
+        select_project_class_changes_by_project_from_db(
+            &mut transaction,
+            user.project as u64,
+        )
+        .await?
+    };
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(project_class_changes))
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use actix_web::{
+    Scope,
+    web::{delete, get, patch, post, scope},
+};
+use avina_wire::user::{Project, ProjectClassChange, UserClass};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::{MySql, Transaction};
+
+use crate::{
+    database::user::project_class_change::select_all_project_class_changes_from_db,
+    error::UnexpectedOnlyError,
+};
+
+mod create;
+use create::project_class_change_create;
+mod list;
+use list::project_class_change_list;
+mod get;
+use get::project_class_change_get;
+mod modify;
+use modify::project_class_change_modify;
+mod delete;
+use delete::project_class_change_delete;
+
+pub fn project_class_changes_scope() -> Scope {
+    scope("/projectclasschanges")
+        .route("/", post().to(project_class_change_create))
+        .route("", get().to(project_class_change_list))
+        .route(
+            "/{project_class_change_id}",
+            get().to(project_class_change_get),
+        )
+        .route(
+            "/{project_class_change_id}/",
+            patch().to(project_class_change_modify),
+        )
+        .route(
+            "/{project_class_change_id}/",
+            delete().to(project_class_change_delete),
+        )
+}
+
+#[derive(Deserialize, Debug)]
+struct ProjectClassChangeIdParam {
+    #[allow(unused)]
+    project_class_change_id: u32,
+}
+
+/// User classes of each project, ordered by the time from which they are in
+/// effect. The first class of a project also applies before its start time.
+#[derive(Default)]
+pub struct UserClassHistory(HashMap<u32, Vec<(DateTime<Utc>, UserClass)>>);
+
+impl UserClassHistory {
+    pub async fn load(
+        transaction: &mut Transaction<'_, MySql>,
+    ) -> Result<Self, UnexpectedOnlyError> {
+        Ok(select_all_project_class_changes_from_db(transaction)
+            .await?
+            .into())
+    }
+
+    /// Returns the user class of the project in effect at the given time,
+    /// or its current one when it has no history.
+    pub fn user_class(
+        &self,
+        project: &Project,
+        at: DateTime<Utc>,
+    ) -> UserClass {
+        let Some(changes) = self.0.get(&project.id) else {
+            return project.user_class;
+        };
+        changes
+            .iter()
+            .take_while(|(start_time, _)| *start_time <= at)
+            .last()
+            .or(changes.first())
+            .map_or(project.user_class, |(_, user_class)| *user_class)
+    }
+
+    /// Returns the times after begin and before end at which any project
+    /// changes its user class.
+    pub fn changes_within(
+        &self,
+        begin: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Vec<DateTime<Utc>> {
+        let mut changes = self
+            .0
+            .values()
+            .flatten()
+            .map(|(start_time, _)| *start_time)
+            .filter(|start_time| begin < *start_time && *start_time < end)
+            .collect::<Vec<_>>();
+        changes.sort();
+        changes.dedup();
+        changes
+    }
+}
+
+impl From<Vec<ProjectClassChange>> for UserClassHistory {
+    fn from(changes: Vec<ProjectClassChange>) -> Self {
+        let mut map: HashMap<u32, Vec<(DateTime<Utc>, UserClass)>> =
+            HashMap::new();
+        for change in changes {
+            map.entry(change.project)
+                .or_default()
+                .push((change.start_time.to_utc(), change.user_class));
+        }
+        for changes in map.values_mut() {
+            changes.sort_by_key(|(start_time, _)| *start_time);
+        }
+        Self(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn day(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 7, day, 0, 0, 0).unwrap()
+    }
+
+    fn change(
+        project: u32,
+        user_class: UserClass,
+        day: DateTime<Utc>,
+    ) -> ProjectClassChange {
+        ProjectClassChange {
+            id: 0,
+            project,
+            project_name: format!("project{project}"),
+            user_class,
+            start_time: day.fixed_offset(),
+        }
+    }
+
+    #[test]
+    fn user_class_is_the_one_in_effect() {
+        let history = UserClassHistory::from(vec![
+            change(1, UserClass::UC1, day(15)),
+            change(1, UserClass::UC3, day(1)),
+            change(2, UserClass::UC2, day(10)),
+        ]);
+        let project = |id| Project {
+            id,
+            name: format!("project{id}"),
+            openstack_id: String::new(),
+            user_class: UserClass::UC6,
+        };
+        assert_eq!(history.user_class(&project(1), day(14)), UserClass::UC3);
+        assert_eq!(history.user_class(&project(1), day(15)), UserClass::UC1);
+        assert_eq!(history.user_class(&project(2), day(1)), UserClass::UC2);
+        assert_eq!(history.user_class(&project(3), day(1)), UserClass::UC6);
+        assert_eq!(history.changes_within(day(1), day(15)), vec![day(10)]);
+        assert_eq!(
+            history.changes_within(day(1), day(16)),
+            vec![day(10), day(15)]
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use actix_web::{
+    HttpResponse,
+    web::{Data, Json, Path, ReqData},
+};
+use anyhow::Context;
+use avina_wire::user::{
+    ProjectClassChange, ProjectClassChangeModifyData, User,
+};
+use sqlx::{Executor, MySql, MySqlPool, Transaction};
+
+use super::ProjectClassChangeIdParam;
+use crate::{
+    authorization::require_admin_user,
+    database::user::project_class_change::select_project_class_change_from_db,
+    error::{NotFoundOrUnexpectedApiError, OptionApiError},
+};
+
+#[tracing::instrument(name = "project_class_change_modify")]
+pub async fn project_class_change_modify(
+    user: ReqData<User>,
+    db_pool: Data<MySqlPool>,
+    data: Json<ProjectClassChangeModifyData>,
+    params: Path<ProjectClassChangeIdParam>,
+) -> Result<HttpResponse, OptionApiError> {
+    require_admin_user(&user)?;
+    if data.id != params.project_class_change_id {
+        return Err(OptionApiError::ValidationError(
+            "ID in URL does not match ID in body".to_string(),
+        ));
+    }
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let project_class_change =
+        update_project_class_change_in_db(&mut transaction, &data).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .json(project_class_change))
+}
+
+#[tracing::instrument(
+    name = "update_project_class_change_in_db",
+    skip(data, transaction)
+)]
+pub async fn update_project_class_change_in_db(
+    transaction: &mut Transaction<'_, MySql>,
+    data: &ProjectClassChangeModifyData,
+) -> Result<ProjectClassChange, NotFoundOrUnexpectedApiError> {
+    let row = select_project_class_change_from_db(transaction, data.id as u64)
+        .await?;
+    let user_class = data.user_class.unwrap_or(row.user_class);
+    let start_time = data.start_time.unwrap_or(row.start_time);
+    let query = sqlx::query!(
+        r#"
+        UPDATE user_project_class_history
+        SET user_class = ?, start_time = ?
+        WHERE id = ?
+        "#,
+        user_class as u32,
+        start_time.to_utc(),
+        data.id,
+    );
+    transaction
+        .execute(query)
+        .await
+        .context("Failed to execute update query")?;
+    Ok(ProjectClassChange {
+        id: data.id,
+        project: row.project,
+        project_name: row.project_name,
+        user_class,
+        start_time,
+    })
+}
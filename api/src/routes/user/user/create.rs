@@ -3,7 +3,7 @@ use actix_web::{
     web::{Data, Json, ReqData},
 };
 use anyhow::Context;
-use avina_wire::user::{User, UserCreateData};
+use avina_wire::user::{Role, User, UserCreateData};
 use sqlx::{Executor, MySql, MySqlPool, Transaction};
 
 use crate::{
@@ -16,24 +16,23 @@ pub struct NewUser {
     pub name: String,
     pub openstack_id: String,
     pub project_id: u32,
-    pub role: u32,
+    pub role: Role,
     pub is_staff: bool,
     pub is_active: bool,
 }
 
-// TODO: validate that role is in range 0-2 or 3
 impl TryFrom<UserCreateData> for NewUser {
     type Error = String;
 
     // TODO: we might need a more complex function with access to the database
     //       and the transaction
     fn try_from(data: UserCreateData) -> Result<Self, Self::Error> {
-        // TODO really validate data, role range, uuid, string length
+        // TODO really validate data, uuid, string length
         Ok(Self {
             name: data.name,
             openstack_id: data.openstack_id,
             project_id: data.project,
-            role: data.role.unwrap_or(1),
+            role: data.role.unwrap_or(Role::User),
             is_staff: data.is_staff.unwrap_or(false),
             is_active: data.is_active.unwrap_or(true),
         })
@@ -94,7 +93,7 @@ pub async fn insert_user_into_db(
         new_user.name,
         new_user.openstack_id,
         new_user.project_id,
-        new_user.role,
+        u32::from(new_user.role),
         new_user.is_staff,
         new_user.is_active,
     );
@@ -62,7 +62,7 @@ pub async fn update_user_in_db(
         name,
         openstack_id,
         project_id,
-        role,
+        u32::from(role),
         is_staff,
         is_active,
         data.id,
@@ -0,0 +1,429 @@
+use std::{str::FromStr, time::Duration};
+
+use actix_web::web::Data;
+use anyhow::{Context, anyhow};
+use avina_wire::{
+    accounting::ServerStateImport,
+    budgeting::UserBudgetSync,
+    scheduler::{JobName, JobRun, JobRunStatus},
+};
+use chrono::{DateTime, TimeDelta, Utc};
+use sqlx::{MySql, MySqlPool, pool::PoolConnection};
+
+use crate::{
+    configuration::{AccountingSettings, JobSettings, SchedulerSettings},
+    database::{
+        budgeting::user_budget::sync_user_budgets_in_db,
+        scheduler::{
+            acquire_job_lock, finish_job_run_in_db, insert_job_run_into_db,
+            release_job_lock, select_last_job_run_from_db,
+        },
+    },
+    openstack::OpenStack,
+    routes::{
+        accounting::{
+            floating_ip_state::import::import_floating_ip_states,
+            server_state::{
+                compact::compact_server_states, import::import_server_states,
+            },
+            volume_state::import::import_volume_states,
+        },
+        resources::flavor::import::import_flavors,
+        user::import::import_users,
+    },
+};
+
+// NOTE: wait this long before looking at a job again, after it could not be
+// run because of an error or because another replica was running it.
+const RETRY_DELAY: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug)]
+pub enum Schedule {
+    Interval(TimeDelta),
+    Cron(Box<cron::Schedule>),
+}
+
+impl Schedule {
+    pub fn from_settings(
+        settings: &JobSettings,
+    ) -> Result<Self, anyhow::Error> {
+        match (settings.interval, &settings.cron) {
+            (Some(0), None) => {
+                Err(anyhow!("Interval of job {} must not be 0", settings.job))
+            }
+            (Some(interval), None) => {
+                Ok(Schedule::Interval(TimeDelta::seconds(interval as i64)))
+            }
+            (None, Some(cron)) => Ok(Schedule::Cron(Box::new(
+                cron::Schedule::from_str(cron).context(format!(
+                    "Invalid cron expression for job {}",
+                    settings.job
+                ))?,
+            ))),
+            _ => Err(anyhow!(
+                "Job {} needs either an interval or a cron expression",
+                settings.job
+            )),
+        }
+    }
+
+    /// Returns when the job is due next, given when it was last started.
+    /// Jobs that never ran, or missed a run, are due immediately.
+    pub fn next_run(
+        &self,
+        last_started_at: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        match (self, last_started_at) {
+            (Schedule::Interval(_), None) => Some(now),
+            (Schedule::Interval(interval), Some(last_started_at)) => {
+                Some(last_started_at + *interval)
+            }
+            (Schedule::Cron(schedule), last_started_at) => {
+                schedule.after(&last_started_at.unwrap_or(now)).next()
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Schedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Schedule::Interval(interval) => {
+                write!(f, "every {}s", interval.num_seconds())
+            }
+            Schedule::Cron(schedule) => f.write_str(schedule.source()),
+        }
+    }
+}
+
+pub struct Scheduler {
+    enabled: bool,
+    jobs: Vec<(JobName, Schedule)>,
+}
+
+impl Scheduler {
+    pub fn from_settings(
+        settings: &SchedulerSettings,
+    ) -> Result<Self, anyhow::Error> {
+        let jobs = settings
+            .jobs
+            .iter()
+            .map(|job| Ok((job.job, Schedule::from_settings(job)?)))
+            .collect::<Result<Vec<_>, anyhow::Error>>()?;
+        Ok(Self {
+            enabled: settings.enabled,
+            jobs,
+        })
+    }
+
+    /// Returns the schedule of the job, if it is run periodically.
+    pub fn schedule(&self, job: JobName) -> Option<&Schedule> {
+        if !self.enabled {
+            return None;
+        }
+        self.jobs
+            .iter()
+            .find(|(name, _)| *name == job)
+            .map(|(_, schedule)| schedule)
+    }
+
+    pub fn spawn(
+        &self,
+        db_pool: MySqlPool,
+        openstack: Data<OpenStack>,
+        accounting: Data<AccountingSettings>,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        for (job, schedule) in &self.jobs {
+            tokio::spawn(run_periodically(
+                *job,
+                schedule.clone(),
+                db_pool.clone(),
+                openstack.clone(),
+                accounting.clone(),
+            ));
+        }
+    }
+}
+
+async fn select_last_started_at(
+    db_pool: &MySqlPool,
+    job: JobName,
+) -> Result<Option<DateTime<Utc>>, anyhow::Error> {
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let last_run = select_last_job_run_from_db(&mut transaction, job).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(last_run.map(|run| run.started_at.to_utc()))
+}
+
+async fn run_periodically(
+    job: JobName,
+    schedule: Schedule,
+    db_pool: MySqlPool,
+    openstack: Data<OpenStack>,
+    accounting: Data<AccountingSettings>,
+) {
+    loop {
+        // NOTE: the next run is derived from the job history, so replicas
+        // agree on it and a restart does not reset the interval.
+        let last_started_at = match select_last_started_at(&db_pool, job).await
+        {
+            Ok(last_started_at) => last_started_at,
+            Err(error) => {
+                tracing::error!("Failed to look up last run of {job}: {error}");
+                tokio::time::sleep(RETRY_DELAY).await;
+                continue;
+            }
+        };
+        let Some(next_run) = schedule.next_run(last_started_at, Utc::now())
+        else {
+            tracing::warn!("Job {job} has no upcoming runs, stopping it");
+            return;
+        };
+        if let Ok(delay) = (next_run - Utc::now()).to_std() {
+            tokio::time::sleep(delay).await;
+        }
+        match run_job(
+            &db_pool,
+            &openstack,
+            &accounting,
+            job,
+            None,
+            Some(next_run),
+        )
+        .await
+        {
+            Ok(Some(run)) => {
+                tracing::info!("Job {job} finished with status {}", run.status);
+            }
+            Ok(None) => {
+                tracing::info!("Job {job} was already run by another replica");
+                tokio::time::sleep(RETRY_DELAY).await;
+            }
+            Err(error) => {
+                tracing::error!("Failed to run job {job}: {error}");
+                tokio::time::sleep(RETRY_DELAY).await;
+            }
+        }
+    }
+}
+
+async fn execute_job(
+    db_pool: &MySqlPool,
+    openstack: &OpenStack,
+    accounting: &AccountingSettings,
+    job: JobName,
+) -> Result<String, anyhow::Error> {
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let result = match job {
+        JobName::ServerStateImport => {
+            serde_json::to_string(&ServerStateImport::from(
+                &import_server_states(&mut transaction, openstack).await?,
+            ))
+        }
+        JobName::ServerStateCompaction => serde_json::to_string(
+            &compact_server_states(
+                &mut transaction,
+                accounting.retention_years,
+            )
+            .await?,
+        ),
+        JobName::UserImport => serde_json::to_string(
+            &import_users(&mut transaction, openstack).await?,
+        ),
+        JobName::FlavorImport => serde_json::to_string(
+            &import_flavors(&mut transaction, openstack).await?,
+        ),
+        JobName::UserBudgetSync => serde_json::to_string(&UserBudgetSync {
+            updated_budget_count: sync_user_budgets_in_db(&mut transaction)
+                .await? as u32,
+        }),
+        JobName::VolumeStateImport => serde_json::to_string(
+            &import_volume_states(&mut transaction, openstack).await?,
+        ),
+        JobName::FloatingIpStateImport => serde_json::to_string(
+            &import_floating_ip_states(&mut transaction, openstack).await?,
+        ),
+    }
+    .context("Failed to serialize job result")?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+    Ok(result)
+}
+
+async fn run_job_locked(
+    db_pool: &MySqlPool,
+    openstack: &OpenStack,
+    accounting: &AccountingSettings,
+    job: JobName,
+    triggered_by: Option<u32>,
+    due: Option<DateTime<Utc>>,
+) -> Result<Option<JobRun>, anyhow::Error> {
+    if let Some(due) = due
+        && select_last_started_at(db_pool, job)
+            .await?
+            .is_some_and(|last_started_at| last_started_at >= due)
+    {
+        return Ok(None);
+    }
+
+    let started_at = Utc::now();
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let id =
+        insert_job_run_into_db(&mut transaction, job, started_at, triggered_by)
+            .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+
+    let (status, message) =
+        match execute_job(db_pool, openstack, accounting, job).await {
+            Ok(result) => (JobRunStatus::Succeeded, result),
+            Err(error) => (JobRunStatus::Failed, error.to_string()),
+        };
+    let finished_at = Utc::now();
+
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    finish_job_run_in_db(
+        &mut transaction,
+        id,
+        status,
+        finished_at,
+        Some(message.clone()),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit transaction")?;
+
+    Ok(Some(JobRun {
+        id: id as u32,
+        job,
+        status,
+        started_at: started_at.fixed_offset(),
+        finished_at: Some(finished_at.fixed_offset()),
+        triggered_by,
+        message: Some(message),
+    }))
+}
+
+/// Holds the advisory lock of a job on its own connection. If the lock is
+/// not released explicitly, because the job future was dropped or panicked,
+/// the connection is detached from the pool and closed when this is dropped,
+/// which makes the database release the lock with the session.
+struct JobLock {
+    connection: Option<PoolConnection<MySql>>,
+    job: JobName,
+}
+
+impl JobLock {
+    async fn acquire(
+        db_pool: &MySqlPool,
+        job: JobName,
+    ) -> Result<Option<Self>, anyhow::Error> {
+        let mut connection = db_pool
+            .acquire()
+            .await
+            .context("Failed to acquire connection")?;
+        if !acquire_job_lock(&mut connection, job).await? {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            connection: Some(connection),
+            job,
+        }))
+    }
+
+    async fn release(mut self) -> Result<(), anyhow::Error> {
+        if let Some(mut connection) = self.connection.take() {
+            release_job_lock(&mut connection, self.job).await?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for JobLock {
+    fn drop(&mut self) {
+        if let Some(connection) = self.connection.take() {
+            tracing::warn!(
+                "Lock of job {} was not released, closing its connection",
+                self.job
+            );
+            drop(connection.detach());
+        }
+    }
+}
+
+/// Runs the job unless it is already running, which is checked with a
+/// database advisory lock, so this is safe across multiple replicas. When
+/// `due` is given, the job is also skipped if it was started since then.
+pub async fn run_job(
+    db_pool: &MySqlPool,
+    openstack: &OpenStack,
+    accounting: &AccountingSettings,
+    job: JobName,
+    triggered_by: Option<u32>,
+    due: Option<DateTime<Utc>>,
+) -> Result<Option<JobRun>, anyhow::Error> {
+    let Some(lock) = JobLock::acquire(db_pool, job).await? else {
+        return Ok(None);
+    };
+    let result =
+        run_job_locked(db_pool, openstack, accounting, job, triggered_by, due)
+            .await;
+    lock.release().await?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interval_schedule_is_due_after_last_run() {
+        let now = Utc::now();
+        let schedule = Schedule::Interval(TimeDelta::seconds(300));
+        assert_eq!(schedule.next_run(None, now), Some(now));
+        assert_eq!(
+            schedule.next_run(Some(now), now),
+            Some(now + TimeDelta::seconds(300))
+        );
+    }
+
+    #[test]
+    fn job_settings_need_exactly_one_schedule() {
+        let settings = JobSettings {
+            job: JobName::UserBudgetSync,
+            interval: None,
+            cron: Some("0 0 4 * * *".to_string()),
+        };
+        assert!(Schedule::from_settings(&settings).is_ok());
+        let settings = JobSettings {
+            interval: Some(60),
+            ..settings
+        };
+        assert!(Schedule::from_settings(&settings).is_err());
+    }
+}
@@ -1,28 +1,34 @@
 use std::net::TcpListener;
 
-use actix_cors::Cors;
 use actix_web::{
     App, HttpServer, dev::Server, middleware::from_fn, web, web::Data,
 };
 use anyhow::Context;
-use avina_wire::user::UserClass;
+use avina_wire::user::{Role, UserClass};
+use rustls::ServerConfig;
 use sqlx::{MySqlPool, mysql::MySqlPoolOptions};
 use tracing_actix_web::TracingLogger;
 
 use crate::{
+    audit::record_audit_log,
     authentication::{extract_user_and_project, require_valid_token},
-    configuration::{DatabaseSettings, Settings},
+    configuration::{
+        AccountingSettings, CorsSettings, DatabaseSettings, Settings,
+    },
     error::{MinimalApiError, not_found},
     openstack::OpenStack,
     routes::{
-        accounting_scope, budgeting_scope, health_check, hello_scope,
-        pricing_scope, quota_scope, resources_scope,
+        accounting_scope, audit_scope, budgeting_scope, health_check,
+        hello_scope, pricing_scope, quota_scope, readiness, resources_scope,
+        scheduler_scope, token_cache_scope,
         user::{
             project::create::{NewProject, insert_project_into_db},
             user::create::{NewUser, insert_user_into_db},
         },
         user_scope,
     },
+    scheduler::Scheduler,
+    token_cache::TokenCache,
 };
 
 pub struct Application {
@@ -45,13 +51,21 @@ impl Application {
         }
 
         let openstack = OpenStack::new(configuration.openstack).await?;
+        let token_cache = TokenCache::from_settings(&configuration.application);
+        let tls_config = configuration.application.tls.server_config()?;
+        let scheduler = Scheduler::from_settings(&configuration.scheduler)?;
 
         let server = run(
             listener,
+            tls_config,
+            configuration.application.cors,
             connection_pool,
             configuration.application.base_url,
             openstack,
+            token_cache,
+            scheduler,
             configuration.application.cloud_usage_url,
+            configuration.accounting,
         )
         .await?;
 
@@ -86,7 +100,7 @@ impl Application {
             name: configuration.openstack.project.clone(),
             openstack_id: configuration.openstack.project_id.clone(),
             project_id: project_id as u32,
-            role: 1,
+            role: Role::User,
             is_staff: true,
             is_active: true,
         };
@@ -121,34 +135,47 @@ pub struct ApplicationBaseUrl(pub String);
 #[derive(Debug)]
 pub struct CloudUsageUrl(pub Option<String>);
 
+#[allow(clippy::too_many_arguments)]
 async fn run(
     listener: TcpListener,
+    tls_config: Option<ServerConfig>,
+    cors: CorsSettings,
     db_pool: MySqlPool,
     base_url: String,
     openstack: OpenStack,
+    token_cache: TokenCache,
+    scheduler: Scheduler,
     cloud_usage_url: Option<String>,
+    accounting: AccountingSettings,
 ) -> Result<Server, anyhow::Error> {
     let db_pool = Data::new(db_pool);
     let base_url = Data::new(ApplicationBaseUrl(base_url));
     let openstack = Data::new(openstack);
+    let token_cache = Data::new(token_cache);
     let cloud_usage_url = Data::new(CloudUsageUrl(cloud_usage_url));
+    let accounting = Data::new(accounting);
+    scheduler.spawn(
+        db_pool.get_ref().clone(),
+        openstack.clone(),
+        accounting.clone(),
+    );
+    let scheduler = Data::new(scheduler);
     let server = HttpServer::new(move || {
-        // TODO: this should be configurable
-        let cors = Cors::default()
-            .allowed_origin("http://localhost:8080")
-            .allow_any_header()
-            .allow_any_method()
-            .expose_any_header();
         App::new()
-            .wrap(cors)
+            .wrap(cors.cors())
             .wrap(TracingLogger::default())
             .app_data(db_pool.clone())
             .app_data(base_url.clone())
             .app_data(openstack.clone())
+            .app_data(token_cache.clone())
             .app_data(cloud_usage_url.clone())
+            .app_data(scheduler.clone())
+            .app_data(accounting.clone())
             .route("/health_check", web::get().to(health_check))
+            .route("/ready", web::get().to(readiness))
             .service(
                 web::scope("/api")
+                    .wrap(from_fn(record_audit_log))
                     .wrap(from_fn(extract_user_and_project))
                     .wrap(from_fn(require_valid_token))
                     .route("/secured_health_check", web::get().to(health_check))
@@ -158,11 +185,17 @@ async fn run(
                     .service(resources_scope())
                     .service(pricing_scope())
                     .service(budgeting_scope())
-                    .service(quota_scope()),
+                    .service(quota_scope())
+                    .service(audit_scope())
+                    .service(scheduler_scope())
+                    .service(token_cache_scope()),
             )
             .default_service(web::route().to(not_found))
-    })
-    .listen(listener)?
+    });
+    let server = match tls_config {
+        Some(tls_config) => server.listen_rustls_0_23(listener, tls_config)?,
+        None => server.listen(listener)?,
+    }
     .run();
 
     Ok(server)
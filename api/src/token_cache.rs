@@ -0,0 +1,194 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::{configuration::ApplicationSettings, openstack::ProjectMinimal};
+
+struct Entry {
+    project: ProjectMinimal,
+    expires: Instant,
+}
+
+// NOTE: tokens are only kept as SHA-256 hashes, so a memory dump of the
+// cache does not leak usable credentials.
+pub struct TokenCache {
+    entries: Mutex<HashMap<String, Entry>>,
+    capacity: usize,
+    max_ttl: Duration,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TokenCacheStats {
+    pub size: usize,
+    pub capacity: usize,
+    pub max_ttl: u64,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+impl TokenCache {
+    pub fn new(capacity: usize, max_ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            capacity,
+            max_ttl,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn from_settings(settings: &ApplicationSettings) -> Self {
+        Self::new(
+            settings.token_cache_capacity,
+            Duration::from_secs(settings.token_cache_max_ttl),
+        )
+    }
+
+    pub fn get(&self, token: &str) -> Option<ProjectMinimal> {
+        let key = hash_token(token);
+        let mut entries = self.entries.lock().unwrap();
+        let project = match entries.get(&key) {
+            Some(entry) if entry.expires > Instant::now() => {
+                Some(entry.project.clone())
+            }
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        };
+        match project {
+            Some(_) => self.hits.fetch_add(1, Ordering::Relaxed),
+            None => self.misses.fetch_add(1, Ordering::Relaxed),
+        };
+        project
+    }
+
+    pub fn insert(
+        &self,
+        token: &str,
+        project: ProjectMinimal,
+        expires_at: Option<DateTime<Utc>>,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+        let ttl = match expires_at {
+            Some(expires_at) => match (expires_at - Utc::now()).to_std() {
+                Ok(remaining) => remaining.min(self.max_ttl),
+                // token already expired, nothing to cache
+                Err(_) => return,
+            },
+            None => self.max_ttl,
+        };
+        if ttl.is_zero() {
+            return;
+        }
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= self.capacity {
+            entries.retain(|_, entry| entry.expires > now);
+        }
+        if entries.len() >= self.capacity {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.expires)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(
+            hash_token(token),
+            Entry {
+                project,
+                expires: now + ttl,
+            },
+        );
+    }
+
+    pub fn revoke(&self, token: &str) -> bool {
+        self.entries
+            .lock()
+            .unwrap()
+            .remove(&hash_token(token))
+            .is_some()
+    }
+
+    pub fn stats(&self) -> TokenCacheStats {
+        TokenCacheStats {
+            size: self.entries.lock().unwrap().len(),
+            capacity: self.capacity,
+            max_ttl: self.max_ttl.as_secs(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(name: &str) -> ProjectMinimal {
+        ProjectMinimal {
+            id: name.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn token_cache_returns_inserted_project() {
+        let cache = TokenCache::new(10, Duration::from_secs(60));
+        assert!(cache.get("token").is_none());
+        cache.insert("token", project("a"), None);
+        assert_eq!(cache.get("token").unwrap().name, "a");
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+    }
+
+    #[test]
+    fn token_cache_skips_expired_tokens() {
+        let cache = TokenCache::new(10, Duration::from_secs(60));
+        let expires_at = Utc::now() - chrono::Duration::seconds(1);
+        cache.insert("token", project("a"), Some(expires_at));
+        assert!(cache.get("token").is_none());
+        assert_eq!(cache.stats().size, 0);
+    }
+
+    #[test]
+    fn token_cache_is_bounded() {
+        let cache = TokenCache::new(2, Duration::from_secs(60));
+        cache.insert("a", project("a"), None);
+        cache.insert("b", project("b"), None);
+        cache.insert("c", project("c"), None);
+        assert_eq!(cache.stats().size, 2);
+        assert!(cache.get("a").is_none());
+        assert!(cache.get("c").is_some());
+    }
+
+    #[test]
+    fn token_cache_revoke_drops_entry() {
+        let cache = TokenCache::new(10, Duration::from_secs(60));
+        cache.insert("token", project("a"), None);
+        assert!(cache.revoke("token"));
+        assert!(!cache.revoke("token"));
+        assert!(cache.get("token").is_none());
+    }
+}
@@ -1,4 +1,4 @@
-use chrono::{DateTime, TimeZone, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, TimeZone, Utc};
 
 pub fn e400<T>(e: T) -> actix_web::Error
 where
@@ -18,3 +18,18 @@ pub fn start_of_the_year(year: u32) -> DateTime<Utc> {
     // TODO: handle this unwrap
     Utc.with_ymd_and_hms(year as i32, 1, 1, 1, 0, 0).unwrap()
 }
+
+/// Returns the fiscal year the time belongs to, which only starts with the
+/// start of the year as defined above.
+pub fn fiscal_year(time: DateTime<Utc>) -> u32 {
+    let year = time.year() as u32;
+    if time < start_of_the_year(year) {
+        year - 1
+    } else {
+        year
+    }
+}
+
+pub fn start_of_the_day(day: NaiveDate) -> DateTime<Utc> {
+    day.and_time(NaiveTime::MIN).and_utc()
+}
@@ -0,0 +1,57 @@
+use avina_test::spawn_app;
+
+#[tokio::test]
+async fn cors_preflight_allows_configured_origin() {
+    // arrange
+    let app = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    // act
+    let response = client
+        .request(
+            reqwest::Method::OPTIONS,
+            format!("{}/api/secured_health_check", app.address),
+        )
+        .header("Origin", "http://localhost:8080")
+        .header("Access-Control-Request-Method", "GET")
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // assert
+    assert!(response.status().is_success());
+    assert_eq!(
+        response
+            .headers()
+            .get("Access-Control-Allow-Origin")
+            .unwrap(),
+        "http://localhost:8080"
+    );
+}
+
+#[tokio::test]
+async fn cors_preflight_rejects_unknown_origin() {
+    // arrange
+    let app = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    // act
+    let response = client
+        .request(
+            reqwest::Method::OPTIONS,
+            format!("{}/api/secured_health_check", app.address),
+        )
+        .header("Origin", "http://example.com")
+        .header("Access-Control-Request-Method", "GET")
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // assert
+    assert!(
+        response
+            .headers()
+            .get("Access-Control-Allow-Origin")
+            .is_none()
+    );
+}
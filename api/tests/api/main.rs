@@ -1,2 +1,4 @@
+mod cors;
 mod health_check;
 mod hello;
+mod readiness;
@@ -0,0 +1,22 @@
+use avina_test::spawn_app;
+
+#[tokio::test]
+async fn readiness_reports_valid_openstack_token() {
+    // arrange
+    let app = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    // act
+    let response = client
+        .get(format!("{}/ready", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // assert
+    assert_eq!(response.status().as_u16(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["ready"], true);
+    assert_eq!(body["openstack_token"]["valid"], true);
+    assert!(body["openstack_token"]["last_error"].is_null());
+}
@@ -0,0 +1,151 @@
+use std::error::Error;
+
+use chrono::{DateTime, FixedOffset};
+use clap::Subcommand;
+
+use crate::common::{
+    Execute, Format, ask_for_confirmation, print_object_list,
+    print_single_object,
+};
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum BillingPolicyCommand {
+    #[clap(about = "List billing policies")]
+    List,
+
+    #[clap(visible_alias = "show", about = "Show billing policy with given ID")]
+    Get { id: u32 },
+
+    #[clap(about = "Create a new billing policy")]
+    Create {
+        #[clap(help = "Server status the policy applies to, e.g. SHUTOFF")]
+        status: String,
+
+        #[clap(
+            long,
+            short,
+            help = "Fraction of the flavor price billed, default: 1.0"
+        )]
+        multiplier: Option<f64>,
+
+        #[clap(long, short, help = "Start time of the policy, default: now")]
+        start_time: Option<DateTime<FixedOffset>>,
+    },
+
+    #[clap(about = "Modify a billing policy")]
+    Modify {
+        #[clap(help = "ID of the billing policy")]
+        id: u32,
+
+        #[clap(
+            long,
+            short = 'S',
+            help = "Server status the policy applies to"
+        )]
+        status: Option<String>,
+
+        #[clap(long, short, help = "Fraction of the flavor price billed")]
+        multiplier: Option<f64>,
+
+        #[clap(long, short, help = "Start time of the policy")]
+        start_time: Option<DateTime<FixedOffset>>,
+    },
+
+    #[clap(about = "Delete billing policy with given ID")]
+    Delete { id: u32 },
+}
+pub(crate) use BillingPolicyCommand::*;
+
+impl Execute for BillingPolicyCommand {
+    async fn execute(
+        &self,
+        api: avina::Api,
+        format: Format,
+    ) -> Result<(), Box<dyn Error>> {
+        match self {
+            List => list(api, format).await,
+            Get { id } => get(api, format, id).await,
+            Create {
+                status,
+                multiplier,
+                start_time,
+            } => {
+                create(api, format, status.to_owned(), *multiplier, *start_time)
+                    .await
+            }
+            Modify {
+                id,
+                status,
+                multiplier,
+                start_time,
+            } => {
+                modify(
+                    api,
+                    format,
+                    *id,
+                    status.to_owned(),
+                    *multiplier,
+                    *start_time,
+                )
+                .await
+            }
+            Delete { id } => delete(api, id).await,
+        }
+    }
+}
+
+async fn list(api: avina::Api, format: Format) -> Result<(), Box<dyn Error>> {
+    let request = api.billing_policy.list();
+    print_object_list(request.send().await?, format)
+}
+
+async fn get(
+    api: avina::Api,
+    format: Format,
+    id: &u32,
+) -> Result<(), Box<dyn Error>> {
+    print_single_object(api.billing_policy.get(*id).await?, format)
+}
+
+async fn create(
+    api: avina::Api,
+    format: Format,
+    status: String,
+    multiplier: Option<f64>,
+    start_time: Option<DateTime<FixedOffset>>,
+) -> Result<(), Box<dyn Error>> {
+    let mut request = api.billing_policy.create(status);
+    if let Some(multiplier) = multiplier {
+        request.multiplier(multiplier);
+    }
+    if let Some(start_time) = start_time {
+        request.start_time(start_time);
+    }
+    print_single_object(request.send().await?, format)
+}
+
+async fn modify(
+    api: avina::Api,
+    format: Format,
+    id: u32,
+    status: Option<String>,
+    multiplier: Option<f64>,
+    start_time: Option<DateTime<FixedOffset>>,
+) -> Result<(), Box<dyn Error>> {
+    let mut request = api.billing_policy.modify(id);
+    if let Some(status) = status {
+        request.status(status);
+    }
+    if let Some(multiplier) = multiplier {
+        request.multiplier(multiplier);
+    }
+    if let Some(start_time) = start_time {
+        request.start_time(start_time);
+    }
+    print_single_object(request.send().await?, format)
+}
+
+async fn delete(api: avina::Api, id: &u32) -> Result<(), Box<dyn Error>> {
+    ask_for_confirmation()?;
+    Ok(api.billing_policy.delete(*id).await?)
+}
@@ -0,0 +1,31 @@
+use std::error::Error;
+
+use clap::Subcommand;
+
+use crate::common::{Execute, Format, print_single_object};
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum DailyUsageCommand {
+    #[clap(about = "Aggregate the usage of all closed days not aggregated yet")]
+    Backfill,
+}
+pub(crate) use DailyUsageCommand::*;
+
+impl Execute for DailyUsageCommand {
+    async fn execute(
+        &self,
+        api: avina::Api,
+        format: Format,
+    ) -> Result<(), Box<dyn Error>> {
+        match self {
+            Backfill => backfill(api, format).await,
+        }
+    }
+}
+
+async fn backfill(
+    api: avina::Api,
+    format: Format,
+) -> Result<(), Box<dyn Error>> {
+    print_single_object(api.daily_usage.backfill().await?, format)
+}
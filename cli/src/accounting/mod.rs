@@ -1,9 +1,21 @@
+mod billing_policy;
+mod daily_usage;
+mod floating_ip_state;
 mod server_consumption;
 mod server_cost;
 mod server_state;
+mod unmatched_server;
+mod volume_cost;
+mod volume_state;
 
+pub(crate) use billing_policy::BillingPolicyCommand;
+pub(crate) use daily_usage::DailyUsageCommand;
+pub(crate) use floating_ip_state::FloatingIpStateCommand;
 pub(crate) use server_consumption::{
     ServerConsumptionFilter, server_consumption,
 };
 pub(crate) use server_cost::{ServerCostFilter, server_cost};
 pub(crate) use server_state::ServerStateCommand;
+pub(crate) use unmatched_server::UnmatchedServerCommand;
+pub(crate) use volume_cost::{VolumeCostFilter, volume_cost};
+pub(crate) use volume_state::VolumeStateCommand;
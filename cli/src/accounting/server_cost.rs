@@ -1,5 +1,6 @@
 use std::error::Error;
 
+use avina_wire::accounting::Granularity;
 use chrono::{DateTime, FixedOffset};
 use clap::Args;
 use uuid::Uuid;
@@ -52,7 +53,19 @@ pub(crate) async fn server_cost(
     end: Option<DateTime<FixedOffset>>,
     filter: ServerCostFilter,
     detail: bool,
+    granularity: Option<Granularity>,
 ) -> Result<(), Box<dyn Error>> {
+    if let Some(granularity) = granularity {
+        return server_cost_series(
+            api,
+            begin,
+            end,
+            filter,
+            detail,
+            granularity,
+        )
+        .await;
+    }
     let mut request = api.server_cost.get();
     if let Some(begin) = begin {
         request.begin(begin);
@@ -91,3 +104,47 @@ pub(crate) async fn server_cost(
         }
     }
 }
+
+async fn server_cost_series(
+    api: avina::Api,
+    begin: Option<DateTime<FixedOffset>>,
+    end: Option<DateTime<FixedOffset>>,
+    filter: ServerCostFilter,
+    detail: bool,
+    granularity: Granularity,
+) -> Result<(), Box<dyn Error>> {
+    let mut request = api.server_cost.series(granularity);
+    if let Some(begin) = begin {
+        request.begin(begin);
+    }
+    if let Some(end) = end {
+        request.end(end);
+    }
+    if detail {
+        if let Some(server) = filter.server {
+            print_json(request.server_detail(server).await?)
+        } else if let Some(user) = filter.user {
+            let user_id = user_find_id(&api, &user).await?;
+            print_json(request.user_detail(user_id).await?)
+        } else if let Some(project) = filter.project {
+            let project_id = project_find_id(&api, &project).await?;
+            print_json(request.project_detail(project_id).await?)
+        } else if filter.all {
+            print_json(request.all_detail().await?)
+        } else {
+            print_json(request.mine_detail().await?)
+        }
+    } else if let Some(server) = filter.server {
+        print_json(request.server(server).await?)
+    } else if let Some(user) = filter.user {
+        let user_id = user_find_id(&api, &user).await?;
+        print_json(request.user(user_id).await?)
+    } else if let Some(project) = filter.project {
+        let project_id = project_find_id(&api, &project).await?;
+        print_json(request.project(project_id).await?)
+    } else if filter.all {
+        print_json(request.all().await?)
+    } else {
+        print_json(request.mine().await?)
+    }
+}
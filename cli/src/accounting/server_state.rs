@@ -7,7 +7,7 @@ use uuid::Uuid;
 #[cfg(not(feature = "resources"))]
 use crate::common::find_id as flavor_find_id;
 use crate::common::{
-    Execute, Format, ask_for_confirmation, print_object_list,
+    Execute, Format, ask_for_confirmation, print_json, print_object_list,
     print_single_object,
 };
 #[cfg(not(feature = "user"))]
@@ -145,6 +145,26 @@ pub(crate) enum ServerStateCommand {
             help = "Suppress output if nothing is imported"
         )]
         quiet: bool,
+
+        #[clap(
+            long,
+            action,
+            help = "Only show what would be imported without storing it"
+        )]
+        dry_run: bool,
+    },
+
+    #[clap(
+        about = "Merge adjacent identical server states and archive the ones of old fiscal years"
+    )]
+    Compact,
+
+    #[clap(
+        about = "Show the states of a server over time with their duration and cost"
+    )]
+    Timeline {
+        #[clap(help = "OpenStack UUIDv4 of the server")]
+        server: Uuid,
     },
 }
 pub(crate) use ServerStateCommand::*;
@@ -205,7 +225,11 @@ impl Execute for ServerStateCommand {
                 .await
             }
             Delete { id } => delete(api, id).await,
-            Import { quiet } => import(api, format, *quiet).await,
+            Import { quiet, dry_run } => {
+                import(api, format, *quiet, *dry_run).await
+            }
+            Compact => compact(api, format).await,
+            Timeline { server } => timeline(api, format, *server).await,
         }
     }
 }
@@ -317,10 +341,56 @@ async fn import(
     api: avina::Api,
     format: Format,
     quiet: bool,
+    dry_run: bool,
 ) -> Result<(), Box<dyn Error>> {
+    if dry_run {
+        return import_dry_run(api, format, quiet).await;
+    }
     let result = api.server_state.import().await?;
     if !quiet || result.new_state_count > 0 || result.end_state_count > 0 {
         return print_single_object(result, format);
     }
     Ok(())
 }
+
+async fn compact(
+    api: avina::Api,
+    format: Format,
+) -> Result<(), Box<dyn Error>> {
+    ask_for_confirmation()?;
+    print_single_object(api.server_state.compact().await?, format)
+}
+
+async fn timeline(
+    api: avina::Api,
+    format: Format,
+    server: Uuid,
+) -> Result<(), Box<dyn Error>> {
+    print_object_list(api.server_state.timeline(server).await?, format)
+}
+
+async fn import_dry_run(
+    api: avina::Api,
+    format: Format,
+    quiet: bool,
+) -> Result<(), Box<dyn Error>> {
+    let diff = api.server_state.import_dry_run().await?;
+    if quiet
+        && diff.new_states.is_empty()
+        && diff.ended_states.is_empty()
+        && diff.skipped_servers.is_empty()
+    {
+        return Ok(());
+    }
+    match format {
+        Format::Json => print_json(diff),
+        Format::Table(_) => {
+            println!("New states:");
+            print_object_list(diff.new_states, format.clone())?;
+            println!("Ended states:");
+            print_object_list(diff.ended_states, format.clone())?;
+            println!("Skipped servers:");
+            print_object_list(diff.skipped_servers, format)
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use std::error::Error;
+
+use clap::Subcommand;
+
+#[cfg(not(feature = "resources"))]
+use crate::common::find_id as flavor_find_id;
+#[cfg(not(feature = "user"))]
+use crate::common::find_id as user_find_id;
+use crate::common::{Execute, Format, print_object_list, print_single_object};
+#[cfg(feature = "resources")]
+use crate::resources::flavor::find_id as flavor_find_id;
+#[cfg(feature = "user")]
+use crate::user::user::find_id as user_find_id;
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum UnmatchedServerCommand {
+    #[clap(about = "List servers with unknown flavor or user")]
+    List {
+        #[clap(short, long, help = "Also list resolved servers", action)]
+        all: bool,
+    },
+
+    #[clap(about = "Map unmatched server with given ID or ignore it")]
+    Resolve {
+        id: u32,
+
+        #[clap(
+            short,
+            long,
+            help = "Name or ID of the flavor to bill the server with"
+        )]
+        flavor: Option<String>,
+
+        #[clap(
+            short,
+            long,
+            help = "Name, ID, or OpenStack ID of the user to bill the server to"
+        )]
+        user: Option<String>,
+
+        #[clap(
+            short,
+            long,
+            help = "Do not bill the server at all",
+            action,
+            conflicts_with_all = ["flavor", "user"]
+        )]
+        ignore: bool,
+    },
+}
+pub(crate) use UnmatchedServerCommand::*;
+
+impl Execute for UnmatchedServerCommand {
+    async fn execute(
+        &self,
+        api: avina::Api,
+        format: Format,
+    ) -> Result<(), Box<dyn Error>> {
+        match self {
+            List { all } => list(api, format, *all).await,
+            Resolve {
+                id,
+                flavor,
+                user,
+                ignore,
+            } => {
+                resolve(
+                    api,
+                    format,
+                    *id,
+                    flavor.to_owned(),
+                    user.to_owned(),
+                    *ignore,
+                )
+                .await
+            }
+        }
+    }
+}
+
+async fn list(
+    api: avina::Api,
+    format: Format,
+    all: bool,
+) -> Result<(), Box<dyn Error>> {
+    let mut request = api.unmatched_server.list();
+    if all {
+        request.all();
+    }
+    print_object_list(request.send().await?, format)
+}
+
+async fn resolve(
+    api: avina::Api,
+    format: Format,
+    id: u32,
+    flavor: Option<String>,
+    user: Option<String>,
+    ignore: bool,
+) -> Result<(), Box<dyn Error>> {
+    let mut request = api.unmatched_server.resolve(id);
+    if let Some(flavor) = flavor {
+        request.flavor(flavor_find_id(&api, &flavor).await?);
+    }
+    if let Some(user) = user {
+        request.user(user_find_id(&api, &user).await?);
+    }
+    if ignore {
+        request.ignore();
+    }
+    print_single_object(request.send().await?, format)
+}
@@ -0,0 +1,132 @@
+use std::error::Error;
+
+use clap::{Args, Subcommand};
+use uuid::Uuid;
+
+use crate::common::{Execute, Format, print_object_list, print_single_object};
+#[cfg(not(feature = "user"))]
+use crate::common::{find_id as user_find_id, find_id as project_find_id};
+#[cfg(feature = "user")]
+use crate::user::{
+    project::find_id as project_find_id, user::find_id as user_find_id,
+};
+
+#[derive(Args, Debug)]
+#[group(multiple = false)]
+pub(crate) struct VolumeStateListFilter {
+    #[clap(
+        short,
+        long,
+        help = "Display volume states of volume with given UUID"
+    )]
+    volume: Option<Uuid>,
+
+    #[clap(
+        short,
+        long,
+        help = "Display volume states of user with given name, ID, or OpenStack ID"
+    )]
+    user: Option<String>,
+
+    #[clap(
+        short,
+        long,
+        help = "Display volume states of project with given name, ID, or OpenStack ID"
+    )]
+    project: Option<String>,
+
+    #[clap(short, long, help = "Display all volume states", action)]
+    all: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum VolumeStateCommand {
+    #[clap(about = "List volume states")]
+    List {
+        #[clap(flatten)]
+        filter: VolumeStateListFilter,
+    },
+
+    #[clap(visible_alias = "show", about = "Show volume state with given ID")]
+    Get { id: u32 },
+
+    #[clap(about = "Import new and end old volume states")]
+    Import {
+        #[clap(
+            long,
+            short,
+            action,
+            help = "Suppress output if nothing is imported"
+        )]
+        quiet: bool,
+
+        #[clap(
+            long,
+            action,
+            help = "Only show what would be imported without storing it"
+        )]
+        dry_run: bool,
+    },
+}
+pub(crate) use VolumeStateCommand::*;
+
+impl Execute for VolumeStateCommand {
+    async fn execute(
+        &self,
+        api: avina::Api,
+        format: Format,
+    ) -> Result<(), Box<dyn Error>> {
+        match self {
+            List { filter } => list(api, format, filter).await,
+            Get { id } => get(api, format, id).await,
+            Import { quiet, dry_run } => {
+                import(api, format, *quiet, *dry_run).await
+            }
+        }
+    }
+}
+
+async fn list(
+    api: avina::Api,
+    format: Format,
+    filter: &VolumeStateListFilter,
+) -> Result<(), Box<dyn Error>> {
+    let mut request = api.volume_state.list();
+    if let Some(volume) = &filter.volume {
+        request.volume(*volume);
+    } else if let Some(user) = &filter.user {
+        let user_id = user_find_id(&api, user).await?;
+        request.user(user_id);
+    } else if let Some(project) = &filter.project {
+        let project_id = project_find_id(&api, project).await?;
+        request.project(project_id);
+    } else if filter.all {
+        request.all();
+    }
+    print_object_list(request.send().await?, format)
+}
+
+async fn get(
+    api: avina::Api,
+    format: Format,
+    id: &u32,
+) -> Result<(), Box<dyn Error>> {
+    print_single_object(api.volume_state.get(*id).await?, format)
+}
+
+async fn import(
+    api: avina::Api,
+    format: Format,
+    quiet: bool,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    let result = if dry_run {
+        api.volume_state.import_dry_run().await?
+    } else {
+        api.volume_state.import().await?
+    };
+    if !quiet || result.new_state_count > 0 || result.end_state_count > 0 {
+        return print_single_object(result, format);
+    }
+    Ok(())
+}
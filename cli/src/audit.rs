@@ -0,0 +1,98 @@
+use std::error::Error;
+
+use chrono::{DateTime, FixedOffset};
+use clap::Subcommand;
+
+#[cfg(not(feature = "user"))]
+use crate::common::find_id as user_find_id;
+use crate::common::{Execute, Format, print_object_list};
+#[cfg(feature = "user")]
+use crate::user::user::find_id as user_find_id;
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum AuditCommand {
+    #[clap(about = "List audit log entries")]
+    List {
+        #[clap(short, long, help = "Only show entries from this time on")]
+        begin: Option<DateTime<FixedOffset>>,
+
+        #[clap(short, long, help = "Only show entries up to this time")]
+        end: Option<DateTime<FixedOffset>>,
+
+        #[clap(
+            short,
+            long,
+            help = "Name, ID, or OpenStack ID of the acting user"
+        )]
+        actor: Option<String>,
+
+        #[clap(
+            short = 't',
+            long,
+            help = "Type of the affected resource, e.g. userbudgets"
+        )]
+        resource_type: Option<String>,
+
+        #[clap(short = 'i', long, help = "ID of the affected resource")]
+        resource_id: Option<u32>,
+    },
+}
+pub(crate) use AuditCommand::*;
+
+impl Execute for AuditCommand {
+    async fn execute(
+        &self,
+        api: avina::Api,
+        format: Format,
+    ) -> Result<(), Box<dyn Error>> {
+        match self {
+            List {
+                begin,
+                end,
+                actor,
+                resource_type,
+                resource_id,
+            } => {
+                list(
+                    api,
+                    format,
+                    *begin,
+                    *end,
+                    actor.to_owned(),
+                    resource_type.to_owned(),
+                    *resource_id,
+                )
+                .await
+            }
+        }
+    }
+}
+
+async fn list(
+    api: avina::Api,
+    format: Format,
+    begin: Option<DateTime<FixedOffset>>,
+    end: Option<DateTime<FixedOffset>>,
+    actor: Option<String>,
+    resource_type: Option<String>,
+    resource_id: Option<u32>,
+) -> Result<(), Box<dyn Error>> {
+    let mut request = api.audit.list();
+    if let Some(begin) = begin {
+        request.begin(begin);
+    }
+    if let Some(end) = end {
+        request.end(end);
+    }
+    if let Some(actor) = actor {
+        let actor_id = user_find_id(&api, &actor).await?;
+        request.actor(actor_id);
+    }
+    if let Some(resource_type) = resource_type {
+        request.resource_type(resource_type);
+    }
+    if let Some(resource_id) = resource_id {
+        request.resource_id(resource_id);
+    }
+    print_object_list(request.send().await?, format)
+}
@@ -1,9 +1,11 @@
 mod budget_bulk_create;
+mod budget_forecast;
 mod budget_over_tree;
 mod project_budget;
 mod user_budget;
 
 pub(crate) use budget_bulk_create::budget_bulk_create;
+pub(crate) use budget_forecast::{BudgetForecastFilter, budget_forecast};
 pub(crate) use budget_over_tree::{BudgetOverTreeFilter, budget_over_tree};
 pub(crate) use project_budget::ProjectBudgetCommand;
 pub(crate) use user_budget::UserBudgetCommand;
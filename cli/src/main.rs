@@ -1,6 +1,7 @@
 use std::{process::ExitCode, str::FromStr};
 
 use avina::{Api, Token};
+use avina_wire::accounting::Granularity;
 use chrono::{DateTime, FixedOffset};
 use clap::{ArgAction::SetFalse, Args, Parser, Subcommand};
 use colored::Colorize;
@@ -10,6 +11,8 @@ mod common;
 
 #[cfg(feature = "accounting")]
 mod accounting;
+#[cfg(feature = "audit")]
+mod audit;
 #[cfg(feature = "budgeting")]
 mod budgeting;
 #[cfg(feature = "hello")]
@@ -20,21 +23,33 @@ mod pricing;
 mod quota;
 #[cfg(feature = "resources")]
 mod resources;
+#[cfg(feature = "scheduler")]
+mod scheduler;
 #[cfg(feature = "user")]
 mod user;
 
-use accounting::ServerStateCommand;
+use accounting::{
+    BillingPolicyCommand, DailyUsageCommand, ServerStateCommand,
+    UnmatchedServerCommand,
+};
+use audit::AuditCommand;
 use budgeting::{ProjectBudgetCommand, UserBudgetCommand};
 use common::{Execute, Format, TableFormat};
 use pricing::FlavorPriceCommand;
 use quota::FlavorQuotaCommand;
 use resources::{FlavorCommand, FlavorGroupCommand};
-use user::UserCommand;
+use scheduler::SchedulerCommand;
+use user::{ApiKeyCommand, ProjectClassChangeCommand, UserCommand};
 
 #[derive(Args, Debug)]
 #[group(required = true, multiple = true)]
 struct CredentialArgs {
-    #[clap(short, long, help = "Openstack API token", env = "OS_TOKEN")]
+    #[clap(
+        short,
+        long,
+        help = "Openstack API token or avina API key",
+        env = "OS_TOKEN"
+    )]
     token: Option<String>,
 
     #[clap(
@@ -201,6 +216,20 @@ enum Command {
         command: pricing::FlavorPriceCommand,
     },
 
+    #[cfg(feature = "pricing")]
+    #[clap(about = "Volume price command")]
+    VolumePrice {
+        #[clap(subcommand)]
+        command: pricing::VolumePriceCommand,
+    },
+
+    #[cfg(feature = "pricing")]
+    #[clap(about = "Floating IP price command")]
+    FloatingIpPrice {
+        #[clap(subcommand)]
+        command: pricing::FloatingIpPriceCommand,
+    },
+
     #[cfg(feature = "quota")]
     #[clap(about = "Flavor quota command")]
     FlavorQuota {
@@ -222,6 +251,13 @@ enum Command {
         command: user::ProjectCommand,
     },
 
+    #[cfg(feature = "user")]
+    #[clap(about = "Project class change command")]
+    ProjectClassChange {
+        #[clap(subcommand)]
+        command: user::ProjectClassChangeCommand,
+    },
+
     #[cfg(feature = "user")]
     #[clap(about = "User command")]
     User {
@@ -229,6 +265,13 @@ enum Command {
         command: user::UserCommand,
     },
 
+    #[cfg(feature = "user")]
+    #[clap(about = "API key command")]
+    ApiKey {
+        #[clap(subcommand)]
+        command: user::ApiKeyCommand,
+    },
+
     #[cfg(feature = "accounting")]
     #[clap(about = "Server state command")]
     ServerState {
@@ -236,6 +279,65 @@ enum Command {
         command: accounting::ServerStateCommand,
     },
 
+    #[cfg(feature = "accounting")]
+    #[clap(about = "Unmatched server command")]
+    UnmatchedServer {
+        #[clap(subcommand)]
+        command: accounting::UnmatchedServerCommand,
+    },
+
+    #[cfg(feature = "accounting")]
+    #[clap(about = "Billing policy command")]
+    BillingPolicy {
+        #[clap(subcommand)]
+        command: accounting::BillingPolicyCommand,
+    },
+
+    #[cfg(feature = "accounting")]
+    #[clap(about = "Daily usage aggregate command")]
+    DailyUsage {
+        #[clap(subcommand)]
+        command: accounting::DailyUsageCommand,
+    },
+
+    #[cfg(feature = "accounting")]
+    #[clap(about = "Volume state command")]
+    VolumeState {
+        #[clap(subcommand)]
+        command: accounting::VolumeStateCommand,
+    },
+
+    #[cfg(feature = "accounting")]
+    #[clap(about = "Floating IP state command")]
+    FloatingIpState {
+        #[clap(subcommand)]
+        command: accounting::FloatingIpStateCommand,
+    },
+
+    #[cfg(feature = "accounting")]
+    #[clap(about = "Volume cost command")]
+    VolumeCost {
+        #[clap(
+            long,
+            short,
+            help = "Begin of the period to calculate the cost for [default: beginning of the running year]"
+        )]
+        begin: Option<DateTime<FixedOffset>>,
+
+        #[clap(
+            long,
+            short,
+            help = "End of the period to calculate the cost for [default: now]"
+        )]
+        end: Option<DateTime<FixedOffset>>,
+
+        #[clap(flatten)]
+        filter: accounting::VolumeCostFilter,
+
+        #[clap(long, short, help = "Show detailed cost breakdown")]
+        detail: bool,
+    },
+
     #[cfg(feature = "accounting")]
     #[clap(about = "Server cost command")]
     ServerCost {
@@ -258,6 +360,13 @@ enum Command {
 
         #[clap(long, short, help = "Show detailed cost breakdown")]
         detail: bool,
+
+        #[clap(
+            long,
+            short,
+            help = "Split the cost into a series of buckets of this size"
+        )]
+        granularity: Option<Granularity>,
     },
 
     #[cfg(feature = "accounting")]
@@ -282,6 +391,13 @@ enum Command {
 
         #[clap(long, short, help = "Show detailed consumption breakdown")]
         detail: bool,
+
+        #[clap(
+            long,
+            short,
+            help = "Split the consumption into a series of buckets of this size"
+        )]
+        granularity: Option<Granularity>,
     },
 
     #[cfg(feature = "budgeting")]
@@ -312,6 +428,13 @@ enum Command {
         end: Option<DateTime<FixedOffset>>,
     },
 
+    #[cfg(feature = "budgeting")]
+    #[clap(about = "Budget year-end forecast command")]
+    BudgetForecast {
+        #[clap(flatten)]
+        filter: budgeting::BudgetForecastFilter,
+    },
+
     #[cfg(feature = "budgeting")]
     #[clap(about = "Budget bulk create command")]
     BudgetBulkCreate {
@@ -323,6 +446,20 @@ enum Command {
         )]
         year: i32,
     },
+
+    #[cfg(feature = "audit")]
+    #[clap(about = "Audit log command")]
+    Audit {
+        #[clap(subcommand)]
+        command: AuditCommand,
+    },
+
+    #[cfg(feature = "scheduler")]
+    #[clap(about = "Scheduled job command")]
+    Scheduler {
+        #[clap(subcommand)]
+        command: SchedulerCommand,
+    },
 }
 
 #[tokio::main]
@@ -378,6 +515,63 @@ async fn main() -> ExitCode {
                 cli.url
             }
         }
+        Command::ApiKey {
+            command:
+                ApiKeyCommand::List { .. }
+                | ApiKeyCommand::Get { .. }
+                | ApiKeyCommand::Create { .. }
+                | ApiKeyCommand::Delete { .. },
+        } => {
+            if cli.rust {
+                cli.rust_url
+            } else {
+                cli.url
+            }
+        }
+        Command::Audit {
+            command: AuditCommand::List { .. },
+        }
+        | Command::ProjectClassChange {
+            command:
+                ProjectClassChangeCommand::List { .. }
+                | ProjectClassChangeCommand::Get { .. }
+                | ProjectClassChangeCommand::Create { .. }
+                | ProjectClassChangeCommand::Modify { .. }
+                | ProjectClassChangeCommand::Delete { .. },
+        }
+        | Command::UnmatchedServer {
+            command:
+                UnmatchedServerCommand::List { .. }
+                | UnmatchedServerCommand::Resolve { .. },
+        }
+        | Command::BillingPolicy {
+            command:
+                BillingPolicyCommand::List
+                | BillingPolicyCommand::Get { .. }
+                | BillingPolicyCommand::Create { .. }
+                | BillingPolicyCommand::Modify { .. }
+                | BillingPolicyCommand::Delete { .. },
+        }
+        | Command::DailyUsage {
+            command: DailyUsageCommand::Backfill,
+        }
+        | Command::VolumePrice { .. }
+        | Command::VolumeState { .. }
+        | Command::VolumeCost { .. }
+        | Command::FloatingIpPrice { .. }
+        | Command::FloatingIpState { .. }
+        | Command::Scheduler {
+            command:
+                SchedulerCommand::Jobs
+                | SchedulerCommand::History { .. }
+                | SchedulerCommand::Run { .. },
+        } => {
+            if cli.rust {
+                cli.rust_url
+            } else {
+                cli.url
+            }
+        }
         Command::ServerState {
             command:
                 ServerStateCommand::List { .. }
@@ -404,10 +598,16 @@ async fn main() -> ExitCode {
         | Command::FlavorGroup {
             command:
                 FlavorGroupCommand::Delete { .. }
-                | FlavorGroupCommand::Modify { .. },
+                | FlavorGroupCommand::Modify { .. }
+                | FlavorGroupCommand::Initialize,
         }
         | Command::FlavorPrice {
-            command: FlavorPriceCommand::Delete { .. },
+            command:
+                FlavorPriceCommand::Delete { .. }
+                | FlavorPriceCommand::Initialize
+                | FlavorPriceCommand::Effective { .. }
+                | FlavorPriceCommand::Timeline { .. }
+                | FlavorPriceCommand::BulkCreate { .. },
         }
         | Command::FlavorQuota {
             command: FlavorQuotaCommand::Delete { .. },
@@ -429,10 +629,12 @@ async fn main() -> ExitCode {
     };
     #[cfg(any(
         feature = "accounting",
+        feature = "audit",
         feature = "budgeting",
         feature = "hello",
         feature = "pricing",
         feature = "resources",
+        feature = "scheduler",
         feature = "user",
     ))]
     match match cli.command {
@@ -443,13 +645,29 @@ async fn main() -> ExitCode {
         #[cfg(feature = "user")]
         Command::User { ref command } => command.execute(api, cli.format).await,
         #[cfg(feature = "user")]
+        Command::ApiKey { ref command } => {
+            command.execute(api, cli.format).await
+        }
+        #[cfg(feature = "user")]
         Command::Project { ref command } => {
             command.execute(api, cli.format).await
         }
+        #[cfg(feature = "user")]
+        Command::ProjectClassChange { ref command } => {
+            command.execute(api, cli.format).await
+        }
         #[cfg(feature = "pricing")]
         Command::FlavorPrice { ref command } => {
             command.execute(api, cli.format).await
         }
+        #[cfg(feature = "pricing")]
+        Command::VolumePrice { ref command } => {
+            command.execute(api, cli.format).await
+        }
+        #[cfg(feature = "pricing")]
+        Command::FloatingIpPrice { ref command } => {
+            command.execute(api, cli.format).await
+        }
         #[cfg(feature = "quota")]
         Command::FlavorQuota { ref command } => {
             command.execute(api, cli.format).await
@@ -469,24 +687,70 @@ async fn main() -> ExitCode {
             command.execute(api, cli.format).await
         }
         #[cfg(feature = "accounting")]
-        Command::ServerCost {
+        Command::UnmatchedServer { ref command } => {
+            command.execute(api, cli.format).await
+        }
+        #[cfg(feature = "accounting")]
+        Command::BillingPolicy { ref command } => {
+            command.execute(api, cli.format).await
+        }
+        #[cfg(feature = "accounting")]
+        Command::DailyUsage { ref command } => {
+            command.execute(api, cli.format).await
+        }
+        #[cfg(feature = "accounting")]
+        Command::VolumeState { ref command } => {
+            command.execute(api, cli.format).await
+        }
+        #[cfg(feature = "accounting")]
+        Command::FloatingIpState { ref command } => {
+            command.execute(api, cli.format).await
+        }
+        #[cfg(feature = "accounting")]
+        Command::VolumeCost {
             begin,
             end,
             filter,
             detail,
         } => {
-            accounting::server_cost(api, cli.format, begin, end, filter, detail)
+            accounting::volume_cost(api, cli.format, begin, end, filter, detail)
                 .await
         }
         #[cfg(feature = "accounting")]
+        Command::ServerCost {
+            begin,
+            end,
+            filter,
+            detail,
+            granularity,
+        } => {
+            accounting::server_cost(
+                api,
+                cli.format,
+                begin,
+                end,
+                filter,
+                detail,
+                granularity,
+            )
+            .await
+        }
+        #[cfg(feature = "accounting")]
         Command::ServerConsumption {
             begin,
             end,
             filter,
             detail,
+            granularity,
         } => {
             accounting::server_consumption(
-                api, cli.format, begin, end, filter, detail,
+                api,
+                cli.format,
+                begin,
+                end,
+                filter,
+                detail,
+                granularity,
             )
             .await
         }
@@ -503,9 +767,21 @@ async fn main() -> ExitCode {
             budgeting::budget_over_tree(api, filter, end).await
         }
         #[cfg(feature = "budgeting")]
+        Command::BudgetForecast { filter } => {
+            budgeting::budget_forecast(api, cli.format, filter).await
+        }
+        #[cfg(feature = "budgeting")]
         Command::BudgetBulkCreate { year } => {
             budgeting::budget_bulk_create(api, cli.format, year).await
         }
+        #[cfg(feature = "audit")]
+        Command::Audit { ref command } => {
+            command.execute(api, cli.format).await
+        }
+        #[cfg(feature = "scheduler")]
+        Command::Scheduler { ref command } => {
+            command.execute(api, cli.format).await
+        }
     } {
         Ok(_) => {}
         Err(error) => {
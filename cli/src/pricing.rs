@@ -4,14 +4,19 @@ use avina_wire::user::UserClass;
 use chrono::{DateTime, FixedOffset};
 use clap::Subcommand;
 
-#[cfg(not(feature = "resources"))]
-use crate::common::find_id as flavor_find_id;
 use crate::common::{
-    Execute, Format, ask_for_confirmation, print_object_list,
+    Execute, Format, ask_for_confirmation, print_json, print_object_list,
     print_single_object,
 };
+#[cfg(not(feature = "resources"))]
+use crate::common::{
+    find_id as flavor_find_id, find_id as flavor_group_find_id,
+};
 #[cfg(feature = "resources")]
-use crate::resources::flavor::find_id as flavor_find_id;
+use crate::resources::{
+    flavor::find_id as flavor_find_id,
+    flavor_group::find_id as flavor_group_find_id,
+};
 
 #[derive(Subcommand, Debug)]
 pub(crate) enum FlavorPriceCommand {
@@ -65,6 +70,106 @@ pub(crate) enum FlavorPriceCommand {
 
     #[clap(about = "Initialize first flavor prices")]
     Initialize,
+
+    #[clap(about = "Show prices in effect at a point in time")]
+    Effective {
+        #[clap(long, short, help = "Point in time, default: now")]
+        time: Option<DateTime<FixedOffset>>,
+
+        #[clap(long, short, help = "Only show prices for this user class")]
+        user_class: Option<UserClass>,
+
+        #[clap(
+            long,
+            short,
+            help = "Name, ID, or OpenStack UUIDv4 of the flavor to show"
+        )]
+        flavor: Option<String>,
+    },
+
+    #[clap(about = "Show how prices changed over a period of time")]
+    Timeline {
+        #[clap(long, short, help = "Begin of the period, default: this year")]
+        begin: Option<DateTime<FixedOffset>>,
+
+        #[clap(long, short, help = "End of the period, default: now")]
+        end: Option<DateTime<FixedOffset>>,
+
+        #[clap(long, short, help = "Only show prices for this user class")]
+        user_class: Option<UserClass>,
+
+        #[clap(
+            long,
+            short,
+            help = "Name, ID, or OpenStack UUIDv4 of the flavor to show"
+        )]
+        flavor: Option<String>,
+    },
+
+    #[clap(about = "Create new prices for many flavors at once")]
+    BulkCreate {
+        #[clap(help = "Start time of the new prices, has to be in the future")]
+        start_time: DateTime<FixedOffset>,
+
+        #[clap(
+            long,
+            short,
+            help = "Name or ID of the flavor group whose flavors to change"
+        )]
+        group: Option<String>,
+
+        #[clap(
+            long,
+            short,
+            help = "Name, ID, or OpenStack UUIDv4 of a flavor, can be repeated"
+        )]
+        flavor: Vec<String>,
+
+        #[clap(
+            long,
+            short,
+            help = "User class to change (1-6), can be repeated, default: all"
+        )]
+        user_class: Vec<UserClass>,
+
+        #[clap(
+            long,
+            short,
+            help = "New unit price of the flavors",
+            conflicts_with = "percentage",
+            required_unless_present = "percentage"
+        )]
+        price: Option<f64>,
+
+        #[clap(
+            long,
+            help = "Change of the current unit prices in percent",
+            allow_negative_numbers = true
+        )]
+        percentage: Option<f64>,
+
+        #[clap(
+            long,
+            help = "Only show how the costs of the projects would change"
+        )]
+        preview: bool,
+
+        #[clap(
+            long,
+            short,
+            help = "Begin of the preview reference period, default: this year",
+            requires = "preview"
+        )]
+        begin: Option<DateTime<FixedOffset>>,
+
+        #[clap(
+            long,
+            short,
+            help = "End of the preview reference period, default: now",
+            requires = "preview"
+        )]
+        end: Option<DateTime<FixedOffset>>,
+    },
 }
 pub(crate) use FlavorPriceCommand::*;
 
@@ -106,6 +211,56 @@ impl Execute for FlavorPriceCommand {
             }
             Delete { id } => delete(api, id).await,
             Initialize => initialize(api, format).await,
+            Effective {
+                time,
+                user_class,
+                flavor,
+            } => {
+                effective(api, format, *time, *user_class, flavor.to_owned())
+                    .await
+            }
+            Timeline {
+                begin,
+                end,
+                user_class,
+                flavor,
+            } => {
+                timeline(
+                    api,
+                    format,
+                    *begin,
+                    *end,
+                    *user_class,
+                    flavor.to_owned(),
+                )
+                .await
+            }
+            BulkCreate {
+                start_time,
+                group,
+                flavor,
+                user_class,
+                price,
+                percentage,
+                preview,
+                begin,
+                end,
+            } => {
+                bulk_create(
+                    api,
+                    format,
+                    *start_time,
+                    group.to_owned(),
+                    flavor,
+                    user_class,
+                    *price,
+                    *percentage,
+                    *preview,
+                    *begin,
+                    *end,
+                )
+                .await
+            }
         }
     }
 }
@@ -180,3 +335,451 @@ async fn initialize(
     let result = api.flavor_price.initialize().await?;
     print_single_object(result, format)
 }
+
+async fn effective(
+    api: avina::Api,
+    format: Format,
+    time: Option<DateTime<FixedOffset>>,
+    user_class: Option<UserClass>,
+    flavor: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    let mut request = api.flavor_price.effective();
+    if let Some(time) = time {
+        request.time(time);
+    }
+    if let Some(user_class) = user_class {
+        request.user_class(user_class);
+    }
+    if let Some(flavor) = flavor {
+        let flavor_id = flavor_find_id(&api, &flavor).await?;
+        request.flavor(flavor_id);
+    }
+    print_object_list(request.send().await?, format)
+}
+
+async fn timeline(
+    api: avina::Api,
+    format: Format,
+    begin: Option<DateTime<FixedOffset>>,
+    end: Option<DateTime<FixedOffset>>,
+    user_class: Option<UserClass>,
+    flavor: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    let mut request = api.flavor_price.timeline();
+    if let Some(begin) = begin {
+        request.begin(begin);
+    }
+    if let Some(end) = end {
+        request.end(end);
+    }
+    if let Some(user_class) = user_class {
+        request.user_class(user_class);
+    }
+    if let Some(flavor) = flavor {
+        let flavor_id = flavor_find_id(&api, &flavor).await?;
+        request.flavor(flavor_id);
+    }
+    print_object_list(request.send().await?, format)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn bulk_create(
+    api: avina::Api,
+    format: Format,
+    start_time: DateTime<FixedOffset>,
+    group: Option<String>,
+    flavors: &[String],
+    user_classes: &[UserClass],
+    price: Option<f64>,
+    percentage: Option<f64>,
+    preview: bool,
+    begin: Option<DateTime<FixedOffset>>,
+    end: Option<DateTime<FixedOffset>>,
+) -> Result<(), Box<dyn Error>> {
+    let mut request = api.flavor_price.bulk_create(start_time);
+    if let Some(group) = group {
+        let group_id = flavor_group_find_id(&api, &group).await?;
+        request.flavor_group(group_id);
+    }
+    for flavor in flavors {
+        let flavor_id = flavor_find_id(&api, flavor).await?;
+        request.flavor(flavor_id);
+    }
+    for user_class in user_classes {
+        request.user_class(*user_class);
+    }
+    if let Some(price) = price {
+        request.unit_price(price);
+    }
+    if let Some(percentage) = percentage {
+        request.percentage(percentage);
+    }
+    if !preview {
+        return print_object_list(request.send().await?, format);
+    }
+    if let Some(begin) = begin {
+        request.begin(begin);
+    }
+    if let Some(end) = end {
+        request.end(end);
+    }
+    let result = request.preview().await?;
+    match format {
+        Format::Json => print_json(result),
+        Format::Table(_) => {
+            print_object_list(result.prices, format.clone())?;
+            print_object_list(result.projects, format)
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum VolumePriceCommand {
+    #[clap(about = "List volume prices")]
+    List,
+
+    #[clap(visible_alias = "show", about = "Show volume price with given ID")]
+    Get { id: u32 },
+
+    #[clap(about = "Create a new volume price")]
+    Create {
+        #[clap(help = "Cinder volume type of the price")]
+        volume_type: String,
+
+        #[clap(help = "User class of the price (1-6)")]
+        user_class: UserClass,
+
+        #[clap(
+            long,
+            short,
+            help = "Price of one GiB for a whole year, default: 0.0"
+        )]
+        price: Option<f64>,
+
+        #[clap(long, short, help = "Start time of the price, default: now")]
+        start_time: Option<DateTime<FixedOffset>>,
+    },
+
+    #[clap(about = "Modify a volume price")]
+    Modify {
+        #[clap(help = "ID of the volume price")]
+        id: u32,
+
+        #[clap(long, short, help = "Cinder volume type of the price")]
+        volume_type: Option<String>,
+
+        #[clap(long, short, help = "User class of the price (1-6)")]
+        user_class: Option<UserClass>,
+
+        #[clap(long, short, help = "Price of one GiB for a whole year")]
+        price: Option<f64>,
+
+        #[clap(long, short, help = "Start time of the volume price")]
+        start_time: Option<DateTime<FixedOffset>>,
+    },
+
+    #[clap(about = "Delete volume price with given ID")]
+    Delete { id: u32 },
+}
+
+impl Execute for VolumePriceCommand {
+    async fn execute(
+        &self,
+        api: avina::Api,
+        format: Format,
+    ) -> Result<(), Box<dyn Error>> {
+        match self {
+            VolumePriceCommand::List => volume_price_list(api, format).await,
+            VolumePriceCommand::Get { id } => {
+                volume_price_get(api, format, id).await
+            }
+            VolumePriceCommand::Create {
+                volume_type,
+                user_class,
+                price,
+                start_time,
+            } => {
+                volume_price_create(
+                    api,
+                    format,
+                    volume_type.clone(),
+                    *user_class,
+                    *price,
+                    *start_time,
+                )
+                .await
+            }
+            VolumePriceCommand::Modify {
+                id,
+                volume_type,
+                user_class,
+                price,
+                start_time,
+            } => {
+                volume_price_modify(
+                    api,
+                    format,
+                    *id,
+                    volume_type.clone(),
+                    *user_class,
+                    *price,
+                    *start_time,
+                )
+                .await
+            }
+            VolumePriceCommand::Delete { id } => {
+                volume_price_delete(api, id).await
+            }
+        }
+    }
+}
+
+async fn volume_price_list(
+    api: avina::Api,
+    format: Format,
+) -> Result<(), Box<dyn Error>> {
+    let request = api.volume_price.list();
+    print_object_list(request.send().await?, format)
+}
+
+async fn volume_price_get(
+    api: avina::Api,
+    format: Format,
+    id: &u32,
+) -> Result<(), Box<dyn Error>> {
+    print_single_object(api.volume_price.get(*id).await?, format)
+}
+
+async fn volume_price_create(
+    api: avina::Api,
+    format: Format,
+    volume_type: String,
+    user_class: UserClass,
+    price: Option<f64>,
+    start_time: Option<DateTime<FixedOffset>>,
+) -> Result<(), Box<dyn Error>> {
+    let mut request = api.volume_price.create(volume_type, user_class);
+    if let Some(price) = price {
+        request.price(price);
+    }
+    if let Some(start_time) = start_time {
+        request.start_time(start_time);
+    }
+    print_single_object(request.send().await?, format)
+}
+
+async fn volume_price_modify(
+    api: avina::Api,
+    format: Format,
+    id: u32,
+    volume_type: Option<String>,
+    user_class: Option<UserClass>,
+    unit_price: Option<f64>,
+    start_time: Option<DateTime<FixedOffset>>,
+) -> Result<(), Box<dyn Error>> {
+    let mut request = api.volume_price.modify(id);
+    if let Some(volume_type) = volume_type {
+        request.volume_type(volume_type);
+    }
+    if let Some(user_class) = user_class {
+        request.user_class(user_class);
+    }
+    if let Some(unit_price) = unit_price {
+        request.unit_price(unit_price);
+    }
+    if let Some(start_time) = start_time {
+        request.start_time(start_time);
+    }
+    print_single_object(request.send().await?, format)
+}
+
+async fn volume_price_delete(
+    api: avina::Api,
+    id: &u32,
+) -> Result<(), Box<dyn Error>> {
+    ask_for_confirmation()?;
+    Ok(api.volume_price.delete(*id).await?)
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum FloatingIpPriceCommand {
+    #[clap(about = "List floating IP prices")]
+    List,
+
+    #[clap(
+        visible_alias = "show",
+        about = "Show floating IP price with given ID"
+    )]
+    Get { id: u32 },
+
+    #[clap(about = "Create a new floating IP price")]
+    Create {
+        #[clap(
+            help = "Pool of the price, i.e. the name of its external network"
+        )]
+        pool: String,
+
+        #[clap(help = "User class of the price (1-6)")]
+        user_class: UserClass,
+
+        #[clap(
+            long,
+            short,
+            help = "Price of one floating IP for a whole year, default: 0.0"
+        )]
+        price: Option<f64>,
+
+        #[clap(long, short, help = "Start time of the price, default: now")]
+        start_time: Option<DateTime<FixedOffset>>,
+    },
+
+    #[clap(about = "Modify a floating IP price")]
+    Modify {
+        #[clap(help = "ID of the floating IP price")]
+        id: u32,
+
+        #[clap(
+            long,
+            short,
+            help = "Pool of the price, i.e. the name of its external network"
+        )]
+        pool: Option<String>,
+
+        #[clap(long, short, help = "User class of the price (1-6)")]
+        user_class: Option<UserClass>,
+
+        #[clap(
+            long,
+            short,
+            help = "Price of one floating IP for a whole year"
+        )]
+        price: Option<f64>,
+
+        #[clap(long, short, help = "Start time of the floating IP price")]
+        start_time: Option<DateTime<FixedOffset>>,
+    },
+
+    #[clap(about = "Delete floating IP price with given ID")]
+    Delete { id: u32 },
+}
+
+impl Execute for FloatingIpPriceCommand {
+    async fn execute(
+        &self,
+        api: avina::Api,
+        format: Format,
+    ) -> Result<(), Box<dyn Error>> {
+        match self {
+            FloatingIpPriceCommand::List => {
+                floating_ip_price_list(api, format).await
+            }
+            FloatingIpPriceCommand::Get { id } => {
+                floating_ip_price_get(api, format, id).await
+            }
+            FloatingIpPriceCommand::Create {
+                pool,
+                user_class,
+                price,
+                start_time,
+            } => {
+                floating_ip_price_create(
+                    api,
+                    format,
+                    pool.clone(),
+                    *user_class,
+                    *price,
+                    *start_time,
+                )
+                .await
+            }
+            FloatingIpPriceCommand::Modify {
+                id,
+                pool,
+                user_class,
+                price,
+                start_time,
+            } => {
+                floating_ip_price_modify(
+                    api,
+                    format,
+                    *id,
+                    pool.clone(),
+                    *user_class,
+                    *price,
+                    *start_time,
+                )
+                .await
+            }
+            FloatingIpPriceCommand::Delete { id } => {
+                floating_ip_price_delete(api, id).await
+            }
+        }
+    }
+}
+
+async fn floating_ip_price_list(
+    api: avina::Api,
+    format: Format,
+) -> Result<(), Box<dyn Error>> {
+    let request = api.floating_ip_price.list();
+    print_object_list(request.send().await?, format)
+}
+
+async fn floating_ip_price_get(
+    api: avina::Api,
+    format: Format,
+    id: &u32,
+) -> Result<(), Box<dyn Error>> {
+    print_single_object(api.floating_ip_price.get(*id).await?, format)
+}
+
+async fn floating_ip_price_create(
+    api: avina::Api,
+    format: Format,
+    pool: String,
+    user_class: UserClass,
+    price: Option<f64>,
+    start_time: Option<DateTime<FixedOffset>>,
+) -> Result<(), Box<dyn Error>> {
+    let mut request = api.floating_ip_price.create(pool, user_class);
+    if let Some(price) = price {
+        request.price(price);
+    }
+    if let Some(start_time) = start_time {
+        request.start_time(start_time);
+    }
+    print_single_object(request.send().await?, format)
+}
+
+async fn floating_ip_price_modify(
+    api: avina::Api,
+    format: Format,
+    id: u32,
+    pool: Option<String>,
+    user_class: Option<UserClass>,
+    unit_price: Option<f64>,
+    start_time: Option<DateTime<FixedOffset>>,
+) -> Result<(), Box<dyn Error>> {
+    let mut request = api.floating_ip_price.modify(id);
+    if let Some(pool) = pool {
+        request.pool(pool);
+    }
+    if let Some(user_class) = user_class {
+        request.user_class(user_class);
+    }
+    if let Some(unit_price) = unit_price {
+        request.unit_price(unit_price);
+    }
+    if let Some(start_time) = start_time {
+        request.start_time(start_time);
+    }
+    print_single_object(request.send().await?, format)
+}
+
+async fn floating_ip_price_delete(
+    api: avina::Api,
+    id: &u32,
+) -> Result<(), Box<dyn Error>> {
+    ask_for_confirmation()?;
+    Ok(api.floating_ip_price.delete(*id).await?)
+}
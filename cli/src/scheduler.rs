@@ -0,0 +1,60 @@
+use std::error::Error;
+
+use avina_wire::scheduler::JobName;
+use clap::Subcommand;
+
+use crate::common::{Execute, Format, print_object_list, print_single_object};
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum SchedulerCommand {
+    #[clap(about = "List scheduled jobs with their last and next run")]
+    Jobs,
+
+    #[clap(about = "Show the run history of scheduled jobs")]
+    History {
+        #[clap(short, long, help = "Only show runs of this job")]
+        job: Option<JobName>,
+    },
+
+    #[clap(about = "Run a job now")]
+    Run { job: JobName },
+}
+pub(crate) use SchedulerCommand::*;
+
+impl Execute for SchedulerCommand {
+    async fn execute(
+        &self,
+        api: avina::Api,
+        format: Format,
+    ) -> Result<(), Box<dyn Error>> {
+        match self {
+            Jobs => jobs(api, format).await,
+            History { job } => history(api, format, *job).await,
+            Run { job } => run(api, format, *job).await,
+        }
+    }
+}
+
+async fn jobs(api: avina::Api, format: Format) -> Result<(), Box<dyn Error>> {
+    print_object_list(api.scheduler.jobs().await?, format)
+}
+
+async fn history(
+    api: avina::Api,
+    format: Format,
+    job: Option<JobName>,
+) -> Result<(), Box<dyn Error>> {
+    let mut request = api.scheduler.runs();
+    if let Some(job) = job {
+        request.job(job);
+    }
+    print_object_list(request.send().await?, format)
+}
+
+async fn run(
+    api: avina::Api,
+    format: Format,
+    job: JobName,
+) -> Result<(), Box<dyn Error>> {
+    print_single_object(api.scheduler.run(job).await?, format)
+}
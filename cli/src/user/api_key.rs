@@ -0,0 +1,145 @@
+use std::error::Error;
+
+use avina_wire::user::ApiKeyScope;
+use chrono::{DateTime, FixedOffset};
+use clap::{Args, Subcommand};
+
+use crate::{
+    common::{
+        Execute, Format, ask_for_confirmation, print_object_list,
+        print_single_object,
+    },
+    user::user::find_id as user_find_id,
+};
+
+#[derive(Args, Debug)]
+#[group(multiple = false)]
+pub(crate) struct ApiKeyListFilter {
+    #[clap(short, long, help = "Display all API keys", action)]
+    all: bool,
+
+    #[clap(
+        short,
+        long,
+        help = "Display API keys of user with given name, ID, or OpenStack ID"
+    )]
+    user: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum ApiKeyCommand {
+    #[clap(about = "List API keys")]
+    List {
+        #[clap(flatten)]
+        filter: ApiKeyListFilter,
+    },
+
+    #[clap(visible_alias = "show", about = "Show API key with given ID")]
+    Get { id: u32 },
+
+    #[clap(about = "Create a new API key, the key is only shown once")]
+    Create {
+        #[clap(help = "Name of the API key")]
+        name: String,
+
+        #[clap(
+            long,
+            short,
+            required = true,
+            value_delimiter = ',',
+            help = "Comma separated API areas the key may be used for"
+        )]
+        scopes: Vec<ApiKeyScope>,
+
+        #[clap(
+            long,
+            short,
+            help = "Name, ID or OpenStack ID of the user owning the key [default: own user]"
+        )]
+        user: Option<String>,
+
+        #[clap(long, short, help = "Expiry date of the key [default: never]")]
+        expires_at: Option<DateTime<FixedOffset>>,
+    },
+
+    #[clap(visible_alias = "revoke", about = "Revoke API key with given ID")]
+    Delete { id: u32 },
+}
+pub(crate) use ApiKeyCommand::*;
+
+impl Execute for ApiKeyCommand {
+    async fn execute(
+        &self,
+        api: avina::Api,
+        format: Format,
+    ) -> Result<(), Box<dyn Error>> {
+        match self {
+            List { filter } => list(api, format, filter).await,
+            Get { id } => get(api, format, id).await,
+            Create {
+                name,
+                scopes,
+                user,
+                expires_at,
+            } => {
+                create(
+                    api,
+                    format,
+                    name.to_owned(),
+                    scopes.to_owned(),
+                    user.to_owned(),
+                    *expires_at,
+                )
+                .await
+            }
+            Delete { id } => delete(api, id).await,
+        }
+    }
+}
+
+async fn list(
+    api: avina::Api,
+    format: Format,
+    filter: &ApiKeyListFilter,
+) -> Result<(), Box<dyn Error>> {
+    let mut request = api.api_key.list();
+    if filter.all {
+        request.all();
+    } else if let Some(user) = &filter.user {
+        let user_id = user_find_id(&api, user).await?;
+        request.user(user_id);
+    }
+    print_object_list(request.send().await?, format)
+}
+
+async fn get(
+    api: avina::Api,
+    format: Format,
+    id: &u32,
+) -> Result<(), Box<dyn Error>> {
+    print_single_object(api.api_key.get(*id).await?, format)
+}
+
+async fn create(
+    api: avina::Api,
+    format: Format,
+    name: String,
+    scopes: Vec<ApiKeyScope>,
+    user: Option<String>,
+    expires_at: Option<DateTime<FixedOffset>>,
+) -> Result<(), Box<dyn Error>> {
+    let mut request = api.api_key.create(name, scopes);
+    if let Some(user) = user {
+        let user_id = user_find_id(&api, &user).await?;
+        request.user(user_id);
+    }
+    if let Some(expires_at) = expires_at {
+        request.expires_at(expires_at);
+    }
+    print_single_object(request.send().await?, format)
+}
+
+async fn delete(api: avina::Api, id: &u32) -> Result<(), Box<dyn Error>> {
+    ask_for_confirmation()?;
+    Ok(api.api_key.delete(*id).await?)
+}
@@ -1,6 +1,10 @@
+pub(crate) mod api_key;
 pub(crate) mod project;
+pub(crate) mod project_class_change;
 #[allow(clippy::module_inception)]
 pub(crate) mod user;
 
+pub(crate) use api_key::ApiKeyCommand;
 pub(crate) use project::ProjectCommand;
+pub(crate) use project_class_change::ProjectClassChangeCommand;
 pub(crate) use user::UserCommand;
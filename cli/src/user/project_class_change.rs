@@ -0,0 +1,148 @@
+use std::error::Error;
+
+use avina_wire::user::UserClass;
+use chrono::{DateTime, FixedOffset};
+use clap::{Args, Subcommand};
+
+use crate::common::{
+    Execute, Format, ask_for_confirmation, print_object_list,
+    print_single_object,
+};
+
+#[derive(Args, Debug)]
+#[group(multiple = false)]
+pub(crate) struct ProjectClassChangeListFilter {
+    #[clap(short, long, help = "Display changes of all projects", action)]
+    all: bool,
+
+    #[clap(short, long, help = "Display changes of the project with given ID")]
+    project: Option<u32>,
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum ProjectClassChangeCommand {
+    #[clap(about = "List user class changes of projects")]
+    List {
+        #[clap(flatten)]
+        filter: ProjectClassChangeListFilter,
+    },
+
+    #[clap(
+        visible_alias = "show",
+        about = "Show project class change with given ID"
+    )]
+    Get { id: u32 },
+
+    #[clap(about = "Create a new project class change")]
+    Create {
+        #[clap(help = "ID of the project")]
+        project: u32,
+
+        #[clap(help = "User class of the project (0,1,2,3,4,5,6)")]
+        user_class: UserClass,
+
+        #[clap(help = "Time from which the user class applies")]
+        start_time: DateTime<FixedOffset>,
+    },
+
+    #[clap(about = "Modify a project class change")]
+    Modify {
+        #[clap(help = "ID of the project class change")]
+        id: u32,
+
+        #[clap(
+            long,
+            short,
+            help = "User class of the project (0,1,2,3,4,5,6)"
+        )]
+        user_class: Option<UserClass>,
+
+        #[clap(long, short, help = "Time from which the user class applies")]
+        start_time: Option<DateTime<FixedOffset>>,
+    },
+
+    #[clap(about = "Delete project class change with given ID")]
+    Delete { id: u32 },
+}
+pub(crate) use ProjectClassChangeCommand::*;
+
+impl Execute for ProjectClassChangeCommand {
+    async fn execute(
+        &self,
+        api: avina::Api,
+        format: Format,
+    ) -> Result<(), Box<dyn Error>> {
+        match self {
+            List { filter } => list(api, format, filter).await,
+            Get { id } => get(api, format, id).await,
+            Create {
+                project,
+                user_class,
+                start_time,
+            } => create(api, format, *project, *user_class, *start_time).await,
+            Modify {
+                id,
+                user_class,
+                start_time,
+            } => modify(api, format, *id, *user_class, *start_time).await,
+            Delete { id } => delete(api, id).await,
+        }
+    }
+}
+
+async fn list(
+    api: avina::Api,
+    format: Format,
+    filter: &ProjectClassChangeListFilter,
+) -> Result<(), Box<dyn Error>> {
+    let mut request = api.project_class_change.list();
+    if filter.all {
+        request.all();
+    } else if let Some(project) = filter.project {
+        request.project(project);
+    }
+    print_object_list(request.send().await?, format)
+}
+
+async fn get(
+    api: avina::Api,
+    format: Format,
+    id: &u32,
+) -> Result<(), Box<dyn Error>> {
+    print_single_object(api.project_class_change.get(*id).await?, format)
+}
+
+async fn create(
+    api: avina::Api,
+    format: Format,
+    project: u32,
+    user_class: UserClass,
+    start_time: DateTime<FixedOffset>,
+) -> Result<(), Box<dyn Error>> {
+    let request = api
+        .project_class_change
+        .create(project, user_class, start_time);
+    print_single_object(request.send().await?, format)
+}
+
+async fn modify(
+    api: avina::Api,
+    format: Format,
+    id: u32,
+    user_class: Option<UserClass>,
+    start_time: Option<DateTime<FixedOffset>>,
+) -> Result<(), Box<dyn Error>> {
+    let mut request = api.project_class_change.modify(id);
+    if let Some(user_class) = user_class {
+        request.user_class(user_class);
+    }
+    if let Some(start_time) = start_time {
+        request.start_time(start_time);
+    }
+    print_single_object(request.send().await?, format)
+}
+
+async fn delete(api: avina::Api, id: &u32) -> Result<(), Box<dyn Error>> {
+    ask_for_confirmation()?;
+    Ok(api.project_class_change.delete(*id).await?)
+}
@@ -1,6 +1,7 @@
 use std::error::Error;
 
 use anyhow::{Context, anyhow};
+use avina_wire::user::Role;
 use clap::{Args, Subcommand};
 
 use crate::{
@@ -53,9 +54,8 @@ pub(crate) enum UserCommand {
         )]
         project: String,
 
-        // TODO we need some enum here
-        #[clap(long, short, help = "Role of the user (1=user, 2=masteruser)")]
-        role: Option<u32>,
+        #[clap(long, short, help = "Role of the user")]
+        role: Option<Role>,
 
         #[clap(long, short, help = "Whether the user is an admin", action)]
         staff: bool,
@@ -82,9 +82,8 @@ pub(crate) enum UserCommand {
         )]
         project: Option<String>,
 
-        // TODO we need some enum here
-        #[clap(long, short, help = "Role of the user (1=user, 2=masteruser)")]
-        role: Option<u32>,
+        #[clap(long, short, help = "Role of the user")]
+        role: Option<Role>,
 
         #[clap(long, short, help = "Whether the user is an admin")]
         staff: Option<bool>,
@@ -201,7 +200,7 @@ async fn create(
     name: String,
     openstack_id: String,
     project: &str,
-    role: Option<u32>,
+    role: Option<Role>,
     staff: bool,
     inactive: bool,
 ) -> Result<(), Box<dyn Error>> {
@@ -227,7 +226,7 @@ async fn modify(
     name: Option<String>,
     openstack_id: Option<String>,
     project: Option<String>,
-    role: Option<u32>,
+    role: Option<Role>,
     staff: Option<bool>,
     active: Option<bool>,
 ) -> Result<(), Box<dyn Error>> {
@@ -292,7 +291,7 @@ pub(crate) async fn find_id(
     let mut request = api.user.list();
     if me.is_staff {
         request.all();
-    } else if me.role == 2 {
+    } else if me.role == Role::MasterUser {
         request.project(me.project.id);
     }
     let users = request.send().await?;
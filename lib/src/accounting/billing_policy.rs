@@ -0,0 +1,182 @@
+use std::rc::Rc;
+
+use anyhow::Context;
+use avina_wire::accounting::{
+    BillingPolicy, BillingPolicyCreateData, BillingPolicyModifyData,
+};
+use chrono::{DateTime, FixedOffset};
+use reqwest::{Method, StatusCode, Url};
+
+use crate::{
+    common::{Client, SerializableNone, request, request_bare},
+    error::ApiError,
+};
+
+#[derive(Debug)]
+pub struct BillingPolicyApi {
+    pub url: String,
+    pub client: Rc<Client>,
+}
+
+#[derive(Debug)]
+pub struct BillingPolicyListRequest {
+    url: String,
+    client: Rc<Client>,
+}
+
+impl BillingPolicyListRequest {
+    pub fn new(url: &str, client: &Rc<Client>) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+        }
+    }
+
+    pub async fn send(&self) -> Result<Vec<BillingPolicy>, ApiError> {
+        let url = Url::parse(self.url.as_str())
+            .context("Could not parse URL GET parameters.")?;
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+}
+
+pub struct BillingPolicyCreateRequest {
+    url: String,
+    client: Rc<Client>,
+
+    data: BillingPolicyCreateData,
+}
+
+impl BillingPolicyCreateRequest {
+    pub fn new(url: &str, client: &Rc<Client>, status: String) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+            data: BillingPolicyCreateData::new(status),
+        }
+    }
+
+    pub fn multiplier(&mut self, multiplier: f64) -> &mut Self {
+        self.data.multiplier = Some(multiplier);
+        self
+    }
+
+    pub fn start_time(
+        &mut self,
+        start_time: DateTime<FixedOffset>,
+    ) -> &mut Self {
+        self.data.start_time = Some(start_time);
+        self
+    }
+
+    pub async fn send(&self) -> Result<BillingPolicy, ApiError> {
+        request(
+            &self.client,
+            Method::POST,
+            &self.url,
+            Some(&self.data),
+            StatusCode::CREATED,
+        )
+        .await
+    }
+}
+
+pub struct BillingPolicyModifyRequest {
+    url: String,
+    client: Rc<Client>,
+
+    data: BillingPolicyModifyData,
+}
+
+impl BillingPolicyModifyRequest {
+    pub fn new(url: &str, client: &Rc<Client>, id: u32) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+            data: BillingPolicyModifyData::new(id),
+        }
+    }
+
+    pub fn status(&mut self, status: String) -> &mut Self {
+        self.data.status = Some(status);
+        self
+    }
+
+    pub fn multiplier(&mut self, multiplier: f64) -> &mut Self {
+        self.data.multiplier = Some(multiplier);
+        self
+    }
+
+    pub fn start_time(
+        &mut self,
+        start_time: DateTime<FixedOffset>,
+    ) -> &mut Self {
+        self.data.start_time = Some(start_time);
+        self
+    }
+
+    pub async fn send(&self) -> Result<BillingPolicy, ApiError> {
+        request(
+            &self.client,
+            Method::PATCH,
+            &self.url,
+            Some(&self.data),
+            StatusCode::OK,
+        )
+        .await
+    }
+}
+
+impl BillingPolicyApi {
+    pub fn new(base_url: &str, client: &Rc<Client>) -> BillingPolicyApi {
+        BillingPolicyApi {
+            url: format!("{base_url}/accounting/billingpolicies"),
+            client: Rc::clone(client),
+        }
+    }
+
+    pub fn list(&self) -> BillingPolicyListRequest {
+        BillingPolicyListRequest::new(self.url.as_ref(), &self.client)
+    }
+
+    pub async fn get(&self, id: u32) -> Result<BillingPolicy, ApiError> {
+        let url = format!("{}/{}", self.url, id);
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    pub fn create(&self, status: String) -> BillingPolicyCreateRequest {
+        let url = format!("{}/", self.url);
+        BillingPolicyCreateRequest::new(url.as_ref(), &self.client, status)
+    }
+
+    pub fn modify(&self, id: u32) -> BillingPolicyModifyRequest {
+        let url = format!("{}/{}/", self.url, id);
+        BillingPolicyModifyRequest::new(url.as_ref(), &self.client, id)
+    }
+
+    pub async fn delete(&self, id: u32) -> Result<(), ApiError> {
+        let url = format!("{}/{}/", self.url, id);
+        request_bare(
+            &self.client,
+            Method::DELETE,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::NO_CONTENT,
+        )
+        .await?;
+        Ok(())
+    }
+}
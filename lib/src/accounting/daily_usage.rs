@@ -0,0 +1,38 @@
+use std::rc::Rc;
+
+use avina_wire::accounting::DailyUsageBackfill;
+use reqwest::{Method, StatusCode};
+
+use crate::{
+    common::{Client, SerializableNone, request},
+    error::ApiError,
+};
+
+#[derive(Debug)]
+pub struct DailyUsageApi {
+    pub url: String,
+    pub client: Rc<Client>,
+}
+
+impl DailyUsageApi {
+    pub fn new(base_url: &str, client: &Rc<Client>) -> DailyUsageApi {
+        DailyUsageApi {
+            url: format!("{base_url}/accounting/dailyusage"),
+            client: Rc::clone(client),
+        }
+    }
+
+    /// Aggregates the usage of all closed days, which are not aggregated
+    /// yet, the same way every server state import does.
+    pub async fn backfill(&self) -> Result<DailyUsageBackfill, ApiError> {
+        let url = format!("{}/backfill/", self.url);
+        request(
+            &self.client,
+            Method::POST,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+}
@@ -0,0 +1,137 @@
+use std::rc::Rc;
+
+use anyhow::Context;
+use avina_wire::accounting::{
+    FloatingIpState, FloatingIpStateImport, FloatingIpStateListParams,
+};
+use reqwest::{Method, StatusCode};
+use uuid::Uuid;
+
+use crate::{
+    common::{Client, SerializableNone, request},
+    error::ApiError,
+};
+
+#[derive(Debug)]
+pub struct FloatingIpStateApi {
+    pub url: String,
+    pub client: Rc<Client>,
+}
+
+#[derive(Debug)]
+pub struct FloatingIpStateListRequest {
+    url: String,
+    client: Rc<Client>,
+
+    params: FloatingIpStateListParams,
+}
+
+impl FloatingIpStateListRequest {
+    pub fn new(url: &str, client: &Rc<Client>) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+
+            params: FloatingIpStateListParams {
+                floating_ip: None,
+                user: None,
+                project: None,
+                all: None,
+            },
+        }
+    }
+
+    pub async fn send(&self) -> Result<Vec<FloatingIpState>, ApiError> {
+        let params = serde_urlencoded::to_string(&self.params)
+            .context("Failed to encode URL parameters")?;
+        let url = if params.is_empty() {
+            self.url.clone()
+        } else {
+            format!("{}?{}", self.url, params)
+        };
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    pub fn floating_ip(&mut self, floating_ip: Uuid) -> &mut Self {
+        self.params.floating_ip = Some(floating_ip);
+        self
+    }
+
+    pub fn user(&mut self, user: u32) -> &mut Self {
+        self.params.user = Some(user);
+        self
+    }
+
+    pub fn project(&mut self, project: u32) -> &mut Self {
+        self.params.project = Some(project);
+        self
+    }
+
+    pub fn all(&mut self) -> &mut Self {
+        self.params.all = Some(true);
+        self
+    }
+}
+
+impl FloatingIpStateApi {
+    pub fn new(base_url: &str, client: &Rc<Client>) -> FloatingIpStateApi {
+        FloatingIpStateApi {
+            url: format!("{base_url}/accounting/floatingipstates"),
+            client: Rc::clone(client),
+        }
+    }
+
+    pub fn list(&self) -> FloatingIpStateListRequest {
+        FloatingIpStateListRequest::new(self.url.as_ref(), &self.client)
+    }
+
+    pub async fn get(&self, id: u32) -> Result<FloatingIpState, ApiError> {
+        // TODO use Url.join
+        let url = format!("{}/{}", self.url, id);
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    pub async fn import(&self) -> Result<FloatingIpStateImport, ApiError> {
+        // TODO use Url.join
+        let url = format!("{}/import/", self.url);
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    /// Runs the import without storing anything and returns what it would
+    /// change.
+    pub async fn import_dry_run(
+        &self,
+    ) -> Result<FloatingIpStateImport, ApiError> {
+        // TODO use Url.join
+        let url = format!("{}/import/?dry_run=true", self.url);
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+}
@@ -1,7 +1,19 @@
+mod billing_policy;
+mod daily_usage;
+mod floating_ip_state;
 mod server_consumption;
 mod server_cost;
 mod server_state;
+mod unmatched_server;
+mod volume_cost;
+mod volume_state;
 
+pub use billing_policy::BillingPolicyApi;
+pub use daily_usage::DailyUsageApi;
+pub use floating_ip_state::FloatingIpStateApi;
 pub use server_consumption::ServerConsumptionApi;
 pub use server_cost::ServerCostApi;
 pub use server_state::ServerStateApi;
+pub use unmatched_server::UnmatchedServerApi;
+pub use volume_cost::VolumeCostApi;
+pub use volume_state::VolumeStateApi;
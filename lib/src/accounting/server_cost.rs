@@ -2,15 +2,16 @@ use std::{fmt::Debug, rc::Rc};
 
 use anyhow::Context;
 use avina_wire::accounting::{
-    ServerCostAll, ServerCostParams, ServerCostProject, ServerCostServer,
-    ServerCostSimple, ServerCostUser,
+    Bucket, Granularity, ServerCostAll, ServerCostParams, ServerCostProject,
+    ServerCostServer, ServerCostSimple, ServerCostUser,
 };
 use chrono::{DateTime, FixedOffset};
-use reqwest::{Client, Method, StatusCode};
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
 use uuid::Uuid;
 
 use crate::{
-    common::{SerializableNone, request},
+    common::{Client, SerializableNone, request},
     error::ApiError,
 };
 
@@ -37,6 +38,7 @@ impl ServerCostRequest {
                 project: None,
                 all: None,
                 detail: None,
+                granularity: None,
             },
         }
     }
@@ -263,6 +265,140 @@ impl ServerCostRequest {
     }
 }
 
+#[derive(Debug)]
+pub struct ServerCostSeriesRequest {
+    url: String,
+    client: Rc<Client>,
+
+    params: ServerCostParams,
+}
+
+impl ServerCostSeriesRequest {
+    pub fn new(
+        url: &str,
+        client: &Rc<Client>,
+        granularity: Granularity,
+    ) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+
+            params: ServerCostParams {
+                begin: None,
+                end: None,
+                server: None,
+                user: None,
+                project: None,
+                all: None,
+                detail: None,
+                granularity: Some(granularity),
+            },
+        }
+    }
+
+    pub fn begin(&mut self, begin: DateTime<FixedOffset>) -> &mut Self {
+        self.params.begin = Some(begin);
+        self
+    }
+
+    pub fn end(&mut self, end: DateTime<FixedOffset>) -> &mut Self {
+        self.params.end = Some(end);
+        self
+    }
+
+    async fn send<T: DeserializeOwned>(&self) -> Result<T, ApiError> {
+        let params = serde_urlencoded::to_string(&self.params)
+            .context("Failed to encode URL parameters")?;
+        request(
+            &self.client,
+            Method::GET,
+            format!("{}?{}", self.url, params).as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    pub async fn server(
+        &mut self,
+        server: Uuid,
+    ) -> Result<Vec<Bucket<ServerCostSimple>>, ApiError> {
+        self.params.server = Some(server);
+        self.send().await
+    }
+
+    pub async fn server_detail(
+        &mut self,
+        server: Uuid,
+    ) -> Result<Vec<Bucket<ServerCostServer>>, ApiError> {
+        self.params.server = Some(server);
+        self.params.detail = Some(true);
+        self.send().await
+    }
+
+    pub async fn user(
+        &mut self,
+        user: u32,
+    ) -> Result<Vec<Bucket<ServerCostSimple>>, ApiError> {
+        self.params.user = Some(user);
+        self.send().await
+    }
+
+    pub async fn user_detail(
+        &mut self,
+        user: u32,
+    ) -> Result<Vec<Bucket<ServerCostUser>>, ApiError> {
+        self.params.user = Some(user);
+        self.params.detail = Some(true);
+        self.send().await
+    }
+
+    pub async fn project(
+        &mut self,
+        project: u32,
+    ) -> Result<Vec<Bucket<ServerCostSimple>>, ApiError> {
+        self.params.project = Some(project);
+        self.send().await
+    }
+
+    pub async fn project_detail(
+        &mut self,
+        project: u32,
+    ) -> Result<Vec<Bucket<ServerCostProject>>, ApiError> {
+        self.params.project = Some(project);
+        self.params.detail = Some(true);
+        self.send().await
+    }
+
+    pub async fn all(
+        &mut self,
+    ) -> Result<Vec<Bucket<ServerCostSimple>>, ApiError> {
+        self.params.all = Some(true);
+        self.send().await
+    }
+
+    pub async fn all_detail(
+        &mut self,
+    ) -> Result<Vec<Bucket<ServerCostAll>>, ApiError> {
+        self.params.all = Some(true);
+        self.params.detail = Some(true);
+        self.send().await
+    }
+
+    pub async fn mine(
+        &mut self,
+    ) -> Result<Vec<Bucket<ServerCostSimple>>, ApiError> {
+        self.send().await
+    }
+
+    pub async fn mine_detail(
+        &mut self,
+    ) -> Result<Vec<Bucket<ServerCostUser>>, ApiError> {
+        self.params.detail = Some(true);
+        self.send().await
+    }
+}
+
 #[derive(Debug)]
 pub struct ServerCostApi {
     pub url: String,
@@ -280,4 +416,12 @@ impl ServerCostApi {
     pub fn get(&self) -> ServerCostRequest {
         ServerCostRequest::new(self.url.as_str(), &self.client)
     }
+
+    pub fn series(&self, granularity: Granularity) -> ServerCostSeriesRequest {
+        ServerCostSeriesRequest::new(
+            self.url.as_str(),
+            &self.client,
+            granularity,
+        )
+    }
 }
@@ -2,15 +2,16 @@ use std::rc::Rc;
 
 use anyhow::Context;
 use avina_wire::accounting::{
-    ServerState, ServerStateCreateData, ServerStateImport,
-    ServerStateListParams, ServerStateModifyData,
+    ServerState, ServerStateCompaction, ServerStateCreateData,
+    ServerStateImport, ServerStateImportDiff, ServerStateListParams,
+    ServerStateModifyData, ServerTimelineSegment,
 };
 use chrono::{DateTime, FixedOffset};
-use reqwest::{Client, Method, StatusCode};
+use reqwest::{Method, StatusCode};
 use uuid::Uuid;
 
 use crate::{
-    common::{SerializableNone, request, request_bare},
+    common::{Client, SerializableNone, request, request_bare},
     error::ApiError,
 };
 
@@ -275,4 +276,59 @@ impl ServerStateApi {
         )
         .await
     }
+
+    /// Runs the import without storing anything and returns what it would
+    /// change.
+    pub async fn import_dry_run(
+        &self,
+    ) -> Result<ServerStateImportDiff, ApiError> {
+        // TODO use Url.join
+        let url = format!("{}/import/?dry_run=true", self.url);
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    /// Returns the states of the server in chronological order, together
+    /// with how long each one lasted and what it cost.
+    pub async fn timeline(
+        &self,
+        server: Uuid,
+    ) -> Result<Vec<ServerTimelineSegment>, ApiError> {
+        // NOTE: the timeline is served under the servers rather than their
+        // states.
+        let url = format!(
+            "{}/{}/timeline/",
+            self.url.replace("/serverstates", "/servers"),
+            server
+        );
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    /// Merges adjacent identical states and archives the ones of closed
+    /// fiscal years according to the retention policy of the server.
+    pub async fn compact(&self) -> Result<ServerStateCompaction, ApiError> {
+        // TODO use Url.join
+        let url = format!("{}/compact/", self.url);
+        request(
+            &self.client,
+            Method::POST,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
 }
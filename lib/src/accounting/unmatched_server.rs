@@ -0,0 +1,125 @@
+use std::rc::Rc;
+
+use anyhow::Context;
+use avina_wire::accounting::{
+    UnmatchedServer, UnmatchedServerListParams, UnmatchedServerResolveData,
+};
+use reqwest::{Method, StatusCode};
+
+use crate::{
+    common::{Client, SerializableNone, request},
+    error::ApiError,
+};
+
+#[derive(Debug)]
+pub struct UnmatchedServerApi {
+    pub url: String,
+    pub client: Rc<Client>,
+}
+
+#[derive(Debug)]
+pub struct UnmatchedServerListRequest {
+    url: String,
+    client: Rc<Client>,
+
+    params: UnmatchedServerListParams,
+}
+
+impl UnmatchedServerListRequest {
+    pub fn new(url: &str, client: &Rc<Client>) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+
+            params: UnmatchedServerListParams { all: None },
+        }
+    }
+
+    pub async fn send(&self) -> Result<Vec<UnmatchedServer>, ApiError> {
+        let params = serde_urlencoded::to_string(&self.params)
+            .context("Failed to encode URL parameters")?;
+        let url = if params.is_empty() {
+            self.url.clone()
+        } else {
+            format!("{}?{}", self.url, params)
+        };
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    pub fn all(&mut self) -> &mut Self {
+        self.params.all = Some(true);
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct UnmatchedServerResolveRequest {
+    url: String,
+    client: Rc<Client>,
+
+    data: UnmatchedServerResolveData,
+}
+
+impl UnmatchedServerResolveRequest {
+    pub fn new(url: &str, client: &Rc<Client>, id: u32) -> Self {
+        Self {
+            url: format!("{url}{id}/resolve/"),
+            client: Rc::clone(client),
+            data: UnmatchedServerResolveData {
+                flavor: None,
+                user: None,
+                ignore: false,
+            },
+        }
+    }
+
+    pub fn flavor(&mut self, flavor: u32) -> &mut Self {
+        self.data.flavor = Some(flavor);
+        self
+    }
+
+    pub fn user(&mut self, user: u32) -> &mut Self {
+        self.data.user = Some(user);
+        self
+    }
+
+    pub fn ignore(&mut self) -> &mut Self {
+        self.data.ignore = true;
+        self
+    }
+
+    pub async fn send(&self) -> Result<UnmatchedServer, ApiError> {
+        request(
+            &self.client,
+            Method::POST,
+            &self.url,
+            Some(&self.data),
+            StatusCode::OK,
+        )
+        .await
+    }
+}
+
+impl UnmatchedServerApi {
+    pub fn new(base_url: &str, client: &Rc<Client>) -> UnmatchedServerApi {
+        UnmatchedServerApi {
+            url: format!("{base_url}/accounting/unmatchedservers/"),
+            client: Rc::clone(client),
+        }
+    }
+
+    pub fn list(&self) -> UnmatchedServerListRequest {
+        UnmatchedServerListRequest::new(self.url.as_ref(), &self.client)
+    }
+
+    pub fn resolve(&self, id: u32) -> UnmatchedServerResolveRequest {
+        UnmatchedServerResolveRequest::new(self.url.as_ref(), &self.client, id)
+    }
+}
@@ -0,0 +1,161 @@
+use std::rc::Rc;
+
+use anyhow::Context;
+use avina_wire::accounting::{
+    VolumeCostAll, VolumeCostParams, VolumeCostProject, VolumeCostSimple,
+    VolumeCostUser, VolumeCostVolume,
+};
+use chrono::{DateTime, FixedOffset};
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+use uuid::Uuid;
+
+use crate::{
+    common::{Client, SerializableNone, request},
+    error::ApiError,
+};
+
+#[derive(Debug)]
+pub struct VolumeCostRequest {
+    url: String,
+    client: Rc<Client>,
+
+    params: VolumeCostParams,
+}
+
+impl VolumeCostRequest {
+    pub fn new(url: &str, client: &Rc<Client>) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+
+            params: VolumeCostParams {
+                begin: None,
+                end: None,
+                volume: None,
+                user: None,
+                project: None,
+                all: None,
+                detail: None,
+            },
+        }
+    }
+
+    pub fn begin(&mut self, begin: DateTime<FixedOffset>) -> &mut Self {
+        self.params.begin = Some(begin);
+        self
+    }
+
+    pub fn end(&mut self, end: DateTime<FixedOffset>) -> &mut Self {
+        self.params.end = Some(end);
+        self
+    }
+
+    async fn send<T: DeserializeOwned>(&self) -> Result<T, ApiError> {
+        let params = serde_urlencoded::to_string(&self.params)
+            .context("Failed to encode URL parameters")?;
+        let url = if params.is_empty() {
+            self.url.clone()
+        } else {
+            format!("{}?{}", self.url, params)
+        };
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    pub async fn volume(
+        &mut self,
+        volume: Uuid,
+    ) -> Result<VolumeCostSimple, ApiError> {
+        self.params.volume = Some(volume);
+        self.send().await
+    }
+
+    pub async fn volume_detail(
+        &mut self,
+        volume: Uuid,
+    ) -> Result<VolumeCostVolume, ApiError> {
+        self.params.volume = Some(volume);
+        self.params.detail = Some(true);
+        self.send().await
+    }
+
+    pub async fn user(
+        &mut self,
+        user: u32,
+    ) -> Result<VolumeCostSimple, ApiError> {
+        self.params.user = Some(user);
+        self.send().await
+    }
+
+    pub async fn user_detail(
+        &mut self,
+        user: u32,
+    ) -> Result<VolumeCostUser, ApiError> {
+        self.params.user = Some(user);
+        self.params.detail = Some(true);
+        self.send().await
+    }
+
+    pub async fn project(
+        &mut self,
+        project: u32,
+    ) -> Result<VolumeCostSimple, ApiError> {
+        self.params.project = Some(project);
+        self.send().await
+    }
+
+    pub async fn project_detail(
+        &mut self,
+        project: u32,
+    ) -> Result<VolumeCostProject, ApiError> {
+        self.params.project = Some(project);
+        self.params.detail = Some(true);
+        self.send().await
+    }
+
+    pub async fn all(&mut self) -> Result<VolumeCostSimple, ApiError> {
+        self.params.all = Some(true);
+        self.send().await
+    }
+
+    pub async fn all_detail(&mut self) -> Result<VolumeCostAll, ApiError> {
+        self.params.all = Some(true);
+        self.params.detail = Some(true);
+        self.send().await
+    }
+
+    pub async fn mine(&mut self) -> Result<VolumeCostSimple, ApiError> {
+        self.send().await
+    }
+
+    pub async fn mine_detail(&mut self) -> Result<VolumeCostUser, ApiError> {
+        self.params.detail = Some(true);
+        self.send().await
+    }
+}
+
+#[derive(Debug)]
+pub struct VolumeCostApi {
+    pub url: String,
+    pub client: Rc<Client>,
+}
+
+impl VolumeCostApi {
+    pub fn new(base_url: &str, client: &Rc<Client>) -> VolumeCostApi {
+        VolumeCostApi {
+            url: format!("{base_url}/accounting/volumecost/"),
+            client: Rc::clone(client),
+        }
+    }
+
+    pub fn get(&self) -> VolumeCostRequest {
+        VolumeCostRequest::new(self.url.as_str(), &self.client)
+    }
+}
@@ -0,0 +1,135 @@
+use std::rc::Rc;
+
+use anyhow::Context;
+use avina_wire::accounting::{
+    VolumeState, VolumeStateImport, VolumeStateListParams,
+};
+use reqwest::{Method, StatusCode};
+use uuid::Uuid;
+
+use crate::{
+    common::{Client, SerializableNone, request},
+    error::ApiError,
+};
+
+#[derive(Debug)]
+pub struct VolumeStateApi {
+    pub url: String,
+    pub client: Rc<Client>,
+}
+
+#[derive(Debug)]
+pub struct VolumeStateListRequest {
+    url: String,
+    client: Rc<Client>,
+
+    params: VolumeStateListParams,
+}
+
+impl VolumeStateListRequest {
+    pub fn new(url: &str, client: &Rc<Client>) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+
+            params: VolumeStateListParams {
+                volume: None,
+                user: None,
+                project: None,
+                all: None,
+            },
+        }
+    }
+
+    pub async fn send(&self) -> Result<Vec<VolumeState>, ApiError> {
+        let params = serde_urlencoded::to_string(&self.params)
+            .context("Failed to encode URL parameters")?;
+        let url = if params.is_empty() {
+            self.url.clone()
+        } else {
+            format!("{}?{}", self.url, params)
+        };
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    pub fn volume(&mut self, volume: Uuid) -> &mut Self {
+        self.params.volume = Some(volume);
+        self
+    }
+
+    pub fn user(&mut self, user: u32) -> &mut Self {
+        self.params.user = Some(user);
+        self
+    }
+
+    pub fn project(&mut self, project: u32) -> &mut Self {
+        self.params.project = Some(project);
+        self
+    }
+
+    pub fn all(&mut self) -> &mut Self {
+        self.params.all = Some(true);
+        self
+    }
+}
+
+impl VolumeStateApi {
+    pub fn new(base_url: &str, client: &Rc<Client>) -> VolumeStateApi {
+        VolumeStateApi {
+            url: format!("{base_url}/accounting/volumestates"),
+            client: Rc::clone(client),
+        }
+    }
+
+    pub fn list(&self) -> VolumeStateListRequest {
+        VolumeStateListRequest::new(self.url.as_ref(), &self.client)
+    }
+
+    pub async fn get(&self, id: u32) -> Result<VolumeState, ApiError> {
+        // TODO use Url.join
+        let url = format!("{}/{}", self.url, id);
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    pub async fn import(&self) -> Result<VolumeStateImport, ApiError> {
+        // TODO use Url.join
+        let url = format!("{}/import/", self.url);
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    /// Runs the import without storing anything and returns what it would
+    /// change.
+    pub async fn import_dry_run(&self) -> Result<VolumeStateImport, ApiError> {
+        // TODO use Url.join
+        let url = format!("{}/import/?dry_run=true", self.url);
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+}
@@ -0,0 +1,98 @@
+use std::rc::Rc;
+
+use anyhow::Context;
+use avina_wire::audit::{AuditLogEntry, AuditLogListParams};
+use chrono::{DateTime, FixedOffset};
+use reqwest::{Method, StatusCode};
+
+use crate::{
+    common::{Client, SerializableNone, request},
+    error::ApiError,
+};
+
+#[derive(Debug)]
+pub struct AuditApi {
+    pub url: String,
+    pub client: Rc<Client>,
+}
+
+#[derive(Debug)]
+pub struct AuditLogListRequest {
+    url: String,
+    client: Rc<Client>,
+
+    params: AuditLogListParams,
+}
+
+impl AuditLogListRequest {
+    pub fn new(url: &str, client: &Rc<Client>) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+
+            params: AuditLogListParams {
+                begin: None,
+                end: None,
+                actor: None,
+                resource_type: None,
+                resource_id: None,
+            },
+        }
+    }
+
+    pub async fn send(&self) -> Result<Vec<AuditLogEntry>, ApiError> {
+        let params = serde_urlencoded::to_string(&self.params)
+            .context("Failed to encode URL parameters.")?;
+        let url = if params.is_empty() {
+            self.url.clone()
+        } else {
+            format!("{}?{}", self.url, params)
+        };
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    pub fn begin(&mut self, begin: DateTime<FixedOffset>) -> &mut Self {
+        self.params.begin = Some(begin);
+        self
+    }
+
+    pub fn end(&mut self, end: DateTime<FixedOffset>) -> &mut Self {
+        self.params.end = Some(end);
+        self
+    }
+
+    pub fn actor(&mut self, actor: u32) -> &mut Self {
+        self.params.actor = Some(actor);
+        self
+    }
+
+    pub fn resource_type(&mut self, resource_type: String) -> &mut Self {
+        self.params.resource_type = Some(resource_type);
+        self
+    }
+
+    pub fn resource_id(&mut self, resource_id: u32) -> &mut Self {
+        self.params.resource_id = Some(resource_id);
+        self
+    }
+}
+
+impl AuditApi {
+    pub fn new(base_url: &str, client: &Rc<Client>) -> AuditApi {
+        AuditApi {
+            url: format!("{base_url}/audit/"),
+            client: Rc::clone(client),
+        }
+    }
+
+    pub fn list(&self) -> AuditLogListRequest {
+        AuditLogListRequest::new(self.url.as_ref(), &self.client)
+    }
+}
@@ -1,9 +1,12 @@
 use std::rc::Rc;
 
 use avina_wire::budgeting::{BudgetBulkCreate, BudgetBulkCreateData};
-use reqwest::{Client, Method, StatusCode};
+use reqwest::{Method, StatusCode};
 
-use crate::{common::request, error::ApiError};
+use crate::{
+    common::{Client, request},
+    error::ApiError,
+};
 
 #[derive(Debug)]
 pub struct BudgetBulkCreateApi {
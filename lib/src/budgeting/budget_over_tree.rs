@@ -3,10 +3,10 @@ use std::rc::Rc;
 use anyhow::Context;
 use avina_wire::budgeting::{BudgetOverTree, BudgetOverTreeParams};
 use chrono::{DateTime, FixedOffset};
-use reqwest::{Client, Method, StatusCode};
+use reqwest::{Method, StatusCode};
 
 use crate::{
-    common::{SerializableNone, request},
+    common::{Client, SerializableNone, request},
     error::ApiError,
 };
 
@@ -8,10 +8,10 @@ use avina_wire::budgeting::{
     UserBudgetSync,
 };
 use chrono::{DateTime, FixedOffset};
-use reqwest::{Client, Method, StatusCode};
+use reqwest::{Method, StatusCode};
 
 use crate::{
-    common::{SerializableNone, request, request_bare},
+    common::{Client, SerializableNone, request, request_bare},
     error::ApiError,
 };
 
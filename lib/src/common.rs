@@ -1,11 +1,14 @@
-use std::fmt::Debug;
+use std::{cell::RefCell, fmt::Debug};
 
 use anyhow::Context;
 use avina_wire::error::ErrorResponse;
-use reqwest::{Client, Method, Response, StatusCode};
+use reqwest::{
+    Method, Response, StatusCode,
+    header::{AUTHORIZATION, HeaderValue},
+};
 use serde::{de::DeserializeOwned, ser::Serialize};
 
-use crate::error::ApiError;
+use crate::{Token, error::ApiError};
 
 #[derive(serde::Serialize, Debug)]
 pub(crate) struct SerializableFoo {}
@@ -16,6 +19,61 @@ macro_rules! SerializableNone {
 }
 pub(crate) use SerializableNone;
 
+/// HTTP client that authenticates every request with the current token and
+/// renews the token when it has expired.
+#[derive(Debug)]
+pub struct Client {
+    http: reqwest::Client,
+    token: RefCell<Token>,
+}
+
+impl Client {
+    pub(crate) fn new(http: reqwest::Client, token: Token) -> Self {
+        Self {
+            http,
+            token: RefCell::new(token),
+        }
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<String>,
+    ) -> Result<Response, ApiError> {
+        let (name, value) = {
+            let token = self.token.borrow();
+            if token.is_api_key() {
+                (AUTHORIZATION.as_str(), format!("Bearer {}", token.as_ref()))
+            } else {
+                ("X-Auth-Token", token.as_ref().to_string())
+            }
+        };
+        let value = HeaderValue::from_str(value.as_str())
+            .context("Failed to create token header value")?;
+        let mut request = self.http.request(method, url).header(name, value);
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+        match request.send().await.context("") {
+            Ok(response) => Ok(response),
+            Err(err) => {
+                let detail =
+                    format!("Could not complete request: {}", err.root_cause());
+                Err(ApiError::ResponseError(detail))
+            }
+        }
+    }
+
+    async fn refresh_token(&self) -> Result<(), ApiError> {
+        // NOTE: renew a copy so no borrow is held across the await point.
+        let mut token = self.token.borrow().clone();
+        token.refresh().await.context("Failed to renew token")?;
+        self.token.replace(token);
+        Ok(())
+    }
+}
+
 pub(crate) async fn request_bare<T>(
     client: &Client,
     method: Method,
@@ -26,20 +84,23 @@ pub(crate) async fn request_bare<T>(
 where
     T: Serialize + Debug,
 {
-    let mut request = client.request(method, url);
-    if let Some(data) = data {
-        request = request.body(serde_json::to_string(&data).context(
-            format!("Could not serialize json request body from {data:?}"),
-        )?);
-    }
-    let response = match request.send().await.context("") {
-        Ok(response) => response,
-        Err(err) => {
-            let detail =
-                format!("Could not complete request: {}", err.root_cause());
-            return Err(ApiError::ResponseError(detail));
-        }
+    let body = match data {
+        Some(data) => Some(serde_json::to_string(&data).context(format!(
+            "Could not serialize json request body from {data:?}"
+        ))?),
+        None => None,
     };
+    let renewable = client.token.borrow().is_renewable();
+    if renewable && client.token.borrow().is_expired() {
+        client.refresh_token().await?;
+    }
+    let mut response = client.send(method.clone(), url, body.clone()).await?;
+    // NOTE: the token may also have been revoked or expired early, so
+    // renew it once and retry before giving up.
+    if renewable && response.status() == StatusCode::UNAUTHORIZED {
+        client.refresh_token().await?;
+        response = client.send(method, url, body).await?;
+    }
     let status = response.status();
     if status != expected_status {
         let text = response.text().await.context(format!(
@@ -1,10 +1,10 @@
 use std::rc::Rc;
 
 use avina_wire::hello::Hello;
-use reqwest::{Client, Method, StatusCode};
+use reqwest::{Method, StatusCode};
 
 use crate::{
-    common::{SerializableNone, request},
+    common::{Client, SerializableNone, request},
     error::ApiError,
 };
 
@@ -7,6 +7,7 @@ use reqwest::{
 };
 
 mod common;
+use common::Client;
 pub mod error;
 use error::ApiError;
 
@@ -15,6 +16,8 @@ pub use token::Token;
 
 #[cfg(feature = "accounting")]
 mod accounting;
+#[cfg(feature = "audit")]
+mod audit;
 #[cfg(feature = "budgeting")]
 mod budgeting;
 #[cfg(feature = "hello")]
@@ -25,18 +28,36 @@ mod pricing;
 mod quota;
 #[cfg(feature = "resources")]
 mod resources;
+#[cfg(feature = "scheduler")]
+mod scheduler;
 #[cfg(feature = "user")]
 mod user;
 
+#[cfg(feature = "accounting")]
+use accounting::BillingPolicyApi;
+#[cfg(feature = "accounting")]
+use accounting::DailyUsageApi;
+#[cfg(feature = "accounting")]
+use accounting::FloatingIpStateApi;
 #[cfg(feature = "accounting")]
 use accounting::ServerConsumptionApi;
 #[cfg(feature = "accounting")]
 use accounting::ServerCostApi;
 #[cfg(feature = "accounting")]
 use accounting::ServerStateApi;
+#[cfg(feature = "accounting")]
+use accounting::UnmatchedServerApi;
+#[cfg(feature = "accounting")]
+use accounting::VolumeCostApi;
+#[cfg(feature = "accounting")]
+use accounting::VolumeStateApi;
+#[cfg(feature = "audit")]
+use audit::AuditApi;
 #[cfg(feature = "budgeting")]
 use budgeting::BudgetBulkCreateApi;
 #[cfg(feature = "budgeting")]
+use budgeting::BudgetForecastApi;
+#[cfg(feature = "budgeting")]
 use budgeting::BudgetOverTreeApi;
 #[cfg(feature = "budgeting")]
 use budgeting::ProjectBudgetApi;
@@ -46,6 +67,10 @@ use budgeting::UserBudgetApi;
 use hello::HelloApi;
 #[cfg(feature = "pricing")]
 use pricing::FlavorPriceApi;
+#[cfg(feature = "pricing")]
+use pricing::FloatingIpPriceApi;
+#[cfg(feature = "pricing")]
+use pricing::VolumePriceApi;
 #[cfg(feature = "quota")]
 use quota::FlavorQuotaApi;
 #[cfg(feature = "resources")]
@@ -54,9 +79,15 @@ use resources::FlavorApi;
 use resources::FlavorGroupApi;
 #[cfg(feature = "resources")]
 use resources::UsageApi;
+#[cfg(feature = "scheduler")]
+use scheduler::SchedulerApi;
+#[cfg(feature = "user")]
+use user::ApiKeyApi;
 #[cfg(feature = "user")]
 use user::ProjectApi;
 #[cfg(feature = "user")]
+use user::ProjectClassChangeApi;
+#[cfg(feature = "user")]
 use user::UserApi;
 
 pub const DEFAULT_TIMEOUT: u64 = 300;
@@ -65,14 +96,17 @@ pub const DEFAULT_TIMEOUT: u64 = 300;
 pub struct Api {
     // url: Rc<str>,
     #[allow(unused)]
-    token: Token,
-    // client: Rc<Client>,
+    client: Rc<Client>,
     #[cfg(feature = "hello")]
     pub hello: HelloApi,
     #[cfg(feature = "user")]
     pub project: ProjectApi,
     #[cfg(feature = "user")]
+    pub project_class_change: ProjectClassChangeApi,
+    #[cfg(feature = "user")]
     pub user: UserApi,
+    #[cfg(feature = "user")]
+    pub api_key: ApiKeyApi,
     #[cfg(feature = "resources")]
     pub flavor: FlavorApi,
     #[cfg(feature = "resources")]
@@ -81,6 +115,10 @@ pub struct Api {
     pub usage: UsageApi,
     #[cfg(feature = "pricing")]
     pub flavor_price: FlavorPriceApi,
+    #[cfg(feature = "pricing")]
+    pub volume_price: VolumePriceApi,
+    #[cfg(feature = "pricing")]
+    pub floating_ip_price: FloatingIpPriceApi,
     #[cfg(feature = "quota")]
     pub flavor_quota: FlavorQuotaApi,
     #[cfg(feature = "accounting")]
@@ -89,6 +127,18 @@ pub struct Api {
     pub server_cost: ServerCostApi,
     #[cfg(feature = "accounting")]
     pub server_consumption: ServerConsumptionApi,
+    #[cfg(feature = "accounting")]
+    pub unmatched_server: UnmatchedServerApi,
+    #[cfg(feature = "accounting")]
+    pub billing_policy: BillingPolicyApi,
+    #[cfg(feature = "accounting")]
+    pub daily_usage: DailyUsageApi,
+    #[cfg(feature = "accounting")]
+    pub volume_state: VolumeStateApi,
+    #[cfg(feature = "accounting")]
+    pub volume_cost: VolumeCostApi,
+    #[cfg(feature = "accounting")]
+    pub floating_ip_state: FloatingIpStateApi,
     #[cfg(feature = "budgeting")]
     pub project_budget: ProjectBudgetApi,
     #[cfg(feature = "budgeting")]
@@ -97,6 +147,12 @@ pub struct Api {
     pub budget_over_tree: BudgetOverTreeApi,
     #[cfg(feature = "budgeting")]
     pub budget_bulk_create: BudgetBulkCreateApi,
+    #[cfg(feature = "budgeting")]
+    pub budget_forecast: BudgetForecastApi,
+    #[cfg(feature = "audit")]
+    pub audit: AuditApi,
+    #[cfg(feature = "scheduler")]
+    pub scheduler: SchedulerApi,
 }
 
 impl Api {
@@ -110,11 +166,6 @@ impl Api {
         let mut headers = HeaderMap::new();
         headers
             .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-        headers.insert(
-            "X-Auth-Token",
-            HeaderValue::from_str(token.as_ref())
-                .context("Failed to create token header value")?,
-        );
         if let Some(impersonate) = impersonate {
             headers.insert(
                 "X-Impersonate",
@@ -133,20 +184,24 @@ impl Api {
             builder = builder.timeout(Duration::from_secs(timeout));
         }
 
-        let client = Rc::new(
+        let client = Rc::new(Client::new(
             builder
                 .default_headers(headers)
                 .build()
                 .context("Failed to build http client")?,
-        );
-        Ok(Api {
             token,
+        ));
+        Ok(Api {
             #[cfg(feature = "hello")]
             hello: HelloApi::new(&url, &client),
             #[cfg(feature = "user")]
             project: ProjectApi::new(&url, &client),
             #[cfg(feature = "user")]
+            project_class_change: ProjectClassChangeApi::new(&url, &client),
+            #[cfg(feature = "user")]
             user: UserApi::new(&url, &client),
+            #[cfg(feature = "user")]
+            api_key: ApiKeyApi::new(&url, &client),
             #[cfg(feature = "resources")]
             flavor: FlavorApi::new(&url, &client),
             #[cfg(feature = "resources")]
@@ -155,6 +210,10 @@ impl Api {
             usage: UsageApi::new(&url, &client),
             #[cfg(feature = "pricing")]
             flavor_price: FlavorPriceApi::new(&url, &client),
+            #[cfg(feature = "pricing")]
+            volume_price: VolumePriceApi::new(&url, &client),
+            #[cfg(feature = "pricing")]
+            floating_ip_price: FloatingIpPriceApi::new(&url, &client),
             #[cfg(feature = "quota")]
             flavor_quota: FlavorQuotaApi::new(&url, &client),
             #[cfg(feature = "accounting")]
@@ -163,6 +222,18 @@ impl Api {
             server_cost: ServerCostApi::new(&url, &client),
             #[cfg(feature = "accounting")]
             server_consumption: ServerConsumptionApi::new(&url, &client),
+            #[cfg(feature = "accounting")]
+            unmatched_server: UnmatchedServerApi::new(&url, &client),
+            #[cfg(feature = "accounting")]
+            billing_policy: BillingPolicyApi::new(&url, &client),
+            #[cfg(feature = "accounting")]
+            daily_usage: DailyUsageApi::new(&url, &client),
+            #[cfg(feature = "accounting")]
+            volume_state: VolumeStateApi::new(&url, &client),
+            #[cfg(feature = "accounting")]
+            volume_cost: VolumeCostApi::new(&url, &client),
+            #[cfg(feature = "accounting")]
+            floating_ip_state: FloatingIpStateApi::new(&url, &client),
             #[cfg(feature = "budgeting")]
             project_budget: ProjectBudgetApi::new(&url, &client),
             #[cfg(feature = "budgeting")]
@@ -171,6 +242,13 @@ impl Api {
             budget_over_tree: BudgetOverTreeApi::new(&url, &client),
             #[cfg(feature = "budgeting")]
             budget_bulk_create: BudgetBulkCreateApi::new(&url, &client),
+            #[cfg(feature = "budgeting")]
+            budget_forecast: BudgetForecastApi::new(&url, &client),
+            #[cfg(feature = "audit")]
+            audit: AuditApi::new(&url, &client),
+            #[cfg(feature = "scheduler")]
+            scheduler: SchedulerApi::new(&url, &client),
+            client,
         })
     }
 }
@@ -3,16 +3,21 @@ use std::rc::Rc;
 use anyhow::Context;
 use avina_wire::{
     pricing::{
-        FlavorPrice, FlavorPriceCreateData, FlavorPriceInitialize,
-        FlavorPriceModifyData,
+        FlavorPrice, FlavorPriceBulkCreateData, FlavorPriceBulkPreview,
+        FlavorPriceBulkPreviewData, FlavorPriceCreateData,
+        FlavorPriceEffective, FlavorPriceEffectiveParams,
+        FlavorPriceInitialize, FlavorPriceModifyData, FlavorPricePeriod,
+        FlavorPriceTimelineParams, FloatingIpPrice, FloatingIpPriceCreateData,
+        FloatingIpPriceModifyData, VolumePrice, VolumePriceCreateData,
+        VolumePriceModifyData,
     },
     user::UserClass,
 };
 use chrono::{DateTime, FixedOffset};
-use reqwest::{Client, Method, StatusCode, Url};
+use reqwest::{Method, StatusCode, Url};
 
 use crate::{
-    common::{SerializableNone, request, request_bare},
+    common::{Client, SerializableNone, request, request_bare},
     error::ApiError,
 };
 
@@ -147,6 +152,215 @@ impl FlavorPriceModifyRequest {
     }
 }
 
+pub struct FlavorPriceBulkCreateRequest {
+    url: String,
+    client: Rc<Client>,
+
+    data: FlavorPriceBulkCreateData,
+    begin: Option<DateTime<FixedOffset>>,
+    end: Option<DateTime<FixedOffset>>,
+}
+
+impl FlavorPriceBulkCreateRequest {
+    pub fn new(
+        url: &str,
+        client: &Rc<Client>,
+        start_time: DateTime<FixedOffset>,
+    ) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+            data: FlavorPriceBulkCreateData::new(start_time),
+            begin: None,
+            end: None,
+        }
+    }
+
+    pub fn flavor_group(&mut self, flavor_group: u32) -> &mut Self {
+        self.data.flavor_group = Some(flavor_group);
+        self
+    }
+
+    pub fn flavor(&mut self, flavor: u32) -> &mut Self {
+        self.data.flavors.push(flavor);
+        self
+    }
+
+    pub fn user_class(&mut self, user_class: UserClass) -> &mut Self {
+        self.data.user_classes.push(user_class);
+        self
+    }
+
+    pub fn unit_price(&mut self, unit_price: f64) -> &mut Self {
+        self.data.unit_price = Some(unit_price);
+        self
+    }
+
+    pub fn percentage(&mut self, percentage: f64) -> &mut Self {
+        self.data.percentage = Some(percentage);
+        self
+    }
+
+    /// Begin of the reference period of the preview.
+    pub fn begin(&mut self, begin: DateTime<FixedOffset>) -> &mut Self {
+        self.begin = Some(begin);
+        self
+    }
+
+    /// End of the reference period of the preview.
+    pub fn end(&mut self, end: DateTime<FixedOffset>) -> &mut Self {
+        self.end = Some(end);
+        self
+    }
+
+    pub async fn send(&self) -> Result<Vec<FlavorPrice>, ApiError> {
+        request(
+            &self.client,
+            Method::POST,
+            &self.url,
+            Some(&self.data),
+            StatusCode::CREATED,
+        )
+        .await
+    }
+
+    pub async fn preview(&self) -> Result<FlavorPriceBulkPreview, ApiError> {
+        // TODO use Url.join
+        let url = format!("{}preview/", self.url);
+        let data = FlavorPriceBulkPreviewData {
+            change: self.data.clone(),
+            begin: self.begin,
+            end: self.end,
+        };
+        request(
+            &self.client,
+            Method::POST,
+            url.as_str(),
+            Some(&data),
+            StatusCode::OK,
+        )
+        .await
+    }
+}
+
+#[derive(Debug)]
+pub struct FlavorPriceEffectiveRequest {
+    url: String,
+    client: Rc<Client>,
+
+    params: FlavorPriceEffectiveParams,
+}
+
+impl FlavorPriceEffectiveRequest {
+    pub fn new(url: &str, client: &Rc<Client>) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+
+            params: FlavorPriceEffectiveParams {
+                time: None,
+                user_class: None,
+                flavor: None,
+            },
+        }
+    }
+
+    pub async fn send(&self) -> Result<Vec<FlavorPriceEffective>, ApiError> {
+        let params = serde_urlencoded::to_string(&self.params)
+            .context("Failed to encode URL parameters")?;
+        let url = if params.is_empty() {
+            self.url.clone()
+        } else {
+            format!("{}?{}", self.url, params)
+        };
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    pub fn time(&mut self, time: DateTime<FixedOffset>) -> &mut Self {
+        self.params.time = Some(time);
+        self
+    }
+
+    pub fn user_class(&mut self, user_class: UserClass) -> &mut Self {
+        self.params.user_class = Some(user_class);
+        self
+    }
+
+    pub fn flavor(&mut self, flavor: u32) -> &mut Self {
+        self.params.flavor = Some(flavor);
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct FlavorPriceTimelineRequest {
+    url: String,
+    client: Rc<Client>,
+
+    params: FlavorPriceTimelineParams,
+}
+
+impl FlavorPriceTimelineRequest {
+    pub fn new(url: &str, client: &Rc<Client>) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+
+            params: FlavorPriceTimelineParams {
+                begin: None,
+                end: None,
+                user_class: None,
+                flavor: None,
+            },
+        }
+    }
+
+    pub async fn send(&self) -> Result<Vec<FlavorPricePeriod>, ApiError> {
+        let params = serde_urlencoded::to_string(&self.params)
+            .context("Failed to encode URL parameters")?;
+        let url = if params.is_empty() {
+            self.url.clone()
+        } else {
+            format!("{}?{}", self.url, params)
+        };
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    pub fn begin(&mut self, begin: DateTime<FixedOffset>) -> &mut Self {
+        self.params.begin = Some(begin);
+        self
+    }
+
+    pub fn end(&mut self, end: DateTime<FixedOffset>) -> &mut Self {
+        self.params.end = Some(end);
+        self
+    }
+
+    pub fn user_class(&mut self, user_class: UserClass) -> &mut Self {
+        self.params.user_class = Some(user_class);
+        self
+    }
+
+    pub fn flavor(&mut self, flavor: u32) -> &mut Self {
+        self.params.flavor = Some(flavor);
+        self
+    }
+}
+
 impl FlavorPriceApi {
     pub fn new(base_url: &str, client: &Rc<Client>) -> FlavorPriceApi {
         FlavorPriceApi {
@@ -210,6 +424,380 @@ impl FlavorPriceApi {
     pub async fn initialize(&self) -> Result<FlavorPriceInitialize, ApiError> {
         // TODO use Url.join
         let url = format!("{}/initialize/", self.url);
+        request(
+            &self.client,
+            Method::POST,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    pub fn effective(&self) -> FlavorPriceEffectiveRequest {
+        // TODO use Url.join
+        let url = format!("{}/effective/", self.url);
+        FlavorPriceEffectiveRequest::new(url.as_ref(), &self.client)
+    }
+
+    pub fn timeline(&self) -> FlavorPriceTimelineRequest {
+        // TODO use Url.join
+        let url = format!("{}/timeline/", self.url);
+        FlavorPriceTimelineRequest::new(url.as_ref(), &self.client)
+    }
+
+    pub fn bulk_create(
+        &self,
+        start_time: DateTime<FixedOffset>,
+    ) -> FlavorPriceBulkCreateRequest {
+        // TODO use Url.join
+        let url = format!("{}/bulk/", self.url);
+        FlavorPriceBulkCreateRequest::new(
+            url.as_ref(),
+            &self.client,
+            start_time,
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct VolumePriceApi {
+    pub url: String,
+    pub client: Rc<Client>,
+}
+
+#[derive(Debug)]
+pub struct VolumePriceListRequest {
+    url: String,
+    client: Rc<Client>,
+}
+
+impl VolumePriceListRequest {
+    pub fn new(url: &str, client: &Rc<Client>) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+        }
+    }
+
+    pub async fn send(&self) -> Result<Vec<VolumePrice>, ApiError> {
+        let url = Url::parse(self.url.as_str())
+            .context("Could not parse URL GET parameters.")?;
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+}
+
+pub struct VolumePriceCreateRequest {
+    url: String,
+    client: Rc<Client>,
+
+    data: VolumePriceCreateData,
+}
+
+impl VolumePriceCreateRequest {
+    pub fn new(
+        url: &str,
+        client: &Rc<Client>,
+        volume_type: String,
+        user_class: UserClass,
+    ) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+            data: VolumePriceCreateData::new(volume_type, user_class),
+        }
+    }
+
+    pub fn price(&mut self, price: f64) -> &mut Self {
+        self.data.price = Some(price);
+        self
+    }
+
+    pub fn start_time(
+        &mut self,
+        start_time: DateTime<FixedOffset>,
+    ) -> &mut Self {
+        self.data.start_time = Some(start_time);
+        self
+    }
+
+    pub async fn send(&self) -> Result<VolumePrice, ApiError> {
+        request(
+            &self.client,
+            Method::POST,
+            &self.url,
+            Some(&self.data),
+            StatusCode::CREATED,
+        )
+        .await
+    }
+}
+
+pub struct VolumePriceModifyRequest {
+    url: String,
+    client: Rc<Client>,
+
+    data: VolumePriceModifyData,
+}
+
+impl VolumePriceModifyRequest {
+    pub fn new(url: &str, client: &Rc<Client>, id: u32) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+            data: VolumePriceModifyData::new(id),
+        }
+    }
+
+    pub fn volume_type(&mut self, volume_type: String) -> &mut Self {
+        self.data.volume_type = Some(volume_type);
+        self
+    }
+
+    pub fn user_class(&mut self, user_class: UserClass) -> &mut Self {
+        self.data.user_class = Some(user_class);
+        self
+    }
+
+    pub fn unit_price(&mut self, unit_price: f64) -> &mut Self {
+        self.data.unit_price = Some(unit_price);
+        self
+    }
+
+    pub fn start_time(
+        &mut self,
+        start_time: DateTime<FixedOffset>,
+    ) -> &mut Self {
+        self.data.start_time = Some(start_time);
+        self
+    }
+
+    pub async fn send(&self) -> Result<VolumePrice, ApiError> {
+        request(
+            &self.client,
+            Method::PATCH,
+            &self.url,
+            Some(&self.data),
+            StatusCode::OK,
+        )
+        .await
+    }
+}
+
+impl VolumePriceApi {
+    pub fn new(base_url: &str, client: &Rc<Client>) -> VolumePriceApi {
+        VolumePriceApi {
+            url: format!("{base_url}/pricing/volumeprices"),
+            client: Rc::clone(client),
+        }
+    }
+
+    pub fn list(&self) -> VolumePriceListRequest {
+        VolumePriceListRequest::new(self.url.as_ref(), &self.client)
+    }
+
+    pub async fn get(&self, id: u32) -> Result<VolumePrice, ApiError> {
+        // TODO use Url.join
+        let url = format!("{}/{}", self.url, id);
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    pub fn create(
+        &self,
+        volume_type: String,
+        user_class: UserClass,
+    ) -> VolumePriceCreateRequest {
+        // TODO use Url.join
+        let url = format!("{}/", self.url);
+        VolumePriceCreateRequest::new(
+            url.as_ref(),
+            &self.client,
+            volume_type,
+            user_class,
+        )
+    }
+
+    pub fn modify(&self, id: u32) -> VolumePriceModifyRequest {
+        // TODO use Url.join
+        let url = format!("{}/{}/", self.url, id);
+        VolumePriceModifyRequest::new(url.as_ref(), &self.client, id)
+    }
+
+    pub async fn delete(&self, id: u32) -> Result<(), ApiError> {
+        // TODO use Url.join
+        let url = format!("{}/{}/", self.url, id);
+        request_bare(
+            &self.client,
+            Method::DELETE,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::NO_CONTENT,
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct FloatingIpPriceApi {
+    pub url: String,
+    pub client: Rc<Client>,
+}
+
+#[derive(Debug)]
+pub struct FloatingIpPriceListRequest {
+    url: String,
+    client: Rc<Client>,
+}
+
+impl FloatingIpPriceListRequest {
+    pub fn new(url: &str, client: &Rc<Client>) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+        }
+    }
+
+    pub async fn send(&self) -> Result<Vec<FloatingIpPrice>, ApiError> {
+        let url = Url::parse(self.url.as_str())
+            .context("Could not parse URL GET parameters.")?;
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+}
+
+pub struct FloatingIpPriceCreateRequest {
+    url: String,
+    client: Rc<Client>,
+
+    data: FloatingIpPriceCreateData,
+}
+
+impl FloatingIpPriceCreateRequest {
+    pub fn new(
+        url: &str,
+        client: &Rc<Client>,
+        pool: String,
+        user_class: UserClass,
+    ) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+            data: FloatingIpPriceCreateData::new(pool, user_class),
+        }
+    }
+
+    pub fn price(&mut self, price: f64) -> &mut Self {
+        self.data.price = Some(price);
+        self
+    }
+
+    pub fn start_time(
+        &mut self,
+        start_time: DateTime<FixedOffset>,
+    ) -> &mut Self {
+        self.data.start_time = Some(start_time);
+        self
+    }
+
+    pub async fn send(&self) -> Result<FloatingIpPrice, ApiError> {
+        request(
+            &self.client,
+            Method::POST,
+            &self.url,
+            Some(&self.data),
+            StatusCode::CREATED,
+        )
+        .await
+    }
+}
+
+pub struct FloatingIpPriceModifyRequest {
+    url: String,
+    client: Rc<Client>,
+
+    data: FloatingIpPriceModifyData,
+}
+
+impl FloatingIpPriceModifyRequest {
+    pub fn new(url: &str, client: &Rc<Client>, id: u32) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+            data: FloatingIpPriceModifyData::new(id),
+        }
+    }
+
+    pub fn pool(&mut self, pool: String) -> &mut Self {
+        self.data.pool = Some(pool);
+        self
+    }
+
+    pub fn user_class(&mut self, user_class: UserClass) -> &mut Self {
+        self.data.user_class = Some(user_class);
+        self
+    }
+
+    pub fn unit_price(&mut self, unit_price: f64) -> &mut Self {
+        self.data.unit_price = Some(unit_price);
+        self
+    }
+
+    pub fn start_time(
+        &mut self,
+        start_time: DateTime<FixedOffset>,
+    ) -> &mut Self {
+        self.data.start_time = Some(start_time);
+        self
+    }
+
+    pub async fn send(&self) -> Result<FloatingIpPrice, ApiError> {
+        request(
+            &self.client,
+            Method::PATCH,
+            &self.url,
+            Some(&self.data),
+            StatusCode::OK,
+        )
+        .await
+    }
+}
+
+impl FloatingIpPriceApi {
+    pub fn new(base_url: &str, client: &Rc<Client>) -> FloatingIpPriceApi {
+        FloatingIpPriceApi {
+            url: format!("{base_url}/pricing/floatingipprices"),
+            client: Rc::clone(client),
+        }
+    }
+
+    pub fn list(&self) -> FloatingIpPriceListRequest {
+        FloatingIpPriceListRequest::new(self.url.as_ref(), &self.client)
+    }
+
+    pub async fn get(&self, id: u32) -> Result<FloatingIpPrice, ApiError> {
+        // TODO use Url.join
+        let url = format!("{}/{}", self.url, id);
         request(
             &self.client,
             Method::GET,
@@ -219,4 +807,39 @@ impl FlavorPriceApi {
         )
         .await
     }
+
+    pub fn create(
+        &self,
+        pool: String,
+        user_class: UserClass,
+    ) -> FloatingIpPriceCreateRequest {
+        // TODO use Url.join
+        let url = format!("{}/", self.url);
+        FloatingIpPriceCreateRequest::new(
+            url.as_ref(),
+            &self.client,
+            pool,
+            user_class,
+        )
+    }
+
+    pub fn modify(&self, id: u32) -> FloatingIpPriceModifyRequest {
+        // TODO use Url.join
+        let url = format!("{}/{}/", self.url, id);
+        FloatingIpPriceModifyRequest::new(url.as_ref(), &self.client, id)
+    }
+
+    pub async fn delete(&self, id: u32) -> Result<(), ApiError> {
+        // TODO use Url.join
+        let url = format!("{}/{}/", self.url, id);
+        request_bare(
+            &self.client,
+            Method::DELETE,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::NO_CONTENT,
+        )
+        .await?;
+        Ok(())
+    }
 }
@@ -5,10 +5,10 @@ use avina_wire::quota::{
     FlavorQuota, FlavorQuotaCheck, FlavorQuotaCreateData,
     FlavorQuotaListParams, FlavorQuotaModifyData,
 };
-use reqwest::{Client, Method, StatusCode, Url};
+use reqwest::{Method, StatusCode, Url};
 
 use crate::{
-    common::{SerializableNone, request, request_bare},
+    common::{Client, SerializableNone, request, request_bare},
     error::ApiError,
 };
 
@@ -6,10 +6,10 @@ use avina_wire::resources::{
     FlavorModifyData, FlavorUsageAggregate, FlavorUsageParams,
     FlavorUsageSimple,
 };
-use reqwest::{Client, Method, StatusCode};
+use reqwest::{Method, StatusCode};
 
 use crate::{
-    common::{SerializableNone, request, request_bare},
+    common::{Client, SerializableNone, request, request_bare},
     error::ApiError,
 };
 
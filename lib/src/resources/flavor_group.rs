@@ -7,10 +7,10 @@ use avina_wire::resources::{
     FlavorGroupModifyData, FlavorGroupUsageAggregate, FlavorGroupUsageParams,
     FlavorGroupUsageSimple,
 };
-use reqwest::{Client, Method, StatusCode};
+use reqwest::{Method, StatusCode};
 
 use crate::{
-    common::{SerializableNone, request, request_bare},
+    common::{Client, SerializableNone, request, request_bare},
     error::ApiError,
 };
 
@@ -383,7 +383,7 @@ impl FlavorGroupApi {
         let url = format!("{}/initialize/", self.url);
         request(
             &self.client,
-            Method::GET,
+            Method::POST,
             url.as_str(),
             SerializableNone!(),
             StatusCode::OK,
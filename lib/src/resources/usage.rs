@@ -1,10 +1,10 @@
 use std::rc::Rc;
 
 use avina_wire::resources::CloudUsage;
-use reqwest::{Client, Method, StatusCode};
+use reqwest::{Method, StatusCode};
 
 use crate::{
-    common::{SerializableNone, request},
+    common::{Client, SerializableNone, request},
     error::ApiError,
 };
 
@@ -0,0 +1,96 @@
+use std::rc::Rc;
+
+use anyhow::Context;
+use avina_wire::scheduler::{Job, JobName, JobRun, JobRunListParams};
+use reqwest::{Method, StatusCode};
+
+use crate::{
+    common::{Client, SerializableNone, request},
+    error::ApiError,
+};
+
+#[derive(Debug)]
+pub struct SchedulerApi {
+    pub url: String,
+    pub client: Rc<Client>,
+}
+
+#[derive(Debug)]
+pub struct JobRunListRequest {
+    url: String,
+    client: Rc<Client>,
+
+    params: JobRunListParams,
+}
+
+impl JobRunListRequest {
+    pub fn new(url: &str, client: &Rc<Client>) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+
+            params: JobRunListParams { job: None },
+        }
+    }
+
+    pub async fn send(&self) -> Result<Vec<JobRun>, ApiError> {
+        let params = serde_urlencoded::to_string(&self.params)
+            .context("Failed to encode URL parameters.")?;
+        let url = if params.is_empty() {
+            self.url.clone()
+        } else {
+            format!("{}?{}", self.url, params)
+        };
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    pub fn job(&mut self, job: JobName) -> &mut Self {
+        self.params.job = Some(job);
+        self
+    }
+}
+
+impl SchedulerApi {
+    pub fn new(base_url: &str, client: &Rc<Client>) -> SchedulerApi {
+        SchedulerApi {
+            url: format!("{base_url}/scheduler"),
+            client: Rc::clone(client),
+        }
+    }
+
+    pub async fn jobs(&self) -> Result<Vec<Job>, ApiError> {
+        let url = format!("{}/jobs/", self.url);
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    pub fn runs(&self) -> JobRunListRequest {
+        let url = format!("{}/runs/", self.url);
+        JobRunListRequest::new(url.as_str(), &self.client)
+    }
+
+    pub async fn run(&self, job: JobName) -> Result<JobRun, ApiError> {
+        let url = format!("{}/jobs/{}/run/", self.url, job);
+        request(
+            &self.client,
+            Method::POST,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+}
@@ -1,7 +1,9 @@
 use std::{convert::AsRef, str::FromStr};
 
 use anyhow::Context;
-use jzon::object;
+use avina_wire::user::API_KEY_PREFIX;
+use chrono::{DateTime, FixedOffset, Utc};
+use jzon::{JsonValue, object};
 use reqwest::{
     Client, ClientBuilder,
     header::{CONTENT_TYPE, HeaderMap, HeaderValue},
@@ -11,16 +13,20 @@ use reqwest::{
 struct TokenInner {
     url: String,
     client: Client,
+    // NOTE: kept to request a new token with the same credentials once the
+    // current one expires.
+    data: JsonValue,
+    renewable: bool,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Token {
     token: String,
+    expires_at: Option<DateTime<FixedOffset>>,
     inner: Option<TokenInner>,
 }
 
 impl Token {
-    // TODO maybe use generic request method in here
     pub async fn new(
         auth_url: &str,
         username: &str,
@@ -29,14 +35,6 @@ impl Token {
         user_domain_name: &str,
         project_domain_id: &str,
     ) -> Result<Self, anyhow::Error> {
-        let mut headers = HeaderMap::new();
-        headers
-            .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-        let client = ClientBuilder::new()
-            .default_headers(headers)
-            .build()
-            .unwrap();
-        let url = format!("{auth_url}/auth/tokens/");
         let data = object! {
             "auth": {
                 "identity": {
@@ -57,9 +55,113 @@ impl Token {
                 }
             }
         };
-        let response = match client
-            .post(url.as_str())
-            .body(data.to_string())
+        Self::authenticate(auth_url, data, true).await
+    }
+
+    /// Authenticates with the ID and secret of an application credential,
+    /// which is already scoped to a project.
+    pub async fn from_application_credential_id(
+        auth_url: &str,
+        application_credential_id: &str,
+        application_credential_secret: &str,
+    ) -> Result<Self, anyhow::Error> {
+        let data = object! {
+            "auth": {
+                "identity": {
+                    "methods": ["application_credential"],
+                    "application_credential": {
+                        "id": application_credential_id,
+                        "secret": application_credential_secret,
+                    }
+                }
+            }
+        };
+        Self::authenticate(auth_url, data, true).await
+    }
+
+    /// Authenticates with the name and secret of an application credential,
+    /// which requires the owning user to be named as well.
+    pub async fn from_application_credential_name(
+        auth_url: &str,
+        application_credential_name: &str,
+        application_credential_secret: &str,
+        username: &str,
+        user_domain_name: &str,
+    ) -> Result<Self, anyhow::Error> {
+        let data = object! {
+            "auth": {
+                "identity": {
+                    "methods": ["application_credential"],
+                    "application_credential": {
+                        "name": application_credential_name,
+                        "secret": application_credential_secret,
+                        "user": {
+                            "name": username,
+                            "domain": {"name": user_domain_name},
+                        }
+                    }
+                }
+            }
+        };
+        Self::authenticate(auth_url, data, true).await
+    }
+
+    /// Exchanges an existing token for a new one scoped to the given project.
+    /// The new token cannot be renewed, since the existing one expires no
+    /// later than it.
+    pub async fn from_token(
+        auth_url: &str,
+        token: &str,
+        project_name: &str,
+        project_domain_id: &str,
+    ) -> Result<Self, anyhow::Error> {
+        let data = object! {
+            "auth": {
+                "identity": {
+                    "methods": ["token"],
+                    "token": {
+                        "id": token.trim(),
+                    }
+                },
+                "scope": {
+                    "project": {
+                        "name": project_name,
+                        "domain": {"id": project_domain_id}
+                    }
+                }
+            }
+        };
+        Self::authenticate(auth_url, data, false).await
+    }
+
+    async fn authenticate(
+        auth_url: &str,
+        data: JsonValue,
+        renewable: bool,
+    ) -> Result<Self, anyhow::Error> {
+        let mut headers = HeaderMap::new();
+        headers
+            .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        let client = ClientBuilder::new()
+            .default_headers(headers)
+            .build()
+            .unwrap();
+        let url = format!("{auth_url}/auth/tokens/");
+        Self::request(TokenInner {
+            url,
+            client,
+            data,
+            renewable,
+        })
+        .await
+    }
+
+    // TODO maybe use generic request method in here
+    async fn request(inner: TokenInner) -> Result<Self, anyhow::Error> {
+        let response = match inner
+            .client
+            .post(inner.url.as_str())
+            .body(inner.data.to_string())
             .send()
             .await
             .context("")
@@ -88,12 +190,57 @@ impl Token {
         }
         .trim()
         .to_string();
+        // NOTE: a missing or unparsable expiry only disables the proactive
+        // renewal, the token is still renewed when a request is rejected.
+        let expires_at = response
+            .json::<serde_json::Value>()
+            .await
+            .ok()
+            .and_then(|body| {
+                body["token"]["expires_at"].as_str().and_then(|expires_at| {
+                    DateTime::parse_from_rfc3339(expires_at).ok()
+                })
+            });
         Ok(Self {
             token,
-            inner: Some(TokenInner { client, url }),
+            expires_at,
+            inner: Some(inner),
         })
     }
 
+    pub fn expires_at(&self) -> Option<DateTime<FixedOffset>> {
+        self.expires_at
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= Utc::now())
+    }
+
+    /// Whether the token was created from credentials and can therefore be
+    /// renewed with [`Token::refresh`].
+    pub fn is_renewable(&self) -> bool {
+        self.inner.as_ref().is_some_and(|inner| inner.renewable)
+    }
+
+    /// Replaces the token with a new one requested with the same
+    /// credentials.
+    pub async fn refresh(&mut self) -> Result<(), anyhow::Error> {
+        let Some(inner) = self.inner.clone() else {
+            return Err(anyhow::anyhow!(
+                "Token cannot be renewed as it was not created from credentials"
+            ));
+        };
+        if !inner.renewable {
+            return Err(anyhow::anyhow!(
+                "Token cannot be renewed as it was created from another token, \
+                 authenticate again instead"
+            ));
+        }
+        *self = Self::request(inner).await?;
+        Ok(())
+    }
+
     pub async fn delete(self) {
         if let Some(inner) = self.inner.clone() {
             let value = HeaderValue::from_str(self.token.as_str()).unwrap();
@@ -116,11 +263,19 @@ impl FromStr for Token {
         Ok(Self {
             // TODO validate that string has correct format
             token: s.trim().to_string(),
+            expires_at: None,
             inner: None,
         })
     }
 }
 
+impl Token {
+    /// Whether this is an avina API key rather than a Keystone token.
+    pub fn is_api_key(&self) -> bool {
+        self.token.starts_with(API_KEY_PREFIX)
+    }
+}
+
 impl AsRef<str> for Token {
     fn as_ref(&self) -> &str {
         self.token.as_str()
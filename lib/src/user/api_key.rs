@@ -0,0 +1,166 @@
+use std::rc::Rc;
+
+use anyhow::Context;
+use avina_wire::user::{
+    ApiKey, ApiKeyCreateData, ApiKeyCreated, ApiKeyListParams, ApiKeyScope,
+};
+use chrono::{DateTime, FixedOffset};
+use reqwest::{Method, StatusCode};
+
+use crate::{
+    common::{Client, SerializableNone, request, request_bare},
+    error::ApiError,
+};
+
+#[derive(Debug)]
+pub struct ApiKeyApi {
+    pub url: String,
+    pub client: Rc<Client>,
+}
+
+#[derive(Debug)]
+pub struct ApiKeyListRequest {
+    url: String,
+    client: Rc<Client>,
+
+    params: ApiKeyListParams,
+}
+
+impl ApiKeyListRequest {
+    pub fn new(url: &str, client: &Rc<Client>) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+
+            params: ApiKeyListParams {
+                user: None,
+                all: None,
+            },
+        }
+    }
+
+    pub async fn send(&self) -> Result<Vec<ApiKey>, ApiError> {
+        let params = serde_urlencoded::to_string(&self.params)
+            .context("Failed to encode URL parameters.")?;
+        // TODO: maybe use url join
+        let url = if params.is_empty() {
+            self.url.clone()
+        } else {
+            format!("{}?{}", self.url, params)
+        };
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    pub fn user(&mut self, user: u32) -> &mut Self {
+        self.params.user = Some(user);
+        self
+    }
+
+    pub fn all(&mut self) -> &mut Self {
+        self.params.all = Some(true);
+        self
+    }
+}
+
+pub struct ApiKeyCreateRequest {
+    url: String,
+    client: Rc<Client>,
+
+    data: ApiKeyCreateData,
+}
+
+impl ApiKeyCreateRequest {
+    pub fn new(
+        url: &str,
+        client: &Rc<Client>,
+        name: String,
+        scopes: Vec<ApiKeyScope>,
+    ) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+            data: ApiKeyCreateData::new(name, scopes),
+        }
+    }
+
+    pub fn user(&mut self, user: u32) -> &mut Self {
+        self.data.user = Some(user);
+        self
+    }
+
+    pub fn expires_at(
+        &mut self,
+        expires_at: DateTime<FixedOffset>,
+    ) -> &mut Self {
+        self.data.expires_at = Some(expires_at);
+        self
+    }
+
+    pub async fn send(&self) -> Result<ApiKeyCreated, ApiError> {
+        request(
+            &self.client,
+            Method::POST,
+            &self.url,
+            Some(&self.data),
+            StatusCode::CREATED,
+        )
+        .await
+    }
+}
+
+impl ApiKeyApi {
+    pub fn new(base_url: &str, client: &Rc<Client>) -> ApiKeyApi {
+        ApiKeyApi {
+            url: format!("{base_url}/user/apikeys"),
+            client: Rc::clone(client),
+        }
+    }
+
+    pub fn list(&self) -> ApiKeyListRequest {
+        ApiKeyListRequest::new(self.url.as_ref(), &self.client)
+    }
+
+    pub async fn get(&self, id: u32) -> Result<ApiKey, ApiError> {
+        // TODO use Url.join
+        let url = format!("{}/{}", self.url, id);
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    pub fn create(
+        &self,
+        name: String,
+        scopes: Vec<ApiKeyScope>,
+    ) -> ApiKeyCreateRequest {
+        // TODO use Url.join
+        let url = format!("{}/", self.url);
+        ApiKeyCreateRequest::new(url.as_ref(), &self.client, name, scopes)
+    }
+
+    pub async fn delete(&self, id: u32) -> Result<(), ApiError> {
+        // TODO use Url.join
+        let url = format!("{}/{}/", self.url, id);
+        request_bare(
+            &self.client,
+            Method::DELETE,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::NO_CONTENT,
+        )
+        .await?;
+        Ok(())
+    }
+}
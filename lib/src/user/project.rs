@@ -5,10 +5,10 @@ use avina_wire::user::{
     Project, ProjectCreateData, ProjectListParams, ProjectModifyData,
     ProjectRetrieved, UserClass,
 };
-use reqwest::{Client, Method, StatusCode};
+use reqwest::{Method, StatusCode};
 
 use crate::{
-    common::{SerializableNone, request, request_bare},
+    common::{Client, SerializableNone, request, request_bare},
     error::ApiError,
 };
 
@@ -0,0 +1,206 @@
+use std::rc::Rc;
+
+use anyhow::Context;
+use avina_wire::user::{
+    ProjectClassChange, ProjectClassChangeCreateData,
+    ProjectClassChangeListParams, ProjectClassChangeModifyData, UserClass,
+};
+use chrono::{DateTime, FixedOffset};
+use reqwest::{Method, StatusCode};
+
+use crate::{
+    common::{Client, SerializableNone, request, request_bare},
+    error::ApiError,
+};
+
+#[derive(Debug)]
+pub struct ProjectClassChangeApi {
+    pub url: String,
+    pub client: Rc<Client>,
+}
+
+#[derive(Debug)]
+pub struct ProjectClassChangeListRequest {
+    url: String,
+    client: Rc<Client>,
+
+    params: ProjectClassChangeListParams,
+}
+
+impl ProjectClassChangeListRequest {
+    pub fn new(url: &str, client: &Rc<Client>) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+
+            params: ProjectClassChangeListParams {
+                project: None,
+                all: None,
+            },
+        }
+    }
+
+    pub async fn send(&self) -> Result<Vec<ProjectClassChange>, ApiError> {
+        let params = serde_urlencoded::to_string(&self.params)
+            .context("Failed to encode URL parameters")?;
+        let url = if params.is_empty() {
+            self.url.clone()
+        } else {
+            format!("{}?{}", self.url, params)
+        };
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    pub fn project(&mut self, project: u32) -> &mut Self {
+        self.params.project = Some(project);
+        self
+    }
+
+    pub fn all(&mut self) -> &mut Self {
+        self.params.all = Some(true);
+        self
+    }
+}
+
+pub struct ProjectClassChangeCreateRequest {
+    url: String,
+    client: Rc<Client>,
+
+    data: ProjectClassChangeCreateData,
+}
+
+impl ProjectClassChangeCreateRequest {
+    pub fn new(
+        url: &str,
+        client: &Rc<Client>,
+        project: u32,
+        user_class: UserClass,
+        start_time: DateTime<FixedOffset>,
+    ) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+            data: ProjectClassChangeCreateData::new(
+                project, user_class, start_time,
+            ),
+        }
+    }
+
+    pub async fn send(&self) -> Result<ProjectClassChange, ApiError> {
+        request(
+            &self.client,
+            Method::POST,
+            &self.url,
+            Some(&self.data),
+            StatusCode::CREATED,
+        )
+        .await
+    }
+}
+
+pub struct ProjectClassChangeModifyRequest {
+    url: String,
+    client: Rc<Client>,
+
+    data: ProjectClassChangeModifyData,
+}
+
+impl ProjectClassChangeModifyRequest {
+    pub fn new(url: &str, client: &Rc<Client>, id: u32) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Rc::clone(client),
+            data: ProjectClassChangeModifyData::new(id),
+        }
+    }
+
+    pub fn user_class(&mut self, user_class: UserClass) -> &mut Self {
+        self.data.user_class = Some(user_class);
+        self
+    }
+
+    pub fn start_time(
+        &mut self,
+        start_time: DateTime<FixedOffset>,
+    ) -> &mut Self {
+        self.data.start_time = Some(start_time);
+        self
+    }
+
+    pub async fn send(&self) -> Result<ProjectClassChange, ApiError> {
+        request(
+            &self.client,
+            Method::PATCH,
+            &self.url,
+            Some(&self.data),
+            StatusCode::OK,
+        )
+        .await
+    }
+}
+
+impl ProjectClassChangeApi {
+    pub fn new(base_url: &str, client: &Rc<Client>) -> ProjectClassChangeApi {
+        ProjectClassChangeApi {
+            url: format!("{base_url}/user/projectclasschanges"),
+            client: Rc::clone(client),
+        }
+    }
+
+    pub fn list(&self) -> ProjectClassChangeListRequest {
+        ProjectClassChangeListRequest::new(self.url.as_ref(), &self.client)
+    }
+
+    pub async fn get(&self, id: u32) -> Result<ProjectClassChange, ApiError> {
+        let url = format!("{}/{}", self.url, id);
+        request(
+            &self.client,
+            Method::GET,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::OK,
+        )
+        .await
+    }
+
+    pub fn create(
+        &self,
+        project: u32,
+        user_class: UserClass,
+        start_time: DateTime<FixedOffset>,
+    ) -> ProjectClassChangeCreateRequest {
+        let url = format!("{}/", self.url);
+        ProjectClassChangeCreateRequest::new(
+            url.as_ref(),
+            &self.client,
+            project,
+            user_class,
+            start_time,
+        )
+    }
+
+    pub fn modify(&self, id: u32) -> ProjectClassChangeModifyRequest {
+        let url = format!("{}/{}/", self.url, id);
+        ProjectClassChangeModifyRequest::new(url.as_ref(), &self.client, id)
+    }
+
+    pub async fn delete(&self, id: u32) -> Result<(), ApiError> {
+        let url = format!("{}/{}/", self.url, id);
+        request_bare(
+            &self.client,
+            Method::DELETE,
+            url.as_str(),
+            SerializableNone!(),
+            StatusCode::NO_CONTENT,
+        )
+        .await?;
+        Ok(())
+    }
+}
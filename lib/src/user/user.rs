@@ -2,13 +2,13 @@ use std::rc::Rc;
 
 use anyhow::Context;
 use avina_wire::user::{
-    User, UserCreateData, UserDetailed, UserImport, UserListParams,
+    Role, User, UserCreateData, UserDetailed, UserImport, UserListParams,
     UserModifyData,
 };
-use reqwest::{Client, Method, StatusCode};
+use reqwest::{Method, StatusCode};
 
 use crate::{
-    common::{SerializableNone, request, request_bare},
+    common::{Client, SerializableNone, request, request_bare},
     error::ApiError,
 };
 
@@ -91,7 +91,7 @@ impl UserCreateRequest {
         }
     }
 
-    pub fn role(&mut self, role: u32) -> &mut Self {
+    pub fn role(&mut self, role: Role) -> &mut Self {
         self.data.role = Some(role);
         self
     }
@@ -149,7 +149,7 @@ impl UserModifyRequest {
         self
     }
 
-    pub fn role(&mut self, role: u32) -> &mut Self {
+    pub fn role(&mut self, role: Role) -> &mut Self {
         self.data.role = Some(role);
         self
     }
@@ -28,7 +28,7 @@ use avina_wire::{
     pricing::FlavorPrice,
     quota::{FlavorQuota, FlavorQuotaCreateData},
     resources::{Flavor, FlavorCreateData, FlavorGroup, FlavorGroupCreateData},
-    user::{Project, User, UserClass},
+    user::{Project, Role, User, UserClass},
 };
 use chrono::{DateTime, Datelike, FixedOffset, Utc};
 use once_cell::sync::Lazy;
@@ -40,7 +40,9 @@ use sqlx::{
 use uuid::Uuid;
 use wiremock::{
     Mock, MockServer, ResponseTemplate,
-    matchers::{header, method, path},
+    matchers::{
+        header, method, path, path_regex, query_param, query_param_is_missing,
+    },
 };
 
 static TRACING: Lazy<()> = Lazy::new(|| {
@@ -70,6 +72,9 @@ pub struct TestApp {
     pub _api_client: reqwest::Client,
     pub keystone_server: MockServer,
     pub keystone_token: String,
+    pub nova_server: MockServer,
+    pub cinder_server: MockServer,
+    pub neutron_server: MockServer,
 }
 
 pub struct TestUser {
@@ -108,6 +113,87 @@ impl TestApp {
             )
     }
 
+    pub fn mock_nova_servers(&self, servers: Vec<serde_json::Value>) -> Mock {
+        Mock::given(method("GET"))
+            .and(path("/v2.1/servers/detail"))
+            .and(query_param_is_missing("deleted"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "servers": servers })),
+            )
+    }
+
+    pub fn mock_nova_deleted_servers(
+        &self,
+        servers: Vec<serde_json::Value>,
+    ) -> Mock {
+        Mock::given(method("GET"))
+            .and(path("/v2.1/servers/detail"))
+            .and(query_param("deleted", "True"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "servers": servers })),
+            )
+    }
+
+    pub fn mock_cinder_volumes(&self, volumes: Vec<serde_json::Value>) -> Mock {
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/[^/]+/volumes/detail$"))
+            .and(query_param("all_tenants", "True"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "volumes": volumes })),
+            )
+    }
+
+    pub fn mock_neutron_floating_ips(
+        &self,
+        floating_ips: Vec<serde_json::Value>,
+    ) -> Mock {
+        Mock::given(method("GET"))
+            .and(path("/v2.0/floatingips"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "floatingips": floating_ips })),
+            )
+    }
+
+    pub fn mock_neutron_networks(
+        &self,
+        networks: Vec<serde_json::Value>,
+    ) -> Mock {
+        Mock::given(method("GET"))
+            .and(path("/v2.0/networks"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "networks": networks })),
+            )
+    }
+
+    pub fn mock_keystone_domains(
+        &self,
+        domains: Vec<serde_json::Value>,
+    ) -> Mock {
+        Mock::given(method("GET"))
+            .and(path("/domains"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "domains": domains })),
+            )
+    }
+
+    pub fn mock_keystone_projects(
+        &self,
+        projects: Vec<serde_json::Value>,
+    ) -> Mock {
+        Mock::given(method("GET"))
+            .and(path("/projects"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "projects": projects })),
+            )
+    }
+
     pub async fn setup_test_user_and_project(
         &self,
         admin: bool,
@@ -133,7 +219,7 @@ impl TestApp {
         transaction: &mut Transaction<'static, MySql>,
         project: &Project,
         is_staff: bool,
-        role: u32,
+        role: Role,
     ) -> Result<TestUser, sqlx::Error> {
         let mut user = User {
             id: 1,
@@ -200,7 +286,7 @@ impl TestApp {
                     &mut transaction,
                     &test_project.project,
                     true,
-                    1,
+                    Role::User,
                 )
                 .await?,
             );
@@ -212,7 +298,7 @@ impl TestApp {
                     &mut transaction,
                     &test_project.project,
                     false,
-                    2,
+                    Role::MasterUser,
                 )
                 .await?,
             );
@@ -224,7 +310,7 @@ impl TestApp {
                     &mut transaction,
                     &test_project.project,
                     false,
-                    1,
+                    Role::User,
                 )
                 .await?,
             );
@@ -568,13 +654,21 @@ pub async fn spawn_app() -> TestApp {
 
     let keystone_server = MockServer::start().await;
     let keystone_token = Uuid::new_v4().to_string();
+    let nova_server = MockServer::start().await;
+    let cinder_server = MockServer::start().await;
+    let neutron_server = MockServer::start().await;
 
     let configuration = {
         let mut c = get_configuration().expect("Failed to read configuration.");
         c.database.database_name = Uuid::new_v4().simple().to_string();
         c.application.port = 0;
         c.openstack.keystone_endpoint = keystone_server.uri();
+        c.openstack.nova_endpoint = nova_server.uri();
+        c.openstack.cinder_endpoint = cinder_server.uri();
+        c.openstack.neutron_endpoint = neutron_server.uri();
         c.application.insert_admin = false;
+        c.scheduler.enabled = false;
+        c.accounting.retention_years = Some(1);
         c
     };
 
@@ -589,6 +683,25 @@ pub async fn spawn_app() -> TestApp {
         .mount(&keystone_server)
         .await;
     // TODO check data sent to keystone
+    // NOTE: tests mocking deleted servers take precedence over this one.
+    Mock::given(method("GET"))
+        .and(path("/v2.1/servers/detail"))
+        .and(query_param("deleted", "True"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({ "servers": [] })),
+        )
+        .with_priority(10)
+        .mount(&nova_server)
+        .await;
+    // NOTE: tests mocking networks take precedence over this one.
+    Mock::given(method("GET"))
+        .and(path("/v2.0/networks"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({ "networks": [] })),
+        )
+        .with_priority(10)
+        .mount(&neutron_server)
+        .await;
 
     let application = Application::build(configuration.clone())
         .await
@@ -609,6 +722,9 @@ pub async fn spawn_app() -> TestApp {
         _api_client: client,
         keystone_server,
         keystone_token,
+        nova_server,
+        cinder_server,
+        neutron_server,
     }
 }
 
@@ -683,7 +799,7 @@ pub async fn insert_user_into_db(
         user.name,
         user.openstack_id,
         user.project,
-        user.role,
+        u32::from(user.role),
         user.is_staff,
         user.is_active,
     );
@@ -693,6 +809,121 @@ pub async fn insert_user_into_db(
         .map(|result| result.last_insert_id())
 }
 
+/// Builds a server as listed by Nova with the given attributes.
+pub fn nova_server(
+    id: Uuid,
+    name: &str,
+    status: &str,
+    tenant_id: &str,
+    flavor_id: &str,
+) -> serde_json::Value {
+    json!({
+        "id": id,
+        "name": name,
+        "description": null,
+        "status": status,
+        "tenant_id": tenant_id,
+        "user_id": random_uuid(),
+        "metadata": {},
+        "hostId": "",
+        "image": "",
+        "flavor": {"id": flavor_id, "links": []},
+        "created": "2025-01-01T00:00:00Z",
+        "updated": "2025-01-01T00:00:00Z",
+        "addresses": {},
+        "accessIPv4": "",
+        "accessIPv6": "",
+        "links": [],
+        "OS-DCF:diskConfig": "MANUAL",
+        "OS-EXT-AZ:availability_zone": "nova",
+        "config_drive": "",
+        "key_name": null,
+        "OS-SRV-USG:launched_at": null,
+        "OS-SRV-USG:terminated_at": null,
+        "OS-EXT-SRV-ATTR:host": null,
+        "OS-EXT-SRV-ATTR:instance_name": "instance-00000001",
+        "OS-EXT-SRV-ATTR:hypervisor_hostname": null,
+        "OS-EXT-STS:task_state": null,
+        "OS-EXT-STS:vm_state": "active",
+        "OS-EXT-STS:power_state": 1,
+        "os-extended-volumes:volumes_attached": [],
+        "security_groups": null,
+    })
+}
+
+/// Builds a volume as listed by Cinder with the given attributes.
+pub fn cinder_volume(
+    id: Uuid,
+    name: &str,
+    status: &str,
+    size: u32,
+    volume_type: &str,
+    tenant_id: &str,
+) -> serde_json::Value {
+    json!({
+        "id": id,
+        "name": name,
+        "description": null,
+        "status": status,
+        "size": size,
+        "volume_type": volume_type,
+        "bootable": "false",
+        "os-vol-tenant-attr:tenant_id": tenant_id,
+        "user_id": random_uuid(),
+        "created_at": "2025-01-01T00:00:00.000000",
+        "updated_at": "2025-01-01T00:00:00.000000",
+    })
+}
+
+/// Builds a floating IP as listed by Neutron with the given attributes.
+pub fn keystone_domain(id: &str, name: &str) -> serde_json::Value {
+    json!({
+        "id": id,
+        "name": name,
+        "description": null,
+        "enabled": true,
+    })
+}
+
+pub fn keystone_project(
+    id: &str,
+    name: &str,
+    domain_id: &str,
+) -> serde_json::Value {
+    json!({
+        "id": id,
+        "name": name,
+        "description": null,
+        "enabled": true,
+        "is_domain": false,
+        "domain_id": domain_id,
+        "parent_id": domain_id,
+        "tags": [],
+    })
+}
+
+pub fn neutron_floating_ip(
+    id: Uuid,
+    floating_ip_address: &str,
+    floating_network_id: &str,
+    project_id: &str,
+) -> serde_json::Value {
+    json!({
+        "id": id,
+        "floating_ip_address": floating_ip_address,
+        "floating_network_id": floating_network_id,
+        "fixed_ip_address": null,
+        "port_id": null,
+        "router_id": null,
+        "status": "DOWN",
+        "project_id": project_id,
+        "tenant_id": project_id,
+        "description": "",
+        "created_at": "2025-01-01T00:00:00Z",
+        "updated_at": "2025-01-01T00:00:00Z",
+    })
+}
+
 pub fn random_uuid() -> String {
     Uuid::new_v4().to_string()
 }
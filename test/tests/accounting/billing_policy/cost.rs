@@ -0,0 +1,96 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_api::database::{
+    accounting::server_state::NewServerState,
+    pricing::flavor_price::NewFlavorPrice,
+};
+use avina_test::{random_alphanumeric_string, spawn_app};
+use chrono::{TimeDelta, TimeZone, Utc};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn e2e_lib_server_cost_applies_billing_policy_in_effect() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let begin = Utc.with_ymd_and_hms(2025, 3, 10, 0, 0, 0).unwrap();
+    let end = begin + TimeDelta::days(4);
+    // NOTE: this is a price per year, so one day costs 1.
+    server
+        .setup_test_flavor_price_with_new_flavor_price(
+            &flavor,
+            NewFlavorPrice {
+                flavor_id: flavor.id as u64,
+                user_class: project.user_class,
+                unit_price: 365.,
+                start_time: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            },
+        )
+        .await
+        .expect("Failed to setup test flavor price");
+    for (status, state_begin, state_end) in [
+        ("ACTIVE", begin, begin + TimeDelta::days(1)),
+        ("SHUTOFF", begin + TimeDelta::days(1), end),
+    ] {
+        server
+            .setup_test_server_state_with_server_state(
+                &flavor,
+                &user,
+                NewServerState {
+                    begin: state_begin,
+                    end: Some(state_end),
+                    instance_id: Uuid::new_v4(),
+                    instance_name: random_alphanumeric_string(10),
+                    flavor: flavor.id,
+                    status: status.to_string(),
+                    user: user.id,
+                },
+            )
+            .await
+            .expect("Failed to setup test server state");
+    }
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    client
+        .billing_policy
+        .create("SHUTOFF".to_string())
+        .multiplier(0.25)
+        .start_time((begin + TimeDelta::days(2)).fixed_offset())
+        .send()
+        .await
+        .unwrap();
+
+    // act
+    let cost = client
+        .server_cost
+        .get()
+        .begin(begin.fixed_offset())
+        .end(end.fixed_offset())
+        .mine()
+        .await
+        .unwrap();
+
+    // assert
+    // NOTE: one day active, one day shut off at the full price and two days
+    // shut off at a quarter of it.
+    assert!((cost.total - 2.5).abs() < 1e-6);
+}
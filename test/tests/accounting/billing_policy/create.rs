@@ -0,0 +1,99 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_test::spawn_app;
+use chrono::{TimeZone, Utc};
+
+#[tokio::test]
+async fn e2e_lib_billing_policy_create_denies_access_to_normal_user() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let create = client
+        .billing_policy
+        .create("SHUTOFF".to_string())
+        .multiplier(0.25)
+        .send()
+        .await;
+
+    // assert
+    assert!(create.is_err());
+    assert_eq!(
+        create.unwrap_err().to_string(),
+        "Admin privileges required".to_string()
+    );
+}
+
+#[tokio::test]
+async fn e2e_lib_billing_policy_create_and_list_works() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let start_time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let created = client
+        .billing_policy
+        .create("shutoff".to_string())
+        .multiplier(0.25)
+        .start_time(start_time.fixed_offset())
+        .send()
+        .await
+        .unwrap();
+    let policies = client.billing_policy.list().send().await.unwrap();
+    let conflicting = client
+        .billing_policy
+        .create("SHUTOFF".to_string())
+        .start_time(start_time.fixed_offset())
+        .send()
+        .await;
+
+    // assert
+    assert_eq!(created.status, "SHUTOFF");
+    assert_eq!(created.multiplier, 0.25);
+    assert!(policies.contains(&created));
+    assert!(
+        policies
+            .iter()
+            .any(|policy| policy.status == "ACTIVE" && policy.multiplier == 1.)
+    );
+    assert!(conflicting.is_err());
+    assert_eq!(
+        client.billing_policy.get(created.id).await.unwrap(),
+        created
+    );
+}
@@ -0,0 +1,267 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_api::database::{
+    accounting::server_state::NewServerState,
+    pricing::flavor_price::NewFlavorPrice,
+};
+use avina_test::{random_alphanumeric_string, spawn_app};
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use uuid::Uuid;
+
+fn at(day: u32, hour: u32, minute: u32, millis: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 3, day, hour, minute, 0).unwrap()
+        + TimeDelta::milliseconds(millis)
+}
+
+#[tokio::test]
+async fn e2e_lib_daily_usage_backfill_denies_access_to_normal_user() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let backfill = client.daily_usage.backfill().await;
+
+    // assert
+    assert!(backfill.is_err());
+    assert_eq!(
+        backfill.unwrap_err().to_string(),
+        "Admin privileges required".to_string()
+    );
+}
+
+#[tokio::test]
+async fn e2e_lib_daily_usage_backfill_keeps_consumption_and_cost() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    server
+        .setup_test_flavor_price_with_new_flavor_price(
+            &flavor,
+            NewFlavorPrice {
+                flavor_id: flavor.id as u64,
+                user_class: project.user_class,
+                unit_price: 365.,
+                start_time: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            },
+        )
+        .await
+        .expect("Failed to setup test flavor price");
+    let server_uuid = Uuid::new_v4();
+    // NOTE: the states begin and end within seconds and across midnight.
+    for (instance_id, status, begin, end) in [
+        (
+            server_uuid,
+            "ACTIVE",
+            at(9, 22, 30, 700),
+            Some(at(11, 3, 15, 800)),
+        ),
+        (
+            server_uuid,
+            "SHUTOFF",
+            at(11, 3, 15, 800),
+            Some(at(13, 12, 0, 250)),
+        ),
+        (
+            server_uuid,
+            "SHELVED_OFFLOADED",
+            at(13, 12, 0, 250),
+            Some(at(14, 6, 0, 0)),
+        ),
+        (Uuid::new_v4(), "ACTIVE", at(10, 10, 0, 500), None),
+    ] {
+        server
+            .setup_test_server_state_with_server_state(
+                &flavor,
+                &user,
+                NewServerState {
+                    begin,
+                    end,
+                    instance_id,
+                    instance_name: random_alphanumeric_string(10),
+                    flavor: flavor.id,
+                    status: status.to_string(),
+                    user: user.id,
+                },
+            )
+            .await
+            .expect("Failed to setup test server state");
+    }
+    let (begin, end) = (at(9, 12, 0, 300), at(16, 8, 0, 0));
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    // NOTE: the second policy changes the billing in the middle of a day.
+    for (status, multiplier, start_time) in [
+        ("SHUTOFF", 0.25, at(12, 0, 0, 0)),
+        ("ACTIVE", 1., at(12, 12, 0, 0)),
+    ] {
+        client
+            .billing_policy
+            .create(status.to_string())
+            .multiplier(multiplier)
+            .start_time(start_time.fixed_offset())
+            .send()
+            .await
+            .unwrap();
+    }
+    let consumption_before = client
+        .server_consumption
+        .get()
+        .begin(begin.fixed_offset())
+        .end(end.fixed_offset())
+        .mine_detail()
+        .await
+        .unwrap();
+    let cost_before = client
+        .server_cost
+        .get()
+        .begin(begin.fixed_offset())
+        .end(end.fixed_offset())
+        .mine_detail()
+        .await
+        .unwrap();
+    let server_cost_before = client
+        .server_cost
+        .get()
+        .begin(begin.fixed_offset())
+        .end(end.fixed_offset())
+        .server(server_uuid)
+        .await
+        .unwrap();
+
+    // act
+    let backfill = client.daily_usage.backfill().await.unwrap();
+    let consumption_after = client
+        .server_consumption
+        .get()
+        .begin(begin.fixed_offset())
+        .end(end.fixed_offset())
+        .mine_detail()
+        .await
+        .unwrap();
+    let cost_after = client
+        .server_cost
+        .get()
+        .begin(begin.fixed_offset())
+        .end(end.fixed_offset())
+        .mine_detail()
+        .await
+        .unwrap();
+    let server_cost_after = client
+        .server_cost
+        .get()
+        .begin(begin.fixed_offset())
+        .end(end.fixed_offset())
+        .server(server_uuid)
+        .await
+        .unwrap();
+
+    // assert
+    assert!(backfill.aggregated_day_count > 0);
+    assert!(backfill.daily_usage_count > 0);
+    assert_eq!(consumption_after, consumption_before);
+    assert_eq!(cost_after, cost_before);
+    assert_eq!(server_cost_after, server_cost_before);
+}
+
+#[tokio::test]
+async fn e2e_lib_daily_usage_is_dropped_when_server_state_changes() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let server_state = server
+        .setup_test_server_state_with_server_state(
+            &flavor,
+            &user,
+            NewServerState {
+                begin: at(10, 0, 0, 0),
+                end: Some(at(13, 0, 0, 0)),
+                instance_id: Uuid::new_v4(),
+                instance_name: random_alphanumeric_string(10),
+                flavor: flavor.id,
+                status: "ACTIVE".to_string(),
+                user: user.id,
+            },
+        )
+        .await
+        .expect("Failed to setup test server state");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    client.daily_usage.backfill().await.unwrap();
+
+    // act
+    client
+        .server_state
+        .modify(server_state.id)
+        .end(at(12, 0, 0, 0).fixed_offset())
+        .send()
+        .await
+        .unwrap();
+    let consumption = client
+        .server_consumption
+        .get()
+        .begin(at(10, 0, 0, 0).fixed_offset())
+        .end(at(14, 0, 0, 0).fixed_offset())
+        .server(server_state.instance_id)
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(
+        consumption.get(&flavor.name).cloned(),
+        Some((2 * 24 * 60 * 60) as f64)
+    );
+}
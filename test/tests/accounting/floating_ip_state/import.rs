@@ -0,0 +1,159 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_test::{neutron_floating_ip, random_uuid, spawn_app};
+use serde_json::json;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn e2e_lib_floating_ip_state_import_creates_state_with_pool_name() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let network_id = random_uuid();
+    server
+        .mock_neutron_networks(vec![json!({
+            "id": network_id,
+            "name": "MWN_pool",
+        })])
+        .mount(&server.neutron_server)
+        .await;
+    let floating_ip_id = Uuid::new_v4();
+    server
+        .mock_neutron_floating_ips(vec![neutron_floating_ip(
+            floating_ip_id,
+            "10.195.0.1",
+            &network_id,
+            &user.openstack_id,
+        )])
+        .mount(&server.neutron_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let import = client.floating_ip_state.import().await.unwrap();
+    let states = client
+        .floating_ip_state
+        .list()
+        .floating_ip(floating_ip_id)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(import.new_state_count, 1);
+    assert_eq!(import.end_state_count, 0);
+    assert_eq!(import.skipped_floating_ip_count, 0);
+    assert_eq!(states.len(), 1);
+    assert_eq!(states[0].user, user.id);
+    assert_eq!(states[0].pool, "MWN_pool");
+    assert_eq!(states[0].floating_ip_address, "10.195.0.1");
+    assert!(states[0].end.is_none());
+}
+
+#[tokio::test]
+async fn e2e_lib_floating_ip_state_import_ends_state_of_released_floating_ip() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    let floating_ip_id = Uuid::new_v4();
+    let mock = server
+        .mock_neutron_floating_ips(vec![neutron_floating_ip(
+            floating_ip_id,
+            "10.195.0.1",
+            &random_uuid(),
+            &user.openstack_id,
+        )])
+        .mount_as_scoped(&server.neutron_server)
+        .await;
+    client.floating_ip_state.import().await.unwrap();
+    drop(mock);
+    server
+        .mock_neutron_floating_ips(vec![])
+        .mount(&server.neutron_server)
+        .await;
+
+    // act
+    let import = client.floating_ip_state.import().await.unwrap();
+    let states = client
+        .floating_ip_state
+        .list()
+        .floating_ip(floating_ip_id)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(import.new_state_count, 0);
+    assert_eq!(import.end_state_count, 1);
+    assert_eq!(states.len(), 1);
+    assert!(states[0].end.is_some());
+}
+
+#[tokio::test]
+async fn e2e_lib_floating_ip_state_import_skips_floating_ip_of_unknown_user() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    server
+        .mock_neutron_floating_ips(vec![neutron_floating_ip(
+            Uuid::new_v4(),
+            "10.195.0.1",
+            &random_uuid(),
+            &random_uuid(),
+        )])
+        .mount(&server.neutron_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let import = client.floating_ip_state.import().await.unwrap();
+
+    // assert
+    assert_eq!(import.new_state_count, 0);
+    assert_eq!(import.skipped_floating_ip_count, 1);
+}
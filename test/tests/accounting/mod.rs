@@ -1 +1,10 @@
+mod billing_policy;
+mod daily_usage;
+mod floating_ip_state;
+mod server;
+mod server_consumption;
+mod server_cost;
 mod server_state;
+mod unmatched_server;
+mod volume_cost;
+mod volume_state;
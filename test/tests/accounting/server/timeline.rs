@@ -0,0 +1,135 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_api::database::{
+    accounting::server_state::NewServerState,
+    pricing::flavor_price::NewFlavorPrice,
+};
+use avina_test::{random_alphanumeric_string, spawn_app};
+use chrono::{TimeDelta, Utc};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn e2e_lib_server_timeline_returns_states_with_duration_and_cost() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let now = Utc::now();
+    server
+        .setup_test_flavor_price_with_new_flavor_price(
+            &flavor,
+            NewFlavorPrice {
+                flavor_id: flavor.id as u64,
+                user_class: project.user_class,
+                // NOTE: this is the price per year, so one day costs 1.
+                unit_price: 365.,
+                start_time: now - TimeDelta::days(10),
+            },
+        )
+        .await
+        .expect("Failed to setup test flavor price");
+    let server_id = Uuid::new_v4();
+    let instance_name = random_alphanumeric_string(10);
+    let mut states = Vec::new();
+    for (days, status) in [(3, "ACTIVE"), (2, "SHELVED_OFFLOADED")] {
+        states.push(
+            server
+                .setup_test_server_state_with_server_state(
+                    &flavor,
+                    &user,
+                    NewServerState {
+                        begin: now - TimeDelta::days(days),
+                        end: Some(now - TimeDelta::days(days - 1)),
+                        instance_id: server_id,
+                        instance_name: instance_name.clone(),
+                        flavor: flavor.id,
+                        status: status.to_string(),
+                        user: user.id,
+                    },
+                )
+                .await
+                .expect("Failed to setup test server state"),
+        );
+    }
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let timeline = client.server_state.timeline(server_id).await.unwrap();
+
+    // assert
+    assert_eq!(timeline.len(), 2);
+    assert_eq!(timeline[0].state, states[0].id);
+    assert_eq!(timeline[0].status, "ACTIVE");
+    assert_eq!(timeline[0].flavor, flavor.id);
+    assert_eq!(timeline[0].user, user.id);
+    assert_eq!(timeline[0].duration, 24 * 60 * 60);
+    assert!((timeline[0].cost - 1.).abs() < 1e-6);
+    assert_eq!(timeline[1].state, states[1].id);
+    assert_eq!(timeline[1].duration, 24 * 60 * 60);
+    assert_eq!(timeline[1].cost, 0.);
+}
+
+#[tokio::test]
+async fn e2e_lib_user_cannot_get_timeline_of_server_of_other_project() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let (other_user, _other_project, _other_token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let server_id = Uuid::new_v4();
+    server
+        .setup_test_server_state_with_server_id(&flavor, &other_user, server_id)
+        .await
+        .expect("Failed to setup test server state");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let timeline = client.server_state.timeline(server_id).await;
+
+    // assert
+    assert!(timeline.is_err());
+    assert_eq!(
+        timeline.unwrap_err().to_string(),
+        "Resource not found".to_string()
+    );
+}
@@ -0,0 +1,123 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_api::database::accounting::server_state::NewServerState;
+use avina_test::{random_alphanumeric_string, spawn_app};
+use avina_wire::accounting::Granularity;
+use chrono::{TimeDelta, TimeZone, Utc};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn e2e_lib_server_consumption_series_splits_states_into_days() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let begin = Utc.with_ymd_and_hms(2025, 3, 10, 0, 0, 0).unwrap();
+    let end = begin + TimeDelta::days(3);
+    let server_id = Uuid::new_v4();
+    server
+        .setup_test_server_state_with_server_state(
+            &flavor,
+            &user,
+            NewServerState {
+                begin: begin + TimeDelta::hours(12),
+                end: Some(end - TimeDelta::hours(12)),
+                instance_id: server_id,
+                instance_name: random_alphanumeric_string(10),
+                flavor: flavor.id,
+                status: "ACTIVE".to_string(),
+                user: user.id,
+            },
+        )
+        .await
+        .expect("Failed to setup test server state");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let series = client
+        .server_consumption
+        .series(Granularity::Day)
+        .begin(begin.fixed_offset())
+        .end(end.fixed_offset())
+        .server(server_id)
+        .await
+        .unwrap();
+    let total = client
+        .server_consumption
+        .get()
+        .begin(begin.fixed_offset())
+        .end(end.fixed_offset())
+        .server(server_id)
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(series.len(), 3);
+    for (i, bucket) in series.iter().enumerate() {
+        assert_eq!(bucket.begin, begin + TimeDelta::days(i as i64));
+        assert_eq!(bucket.end, begin + TimeDelta::days(i as i64 + 1));
+    }
+    assert_eq!(series[0].value[&flavor.name], 12. * 60. * 60.);
+    assert_eq!(series[1].value[&flavor.name], 24. * 60. * 60.);
+    assert_eq!(series[2].value[&flavor.name], 12. * 60. * 60.);
+    assert_eq!(
+        series.iter().map(|b| b.value[&flavor.name]).sum::<f64>(),
+        total[&flavor.name]
+    );
+}
+
+#[tokio::test]
+async fn e2e_lib_user_cannot_get_server_consumption_series_of_other_project() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let (_other_user, other_project, _other_token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let series = client
+        .server_consumption
+        .series(Granularity::Week)
+        .project(other_project.id)
+        .await;
+
+    // assert
+    assert!(series.is_err());
+}
@@ -0,0 +1,98 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_api::database::{
+    accounting::floating_ip_state::{
+        NewFloatingIpState, insert_floating_ip_state_into_db,
+    },
+    pricing::floating_ip_price::{
+        NewFloatingIpPrice, insert_floating_ip_price_into_db,
+    },
+};
+use avina_test::spawn_app;
+use chrono::{TimeDelta, TimeZone, Utc};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn e2e_lib_server_cost_includes_floating_ips_per_pool() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let begin = Utc.with_ymd_and_hms(2025, 3, 10, 0, 0, 0).unwrap();
+    let end = begin + TimeDelta::days(3);
+    let mut transaction = server
+        .db_pool
+        .begin()
+        .await
+        .expect("Failed to begin transaction.");
+    // NOTE: this is the price of one floating IP per year, so it costs 1 a
+    // day.
+    insert_floating_ip_price_into_db(
+        &mut transaction,
+        &NewFloatingIpPrice {
+            pool: "MWN_pool".to_string(),
+            user_class: project.user_class,
+            unit_price: 365.,
+            start_time: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+        },
+    )
+    .await
+    .expect("Failed to insert floating IP price.");
+    insert_floating_ip_state_into_db(
+        &mut transaction,
+        &NewFloatingIpState {
+            begin: begin + TimeDelta::days(1),
+            end: None,
+            floating_ip_id: Uuid::new_v4(),
+            floating_ip_address: "10.195.0.1".to_string(),
+            pool: "MWN_pool".to_string(),
+            user: user.id,
+        },
+    )
+    .await
+    .expect("Failed to insert floating IP state.");
+    transaction
+        .commit()
+        .await
+        .expect("Failed to commit transaction.");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let simple = client
+        .server_cost
+        .get()
+        .begin(begin.fixed_offset())
+        .end(end.fixed_offset())
+        .user(user.id)
+        .await
+        .unwrap();
+    let detail = client
+        .server_cost
+        .get()
+        .begin(begin.fixed_offset())
+        .end(end.fixed_offset())
+        .user_detail(user.id)
+        .await
+        .unwrap();
+
+    // assert
+    assert!((simple.total - 2.).abs() < 1e-6);
+    assert!((detail.total - 2.).abs() < 1e-6);
+    assert!((detail.floating_ips["MWN_pool"] - 2.).abs() < 1e-6);
+    assert!(detail.servers.is_empty());
+}
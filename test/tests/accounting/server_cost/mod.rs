@@ -0,0 +1,2 @@
+mod floating_ip;
+mod series;
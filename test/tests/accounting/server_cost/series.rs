@@ -0,0 +1,168 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_api::database::{
+    accounting::server_state::NewServerState,
+    pricing::flavor_price::NewFlavorPrice,
+};
+use avina_test::{random_alphanumeric_string, spawn_app};
+use avina_wire::accounting::Granularity;
+use chrono::{TimeDelta, TimeZone, Utc};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn e2e_lib_server_cost_series_splits_buckets_at_price_changes() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let begin = Utc.with_ymd_and_hms(2025, 3, 10, 0, 0, 0).unwrap();
+    let end = begin + TimeDelta::days(3);
+    // NOTE: these are prices per year, so one day costs 1 and later 2.
+    for (unit_price, start_time) in [
+        (365., Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()),
+        (730., begin + TimeDelta::hours(36)),
+    ] {
+        server
+            .setup_test_flavor_price_with_new_flavor_price(
+                &flavor,
+                NewFlavorPrice {
+                    flavor_id: flavor.id as u64,
+                    user_class: project.user_class,
+                    unit_price,
+                    start_time,
+                },
+            )
+            .await
+            .expect("Failed to setup test flavor price");
+    }
+    server
+        .setup_test_server_state_with_server_state(
+            &flavor,
+            &user,
+            NewServerState {
+                begin: begin + TimeDelta::hours(12),
+                end: Some(end - TimeDelta::hours(12)),
+                instance_id: Uuid::new_v4(),
+                instance_name: random_alphanumeric_string(10),
+                flavor: flavor.id,
+                status: "ACTIVE".to_string(),
+                user: user.id,
+            },
+        )
+        .await
+        .expect("Failed to setup test server state");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let series = client
+        .server_cost
+        .series(Granularity::Day)
+        .begin(begin.fixed_offset())
+        .end(end.fixed_offset())
+        .mine()
+        .await
+        .unwrap();
+    let total = client
+        .server_cost
+        .get()
+        .begin(begin.fixed_offset())
+        .end(end.fixed_offset())
+        .mine()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(series.len(), 3);
+    for (bucket, expected) in series.iter().zip([0.5, 1.5, 1.]) {
+        assert!((bucket.value.total - expected).abs() < 1e-6);
+    }
+    assert!(
+        (series.iter().map(|b| b.value.total).sum::<f64>() - total.total).abs()
+            < 1e-6
+    );
+}
+
+#[tokio::test]
+async fn e2e_lib_server_cost_series_detail_contains_servers() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let begin = Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap();
+    let server_id = Uuid::new_v4();
+    server
+        .setup_test_server_state_with_server_state(
+            &flavor,
+            &user,
+            NewServerState {
+                begin,
+                end: Some(begin + TimeDelta::days(40)),
+                instance_id: server_id,
+                instance_name: random_alphanumeric_string(10),
+                flavor: flavor.id,
+                status: "ACTIVE".to_string(),
+                user: user.id,
+            },
+        )
+        .await
+        .expect("Failed to setup test server state");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let series = client
+        .server_cost
+        .series(Granularity::Month)
+        .begin(begin.fixed_offset())
+        .end((begin + TimeDelta::days(61)).fixed_offset())
+        .all_detail()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(series.len(), 2);
+    for bucket in &series {
+        let project = bucket.value.projects.values().find(|p| {
+            p.users
+                .get(&user.name)
+                .is_some_and(|u| u.servers.contains_key(&server_id))
+        });
+        assert!(project.is_some());
+    }
+}
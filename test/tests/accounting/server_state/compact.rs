@@ -0,0 +1,199 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_api::{
+    database::accounting::server_state::NewServerState,
+    utils::start_of_the_year,
+};
+use avina_test::{random_alphanumeric_string, spawn_app};
+use chrono::{Datelike, TimeDelta, Utc};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn e2e_lib_server_state_compact_merges_adjacent_states() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let server_id = Uuid::new_v4();
+    let instance_name = random_alphanumeric_string(10);
+    let split = Utc::now() - TimeDelta::days(1);
+    let first_state = server
+        .setup_test_server_state_with_server_state(
+            &flavor,
+            &user,
+            NewServerState {
+                begin: split - TimeDelta::days(1),
+                end: Some(split),
+                instance_id: server_id,
+                instance_name: instance_name.clone(),
+                flavor: flavor.id,
+                status: "ACTIVE".to_string(),
+                user: user.id,
+            },
+        )
+        .await
+        .expect("Failed to setup test server state");
+    server
+        .setup_test_server_state_with_server_state(
+            &flavor,
+            &user,
+            NewServerState {
+                begin: split,
+                end: None,
+                instance_id: server_id,
+                instance_name,
+                flavor: flavor.id,
+                status: "ACTIVE".to_string(),
+                user: user.id,
+            },
+        )
+        .await
+        .expect("Failed to setup test server state");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let compaction = client.server_state.compact().await.unwrap();
+    let states = client
+        .server_state
+        .list()
+        .server(server_id)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(compaction.merged_state_count, 1);
+    assert_eq!(compaction.archived_state_count, 0);
+    assert_eq!(states.len(), 1);
+    assert_eq!(states[0].id, first_state.id);
+    assert!(
+        (states[0].begin - first_state.begin)
+            .num_milliseconds()
+            .abs()
+            < 1
+    );
+    assert!(states[0].end.is_none());
+}
+
+#[tokio::test]
+async fn e2e_lib_server_state_compact_archives_old_fiscal_years() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let server_id = Uuid::new_v4();
+    let year = Utc::now().year() as u32 - 3;
+    let begin = start_of_the_year(year);
+    let end = start_of_the_year(year + 1);
+    server
+        .setup_test_server_state_with_server_state(
+            &flavor,
+            &user,
+            NewServerState {
+                begin: begin + TimeDelta::days(40),
+                end: Some(begin + TimeDelta::days(50)),
+                instance_id: server_id,
+                instance_name: random_alphanumeric_string(10),
+                flavor: flavor.id,
+                status: "ACTIVE".to_string(),
+                user: user.id,
+            },
+        )
+        .await
+        .expect("Failed to setup test server state");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    let server_consumption = client
+        .server_consumption
+        .get()
+        .begin(begin.fixed_offset())
+        .end(end.fixed_offset())
+        .server(server_id)
+        .await
+        .unwrap();
+    let user_consumption = client
+        .server_consumption
+        .get()
+        .begin(begin.fixed_offset())
+        .end(end.fixed_offset())
+        .user(user.id)
+        .await
+        .unwrap();
+
+    // act
+    let compaction = client.server_state.compact().await.unwrap();
+    let states = client
+        .server_state
+        .list()
+        .server(server_id)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(compaction.archived_state_count, 1);
+    assert_eq!(compaction.archived_until, Some(year + 1));
+    assert!(states.is_empty());
+    assert_eq!(
+        server_consumption.get(&flavor.name),
+        Some(&(10. * 24. * 60. * 60.))
+    );
+    assert_eq!(
+        client
+            .server_consumption
+            .get()
+            .begin(begin.fixed_offset())
+            .end(end.fixed_offset())
+            .server(server_id)
+            .await
+            .unwrap(),
+        server_consumption
+    );
+    assert_eq!(
+        client
+            .server_consumption
+            .get()
+            .begin(begin.fixed_offset())
+            .end(end.fixed_offset())
+            .user(user.id)
+            .await
+            .unwrap(),
+        user_consumption
+    );
+}
@@ -0,0 +1,689 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_api::database::{
+    accounting::server_state::NewServerState,
+    resources::flavor::insert_flavor_into_db,
+};
+use avina_test::{
+    nova_server, random_alphanumeric_string, random_uuid, spawn_app,
+};
+use avina_wire::resources::FlavorCreateData;
+use chrono::{DateTime, TimeDelta, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+// NOTE: Nova returns launch and termination times without a timezone.
+fn nova_timestamp(datetime: DateTime<Utc>) -> String {
+    datetime.format("%Y-%m-%dT%H:%M:%S%.6f").to_string()
+}
+
+#[tokio::test]
+async fn e2e_lib_server_state_import_replaces_state_of_resized_server() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let old_flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let new_flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let server_id = Uuid::new_v4();
+    let state = server
+        .setup_test_server_state_with_server_id(&old_flavor, &user, server_id)
+        .await
+        .expect("Failed to setup test server state");
+    server
+        .mock_nova_servers(vec![nova_server(
+            server_id,
+            &state.instance_name,
+            &state.status,
+            &user.openstack_id,
+            &new_flavor.openstack_id,
+        )])
+        .mount(&server.nova_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let import = client.server_state.import().await.unwrap();
+    let states = client
+        .server_state
+        .list()
+        .server(server_id)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(import.new_state_count, 1);
+    assert_eq!(import.end_state_count, 1);
+    assert_eq!(states.len(), 2);
+    let old_state = states.iter().find(|s| s.id == state.id).unwrap();
+    assert!(old_state.end.is_some());
+    assert_eq!(old_state.flavor, old_flavor.id);
+    let new_state = states.iter().find(|s| s.id != state.id).unwrap();
+    assert!(new_state.end.is_none());
+    assert_eq!(new_state.flavor, new_flavor.id);
+    assert_eq!(new_state.status, state.status);
+}
+
+#[tokio::test]
+async fn e2e_lib_server_state_import_replaces_state_of_renamed_server() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let server_id = Uuid::new_v4();
+    let state = server
+        .setup_test_server_state_with_server_id(&flavor, &user, server_id)
+        .await
+        .expect("Failed to setup test server state");
+    server
+        .mock_nova_servers(vec![nova_server(
+            server_id,
+            "renamed",
+            &state.status,
+            &user.openstack_id,
+            &flavor.openstack_id,
+        )])
+        .mount(&server.nova_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let import = client.server_state.import().await.unwrap();
+    let states = client
+        .server_state
+        .list()
+        .server(server_id)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(import.new_state_count, 1);
+    assert_eq!(import.end_state_count, 1);
+    let new_state = states.iter().find(|s| s.id != state.id).unwrap();
+    assert_eq!(new_state.instance_name, "renamed");
+}
+
+#[tokio::test]
+async fn e2e_lib_server_state_import_keeps_state_of_unchanged_server() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let server_id = Uuid::new_v4();
+    let state = server
+        .setup_test_server_state_with_server_id(&flavor, &user, server_id)
+        .await
+        .expect("Failed to setup test server state");
+    server
+        .mock_nova_servers(vec![nova_server(
+            server_id,
+            &state.instance_name,
+            &state.status,
+            &user.openstack_id,
+            &flavor.openstack_id,
+        )])
+        .mount(&server.nova_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let import = client.server_state.import().await.unwrap();
+
+    // assert
+    assert_eq!(import.new_state_count, 0);
+    assert_eq!(import.end_state_count, 0);
+}
+
+#[tokio::test]
+async fn e2e_lib_server_state_import_dry_run_reports_resize_without_storing() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let old_flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let new_flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let server_id = Uuid::new_v4();
+    let state = server
+        .setup_test_server_state_with_server_id(&old_flavor, &user, server_id)
+        .await
+        .expect("Failed to setup test server state");
+    server
+        .mock_nova_servers(vec![nova_server(
+            server_id,
+            &state.instance_name,
+            &state.status,
+            &user.openstack_id,
+            &new_flavor.openstack_id,
+        )])
+        .mount(&server.nova_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let diff = client.server_state.import_dry_run().await.unwrap();
+    let states = client
+        .server_state
+        .list()
+        .server(server_id)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(diff.new_states.len(), 1);
+    assert_eq!(diff.new_states[0].instance_id, server_id);
+    assert_eq!(diff.new_states[0].changes, Some("flavor".to_string()));
+    assert_eq!(diff.ended_states.len(), 1);
+    assert_eq!(diff.ended_states[0].id, state.id);
+    assert!(diff.skipped_servers.is_empty());
+    assert_eq!(states.len(), 1);
+    assert!(states[0].end.is_none());
+}
+
+#[tokio::test]
+async fn e2e_lib_server_state_import_dry_run_returns_diff_without_storing() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let deleted_server_id = Uuid::new_v4();
+    let deleted_state = server
+        .setup_test_server_state_with_server_id(
+            &flavor,
+            &user,
+            deleted_server_id,
+        )
+        .await
+        .expect("Failed to setup test server state");
+    let new_server_id = Uuid::new_v4();
+    let unknown_server_id = Uuid::new_v4();
+    server
+        .mock_nova_servers(vec![
+            nova_server(
+                new_server_id,
+                &random_alphanumeric_string(10),
+                "ACTIVE",
+                &user.openstack_id,
+                &flavor.openstack_id,
+            ),
+            nova_server(
+                unknown_server_id,
+                &random_alphanumeric_string(10),
+                "ACTIVE",
+                &user.openstack_id,
+                &random_uuid(),
+            ),
+        ])
+        .mount(&server.nova_server)
+        .await;
+    server
+        .mock_nova_deleted_servers(vec![])
+        .mount(&server.nova_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let diff = client.server_state.import_dry_run().await.unwrap();
+    let new_states = client
+        .server_state
+        .list()
+        .server(new_server_id)
+        .send()
+        .await
+        .unwrap();
+    let deleted_states = client
+        .server_state
+        .list()
+        .server(deleted_server_id)
+        .send()
+        .await
+        .unwrap();
+    let unmatched_servers =
+        client.unmatched_server.list().all().send().await.unwrap();
+
+    // assert
+    assert_eq!(diff.new_states.len(), 1);
+    assert_eq!(diff.new_states[0].instance_id, new_server_id);
+    assert_eq!(diff.new_states[0].changes, None);
+    assert_eq!(diff.ended_states.len(), 1);
+    assert_eq!(diff.ended_states[0].id, deleted_state.id);
+    assert_eq!(diff.skipped_servers.len(), 1);
+    assert_eq!(diff.skipped_servers[0].instance_id, unknown_server_id);
+    assert!(new_states.is_empty());
+    assert_eq!(deleted_states.len(), 1);
+    assert!(deleted_states[0].end.is_none());
+    assert!(
+        !unmatched_servers
+            .iter()
+            .any(|u| u.instance_id == unknown_server_id)
+    );
+}
+
+#[tokio::test]
+async fn e2e_lib_server_state_import_begins_new_state_at_launch() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let server_id = Uuid::new_v4();
+    let launched_at = Utc::now() - TimeDelta::minutes(50);
+    let mut nova_server = nova_server(
+        server_id,
+        "new",
+        "ACTIVE",
+        &user.openstack_id,
+        &flavor.openstack_id,
+    );
+    nova_server["OS-SRV-USG:launched_at"] = json!(nova_timestamp(launched_at));
+    server
+        .mock_nova_servers(vec![nova_server])
+        .mount(&server.nova_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let import = client.server_state.import().await.unwrap();
+    let states = client
+        .server_state
+        .list()
+        .server(server_id)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(import.new_state_count, 1);
+    assert_eq!(states.len(), 1);
+    assert!((states[0].begin.to_utc() - launched_at).num_seconds().abs() < 1);
+    assert!(states[0].end.is_none());
+}
+
+#[tokio::test]
+async fn e2e_lib_server_state_import_ends_state_of_deleted_server_at_termination()
+ {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let server_id = Uuid::new_v4();
+    let state = server
+        .setup_test_server_state_with_server_state(
+            &flavor,
+            &user,
+            NewServerState {
+                begin: Utc::now() - TimeDelta::hours(2),
+                end: None,
+                instance_id: server_id,
+                instance_name: random_alphanumeric_string(10),
+                flavor: flavor.id,
+                status: "ACTIVE".to_string(),
+                user: user.id,
+            },
+        )
+        .await
+        .expect("Failed to setup test server state");
+    let terminated_at = Utc::now() - TimeDelta::hours(1);
+    let mut deleted_server = nova_server(
+        server_id,
+        &state.instance_name,
+        "DELETED",
+        &user.openstack_id,
+        &flavor.openstack_id,
+    );
+    deleted_server["OS-SRV-USG:terminated_at"] =
+        json!(nova_timestamp(terminated_at));
+    server
+        .mock_nova_servers(vec![])
+        .mount(&server.nova_server)
+        .await;
+    server
+        .mock_nova_deleted_servers(vec![deleted_server])
+        .with_priority(1)
+        .mount(&server.nova_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let import = client.server_state.import().await.unwrap();
+    let states = client
+        .server_state
+        .list()
+        .server(server_id)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(import.end_state_count, 1);
+    assert_eq!(states.len(), 1);
+    let end = states[0].end.unwrap().to_utc();
+    assert!((end - terminated_at).num_seconds().abs() < 1);
+}
+
+#[tokio::test]
+async fn e2e_lib_server_state_import_records_server_deleted_between_imports() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let running_server_id = Uuid::new_v4();
+    let running_state = server
+        .setup_test_server_state_with_server_state(
+            &flavor,
+            &user,
+            NewServerState {
+                begin: Utc::now() - TimeDelta::hours(2),
+                end: None,
+                instance_id: running_server_id,
+                instance_name: random_alphanumeric_string(10),
+                flavor: flavor.id,
+                status: "ACTIVE".to_string(),
+                user: user.id,
+            },
+        )
+        .await
+        .expect("Failed to setup test server state");
+    let deleted_server_id = Uuid::new_v4();
+    let launched_at = Utc::now() - TimeDelta::minutes(100);
+    let terminated_at = Utc::now() - TimeDelta::minutes(50);
+    let mut deleted_server = nova_server(
+        deleted_server_id,
+        "short-lived",
+        "DELETED",
+        &user.openstack_id,
+        &flavor.openstack_id,
+    );
+    deleted_server["OS-SRV-USG:launched_at"] =
+        json!(nova_timestamp(launched_at));
+    deleted_server["OS-SRV-USG:terminated_at"] =
+        json!(nova_timestamp(terminated_at));
+    server
+        .mock_nova_servers(vec![nova_server(
+            running_server_id,
+            &running_state.instance_name,
+            &running_state.status,
+            &user.openstack_id,
+            &flavor.openstack_id,
+        )])
+        .mount(&server.nova_server)
+        .await;
+    server
+        .mock_nova_deleted_servers(vec![deleted_server])
+        .with_priority(1)
+        .mount(&server.nova_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let import = client.server_state.import().await.unwrap();
+    let states = client
+        .server_state
+        .list()
+        .server(deleted_server_id)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(import.new_state_count, 1);
+    assert_eq!(import.end_state_count, 0);
+    assert_eq!(states.len(), 1);
+    assert_eq!(states[0].status, "ACTIVE");
+    assert!((states[0].begin.to_utc() - launched_at).num_seconds().abs() < 1);
+    let end = states[0].end.unwrap().to_utc();
+    assert!((end - terminated_at).num_seconds().abs() < 1);
+}
+
+#[tokio::test]
+async fn e2e_lib_server_state_import_does_not_overlap_state_after_delayed_resize()
+ {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let old_flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let server_id = Uuid::new_v4();
+    let launched_at = Utc::now() - TimeDelta::days(10);
+    let resized_at = Utc::now() - TimeDelta::days(2);
+    let state = server
+        .setup_test_server_state_with_server_state(
+            &old_flavor,
+            &user,
+            NewServerState {
+                begin: launched_at,
+                end: None,
+                instance_id: server_id,
+                instance_name: random_alphanumeric_string(10),
+                flavor: old_flavor.id,
+                status: "ACTIVE".to_string(),
+                user: user.id,
+            },
+        )
+        .await
+        .expect("Failed to setup test server state");
+    // NOTE: the server is resized to a flavor that is not imported yet.
+    let new_flavor_openstack_id = random_uuid();
+    let mut nova_server = nova_server(
+        server_id,
+        &state.instance_name,
+        &state.status,
+        &user.openstack_id,
+        &new_flavor_openstack_id,
+    );
+    nova_server["OS-SRV-USG:launched_at"] = json!(nova_timestamp(launched_at));
+    nova_server["updated"] = json!(resized_at.to_rfc3339());
+    server
+        .mock_nova_servers(vec![nova_server])
+        .mount(&server.nova_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    let first_import = client.server_state.import().await.unwrap();
+    let mut transaction = server
+        .db_pool
+        .begin()
+        .await
+        .expect("Failed to begin transaction");
+    insert_flavor_into_db(
+        &mut transaction,
+        &FlavorCreateData {
+            name: random_alphanumeric_string(10),
+            openstack_id: new_flavor_openstack_id,
+            group: None,
+            weight: None,
+        },
+    )
+    .await
+    .expect("Failed to insert flavor");
+    transaction
+        .commit()
+        .await
+        .expect("Failed to commit transaction");
+
+    // act
+    let second_import = client.server_state.import().await.unwrap();
+    let states = client
+        .server_state
+        .list()
+        .server(server_id)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(first_import.new_state_count, 0);
+    assert_eq!(first_import.end_state_count, 1);
+    assert_eq!(second_import.new_state_count, 1);
+    assert_eq!(states.len(), 2);
+    let old_state = states.iter().find(|s| s.id == state.id).unwrap();
+    let new_state = states.iter().find(|s| s.id != state.id).unwrap();
+    assert_eq!(new_state.begin, old_state.end.unwrap());
+    assert!((new_state.begin.to_utc() - resized_at).num_seconds().abs() < 1);
+}
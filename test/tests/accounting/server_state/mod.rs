@@ -1,6 +1,8 @@
+mod compact;
 mod create;
 mod delete;
 mod get;
+mod import;
 mod list;
 mod modify;
 
@@ -0,0 +1,96 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_test::{
+    nova_server, random_alphanumeric_string, random_uuid, spawn_app,
+};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn e2e_lib_normal_user_cannot_list_unmatched_servers() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let list = client.unmatched_server.list().send().await;
+
+    // assert
+    assert!(list.is_err());
+    assert_eq!(list.unwrap_err().to_string(), "Admin privileges required");
+}
+
+#[tokio::test]
+async fn e2e_lib_server_state_import_quarantines_server_with_unknown_flavor() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let server_id = Uuid::new_v4();
+    let flavor_openstack_id = random_uuid();
+    let instance_name = random_alphanumeric_string(10);
+    server
+        .mock_nova_servers(vec![nova_server(
+            server_id,
+            &instance_name,
+            "ACTIVE",
+            &user.openstack_id,
+            &flavor_openstack_id,
+        )])
+        .mount(&server.nova_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let import = client.server_state.import().await.unwrap();
+    let unmatched_servers =
+        client.unmatched_server.list().send().await.unwrap();
+    let states = client
+        .server_state
+        .list()
+        .server(server_id)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(import.new_state_count, 0);
+    assert!(states.is_empty());
+    assert_eq!(unmatched_servers.len(), 1);
+    let unmatched_server = &unmatched_servers[0];
+    assert_eq!(unmatched_server.instance_id, server_id);
+    assert_eq!(unmatched_server.instance_name, instance_name);
+    assert_eq!(unmatched_server.flavor_openstack_id, flavor_openstack_id);
+    assert_eq!(unmatched_server.user_openstack_id, user.openstack_id);
+    assert!(unmatched_server.resolution.is_none());
+}
@@ -0,0 +1,2 @@
+mod list;
+mod resolve;
@@ -0,0 +1,254 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_api::database::accounting::server_state::NewServerState;
+use avina_test::{
+    nova_server, random_alphanumeric_string, random_uuid, spawn_app,
+};
+use avina_wire::accounting::UnmatchedServerResolution;
+use chrono::{TimeDelta, TimeZone, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn e2e_lib_unmatched_server_resolve_with_flavor_backfills_state() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let server_id = Uuid::new_v4();
+    server
+        .mock_nova_servers(vec![nova_server(
+            server_id,
+            &random_alphanumeric_string(10),
+            "ACTIVE",
+            &user.openstack_id,
+            &random_uuid(),
+        )])
+        .mount(&server.nova_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    client.server_state.import().await.unwrap();
+    let unmatched_server = client
+        .unmatched_server
+        .list()
+        .send()
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+
+    // act
+    let resolved = client
+        .unmatched_server
+        .resolve(unmatched_server.id)
+        .flavor(flavor.id)
+        .send()
+        .await
+        .unwrap();
+    let import = client.server_state.import().await.unwrap();
+    let states = client
+        .server_state
+        .list()
+        .server(server_id)
+        .send()
+        .await
+        .unwrap();
+    let unmatched_servers =
+        client.unmatched_server.list().send().await.unwrap();
+
+    // assert
+    assert_eq!(resolved.resolution, Some(UnmatchedServerResolution::Mapped));
+    assert_eq!(resolved.flavor, Some(flavor.id));
+    assert_eq!(resolved.user, Some(user.id));
+    assert!(unmatched_servers.is_empty());
+    assert_eq!(import.new_state_count, 0);
+    assert_eq!(import.end_state_count, 0);
+    assert_eq!(states.len(), 1);
+    assert_eq!(states[0].flavor, flavor.id);
+    assert_eq!(states[0].user, user.id);
+    assert!(states[0].end.is_none());
+    // NOTE: the server has not been launched, so it is billed from its
+    // creation in Nova.
+    assert_eq!(
+        states[0].begin.to_utc(),
+        Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn e2e_lib_unmatched_server_resolve_does_not_overlap_last_state() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let server_id = Uuid::new_v4();
+    let launched_at = Utc::now() - TimeDelta::days(10);
+    let state = server
+        .setup_test_server_state_with_server_state(
+            &flavor,
+            &user,
+            NewServerState {
+                begin: launched_at,
+                end: None,
+                instance_id: server_id,
+                instance_name: random_alphanumeric_string(10),
+                flavor: flavor.id,
+                status: "ACTIVE".to_string(),
+                user: user.id,
+            },
+        )
+        .await
+        .expect("Failed to setup test server state");
+    // NOTE: the server is resized to a flavor that is not imported yet.
+    let mut nova_server = nova_server(
+        server_id,
+        &state.instance_name,
+        &state.status,
+        &user.openstack_id,
+        &random_uuid(),
+    );
+    nova_server["OS-SRV-USG:launched_at"] =
+        json!(launched_at.format("%Y-%m-%dT%H:%M:%S%.6f").to_string());
+    server
+        .mock_nova_servers(vec![nova_server])
+        .mount(&server.nova_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    client.server_state.import().await.unwrap();
+    let unmatched_server = client
+        .unmatched_server
+        .list()
+        .send()
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+
+    // act
+    client
+        .unmatched_server
+        .resolve(unmatched_server.id)
+        .flavor(flavor.id)
+        .send()
+        .await
+        .unwrap();
+    let states = client
+        .server_state
+        .list()
+        .server(server_id)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(states.len(), 2);
+    let old_state = states.iter().find(|s| s.id == state.id).unwrap();
+    let new_state = states.iter().find(|s| s.id != state.id).unwrap();
+    assert_eq!(new_state.begin, old_state.end.unwrap());
+    assert!(new_state.end.is_none());
+}
+
+#[tokio::test]
+async fn e2e_lib_unmatched_server_resolve_with_ignore_works() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let server_id = Uuid::new_v4();
+    server
+        .mock_nova_servers(vec![nova_server(
+            server_id,
+            &random_alphanumeric_string(10),
+            "ACTIVE",
+            &user.openstack_id,
+            &random_uuid(),
+        )])
+        .mount(&server.nova_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    client.server_state.import().await.unwrap();
+    let unmatched_server = client
+        .unmatched_server
+        .list()
+        .send()
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+
+    // act
+    let resolved = client
+        .unmatched_server
+        .resolve(unmatched_server.id)
+        .ignore()
+        .send()
+        .await
+        .unwrap();
+    let resolved_again = client
+        .unmatched_server
+        .resolve(unmatched_server.id)
+        .ignore()
+        .send()
+        .await;
+    let all = client.unmatched_server.list().all().send().await.unwrap();
+
+    // assert
+    assert_eq!(
+        resolved.resolution,
+        Some(UnmatchedServerResolution::Ignored)
+    );
+    assert!(resolved.resolved_at.is_some());
+    assert!(resolved_again.is_err());
+    assert!(all.iter().any(|u| u.id == unmatched_server.id));
+}
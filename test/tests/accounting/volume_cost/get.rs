@@ -0,0 +1,85 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_api::database::{
+    accounting::volume_state::{NewVolumeState, insert_volume_state_into_db},
+    pricing::volume_price::{NewVolumePrice, insert_volume_price_into_db},
+};
+use avina_test::{random_alphanumeric_string, spawn_app};
+use chrono::{TimeDelta, TimeZone, Utc};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn e2e_lib_volume_cost_is_prorated_by_size_and_time() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let begin = Utc.with_ymd_and_hms(2025, 3, 10, 0, 0, 0).unwrap();
+    let end = begin + TimeDelta::days(2);
+    let volume_id = Uuid::new_v4();
+    let mut transaction = server
+        .db_pool
+        .begin()
+        .await
+        .expect("Failed to begin transaction.");
+    // NOTE: this is the price of one GiB per year, so one GiB costs 1 a day.
+    insert_volume_price_into_db(
+        &mut transaction,
+        &NewVolumePrice {
+            volume_type: "ssd".to_string(),
+            user_class: project.user_class,
+            unit_price: 365.,
+            start_time: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+        },
+    )
+    .await
+    .expect("Failed to insert volume price.");
+    insert_volume_state_into_db(
+        &mut transaction,
+        &NewVolumeState {
+            begin: begin + TimeDelta::days(1),
+            end: None,
+            volume_id,
+            volume_name: random_alphanumeric_string(10),
+            size: 3,
+            volume_type: "ssd".to_string(),
+            status: "in-use".to_string(),
+            user: user.id,
+        },
+    )
+    .await
+    .expect("Failed to insert volume state.");
+    transaction
+        .commit()
+        .await
+        .expect("Failed to commit transaction.");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let cost = client
+        .volume_cost
+        .get()
+        .begin(begin.fixed_offset())
+        .end(end.fixed_offset())
+        .volume(volume_id)
+        .await
+        .unwrap();
+
+    // assert
+    assert!((cost.total - 3.).abs() < 1e-6);
+}
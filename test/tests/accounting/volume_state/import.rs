@@ -0,0 +1,174 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_test::{cinder_volume, spawn_app};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn e2e_lib_volume_state_import_creates_state_of_new_volume() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let volume_id = Uuid::new_v4();
+    server
+        .mock_cinder_volumes(vec![cinder_volume(
+            volume_id,
+            "data",
+            "in-use",
+            10,
+            "ssd",
+            &user.openstack_id,
+        )])
+        .mount(&server.cinder_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let import = client.volume_state.import().await.unwrap();
+    let states = client
+        .volume_state
+        .list()
+        .volume(volume_id)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(import.new_state_count, 1);
+    assert_eq!(import.end_state_count, 0);
+    assert_eq!(import.skipped_volume_count, 0);
+    assert_eq!(states.len(), 1);
+    assert_eq!(states[0].user, user.id);
+    assert_eq!(states[0].size, 10);
+    assert_eq!(states[0].volume_type, "ssd");
+    assert!(states[0].end.is_none());
+}
+
+#[tokio::test]
+async fn e2e_lib_volume_state_import_replaces_state_of_extended_volume() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let volume_id = Uuid::new_v4();
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    let mock = server
+        .mock_cinder_volumes(vec![cinder_volume(
+            volume_id,
+            "data",
+            "available",
+            10,
+            "ssd",
+            &user.openstack_id,
+        )])
+        .mount_as_scoped(&server.cinder_server)
+        .await;
+    client.volume_state.import().await.unwrap();
+    drop(mock);
+    server
+        .mock_cinder_volumes(vec![cinder_volume(
+            volume_id,
+            "data",
+            "available",
+            20,
+            "ssd",
+            &user.openstack_id,
+        )])
+        .mount(&server.cinder_server)
+        .await;
+
+    // act
+    let import = client.volume_state.import().await.unwrap();
+    let states = client
+        .volume_state
+        .list()
+        .volume(volume_id)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(import.new_state_count, 1);
+    assert_eq!(import.end_state_count, 1);
+    assert_eq!(states.len(), 2);
+    let old_state = states.iter().find(|s| s.size == 10).unwrap();
+    assert!(old_state.end.is_some());
+    let new_state = states.iter().find(|s| s.size == 20).unwrap();
+    assert!(new_state.end.is_none());
+}
+
+#[tokio::test]
+async fn e2e_lib_volume_state_import_dry_run_does_not_store_states() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let volume_id = Uuid::new_v4();
+    server
+        .mock_cinder_volumes(vec![cinder_volume(
+            volume_id,
+            "data",
+            "available",
+            10,
+            "ssd",
+            &user.openstack_id,
+        )])
+        .mount(&server.cinder_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let import = client.volume_state.import_dry_run().await.unwrap();
+    let states = client
+        .volume_state
+        .list()
+        .volume(volume_id)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(import.new_state_count, 1);
+    assert!(states.is_empty());
+}
@@ -0,0 +1,145 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_test::{random_alphanumeric_string, spawn_app};
+use avina_wire::user::ApiKeyScope;
+
+#[tokio::test]
+async fn e2e_lib_audit_log_list_denies_access_to_normal_user() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let list = client.audit.list().send().await;
+
+    // assert
+    assert!(list.is_err());
+    assert_eq!(list.unwrap_err().to_string(), "Admin privileges required");
+}
+
+#[tokio::test]
+async fn e2e_lib_audit_log_list_contains_mutating_requests() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    let created = client
+        .api_key
+        .create(
+            random_alphanumeric_string(10),
+            vec![ApiKeyScope::Accounting],
+        )
+        .send()
+        .await
+        .unwrap();
+    client.api_key.delete(created.api_key.id).await.unwrap();
+
+    // act
+    let entries = client
+        .audit
+        .list()
+        .actor(user.id)
+        .resource_type("apikeys".to_string())
+        .resource_id(created.api_key.id)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].method, "POST");
+    assert_eq!(entries[0].actor, user.id);
+    assert!(entries[0].before.is_none());
+    let after = entries[0].after.as_ref().unwrap();
+    assert_eq!(after["name"], created.api_key.name.as_str());
+    assert!(after.get("key").is_none());
+    assert_eq!(entries[1].method, "DELETE");
+    assert_eq!(entries[1].impersonated, None);
+    assert_eq!(
+        entries[1].before.as_ref().unwrap()["id"],
+        created.api_key.id
+    );
+    assert!(entries[1].after.is_none());
+}
+
+#[tokio::test]
+async fn e2e_lib_audit_log_records_id_of_created_api_key() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let created = client
+        .api_key
+        .create(
+            random_alphanumeric_string(10),
+            vec![ApiKeyScope::Accounting],
+        )
+        .send()
+        .await
+        .unwrap();
+    let entries = client
+        .audit
+        .list()
+        .actor(user.id)
+        .resource_type("apikeys".to_string())
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].method, "POST");
+    assert_eq!(entries[0].resource_id, Some(created.api_key.id));
+    let after = entries[0].after.as_ref().unwrap();
+    assert_eq!(after["id"], created.api_key.id);
+    assert!(after.get("key").is_none());
+}
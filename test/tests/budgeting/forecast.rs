@@ -0,0 +1,301 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_api::database::{
+    accounting::{
+        floating_ip_state::{
+            NewFloatingIpState, insert_floating_ip_state_into_db,
+        },
+        server_state::NewServerState,
+        volume_state::{NewVolumeState, insert_volume_state_into_db},
+    },
+    budgeting::user_budget::NewUserBudget,
+    pricing::{
+        flavor_price::NewFlavorPrice,
+        floating_ip_price::{
+            NewFloatingIpPrice, insert_floating_ip_price_into_db,
+        },
+        volume_price::{NewVolumePrice, insert_volume_price_into_db},
+    },
+};
+use avina_test::{random_alphanumeric_string, spawn_app};
+use chrono::{Datelike, TimeDelta, Utc};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn e2e_lib_budget_forecast_bills_running_servers_until_year_end() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let now = Utc::now();
+    server
+        .setup_test_flavor_price_with_new_flavor_price(
+            &flavor,
+            NewFlavorPrice {
+                flavor_id: flavor.id as u64,
+                user_class: project.user_class,
+                // NOTE: this is the price per year, so one day costs 1.
+                unit_price: 365.,
+                start_time: now - TimeDelta::days(10),
+            },
+        )
+        .await
+        .expect("Failed to setup test flavor price");
+    server
+        .setup_test_server_state_with_server_state(
+            &flavor,
+            &user,
+            NewServerState {
+                begin: now - TimeDelta::hours(1),
+                end: None,
+                instance_id: Uuid::new_v4(),
+                instance_name: random_alphanumeric_string(10),
+                flavor: flavor.id,
+                status: "ACTIVE".to_string(),
+                user: user.id,
+            },
+        )
+        .await
+        .expect("Failed to setup test server state");
+    server
+        .setup_test_user_budget_with_new_user_budget(
+            &user,
+            &NewUserBudget {
+                user_id: user.id as u64,
+                year: now.year() as u32,
+                amount: 2,
+            },
+        )
+        .await
+        .expect("Failed to setup test user budget");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let forecast = client.budget_forecast.get().send().await.unwrap();
+    let overs = client
+        .user_budget
+        .over()
+        .user(user.id)
+        .detail()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(forecast.budget, Some(2));
+    assert!(forecast.running_cost > 0.);
+    assert!(
+        (forecast.projected_total - forecast.cost - forecast.running_cost)
+            .abs()
+            < 1e-6
+    );
+    assert!(
+        (forecast.projected_overrun - (forecast.projected_total - 2.).max(0.))
+            .abs()
+            < 1e-6
+    );
+    let exhausted_at = forecast.exhausted_at.unwrap();
+    assert!(exhausted_at > now + TimeDelta::days(1));
+    assert!(exhausted_at < now + TimeDelta::days(2));
+    assert_eq!(overs.len(), 1);
+    assert!(overs[0].projected_total > overs[0].cost);
+    assert!(overs[0].exhausted_at.is_some());
+}
+
+#[tokio::test]
+async fn e2e_lib_budget_forecast_bills_allocated_volumes_until_year_end() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let now = Utc::now();
+    let mut transaction = server
+        .db_pool
+        .begin()
+        .await
+        .expect("Failed to begin transaction.");
+    // NOTE: this is the price of one GiB per year, so one GiB costs 1 a day.
+    insert_volume_price_into_db(
+        &mut transaction,
+        &NewVolumePrice {
+            volume_type: "ssd".to_string(),
+            user_class: project.user_class,
+            unit_price: 365.,
+            start_time: now - TimeDelta::days(10),
+        },
+    )
+    .await
+    .expect("Failed to insert volume price.");
+    insert_volume_state_into_db(
+        &mut transaction,
+        &NewVolumeState {
+            begin: now - TimeDelta::hours(1),
+            end: None,
+            volume_id: Uuid::new_v4(),
+            volume_name: random_alphanumeric_string(10),
+            size: 2,
+            volume_type: "ssd".to_string(),
+            status: "available".to_string(),
+            user: user.id,
+        },
+    )
+    .await
+    .expect("Failed to insert volume state.");
+    transaction
+        .commit()
+        .await
+        .expect("Failed to commit transaction.");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let forecast = client.budget_forecast.get().send().await.unwrap();
+
+    // assert
+    assert!(forecast.cost > 0.);
+    assert!(forecast.running_cost > 0.);
+    assert!(
+        (forecast.projected_total - forecast.cost - forecast.running_cost)
+            .abs()
+            < 1e-6
+    );
+}
+
+#[tokio::test]
+async fn e2e_lib_budget_forecast_bills_allocated_floating_ips_until_year_end() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let now = Utc::now();
+    let mut transaction = server
+        .db_pool
+        .begin()
+        .await
+        .expect("Failed to begin transaction.");
+    // NOTE: this is the price per year, so one floating IP costs 1 a day.
+    insert_floating_ip_price_into_db(
+        &mut transaction,
+        &NewFloatingIpPrice {
+            pool: "MWN_pool".to_string(),
+            user_class: project.user_class,
+            unit_price: 365.,
+            start_time: now - TimeDelta::days(10),
+        },
+    )
+    .await
+    .expect("Failed to insert floating IP price.");
+    insert_floating_ip_state_into_db(
+        &mut transaction,
+        &NewFloatingIpState {
+            begin: now - TimeDelta::hours(1),
+            end: None,
+            floating_ip_id: Uuid::new_v4(),
+            floating_ip_address: "10.195.0.1".to_string(),
+            pool: "MWN_pool".to_string(),
+            user: user.id,
+        },
+    )
+    .await
+    .expect("Failed to insert floating IP state.");
+    transaction
+        .commit()
+        .await
+        .expect("Failed to commit transaction.");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let forecast = client.budget_forecast.get().send().await.unwrap();
+
+    // assert
+    assert!(forecast.cost > 0.);
+    assert!(forecast.running_cost > 0.);
+    assert!(
+        (forecast.projected_total - forecast.cost - forecast.running_cost)
+            .abs()
+            < 1e-6
+    );
+}
+
+#[tokio::test]
+async fn e2e_lib_user_cannot_get_budget_forecast_of_other_project() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let (_other_user, other_project, _other_token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let forecast = client
+        .budget_forecast
+        .get()
+        .project(other_project.id)
+        .send()
+        .await;
+
+    // assert
+    assert!(forecast.is_err());
+}
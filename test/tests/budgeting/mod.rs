@@ -1,2 +1,3 @@
+mod forecast;
 mod project_budget;
 mod user_budget;
@@ -1,7 +1,10 @@
 mod accounting;
+mod audit;
 mod budgeting;
 mod hello;
 mod pricing;
 mod quota;
 mod resources;
+mod scheduler;
+mod token;
 mod user;
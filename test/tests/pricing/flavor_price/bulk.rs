@@ -0,0 +1,361 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_api::database::{
+    accounting::server_state::NewServerState,
+    pricing::flavor_price::NewFlavorPrice,
+};
+use avina_test::{random_alphanumeric_string, spawn_app};
+use avina_wire::user::UserClass;
+use chrono::{Datelike, TimeDelta, TimeZone, Utc};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn e2e_lib_flavor_price_bulk_create_denies_access_to_normal_user() {
+    // arrange
+    let server = spawn_app().await;
+    let test_project = server
+        .setup_test_project(0, 0, 1)
+        .await
+        .expect("Failed to setup test project");
+    let user = test_project.normals[0].user.clone();
+    let token = test_project.normals[0].token.clone();
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let bulk_create = client
+        .flavor_price
+        .bulk_create((Utc::now() + TimeDelta::days(30)).into())
+        .flavor(flavor.id)
+        .unit_price(100.)
+        .send()
+        .await;
+
+    // assert
+    assert!(bulk_create.is_err());
+    assert_eq!(
+        bulk_create.unwrap_err().to_string(),
+        "Admin privileges required".to_string()
+    );
+}
+
+#[tokio::test]
+async fn e2e_lib_flavor_price_bulk_create_rejects_past_start_time() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let bulk_create = client
+        .flavor_price
+        .bulk_create((Utc::now() - TimeDelta::days(1)).into())
+        .flavor(flavor.id)
+        .unit_price(100.)
+        .send()
+        .await;
+
+    // assert
+    assert!(bulk_create.is_err());
+    assert_eq!(
+        bulk_create.unwrap_err().to_string(),
+        "Start time has to be in the future".to_string()
+    );
+}
+
+#[tokio::test]
+async fn e2e_lib_flavor_price_bulk_create_applies_percentage() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor1 = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let flavor2 = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    server
+        .setup_test_flavor_price_with_new_flavor_price(
+            &flavor1,
+            NewFlavorPrice {
+                flavor_id: flavor1.id as u64,
+                user_class: UserClass::UC1,
+                unit_price: 100.,
+                start_time: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            },
+        )
+        .await
+        .expect("Failed to setup test flavor price");
+    let start_time = Utc
+        .with_ymd_and_hms(Utc::now().year() + 1, 1, 1, 0, 0, 0)
+        .unwrap();
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let created = client
+        .flavor_price
+        .bulk_create(start_time.into())
+        .flavor(flavor1.id)
+        .flavor(flavor2.id)
+        .user_class(UserClass::UC1)
+        .user_class(UserClass::UC2)
+        .percentage(10.)
+        .send()
+        .await
+        .unwrap();
+    let prices = client.flavor_price.list().send().await.unwrap();
+
+    // assert
+    assert_eq!(created.len(), 4);
+    for price in &created {
+        assert!(prices.contains(price));
+        if price.flavor == flavor1.id && price.user_class == UserClass::UC1 {
+            assert_eq!(price.unit_price, 110.);
+        } else {
+            assert_eq!(price.unit_price, 0.);
+        }
+    }
+    assert_eq!(prices.len(), 5);
+}
+
+#[tokio::test]
+async fn e2e_lib_flavor_price_bulk_preview_shows_project_cost_change() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    // NOTE: these are prices per year, so one day costs 1 for every class.
+    for user_class in [
+        UserClass::NA,
+        UserClass::UC1,
+        UserClass::UC2,
+        UserClass::UC3,
+        UserClass::UC4,
+        UserClass::UC5,
+        UserClass::UC6,
+    ] {
+        server
+            .setup_test_flavor_price_with_new_flavor_price(
+                &flavor,
+                NewFlavorPrice {
+                    flavor_id: flavor.id as u64,
+                    user_class,
+                    unit_price: 365.,
+                    start_time: Utc
+                        .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+                        .unwrap(),
+                },
+            )
+            .await
+            .expect("Failed to setup test flavor price");
+    }
+    let begin = Utc.with_ymd_and_hms(2025, 3, 10, 0, 0, 0).unwrap();
+    let end = begin + TimeDelta::days(4);
+    server
+        .setup_test_server_state_with_server_state(
+            &flavor,
+            &user,
+            NewServerState {
+                begin,
+                end: Some(end),
+                instance_id: Uuid::new_v4(),
+                instance_name: random_alphanumeric_string(10),
+                flavor: flavor.id,
+                status: "ACTIVE".to_string(),
+                user: user.id,
+            },
+        )
+        .await
+        .expect("Failed to setup test server state");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let preview = client
+        .flavor_price
+        .bulk_create((Utc::now() + TimeDelta::days(30)).into())
+        .flavor(flavor.id)
+        .percentage(100.)
+        .begin(begin.into())
+        .end(end.into())
+        .preview()
+        .await
+        .unwrap();
+    let prices = client.flavor_price.list().send().await.unwrap();
+
+    // assert
+    assert_eq!(preview.prices.len(), 7);
+    assert!(preview.prices.iter().all(|price| price.new_price == 730.));
+    assert_eq!(preview.projects.len(), 1);
+    let impact = &preview.projects[0];
+    assert_eq!(impact.project, project.id);
+    assert!((impact.current_cost - 4.).abs() < 1e-6);
+    assert!((impact.new_cost - 8.).abs() < 1e-6);
+    assert!((impact.difference - 4.).abs() < 1e-6);
+    // NOTE: previews must not change any prices
+    assert_eq!(prices.len(), 7);
+    assert!(prices.iter().all(|price| price.unit_price == 365.));
+}
+
+#[tokio::test]
+async fn e2e_lib_flavor_price_bulk_preview_keeps_current_cost_of_past_prices() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let begin = Utc.with_ymd_and_hms(2025, 3, 10, 0, 0, 0).unwrap();
+    let end = begin + TimeDelta::days(4);
+    // NOTE: one day costs 1 until the middle of the period and 2 afterwards.
+    for (unit_price, start_time) in [
+        (365., Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()),
+        (730., begin + TimeDelta::days(2)),
+    ] {
+        for user_class in [
+            UserClass::NA,
+            UserClass::UC1,
+            UserClass::UC2,
+            UserClass::UC3,
+            UserClass::UC4,
+            UserClass::UC5,
+            UserClass::UC6,
+        ] {
+            server
+                .setup_test_flavor_price_with_new_flavor_price(
+                    &flavor,
+                    NewFlavorPrice {
+                        flavor_id: flavor.id as u64,
+                        user_class,
+                        unit_price,
+                        start_time,
+                    },
+                )
+                .await
+                .expect("Failed to setup test flavor price");
+        }
+    }
+    server
+        .setup_test_server_state_with_server_state(
+            &flavor,
+            &user,
+            NewServerState {
+                begin,
+                end: Some(end),
+                instance_id: Uuid::new_v4(),
+                instance_name: random_alphanumeric_string(10),
+                flavor: flavor.id,
+                status: "ACTIVE".to_string(),
+                user: user.id,
+            },
+        )
+        .await
+        .expect("Failed to setup test server state");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let preview = client
+        .flavor_price
+        .bulk_create((Utc::now() + TimeDelta::days(30)).into())
+        .flavor(flavor.id)
+        .percentage(100.)
+        .begin(begin.into())
+        .end(end.into())
+        .preview()
+        .await
+        .unwrap();
+
+    // assert
+    assert!(preview.prices.iter().all(|price| price.new_price == 1460.));
+    assert_eq!(preview.projects.len(), 1);
+    let impact = &preview.projects[0];
+    assert_eq!(impact.project, project.id);
+    assert!((impact.current_cost - 6.).abs() < 1e-6);
+    assert!((impact.new_cost - 16.).abs() < 1e-6);
+    assert!((impact.difference - 10.).abs() < 1e-6);
+}
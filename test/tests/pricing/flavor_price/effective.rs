@@ -0,0 +1,140 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_api::database::pricing::flavor_price::NewFlavorPrice;
+use avina_test::spawn_app;
+use avina_wire::user::UserClass;
+use chrono::{TimeZone, Utc};
+
+#[tokio::test]
+async fn e2e_lib_flavor_price_effective_returns_prices_active_at_time() {
+    // arrange
+    let server = spawn_app().await;
+    let test_project = server
+        .setup_test_project(0, 0, 1)
+        .await
+        .expect("Failed to setup test project");
+    let user = test_project.normals[0].user.clone();
+    let token = test_project.normals[0].token.clone();
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    for (unit_price, month) in [(100., 1), (200., 6)] {
+        server
+            .setup_test_flavor_price_with_new_flavor_price(
+                &flavor,
+                NewFlavorPrice {
+                    flavor_id: flavor.id as u64,
+                    user_class: UserClass::UC1,
+                    unit_price,
+                    start_time: Utc
+                        .with_ymd_and_hms(2025, month, 1, 0, 0, 0)
+                        .unwrap(),
+                },
+            )
+            .await
+            .expect("Failed to setup test flavor price");
+    }
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let before = client
+        .flavor_price
+        .effective()
+        .time(Utc.with_ymd_and_hms(2024, 12, 1, 0, 0, 0).unwrap().into())
+        .flavor(flavor.id)
+        .send()
+        .await
+        .unwrap();
+    let first = client
+        .flavor_price
+        .effective()
+        .time(Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap().into())
+        .flavor(flavor.id)
+        .send()
+        .await
+        .unwrap();
+    let second = client
+        .flavor_price
+        .effective()
+        .time(Utc.with_ymd_and_hms(2025, 7, 1, 0, 0, 0).unwrap().into())
+        .flavor(flavor.id)
+        .user_class(UserClass::UC1)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert!(before.iter().all(|price| price.unit_price == 0.));
+    assert_eq!(first.len(), before.len());
+    assert!(first.iter().any(|price| price.user_class == UserClass::UC1));
+    for price in first {
+        assert_eq!(price.flavor, flavor.id);
+        assert_eq!(price.flavor_name, flavor.name);
+        if price.user_class == UserClass::UC1 {
+            assert_eq!(price.unit_price, 100.);
+        } else {
+            assert_eq!(price.unit_price, 0.);
+        }
+    }
+    assert_eq!(second.len(), 1);
+    assert_eq!(second[0].user_class, UserClass::UC1);
+    assert_eq!(second[0].unit_price, 200.);
+}
+
+#[tokio::test]
+async fn e2e_lib_flavor_price_effective_returns_not_found_for_unknown_flavor() {
+    // arrange
+    let server = spawn_app().await;
+    let test_project = server
+        .setup_test_project(0, 0, 1)
+        .await
+        .expect("Failed to setup test project");
+    let user = test_project.normals[0].user.clone();
+    let token = test_project.normals[0].token.clone();
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let effective = client
+        .flavor_price
+        .effective()
+        .flavor(flavor.id + 1)
+        .send()
+        .await;
+
+    // assert
+    assert!(effective.is_err());
+    assert_eq!(
+        effective.unwrap_err().to_string(),
+        "Resource not found".to_string()
+    );
+}
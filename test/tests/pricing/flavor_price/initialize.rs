@@ -0,0 +1,113 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_test::spawn_app;
+use avina_wire::user::UserClass;
+
+#[tokio::test]
+async fn e2e_lib_flavor_price_initialize_denies_access_to_normal_user() {
+    // arrange
+    let server = spawn_app().await;
+    let test_project = server
+        .setup_test_project(0, 0, 1)
+        .await
+        .expect("Failed to setup test project");
+    let user = test_project.normals[0].user.clone();
+    let token = test_project.normals[0].token.clone();
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let initialize = client.flavor_price.initialize().await;
+
+    // assert
+    assert!(initialize.is_err());
+    assert_eq!(
+        initialize.unwrap_err().to_string(),
+        "Admin privileges required".to_string()
+    );
+}
+
+#[tokio::test]
+async fn e2e_lib_flavor_price_initialize_creates_missing_prices() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor1 = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let flavor2 = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let flavor_price = server
+        .setup_test_flavor_price(&flavor1, UserClass::UC1)
+        .await
+        .expect("Failed to setup test flavor price");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let first = client.flavor_price.initialize().await.unwrap();
+    let second = client.flavor_price.initialize().await.unwrap();
+    let prices = client.flavor_price.list().send().await.unwrap();
+    let entries = client
+        .audit
+        .list()
+        .actor(user.id)
+        .resource_type("flavorprices".to_string())
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    // NOTE: 2 flavors times 7 user classes, one price already existed
+    assert_eq!(first.new_flavor_price_count, 13);
+    assert_eq!(second.new_flavor_price_count, 0);
+    assert_eq!(prices.len(), 14);
+    for flavor in [&flavor1, &flavor2] {
+        assert_eq!(prices.iter().filter(|p| p.flavor == flavor.id).count(), 7);
+    }
+    assert!(prices.contains(&flavor_price));
+    assert!(
+        prices
+            .iter()
+            .filter(|p| p.id != flavor_price.id)
+            .all(|p| p.unit_price == 0.)
+    );
+    assert_eq!(
+        entries
+            .iter()
+            .filter(
+                |e| e.method == "POST" && e.endpoint.ends_with("/initialize/")
+            )
+            .count(),
+        2
+    );
+}
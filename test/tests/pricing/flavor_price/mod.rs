@@ -1 +1,5 @@
+mod bulk;
 mod delete;
+mod effective;
+mod initialize;
+mod timeline;
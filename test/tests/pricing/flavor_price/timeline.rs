@@ -0,0 +1,118 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_api::database::pricing::flavor_price::NewFlavorPrice;
+use avina_test::spawn_app;
+use avina_wire::user::UserClass;
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+
+#[tokio::test]
+async fn e2e_lib_flavor_price_timeline_returns_price_periods() {
+    // arrange
+    let server = spawn_app().await;
+    let test_project = server
+        .setup_test_project(0, 0, 1)
+        .await
+        .expect("Failed to setup test project");
+    let user = test_project.normals[0].user.clone();
+    let token = test_project.normals[0].token.clone();
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    for (unit_price, month) in [(100., 1), (200., 6)] {
+        server
+            .setup_test_flavor_price_with_new_flavor_price(
+                &flavor,
+                NewFlavorPrice {
+                    flavor_id: flavor.id as u64,
+                    user_class: UserClass::UC1,
+                    unit_price,
+                    start_time: Utc
+                        .with_ymd_and_hms(2025, month, 1, 0, 0, 0)
+                        .unwrap(),
+                },
+            )
+            .await
+            .expect("Failed to setup test flavor price");
+    }
+    let begin = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    let change = Utc.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap();
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let timeline = client
+        .flavor_price
+        .timeline()
+        .begin(begin.into())
+        .end(end.into())
+        .flavor(flavor.id)
+        .user_class(UserClass::UC1)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(timeline.len(), 2);
+    assert_eq!(timeline[0].flavor, flavor.id);
+    assert_eq!(timeline[0].unit_price, 100.);
+    assert_eq!(timeline[0].begin, DateTime::<FixedOffset>::from(begin));
+    assert_eq!(timeline[0].end, DateTime::<FixedOffset>::from(change));
+    assert_eq!(timeline[1].unit_price, 200.);
+    assert_eq!(timeline[1].begin, DateTime::<FixedOffset>::from(change));
+    assert_eq!(timeline[1].end, DateTime::<FixedOffset>::from(end));
+}
+
+#[tokio::test]
+async fn e2e_lib_flavor_price_timeline_rejects_begin_after_end() {
+    // arrange
+    let server = spawn_app().await;
+    let test_project = server
+        .setup_test_project(0, 0, 1)
+        .await
+        .expect("Failed to setup test project");
+    let user = test_project.normals[0].user.clone();
+    let token = test_project.normals[0].token.clone();
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let timeline = client
+        .flavor_price
+        .timeline()
+        .begin(Utc.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap().into())
+        .end(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap().into())
+        .send()
+        .await;
+
+    // assert
+    assert!(timeline.is_err());
+    assert_eq!(
+        timeline.unwrap_err().to_string(),
+        "Begin must not be after end".to_string()
+    );
+}
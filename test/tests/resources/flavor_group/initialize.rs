@@ -0,0 +1,112 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_api::database::resources::flavor::insert_flavor_into_db;
+use avina_test::{random_alphanumeric_string, random_uuid, spawn_app};
+use avina_wire::resources::FlavorCreateData;
+
+#[tokio::test]
+async fn e2e_lib_flavor_group_initialize_denies_access_to_normal_user() {
+    // arrange
+    let server = spawn_app().await;
+    let test_project = server
+        .setup_test_project(0, 0, 1)
+        .await
+        .expect("Failed to setup test project");
+    let user = test_project.normals[0].user.clone();
+    let token = test_project.normals[0].token.clone();
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let initialize = client.flavor_group.initialize().await;
+
+    // assert
+    assert!(initialize.is_err());
+    assert_eq!(
+        initialize.unwrap_err().to_string(),
+        "Admin privileges required".to_string()
+    );
+}
+
+#[tokio::test]
+async fn e2e_lib_flavor_group_initialize_groups_flavors_by_name() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let prefix1 = random_alphanumeric_string(10);
+    let prefix2 = random_alphanumeric_string(10);
+    let mut transaction = server
+        .db_pool
+        .begin()
+        .await
+        .expect("Failed to begin transaction");
+    for name in [
+        format!("{prefix1}.small"),
+        format!("{prefix1}.large"),
+        format!("{prefix2}.1"),
+        random_alphanumeric_string(10),
+    ] {
+        let data = FlavorCreateData {
+            name,
+            openstack_id: random_uuid(),
+            group: None,
+            weight: None,
+        };
+        insert_flavor_into_db(&mut transaction, &data)
+            .await
+            .expect("Failed to insert flavor");
+    }
+    transaction
+        .commit()
+        .await
+        .expect("Failed to commit transaction");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let first = client.flavor_group.initialize().await.unwrap();
+    let second = client.flavor_group.initialize().await.unwrap();
+    let groups = client.flavor_group.list().all().send().await.unwrap();
+    let flavors = client.flavor.list().all().send().await.unwrap();
+
+    // assert
+    assert_eq!(first.new_flavor_group_count, 2);
+    assert_eq!(first.new_flavor_count, 3);
+    assert_eq!(second.new_flavor_group_count, 0);
+    assert_eq!(second.new_flavor_count, 0);
+    assert_eq!(groups.len(), 2);
+    for flavor in flavors {
+        match flavor.name.rsplit_once('.') {
+            Some((group_name, _)) => {
+                assert_eq!(flavor.group_name, Some(group_name.to_string()))
+            }
+            None => assert_eq!(flavor.group, None),
+        }
+    }
+}
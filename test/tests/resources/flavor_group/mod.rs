@@ -1,2 +1,3 @@
 mod delete;
+mod initialize;
 mod modify;
@@ -0,0 +1,95 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_test::spawn_app;
+use avina_wire::scheduler::{JobName, JobRunStatus};
+
+#[tokio::test]
+async fn e2e_lib_scheduler_job_run_denies_access_to_normal_user() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let run = client.scheduler.run(JobName::UserBudgetSync).await;
+    let runs = client.scheduler.runs().send().await;
+
+    // assert
+    assert!(run.is_err());
+    assert_eq!(run.unwrap_err().to_string(), "Admin privileges required");
+    assert!(runs.is_err());
+    assert_eq!(runs.unwrap_err().to_string(), "Admin privileges required");
+}
+
+#[tokio::test]
+async fn e2e_lib_scheduler_job_run_is_recorded_in_history() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let run = client.scheduler.run(JobName::UserBudgetSync).await.unwrap();
+    let runs = client
+        .scheduler
+        .runs()
+        .job(JobName::UserBudgetSync)
+        .send()
+        .await
+        .unwrap();
+    let other_runs = client
+        .scheduler
+        .runs()
+        .job(JobName::FlavorImport)
+        .send()
+        .await
+        .unwrap();
+    let jobs = client.scheduler.jobs().await.unwrap();
+
+    // assert
+    assert_eq!(run.job, JobName::UserBudgetSync);
+    assert_eq!(run.status, JobRunStatus::Succeeded);
+    assert_eq!(run.triggered_by, Some(user.id));
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0].id, run.id);
+    assert_eq!(runs[0].status, JobRunStatus::Succeeded);
+    assert_eq!(runs[0].message, run.message);
+    assert!(other_runs.is_empty());
+    let job = jobs
+        .into_iter()
+        .find(|j| j.name == JobName::UserBudgetSync)
+        .unwrap();
+    assert_eq!(job.last_status, Some(JobRunStatus::Succeeded));
+    assert_eq!(job.schedule, None);
+}
@@ -0,0 +1,90 @@
+use avina::{Api, Token};
+use avina_test::{random_uuid, spawn_app};
+use chrono::DateTime;
+use serde_json::json;
+use wiremock::{
+    Mock, ResponseTemplate,
+    matchers::{method, path},
+};
+
+#[tokio::test]
+async fn e2e_lib_token_from_application_credential_parses_expiry() {
+    // arrange
+    let server = spawn_app().await;
+    let expires_at = "2100-01-01T00:00:00.000000Z";
+    Mock::given(method("POST"))
+        .and(path("/auth/tokens/"))
+        .respond_with(
+            ResponseTemplate::new(201)
+                .append_header("X-Subject-Token", random_uuid())
+                .set_body_json(json!({"token": {"expires_at": expires_at}})),
+        )
+        .with_priority(1)
+        .mount(&server.keystone_server)
+        .await;
+
+    // act
+    let token = Token::from_application_credential_id(
+        &server.keystone_server.uri(),
+        "application_credential_id",
+        "application_credential_secret",
+    )
+    .await
+    .unwrap();
+
+    // assert
+    assert!(token.is_renewable());
+    assert!(!token.is_expired());
+    assert_eq!(
+        token.expires_at(),
+        Some(DateTime::parse_from_rfc3339(expires_at).unwrap())
+    );
+}
+
+#[tokio::test]
+async fn e2e_lib_api_renews_token_rejected_as_unauthorized() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, _token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(
+            &server.keystone_token,
+            &user.openstack_id,
+            &user.name,
+        )
+        .mount(&server.keystone_server)
+        .await;
+    // NOTE: the first token handed out is unknown to the server, the renewed
+    // one is the regular keystone token that is valid for the user.
+    Mock::given(method("POST"))
+        .and(path("/auth/tokens/"))
+        .respond_with(
+            ResponseTemplate::new(201)
+                .append_header("X-Subject-Token", random_uuid()),
+        )
+        .with_priority(1)
+        .up_to_n_times(1)
+        .mount(&server.keystone_server)
+        .await;
+    let token = Token::from_application_credential_id(
+        &server.keystone_server.uri(),
+        "application_credential_id",
+        "application_credential_secret",
+    )
+    .await
+    .unwrap();
+    assert_ne!(token.as_ref(), server.keystone_token);
+
+    // arrange
+    let client =
+        Api::new(format!("{}/api", server.address), token, None, None).unwrap();
+
+    // act
+    let me = client.user.me().await.unwrap();
+
+    // assert
+    assert_eq!(me.id, user.id);
+}
@@ -0,0 +1,170 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_test::{random_alphanumeric_string, spawn_app};
+use avina_wire::user::ApiKeyScope;
+
+#[tokio::test]
+async fn e2e_lib_api_key_authenticates_as_its_user() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    let created = client
+        .api_key
+        .create(random_alphanumeric_string(10), vec![ApiKeyScope::User])
+        .send()
+        .await
+        .unwrap();
+
+    // arrange
+    let key_client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&created.key).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let me = key_client.user.me().await.unwrap();
+
+    // assert
+    assert_eq!(me.id, user.id);
+    assert_eq!(me.name, user.name);
+    let api_key = client.api_key.get(created.api_key.id).await.unwrap();
+    assert!(api_key.last_used_at.is_some());
+}
+
+#[tokio::test]
+async fn e2e_lib_api_key_is_rejected_outside_its_scopes() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    let created = client
+        .api_key
+        .create(
+            random_alphanumeric_string(10),
+            vec![ApiKeyScope::Accounting],
+        )
+        .send()
+        .await
+        .unwrap();
+
+    // arrange
+    let key_client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&created.key).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let me = key_client.user.me().await;
+
+    // assert
+    assert!(me.is_err());
+    assert_eq!(
+        me.unwrap_err().to_string(),
+        "API key is not valid for this endpoint"
+    );
+}
+
+#[tokio::test]
+async fn e2e_lib_api_key_cannot_manage_api_keys() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    let created = client
+        .api_key
+        .create(random_alphanumeric_string(10), vec![ApiKeyScope::User])
+        .send()
+        .await
+        .unwrap();
+
+    // arrange
+    let key_client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&created.key).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let create = key_client
+        .api_key
+        .create(random_alphanumeric_string(10), vec![ApiKeyScope::User])
+        .send()
+        .await;
+
+    // assert
+    assert!(create.is_err());
+    assert_eq!(
+        create.unwrap_err().to_string(),
+        "API keys cannot be used to manage API keys"
+    );
+}
+
+#[tokio::test]
+async fn e2e_lib_unknown_api_key_is_rejected() {
+    // arrange
+    let server = spawn_app().await;
+    let key_client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&format!("avina_{}", random_alphanumeric_string(40)))
+            .unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let me = key_client.user.me().await;
+
+    // assert
+    assert!(me.is_err());
+    assert_eq!(me.unwrap_err().to_string(), "Invalid API key");
+}
@@ -0,0 +1,127 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_test::{random_alphanumeric_string, spawn_app};
+use avina_wire::user::{API_KEY_PREFIX, ApiKeyScope};
+
+#[tokio::test]
+async fn e2e_lib_api_key_create_works_for_own_user() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    let name = random_alphanumeric_string(10);
+
+    // act
+    let created = client
+        .api_key
+        .create(name.clone(), vec![ApiKeyScope::User])
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert!(created.key.starts_with(API_KEY_PREFIX));
+    assert!(created.key.starts_with(&created.api_key.prefix));
+    assert_eq!(created.api_key.name, name);
+    assert_eq!(created.api_key.scopes, vec![ApiKeyScope::User]);
+    assert_eq!(created.api_key.user, user.id);
+    assert_eq!(created.api_key.username, user.name);
+    assert!(created.api_key.expires_at.is_none());
+    let fetched = client.api_key.get(created.api_key.id).await.unwrap();
+    assert_eq!(fetched, created.api_key);
+}
+
+#[tokio::test]
+async fn e2e_lib_api_key_create_denies_other_user_to_normal_user() {
+    // arrange
+    let server = spawn_app().await;
+    let test_project = server
+        .setup_test_project(0, 0, 2)
+        .await
+        .expect("Failed to setup test project");
+    let user = test_project.normals[0].user.clone();
+    let token = test_project.normals[0].token.clone();
+    let other = test_project.normals[1].user.clone();
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let create = client
+        .api_key
+        .create(random_alphanumeric_string(10), vec![ApiKeyScope::User])
+        .user(other.id)
+        .send()
+        .await;
+
+    // assert
+    assert!(create.is_err());
+    assert_eq!(create.unwrap_err().to_string(), "Admin privileges required");
+}
+
+#[tokio::test]
+async fn e2e_lib_api_key_create_works_for_other_user_as_admin() {
+    // arrange
+    let server = spawn_app().await;
+    let (admin, _project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    let test_project = server
+        .setup_test_project(0, 0, 1)
+        .await
+        .expect("Failed to setup test project");
+    let user = test_project.normals[0].user.clone();
+    server
+        .mock_keystone_auth(&token, &admin.openstack_id, &admin.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let created = client
+        .api_key
+        .create(random_alphanumeric_string(10), vec![ApiKeyScope::Budgeting])
+        .user(user.id)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(created.api_key.user, user.id);
+    assert_eq!(created.api_key.username, user.name);
+}
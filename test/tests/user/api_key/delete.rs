@@ -0,0 +1,102 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_test::{random_alphanumeric_string, spawn_app};
+use avina_wire::user::ApiKeyScope;
+
+#[tokio::test]
+async fn e2e_lib_api_key_delete_revokes_key() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    let created = client
+        .api_key
+        .create(random_alphanumeric_string(10), vec![ApiKeyScope::User])
+        .send()
+        .await
+        .unwrap();
+    let key_client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&created.key).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    assert!(key_client.user.me().await.is_ok());
+
+    // act
+    client.api_key.delete(created.api_key.id).await.unwrap();
+
+    // assert
+    let me = key_client.user.me().await;
+    assert!(me.is_err());
+    assert_eq!(me.unwrap_err().to_string(), "Invalid API key");
+    assert!(client.api_key.get(created.api_key.id).await.is_err());
+}
+
+#[tokio::test]
+async fn e2e_lib_api_key_delete_denies_foreign_key_to_normal_user() {
+    // arrange
+    let server = spawn_app().await;
+    let test_project = server
+        .setup_test_project(0, 0, 2)
+        .await
+        .expect("Failed to setup test project");
+    let user1 = test_project.normals[0].user.clone();
+    let token1 = test_project.normals[0].token.clone();
+    let user2 = test_project.normals[1].user.clone();
+    let token2 = test_project.normals[1].token.clone();
+    server
+        .mock_keystone_auth(&token1, &user1.openstack_id, &user1.name)
+        .mount(&server.keystone_server)
+        .await;
+    server
+        .mock_keystone_auth(&token2, &user2.openstack_id, &user2.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client1 = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token1).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    let client2 = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token2).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    let created = client1
+        .api_key
+        .create(random_alphanumeric_string(10), vec![ApiKeyScope::User])
+        .send()
+        .await
+        .unwrap();
+
+    // act
+    let delete = client2.api_key.delete(created.api_key.id).await;
+
+    // assert
+    assert!(delete.is_err());
+    assert_eq!(delete.unwrap_err().to_string(), "Resource not found");
+}
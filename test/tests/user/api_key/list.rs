@@ -0,0 +1,74 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_test::{random_alphanumeric_string, spawn_app};
+use avina_wire::user::ApiKeyScope;
+
+#[tokio::test]
+async fn e2e_lib_api_key_list_returns_own_keys() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    let created = client
+        .api_key
+        .create(
+            random_alphanumeric_string(10),
+            vec![ApiKeyScope::Accounting],
+        )
+        .send()
+        .await
+        .unwrap();
+
+    // act
+    let api_keys = client.api_key.list().send().await.unwrap();
+
+    // assert
+    assert_eq!(api_keys.len(), 1);
+    assert_eq!(api_keys[0], created.api_key);
+}
+
+#[tokio::test]
+async fn e2e_lib_api_key_list_all_denies_access_to_normal_user() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let list = client.api_key.list().all().send().await;
+
+    // assert
+    assert!(list.is_err());
+    assert_eq!(list.unwrap_err().to_string(), "Admin privileges required");
+}
@@ -0,0 +1,4 @@
+mod authentication;
+mod create;
+mod delete;
+mod list;
@@ -39,3 +39,77 @@ async fn e2e_lib_user_me_works() {
     assert_eq!(me.project.name, project.name);
     assert_eq!(me.project_name, project.name);
 }
+
+#[tokio::test]
+async fn e2e_lib_user_me_returns_impersonated_user_for_admin() {
+    // arrange
+    let server = spawn_app().await;
+    let (admin, _admin_project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    let test_project = server
+        .setup_test_project(0, 0, 1)
+        .await
+        .expect("Failed to setup test project");
+    let user = test_project.normals[0].user.clone();
+    server
+        .mock_keystone_auth(&token, &admin.openstack_id, &admin.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        Some(user.id),
+        None,
+    )
+    .unwrap();
+
+    // act
+    let me = client.user.me().await.unwrap();
+
+    // assert
+    assert_eq!(me.id, user.id);
+    assert_eq!(me.name, user.name);
+    assert_eq!(me.is_staff, user.is_staff);
+    assert_eq!(me.project.id, test_project.project.id);
+    assert_eq!(me.project.name, test_project.project.name);
+}
+
+#[tokio::test]
+async fn e2e_lib_user_me_denies_impersonation_to_normal_user() {
+    // arrange
+    let server = spawn_app().await;
+    let test_project = server
+        .setup_test_project(0, 0, 2)
+        .await
+        .expect("Failed to setup test project");
+    let user = test_project.normals[0].user.clone();
+    let token = test_project.normals[0].token.clone();
+    let other = test_project.normals[1].user.clone();
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        Some(other.id),
+        None,
+    )
+    .unwrap();
+
+    // act
+    let me = client.user.me().await;
+
+    // assert
+    assert!(me.is_err());
+    assert_eq!(
+        me.unwrap_err().to_string(),
+        "Admin privileges required for impersonation"
+    );
+}
@@ -1,5 +1,6 @@
+mod api_key;
 mod me;
 mod project;
+mod project_class_change;
 #[allow(clippy::module_inception)]
 mod user;
-// TODO: test that non-existent ids return 404
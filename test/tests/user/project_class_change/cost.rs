@@ -0,0 +1,226 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_api::database::{
+    accounting::server_state::NewServerState,
+    pricing::flavor_price::NewFlavorPrice,
+};
+use avina_test::{
+    keystone_domain, keystone_project, random_alphanumeric_string, spawn_app,
+};
+use avina_wire::user::UserClass;
+use chrono::{TimeDelta, TimeZone, Utc};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn e2e_lib_server_cost_applies_user_class_in_effect() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    let begin = Utc.with_ymd_and_hms(2025, 3, 10, 0, 0, 0).unwrap();
+    let end = begin + TimeDelta::days(4);
+    // NOTE: these are prices per year, so one day costs 1 or 2.
+    for (user_class, unit_price) in
+        [(UserClass::UC1, 365.), (UserClass::UC2, 730.)]
+    {
+        server
+            .setup_test_flavor_price_with_new_flavor_price(
+                &flavor,
+                NewFlavorPrice {
+                    flavor_id: flavor.id as u64,
+                    user_class,
+                    unit_price,
+                    start_time: Utc
+                        .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+                        .unwrap(),
+                },
+            )
+            .await
+            .expect("Failed to setup test flavor price");
+    }
+    let server_uuid = Uuid::new_v4();
+    server
+        .setup_test_server_state_with_server_state(
+            &flavor,
+            &user,
+            NewServerState {
+                begin,
+                end: Some(end),
+                instance_id: server_uuid,
+                instance_name: random_alphanumeric_string(10),
+                flavor: flavor.id,
+                status: "ACTIVE".to_string(),
+                user: user.id,
+            },
+        )
+        .await
+        .expect("Failed to setup test server state");
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    for (user_class, start_time) in [
+        (
+            UserClass::UC1,
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+        ),
+        (UserClass::UC2, begin + TimeDelta::days(2)),
+    ] {
+        client
+            .project_class_change
+            .create(project.id, user_class, start_time.fixed_offset())
+            .send()
+            .await
+            .unwrap();
+    }
+
+    // act
+    let server_cost = client
+        .server_cost
+        .get()
+        .begin(begin.fixed_offset())
+        .end(end.fixed_offset())
+        .server(server_uuid)
+        .await
+        .unwrap();
+    let project_cost = client
+        .server_cost
+        .get()
+        .begin(begin.fixed_offset())
+        .end(end.fixed_offset())
+        .project(project.id)
+        .await
+        .unwrap();
+
+    // assert
+    // NOTE: two days in user class 1 and two days in user class 2.
+    assert!((server_cost.total - 6.).abs() < 1e-6);
+    assert!((project_cost.total - 6.).abs() < 1e-6);
+}
+
+#[tokio::test]
+async fn e2e_lib_server_cost_keeps_user_class_of_imported_project() {
+    // arrange
+    let server = spawn_app().await;
+    let (admin, _admin_project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &admin.openstack_id, &admin.name)
+        .mount(&server.keystone_server)
+        .await;
+    let domain_id = Uuid::new_v4().simple().to_string();
+    let domain_name = random_alphanumeric_string(10);
+    let user_name = random_alphanumeric_string(10);
+    server
+        .mock_keystone_domains(vec![keystone_domain(&domain_id, &domain_name)])
+        .mount(&server.keystone_server)
+        .await;
+    server
+        .mock_keystone_projects(vec![keystone_project(
+            &Uuid::new_v4().simple().to_string(),
+            &user_name,
+            &domain_id,
+        )])
+        .mount(&server.keystone_server)
+        .await;
+    let flavor = server
+        .setup_test_flavor()
+        .await
+        .expect("Failed to setup test flavor");
+    // NOTE: these are prices per year, so one day costs 1 or 2.
+    for (user_class, unit_price) in
+        [(UserClass::NA, 365.), (UserClass::UC1, 730.)]
+    {
+        server
+            .setup_test_flavor_price_with_new_flavor_price(
+                &flavor,
+                NewFlavorPrice {
+                    flavor_id: flavor.id as u64,
+                    user_class,
+                    unit_price,
+                    start_time: Utc
+                        .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+                        .unwrap(),
+                },
+            )
+            .await
+            .expect("Failed to setup test flavor price");
+    }
+    let begin = Utc.with_ymd_and_hms(2025, 3, 10, 0, 0, 0).unwrap();
+    let end = begin + TimeDelta::days(4);
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+    client.user.import().await.unwrap();
+    let user = client
+        .user
+        .list()
+        .all()
+        .send()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|user| user.name == user_name)
+        .expect("Failed to find imported user");
+    server
+        .setup_test_server_state_with_server_state(
+            &flavor,
+            &user,
+            NewServerState {
+                begin,
+                end: Some(end),
+                instance_id: Uuid::new_v4(),
+                instance_name: random_alphanumeric_string(10),
+                flavor: flavor.id,
+                status: "ACTIVE".to_string(),
+                user: user.id,
+            },
+        )
+        .await
+        .expect("Failed to setup test server state");
+    client
+        .project
+        .modify(user.project)
+        .user_class(UserClass::UC1)
+        .send()
+        .await
+        .unwrap();
+
+    // act
+    let project_cost = client
+        .server_cost
+        .get()
+        .begin(begin.fixed_offset())
+        .end(end.fixed_offset())
+        .project(user.project)
+        .await
+        .unwrap();
+
+    // assert
+    // NOTE: the usage happened while the project was still in class NA.
+    assert!((project_cost.total - 4.).abs() < 1e-6);
+}
@@ -0,0 +1,108 @@
+use std::str::FromStr;
+
+use avina::{Api, Token};
+use avina_test::spawn_app;
+use avina_wire::user::UserClass;
+use chrono::{DateTime, TimeDelta, Utc};
+
+#[tokio::test]
+async fn e2e_lib_project_class_change_list_denies_access_to_other_project() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, _project, token) = server
+        .setup_test_user_and_project(false)
+        .await
+        .expect("Failed to setup test user and project.");
+    let other_project = server
+        .setup_test_project(0, 0, 1)
+        .await
+        .expect("Failed to setup test project");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    let list = client
+        .project_class_change
+        .list()
+        .project(other_project.project.id)
+        .send()
+        .await;
+
+    // assert
+    assert!(list.is_err());
+}
+
+#[tokio::test]
+async fn e2e_lib_project_modify_records_project_class_changes() {
+    // arrange
+    let server = spawn_app().await;
+    let (user, project, token) = server
+        .setup_test_user_and_project(true)
+        .await
+        .expect("Failed to setup test user and project.");
+    server
+        .mock_keystone_auth(&token, &user.openstack_id, &user.name)
+        .mount(&server.keystone_server)
+        .await;
+    let first_class = if project.user_class == UserClass::UC1 {
+        UserClass::UC2
+    } else {
+        UserClass::UC1
+    };
+    let second_class = UserClass::UC3;
+
+    // arrange
+    let client = Api::new(
+        format!("{}/api", server.address),
+        Token::from_str(&token).unwrap(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    // act
+    client
+        .project
+        .modify(project.id)
+        .user_class(first_class)
+        .send()
+        .await
+        .unwrap();
+    client
+        .project
+        .modify(project.id)
+        .user_class(second_class)
+        .send()
+        .await
+        .unwrap();
+    let changes = client
+        .project_class_change
+        .list()
+        .project(project.id)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    // NOTE: the project has no history yet, so its first class applies from
+    // the beginning.
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].project, project.id);
+    assert_eq!(changes[0].user_class, first_class);
+    assert_eq!(changes[0].start_time, DateTime::UNIX_EPOCH.fixed_offset());
+    assert_eq!(changes[1].user_class, second_class);
+    assert!(
+        Utc::now() - changes[1].start_time.to_utc() < TimeDelta::minutes(1)
+    );
+}
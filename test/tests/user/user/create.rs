@@ -2,8 +2,7 @@ use std::str::FromStr;
 
 use avina::{Api, Token};
 use avina_test::{
-    random_alphanumeric_string, random_bool, random_number, random_uuid,
-    spawn_app,
+    random_alphanumeric_string, random_bool, random_uuid, spawn_app,
 };
 
 #[tokio::test]
@@ -115,7 +114,7 @@ async fn e2e_lib_user_create_works() {
     // act
     let name = random_alphanumeric_string(10);
     let openstack_id = random_uuid();
-    let role = random_number(0..3);
+    let role = rand::random();
     let is_staff = random_bool();
     let is_active = random_bool();
     let mut request =
@@ -167,7 +166,7 @@ async fn e2e_lib_user_create_twice_returns_bad_request() {
     // act and assert 1 - create
     let name = random_alphanumeric_string(10);
     let openstack_id = random_uuid();
-    let role = random_number(0..3);
+    let role = rand::random();
     let is_staff = random_bool();
     let is_active = random_bool();
     let mut request =
@@ -233,7 +232,7 @@ async fn e2e_lib_user_create_and_get_works() {
     // act and assert 1 - create
     let name = random_alphanumeric_string(10);
     let openstack_id = random_uuid();
-    let role = random_number(0..3);
+    let role = rand::random();
     let is_staff = random_bool();
     let is_active = random_bool();
     let mut request =
@@ -294,7 +293,7 @@ async fn e2e_lib_user_create_and_list_works() {
     // act and assert 1 - create
     let name = random_alphanumeric_string(10);
     let openstack_id = random_uuid();
-    let role = random_number(0..3);
+    let role = rand::random();
     let is_staff = random_bool();
     let is_active = random_bool();
     let mut request =
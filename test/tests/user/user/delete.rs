@@ -2,8 +2,7 @@ use std::str::FromStr;
 
 use avina::{Api, Token};
 use avina_test::{
-    random_alphanumeric_string, random_bool, random_number, random_uuid,
-    spawn_app,
+    random_alphanumeric_string, random_bool, random_uuid, spawn_app,
 };
 
 #[tokio::test]
@@ -99,7 +98,7 @@ async fn e2e_lib_user_create_get_delete_get_works() {
     // act and assert 1 - create
     let name = random_alphanumeric_string(10);
     let openstack_id = random_uuid();
-    let role = random_number(0..3);
+    let role = rand::random();
     let is_staff = random_bool();
     let is_active = random_bool();
     let mut request =
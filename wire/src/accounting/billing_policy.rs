@@ -0,0 +1,68 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "tabled")]
+use tabled::Tabled;
+
+/// Fraction of the flavor price billed while a server is in the status,
+/// from the start time until the next policy for the same status.
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct BillingPolicy {
+    pub id: u32,
+    pub status: String,
+    pub multiplier: f64,
+    pub start_time: DateTime<FixedOffset>,
+}
+
+impl Display for BillingPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!(
+            "BillingPolicy(id={}, status={})",
+            self.id, self.status
+        ))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BillingPolicyCreateData {
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiplier: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<DateTime<FixedOffset>>,
+}
+
+impl BillingPolicyCreateData {
+    pub fn new(status: String) -> Self {
+        Self {
+            status,
+            multiplier: None,
+            start_time: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BillingPolicyModifyData {
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiplier: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<DateTime<FixedOffset>>,
+}
+
+impl BillingPolicyModifyData {
+    pub fn new(id: u32) -> Self {
+        Self {
+            id,
+            status: None,
+            multiplier: None,
+            start_time: None,
+        }
+    }
+}
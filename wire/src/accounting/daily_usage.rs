@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "tabled")]
+use tabled::Tabled;
+
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct DailyUsageBackfill {
+    /// Closed days that were not aggregated before, including the ones
+    /// without any usage.
+    pub aggregated_day_count: u32,
+    /// Seconds per day, server, flavor, user and status that were stored.
+    pub daily_usage_count: u32,
+}
@@ -0,0 +1,53 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "tabled")]
+use tabled::Tabled;
+use uuid::Uuid;
+
+#[cfg(feature = "tabled")]
+use crate::common::display_option;
+
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct FloatingIpState {
+    pub id: u32,
+    pub begin: DateTime<FixedOffset>,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub end: Option<DateTime<FixedOffset>>,
+    pub floating_ip_id: Uuid,
+    pub floating_ip_address: String,
+    /// Name of the external network the address is allocated from.
+    pub pool: String,
+    pub user: u32,
+    pub username: String,
+}
+
+impl Display for FloatingIpState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!("FloatingIpState(id={})", self.id))
+    }
+}
+
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct FloatingIpStateImport {
+    pub new_state_count: u32,
+    pub end_state_count: u32,
+    /// Floating IPs whose owner is not known, which are not accounted.
+    pub skipped_floating_ip_count: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FloatingIpStateImportParams {
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FloatingIpStateListParams {
+    pub floating_ip: Option<Uuid>,
+    pub user: Option<u32>,
+    pub project: Option<u32>,
+    pub all: Option<bool>,
+}
@@ -1,7 +1,21 @@
+mod billing_policy;
+mod daily_usage;
+mod floating_ip_state;
+mod series;
 mod server_consumption;
 mod server_cost;
 mod server_state;
+mod unmatched_server;
+mod volume_cost;
+mod volume_state;
 
+pub use billing_policy::*;
+pub use daily_usage::*;
+pub use floating_ip_state::*;
+pub use series::*;
 pub use server_consumption::*;
 pub use server_cost::*;
 pub use server_state::*;
+pub use unmatched_server::*;
+pub use volume_cost::*;
+pub use volume_state::*;
@@ -4,6 +4,8 @@ use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::accounting::Granularity;
+
 pub type ServerConsumptionFlavors = HashMap<String, f64>;
 
 pub type ServerConsumptionServer = ServerConsumptionFlavors;
@@ -35,4 +37,5 @@ pub struct ServerConsumptionParams {
     pub project: Option<u32>,
     pub all: Option<bool>,
     pub detail: Option<bool>,
+    pub granularity: Option<Granularity>,
 }
@@ -6,36 +6,47 @@ use serde::{Deserialize, Serialize};
 use tabled::Tabled;
 use uuid::Uuid;
 
+use crate::accounting::Granularity;
+
 #[cfg_attr(feature = "tabled", derive(Tabled))]
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct ServerCostSimple {
     pub total: f64,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct ServerCostServer {
     pub total: f64,
     pub flavors: HashMap<String, f64>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct ServerCostUser {
     pub total: f64,
     pub flavors: HashMap<String, f64>,
+    /// Cost of the floating IPs per pool.
+    #[serde(default)]
+    pub floating_ips: HashMap<String, f64>,
     pub servers: HashMap<Uuid, ServerCostServer>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct ServerCostProject {
     pub total: f64,
     pub flavors: HashMap<String, f64>,
+    /// Cost of the floating IPs per pool.
+    #[serde(default)]
+    pub floating_ips: HashMap<String, f64>,
     pub users: HashMap<String, ServerCostUser>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct ServerCostAll {
     pub total: f64,
     pub flavors: HashMap<String, f64>,
+    /// Cost of the floating IPs per pool.
+    #[serde(default)]
+    pub floating_ips: HashMap<String, f64>,
     pub projects: HashMap<String, ServerCostProject>,
 }
 
@@ -48,4 +59,5 @@ pub struct ServerCostParams {
     pub project: Option<u32>,
     pub all: Option<bool>,
     pub detail: Option<bool>,
+    pub granularity: Option<Granularity>,
 }
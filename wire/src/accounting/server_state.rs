@@ -38,6 +38,94 @@ pub struct ServerStateImport {
     pub end_state_count: u32,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ServerStateImportParams {
+    pub dry_run: Option<bool>,
+}
+
+/// Server that gets a new state, either because it is new or because its
+/// status, flavor, name or user changed.
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ServerStateImportNewState {
+    pub instance_id: Uuid,
+    pub instance_name: String,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub old_status: Option<String>,
+    pub new_status: String,
+    /// Attributes that changed, empty for new servers.
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub changes: Option<String>,
+}
+
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ServerStateImportEndedState {
+    pub id: u32,
+    pub instance_id: Uuid,
+    pub instance_name: String,
+    pub status: String,
+}
+
+/// Server for which no state can be created, because its flavor or user is
+/// unknown.
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ServerStateImportSkippedServer {
+    pub instance_id: Uuid,
+    pub instance_name: String,
+    pub reason: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ServerStateImportDiff {
+    pub new_states: Vec<ServerStateImportNewState>,
+    pub ended_states: Vec<ServerStateImportEndedState>,
+    pub skipped_servers: Vec<ServerStateImportSkippedServer>,
+}
+
+impl From<&ServerStateImportDiff> for ServerStateImport {
+    fn from(diff: &ServerStateImportDiff) -> Self {
+        Self {
+            new_state_count: diff.new_states.len() as u32,
+            end_state_count: diff.ended_states.len() as u32,
+        }
+    }
+}
+
+/// State of a server within its timeline, together with how long it lasted
+/// and what it cost.
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ServerTimelineSegment {
+    pub state: u32,
+    pub begin: DateTime<FixedOffset>,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub end: Option<DateTime<FixedOffset>>,
+    pub instance_name: String,
+    pub flavor: u32,
+    pub flavor_name: String,
+    pub status: String,
+    pub user: u32,
+    pub username: String,
+    /// Seconds from begin to end, or until now for the current state.
+    pub duration: u64,
+    pub cost: f64,
+}
+
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ServerStateCompaction {
+    /// Adjacent states with the same server, name, flavor, status and user
+    /// that were merged into their predecessor.
+    pub merged_state_count: u32,
+    /// States of closed fiscal years that were archived into summaries.
+    pub archived_state_count: u32,
+    /// Last fiscal year that is archived, empty without a retention policy.
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub archived_until: Option<u32>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ServerStateListParams {
     pub server: Option<Uuid>,
@@ -0,0 +1,103 @@
+use std::{fmt::Display, str::FromStr};
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "tabled")]
+use tabled::Tabled;
+use uuid::Uuid;
+
+#[cfg(feature = "tabled")]
+use crate::common::display_option;
+use crate::error::ConversionError;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UnmatchedServerResolution {
+    /// Billed with the flavor and user it was mapped to.
+    Mapped,
+    /// Deliberately not billed.
+    Ignored,
+}
+
+impl UnmatchedServerResolution {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnmatchedServerResolution::Mapped => "mapped",
+            UnmatchedServerResolution::Ignored => "ignored",
+        }
+    }
+}
+
+impl Display for UnmatchedServerResolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for UnmatchedServerResolution {
+    type Err = ConversionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mapped" => Ok(UnmatchedServerResolution::Mapped),
+            "ignored" => Ok(UnmatchedServerResolution::Ignored),
+            _ => Err(ConversionError(format!(
+                "Unknown unmatched server resolution: {s}"
+            ))),
+        }
+    }
+}
+
+/// Server the import could not create states for, because its flavor or
+/// user is unknown.
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct UnmatchedServer {
+    pub id: u32,
+    pub instance_id: Uuid,
+    pub instance_name: String,
+    pub status: String,
+    pub flavor_openstack_id: String,
+    pub user_openstack_id: String,
+    pub reason: String,
+    /// Server as last returned by Nova, serialized as JSON.
+    #[cfg_attr(feature = "tabled", tabled(skip))]
+    pub data: String,
+    pub first_seen: DateTime<FixedOffset>,
+    pub last_seen: DateTime<FixedOffset>,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub resolution: Option<UnmatchedServerResolution>,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub resolved_at: Option<DateTime<FixedOffset>>,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub flavor: Option<u32>,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub user: Option<u32>,
+}
+
+impl Display for UnmatchedServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!(
+            "UnmatchedServer(id={}, instance_id={})",
+            self.id, self.instance_id
+        ))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UnmatchedServerListParams {
+    /// Also list servers that have been resolved already.
+    pub all: Option<bool>,
+}
+
+/// Either maps the server to a flavor and user, where the known ones are
+/// looked up by their OpenStack ID when not given, or ignores it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct UnmatchedServerResolveData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flavor: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<u32>,
+    #[serde(default)]
+    pub ignore: bool,
+}
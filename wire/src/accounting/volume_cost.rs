@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "tabled")]
+use tabled::Tabled;
+use uuid::Uuid;
+
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct VolumeCostSimple {
+    pub total: f64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct VolumeCostVolume {
+    pub total: f64,
+    pub volume_types: HashMap<String, f64>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct VolumeCostUser {
+    pub total: f64,
+    pub volume_types: HashMap<String, f64>,
+    pub volumes: HashMap<Uuid, VolumeCostVolume>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct VolumeCostProject {
+    pub total: f64,
+    pub volume_types: HashMap<String, f64>,
+    pub users: HashMap<String, VolumeCostUser>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct VolumeCostAll {
+    pub total: f64,
+    pub volume_types: HashMap<String, f64>,
+    pub projects: HashMap<String, VolumeCostProject>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VolumeCostParams {
+    pub begin: Option<DateTime<FixedOffset>>,
+    pub end: Option<DateTime<FixedOffset>>,
+    pub volume: Option<Uuid>,
+    pub user: Option<u32>,
+    pub project: Option<u32>,
+    pub all: Option<bool>,
+    pub detail: Option<bool>,
+}
@@ -0,0 +1,55 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "tabled")]
+use tabled::Tabled;
+use uuid::Uuid;
+
+#[cfg(feature = "tabled")]
+use crate::common::display_option;
+
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct VolumeState {
+    pub id: u32,
+    pub begin: DateTime<FixedOffset>,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub end: Option<DateTime<FixedOffset>>,
+    pub volume_id: Uuid,
+    pub volume_name: String,
+    /// Size in GiB.
+    pub size: u32,
+    pub volume_type: String,
+    pub status: String,
+    pub user: u32,
+    pub username: String,
+}
+
+impl Display for VolumeState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!("VolumeState(id={})", self.id))
+    }
+}
+
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct VolumeStateImport {
+    pub new_state_count: u32,
+    pub end_state_count: u32,
+    /// Volumes whose owner is not known, which are not accounted.
+    pub skipped_volume_count: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VolumeStateImportParams {
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VolumeStateListParams {
+    pub volume: Option<Uuid>,
+    pub user: Option<u32>,
+    pub project: Option<u32>,
+    pub all: Option<bool>,
+}
@@ -0,0 +1,52 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+#[cfg(feature = "tabled")]
+use tabled::Tabled;
+
+#[cfg(feature = "tabled")]
+use crate::common::display_option;
+
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct AuditLogEntry {
+    pub id: u32,
+    pub timestamp: DateTime<FixedOffset>,
+    pub method: String,
+    pub endpoint: String,
+    pub status: u16,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub resource_type: Option<String>,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub resource_id: Option<u32>,
+    pub actor: u32,
+    pub actor_name: String,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub impersonated: Option<u32>,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub impersonated_name: Option<String>,
+    #[cfg_attr(feature = "tabled", tabled(skip))]
+    pub before: Option<Value>,
+    #[cfg_attr(feature = "tabled", tabled(skip))]
+    pub after: Option<Value>,
+}
+
+impl Display for AuditLogEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!(
+            "AuditLogEntry(id={}, method={}, endpoint={})",
+            self.id, self.method, self.endpoint
+        ))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuditLogListParams {
+    pub begin: Option<DateTime<FixedOffset>>,
+    pub end: Option<DateTime<FixedOffset>>,
+    pub actor: Option<u32>,
+    pub resource_type: Option<String>,
+    pub resource_id: Option<u32>,
+}
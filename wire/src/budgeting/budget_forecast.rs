@@ -0,0 +1,26 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "tabled")]
+use tabled::Tabled;
+
+#[cfg(feature = "tabled")]
+use crate::common::display_option;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BudgetForecastParams {
+    pub project: Option<u32>,
+    pub user: Option<u32>,
+}
+
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct BudgetForecast {
+    pub cost: f64,
+    pub running_cost: f64,
+    pub projected_total: f64,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub budget: Option<u32>,
+    pub projected_overrun: f64,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub exhausted_at: Option<DateTime<FixedOffset>>,
+}
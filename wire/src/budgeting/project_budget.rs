@@ -7,6 +7,8 @@ use sqlx::FromRow;
 #[cfg(feature = "tabled")]
 use tabled::Tabled;
 
+#[cfg(feature = "tabled")]
+use crate::common::display_option;
 use crate::common::is_false;
 
 #[cfg_attr(feature = "sqlx", derive(FromRow))]
@@ -102,4 +104,8 @@ pub struct ProjectBudgetOverDetail {
     pub over: bool,
     pub cost: f64,
     pub budget: u32,
+    pub projected_total: f64,
+    pub projected_overrun: f64,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub exhausted_at: Option<DateTime<FixedOffset>>,
 }
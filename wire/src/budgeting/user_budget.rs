@@ -119,6 +119,10 @@ pub struct UserBudgetOverDetail {
     pub over: bool,
     pub cost: f64,
     pub budget: u32,
+    pub projected_total: f64,
+    pub projected_overrun: f64,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub exhausted_at: Option<DateTime<FixedOffset>>,
 }
 
 #[cfg_attr(feature = "tabled", derive(Tabled))]
@@ -3,6 +3,8 @@ pub mod error;
 
 #[cfg(feature = "accounting")]
 pub mod accounting;
+#[cfg(feature = "audit")]
+pub mod audit;
 #[cfg(feature = "budgeting")]
 pub mod budgeting;
 #[cfg(feature = "hello")]
@@ -13,5 +15,7 @@ pub mod pricing;
 pub mod quota;
 #[cfg(feature = "resources")]
 pub mod resources;
+#[cfg(feature = "scheduler")]
+pub mod scheduler;
 #[cfg(feature = "user")]
 pub mod user;
@@ -79,3 +79,265 @@ impl FlavorPriceModifyData {
         }
     }
 }
+
+/// Price of a flavor for a user class at a given point in time, defaulting
+/// to 0 when no price has been set yet.
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct FlavorPriceEffective {
+    pub flavor: u32,
+    pub flavor_name: String,
+    pub user_class: UserClass,
+    pub unit_price: f64,
+}
+
+impl Display for FlavorPriceEffective {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!(
+            "FlavorPriceEffective(flavor={}, user_class={}, unit_price={})",
+            self.flavor_name, self.user_class, self.unit_price
+        ))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FlavorPriceEffectiveParams {
+    pub time: Option<DateTime<FixedOffset>>,
+    pub user_class: Option<UserClass>,
+    pub flavor: Option<u32>,
+}
+
+/// Period during which a flavor had a constant price for a user class.
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct FlavorPricePeriod {
+    pub flavor: u32,
+    pub flavor_name: String,
+    pub user_class: UserClass,
+    pub unit_price: f64,
+    pub begin: DateTime<FixedOffset>,
+    pub end: DateTime<FixedOffset>,
+}
+
+impl Display for FlavorPricePeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!(
+            "FlavorPricePeriod(flavor={}, user_class={}, begin={}, end={})",
+            self.flavor_name, self.user_class, self.begin, self.end
+        ))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FlavorPriceTimelineParams {
+    pub begin: Option<DateTime<FixedOffset>>,
+    pub end: Option<DateTime<FixedOffset>>,
+    pub user_class: Option<UserClass>,
+    pub flavor: Option<u32>,
+}
+
+/// Changes the prices of all flavors in the flavor group and the flavor list
+/// for the given user classes, or for all user classes if none are given.
+/// Either a new unit price or a percentage relative to the price in effect
+/// at the start time has to be given.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FlavorPriceBulkCreateData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flavor_group: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flavors: Vec<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_classes: Vec<UserClass>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit_price: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percentage: Option<f64>,
+    pub start_time: DateTime<FixedOffset>,
+}
+
+impl FlavorPriceBulkCreateData {
+    pub fn new(start_time: DateTime<FixedOffset>) -> Self {
+        Self {
+            flavor_group: None,
+            flavors: vec![],
+            user_classes: vec![],
+            unit_price: None,
+            percentage: None,
+            start_time,
+        }
+    }
+}
+
+/// The reference period defaults to the current year, like for the server
+/// cost.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FlavorPriceBulkPreviewData {
+    #[serde(flatten)]
+    pub change: FlavorPriceBulkCreateData,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub begin: Option<DateTime<FixedOffset>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<DateTime<FixedOffset>>,
+}
+
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct FlavorPriceBulkChange {
+    pub flavor: u32,
+    pub flavor_name: String,
+    pub user_class: UserClass,
+    pub current_price: f64,
+    pub new_price: f64,
+}
+
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct FlavorPriceBulkProjectImpact {
+    pub project: u32,
+    pub project_name: String,
+    pub current_cost: f64,
+    pub new_cost: f64,
+    pub difference: f64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct FlavorPriceBulkPreview {
+    pub begin: DateTime<FixedOffset>,
+    pub end: DateTime<FixedOffset>,
+    pub prices: Vec<FlavorPriceBulkChange>,
+    pub projects: Vec<FlavorPriceBulkProjectImpact>,
+}
+
+/// Price of one GiB of a volume type for a whole year.
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct VolumePrice {
+    pub id: u32,
+    pub volume_type: String,
+    pub user_class: UserClass,
+    pub unit_price: f64,
+    pub start_time: DateTime<FixedOffset>,
+}
+
+impl Display for VolumePrice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!(
+            "VolumePrice(id={}, volume_type={})",
+            self.id, self.volume_type
+        ))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VolumePriceCreateData {
+    pub volume_type: String,
+    pub user_class: UserClass,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<DateTime<FixedOffset>>,
+}
+
+impl VolumePriceCreateData {
+    pub fn new(volume_type: String, user_class: UserClass) -> Self {
+        Self {
+            volume_type,
+            user_class,
+            price: None,
+            start_time: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VolumePriceModifyData {
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_class: Option<UserClass>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit_price: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<DateTime<FixedOffset>>,
+}
+
+impl VolumePriceModifyData {
+    pub fn new(id: u32) -> Self {
+        Self {
+            id,
+            volume_type: None,
+            user_class: None,
+            unit_price: None,
+            start_time: None,
+        }
+    }
+}
+
+/// Price of one floating IP of a pool for a whole year.
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct FloatingIpPrice {
+    pub id: u32,
+    pub pool: String,
+    pub user_class: UserClass,
+    pub unit_price: f64,
+    pub start_time: DateTime<FixedOffset>,
+}
+
+impl Display for FloatingIpPrice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!(
+            "FloatingIpPrice(id={}, pool={})",
+            self.id, self.pool
+        ))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FloatingIpPriceCreateData {
+    pub pool: String,
+    pub user_class: UserClass,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<DateTime<FixedOffset>>,
+}
+
+impl FloatingIpPriceCreateData {
+    pub fn new(pool: String, user_class: UserClass) -> Self {
+        Self {
+            pool,
+            user_class,
+            price: None,
+            start_time: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FloatingIpPriceModifyData {
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pool: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_class: Option<UserClass>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit_price: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<DateTime<FixedOffset>>,
+}
+
+impl FloatingIpPriceModifyData {
+    pub fn new(id: u32) -> Self {
+        Self {
+            id,
+            pool: None,
+            user_class: None,
+            unit_price: None,
+            start_time: None,
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use std::{fmt::Display, str::FromStr};
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+#[cfg(feature = "tabled")]
+use tabled::Tabled;
+
+#[cfg(feature = "tabled")]
+use crate::common::display_option;
+use crate::error::ConversionError;
+
+#[derive(
+    clap::ValueEnum,
+    Hash,
+    PartialEq,
+    Eq,
+    Clone,
+    EnumIter,
+    Debug,
+    Deserialize,
+    Serialize,
+    Copy,
+)]
+#[serde(rename_all = "snake_case")]
+#[clap(rename_all = "snake_case")]
+pub enum JobName {
+    ServerStateImport,
+    ServerStateCompaction,
+    UserImport,
+    FlavorImport,
+    UserBudgetSync,
+    VolumeStateImport,
+    FloatingIpStateImport,
+}
+
+impl JobName {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobName::ServerStateImport => "server_state_import",
+            JobName::ServerStateCompaction => "server_state_compaction",
+            JobName::UserImport => "user_import",
+            JobName::FlavorImport => "flavor_import",
+            JobName::UserBudgetSync => "user_budget_sync",
+            JobName::VolumeStateImport => "volume_state_import",
+            JobName::FloatingIpStateImport => "floating_ip_state_import",
+        }
+    }
+}
+
+impl Display for JobName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for JobName {
+    type Err = ConversionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "server_state_import" => Ok(JobName::ServerStateImport),
+            "server_state_compaction" => Ok(JobName::ServerStateCompaction),
+            "user_import" => Ok(JobName::UserImport),
+            "flavor_import" => Ok(JobName::FlavorImport),
+            "user_budget_sync" => Ok(JobName::UserBudgetSync),
+            "volume_state_import" => Ok(JobName::VolumeStateImport),
+            "floating_ip_state_import" => Ok(JobName::FloatingIpStateImport),
+            _ => Err(ConversionError(format!("Unknown job name: {s}"))),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobRunStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl JobRunStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobRunStatus::Running => "running",
+            JobRunStatus::Succeeded => "succeeded",
+            JobRunStatus::Failed => "failed",
+        }
+    }
+}
+
+impl Display for JobRunStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for JobRunStatus {
+    type Err = ConversionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "running" => Ok(JobRunStatus::Running),
+            "succeeded" => Ok(JobRunStatus::Succeeded),
+            "failed" => Ok(JobRunStatus::Failed),
+            _ => Err(ConversionError(format!("Unknown job run status: {s}"))),
+        }
+    }
+}
+
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct JobRun {
+    pub id: u32,
+    pub job: JobName,
+    pub status: JobRunStatus,
+    pub started_at: DateTime<FixedOffset>,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub finished_at: Option<DateTime<FixedOffset>>,
+    /// User who triggered the run manually, empty for scheduled runs.
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub triggered_by: Option<u32>,
+    /// Result of a successful run as JSON or the error of a failed one.
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub message: Option<String>,
+}
+
+impl Display for JobRun {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!(
+            "JobRun(id={}, job={}, status={})",
+            self.id, self.job, self.status
+        ))
+    }
+}
+
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Job {
+    pub name: JobName,
+    /// Interval in seconds or cron expression, empty if not scheduled.
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub schedule: Option<String>,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub next_run: Option<DateTime<FixedOffset>>,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub last_status: Option<JobRunStatus>,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub last_started_at: Option<DateTime<FixedOffset>>,
+}
+
+impl Display for Job {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!("Job(name={})", self.name))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JobRunListParams {
+    pub job: Option<JobName>,
+}
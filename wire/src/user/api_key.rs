@@ -0,0 +1,141 @@
+use std::{fmt::Display, str::FromStr};
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+#[cfg(feature = "tabled")]
+use tabled::Tabled;
+
+#[cfg(feature = "tabled")]
+use crate::common::display_option;
+use crate::error::ConversionError;
+
+/// Every API key starts with this, which lets clients tell them apart from
+/// Keystone tokens.
+pub const API_KEY_PREFIX: &str = "avina_";
+
+#[derive(
+    clap::ValueEnum,
+    Hash,
+    PartialEq,
+    Eq,
+    Clone,
+    EnumIter,
+    Debug,
+    Deserialize,
+    Serialize,
+    Copy,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiKeyScope {
+    Accounting,
+    Budgeting,
+    Pricing,
+    Quota,
+    Resources,
+    User,
+}
+
+impl ApiKeyScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiKeyScope::Accounting => "accounting",
+            ApiKeyScope::Budgeting => "budgeting",
+            ApiKeyScope::Pricing => "pricing",
+            ApiKeyScope::Quota => "quota",
+            ApiKeyScope::Resources => "resources",
+            ApiKeyScope::User => "user",
+        }
+    }
+}
+
+impl Display for ApiKeyScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ApiKeyScope {
+    type Err = ConversionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "accounting" => Ok(ApiKeyScope::Accounting),
+            "budgeting" => Ok(ApiKeyScope::Budgeting),
+            "pricing" => Ok(ApiKeyScope::Pricing),
+            "quota" => Ok(ApiKeyScope::Quota),
+            "resources" => Ok(ApiKeyScope::Resources),
+            "user" => Ok(ApiKeyScope::User),
+            _ => Err(ConversionError(format!("Unknown API key scope: {s}"))),
+        }
+    }
+}
+
+#[cfg(feature = "tabled")]
+fn display_scopes(scopes: &[ApiKeyScope]) -> String {
+    scopes
+        .iter()
+        .map(|scope| scope.as_str())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ApiKey {
+    pub id: u32,
+    pub name: String,
+    pub prefix: String,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_scopes"))]
+    pub scopes: Vec<ApiKeyScope>,
+    pub user: u32,
+    pub username: String,
+    pub created_at: DateTime<FixedOffset>,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub expires_at: Option<DateTime<FixedOffset>>,
+    #[cfg_attr(feature = "tabled", tabled(display = "display_option"))]
+    pub last_used_at: Option<DateTime<FixedOffset>>,
+}
+
+impl Display for ApiKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!("ApiKey(id={}, name={})", self.id, self.name))
+    }
+}
+
+/// Returned only once on creation, since the key itself is not stored.
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ApiKeyCreated {
+    #[cfg_attr(feature = "tabled", tabled(inline))]
+    #[serde(flatten)]
+    pub api_key: ApiKey,
+    pub key: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ApiKeyListParams {
+    pub user: Option<u32>,
+    pub all: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ApiKeyCreateData {
+    pub name: String,
+    pub scopes: Vec<ApiKeyScope>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<FixedOffset>>,
+}
+
+impl ApiKeyCreateData {
+    pub fn new(name: String, scopes: Vec<ApiKeyScope>) -> Self {
+        Self {
+            name,
+            scopes,
+            user: None,
+            expires_at: None,
+        }
+    }
+}
@@ -1,6 +1,10 @@
+mod api_key;
 mod project;
+mod project_class_change;
 #[allow(clippy::module_inception)]
 mod user;
 
+pub use api_key::*;
 pub use project::*;
+pub use project_class_change::*;
 pub use user::*;
@@ -0,0 +1,76 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "tabled")]
+use tabled::Tabled;
+
+use crate::user::UserClass;
+
+/// User class of a project from the start time until the next change of the
+/// same project.
+#[cfg_attr(feature = "tabled", derive(Tabled))]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ProjectClassChange {
+    pub id: u32,
+    pub project: u32,
+    pub project_name: String,
+    pub user_class: UserClass,
+    pub start_time: DateTime<FixedOffset>,
+}
+
+impl Display for ProjectClassChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!(
+            "ProjectClassChange(id={}, project={}, user_class={})",
+            self.id, self.project_name, self.user_class
+        ))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProjectClassChangeListParams {
+    pub project: Option<u32>,
+    pub all: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProjectClassChangeCreateData {
+    pub project: u32,
+    pub user_class: UserClass,
+    pub start_time: DateTime<FixedOffset>,
+}
+
+impl ProjectClassChangeCreateData {
+    pub fn new(
+        project: u32,
+        user_class: UserClass,
+        start_time: DateTime<FixedOffset>,
+    ) -> Self {
+        Self {
+            project,
+            user_class,
+            start_time,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProjectClassChangeModifyData {
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_class: Option<UserClass>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<DateTime<FixedOffset>>,
+}
+
+impl ProjectClassChangeModifyData {
+    pub fn new(id: u32) -> Self {
+        Self {
+            id,
+            user_class: None,
+            start_time: None,
+        }
+    }
+}
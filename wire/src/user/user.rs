@@ -1,12 +1,74 @@
 use std::{cmp::PartialEq, fmt::Display};
 
+use rand::{
+    Rng,
+    distr::{Distribution, StandardUniform},
+};
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "sqlx")]
 use sqlx::FromRow;
+use strum::EnumIter;
 #[cfg(feature = "tabled")]
 use tabled::Tabled;
 
-use crate::user::ProjectMinimal;
+use crate::{error::ConversionError, user::ProjectMinimal};
+
+// NOTE: roles are sent as plain integers over the wire to stay compatible
+// with the legacy API.
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[derive(
+    clap::ValueEnum,
+    Hash,
+    PartialEq,
+    Eq,
+    Clone,
+    EnumIter,
+    Debug,
+    Deserialize,
+    Serialize,
+    Copy,
+)]
+#[serde(try_from = "u32", into = "u32")]
+#[repr(u16)]
+pub enum Role {
+    #[value(alias = "1")]
+    User = 1,
+    #[value(alias = "2")]
+    MasterUser = 2,
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl From<Role> for u32 {
+    fn from(role: Role) -> Self {
+        role as u32
+    }
+}
+
+impl TryFrom<u32> for Role {
+    type Error = ConversionError;
+
+    fn try_from(u: u32) -> Result<Self, Self::Error> {
+        match u {
+            1 => Ok(Role::User),
+            2 => Ok(Role::MasterUser),
+            _ => Err(ConversionError(format!("Unknown role value: {u}"))),
+        }
+    }
+}
+
+impl Distribution<Role> for StandardUniform {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Role {
+        match rng.random_range(0..2) {
+            0 => Role::User,
+            _ => Role::MasterUser,
+        }
+    }
+}
 
 #[cfg_attr(feature = "sqlx", derive(FromRow))]
 #[cfg_attr(feature = "tabled", derive(Tabled))]
@@ -19,7 +81,8 @@ pub struct User {
     #[cfg_attr(feature = "sqlx", sqlx(try_from = "i32"))]
     pub project: u32,
     pub project_name: String,
-    pub role: u32,
+    #[cfg_attr(feature = "sqlx", sqlx(try_from = "u32"))]
+    pub role: Role,
     pub is_staff: bool,
     pub is_active: bool,
 }
@@ -103,7 +166,8 @@ pub struct UserDetailed {
     #[cfg_attr(feature = "sqlx", sqlx(flatten))]
     pub project: ProjectMinimal,
     pub project_name: String,
-    pub role: u32,
+    #[cfg_attr(feature = "sqlx", sqlx(try_from = "u32"))]
+    pub role: Role,
     pub is_staff: bool,
     pub is_active: bool,
 }
@@ -148,8 +212,7 @@ pub struct UserCreateData {
     // TODO can't this be optional?
     pub project: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
-    // this could be an enum right
-    pub role: Option<u32>,
+    pub role: Option<Role>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_staff: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -180,7 +243,7 @@ pub struct UserModifyData {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub project: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub role: Option<u32>,
+    pub role: Option<Role>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_staff: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]